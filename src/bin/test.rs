@@ -119,4 +119,20 @@ fn main() {
             Err(e) => println!("执行失败: {}", e),
         }
     }
+
+    // 第四组测试：导出后导入新的内存数据库，数据应完全一致
+    println!("\n=== 导出导入测试 ===");
+    let dump = db.dump_sql().unwrap();
+    let mut restored = Database::new(StorageType::Memory);
+    for result in restored.execute_many(&dump, true) {
+        result.expect("导入导出语句失败");
+    }
+    for name in db.list_tables().unwrap() {
+        let original = db.get_table(&name).unwrap().unwrap();
+        let copy = restored.get_table(&name).unwrap().expect("导入后缺少表");
+        assert_eq!(original.columns, copy.columns, "表 {} 结构不一致", name);
+        assert_eq!(original.rows, copy.rows, "表 {} 数据不一致", name);
+    }
+    assert_eq!(dump, restored.dump_sql().unwrap());
+    println!("导出导入结果一致");
 } 
\ No newline at end of file
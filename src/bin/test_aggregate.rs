@@ -0,0 +1,50 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::types::DataType;
+use simple_db::StatementResult;
+
+// 执行查询并返回结果行
+fn rows(db: &mut Database, sql: &str) -> Vec<Vec<DataType>> {
+    match db.execute(sql).unwrap() {
+        StatementResult::ResultSet(result) => {
+            println!("{} -> {:?}", sql, result.rows);
+            result.rows
+        }
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+}
+
+fn main() {
+    println!("=== 测试聚合函数与 DISTINCT ===");
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql("CREATE TABLE t (id INT, v INT, s VARCHAR(5))").unwrap();
+    // v 和 s 中都有重复值和 NULL
+    db.execute_sql("INSERT INTO t VALUES (1, 10, 'a'), (2, 10, 'a'), (3, NULL, NULL), (4, 20, 'b'), (5, NULL, 'b'), (6, 30, NULL)").unwrap();
+
+    // COUNT(*) 计算全部行，COUNT(col) 不计 NULL，COUNT(DISTINCT col) 再去掉重复值
+    assert_eq!(
+        rows(&mut db, "SELECT COUNT(*), COUNT(v), COUNT(DISTINCT v), COUNT(s), COUNT(DISTINCT s) FROM t"),
+        vec![vec![DataType::Int(6), DataType::Int(4), DataType::Int(3), DataType::Int(4), DataType::Int(2)]]
+    );
+
+    // 其他聚合函数同样忽略 NULL，DISTINCT 只计算不同的值
+    assert_eq!(
+        rows(&mut db, "SELECT SUM(v), SUM(DISTINCT v), MIN(v), MAX(v) FROM t"),
+        vec![vec![DataType::Int(70), DataType::Int(60), DataType::Int(10), DataType::Int(30)]]
+    );
+    assert_eq!(
+        rows(&mut db, "SELECT AVG(v), AVG(DISTINCT v) FROM t"),
+        vec![vec![DataType::Float(17.5), DataType::Float(20.0)]]
+    );
+
+    // 全部为 NULL 或没有行时计数为 0
+    assert_eq!(
+        rows(&mut db, "SELECT COUNT(v), COUNT(DISTINCT v) FROM t WHERE id = 3"),
+        vec![vec![DataType::Int(0), DataType::Int(0)]]
+    );
+    assert_eq!(
+        rows(&mut db, "SELECT COUNT(*), COUNT(DISTINCT s) FROM t WHERE id > 100"),
+        vec![vec![DataType::Int(0), DataType::Int(0)]]
+    );
+
+    println!("测试通过");
+}
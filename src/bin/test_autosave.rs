@@ -0,0 +1,87 @@
+use simple_db::core::db::{Database, StorageType};
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+// 表文件写成单行 JSON，保存后会被重写为带缩进的格式，由此判断退出时是否写盘
+fn prepare_compact_table(dir: &Path) {
+    let _ = fs::remove_dir_all(dir);
+    let mut db = Database::new(StorageType::File(dir.join("db")));
+    db.execute_sql("CREATE TABLE t (id INT)").unwrap();
+    db.execute_sql("INSERT INTO t VALUES (1)").unwrap();
+    drop(db);
+
+    let path = dir.join("db").join("tables").join("t.json");
+    let value: serde_json::Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+    fs::write(&path, serde_json::to_string(&value).unwrap()).unwrap();
+}
+
+// 在给定目录中运行交互式 simple_db，返回标准输出
+fn run_shell(dir: &Path, input: &str) -> String {
+    // simple_db 与当前测试程序位于同一目录
+    let binary = std::env::current_exe().unwrap().with_file_name("simple_db");
+    let mut child = Command::new(&binary)
+        .current_dir(dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap_or_else(|e| panic!("无法运行 {}: {}", binary.display(), e));
+    child.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success(), "退出状态 {}", output.status);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+fn table_file_rewritten(dir: &Path) -> bool {
+    fs::read_to_string(dir.join("db").join("tables").join("t.json")).unwrap().contains('\n')
+}
+
+fn main() {
+    println!("=== 测试退出时自动保存 ===");
+    let base_dir = std::env::temp_dir().join("simple_db_test_autosave");
+
+    // 默认开启：exit 前保存数据库
+    let dir = base_dir.join("exit");
+    prepare_compact_table(&dir);
+    run_shell(&dir, "SELECT * FROM t;\nexit\n");
+    assert!(table_file_rewritten(&dir));
+
+    // 输入结束时同样保存
+    let dir = base_dir.join("eof");
+    prepare_compact_table(&dir);
+    run_shell(&dir, "SELECT * FROM t;\n");
+    assert!(table_file_rewritten(&dir));
+
+    // 关闭后退出时不写盘
+    let dir = base_dir.join("off");
+    prepare_compact_table(&dir);
+    let output = run_shell(&dir, "SET autosave = off\nexit\n");
+    assert!(output.contains("选项 autosave 已设置为 off"));
+    assert!(!table_file_rewritten(&dir));
+
+    // 选项的取值
+    let mut db = Database::new(StorageType::Memory);
+    assert!(db.autosave());
+    db.set_option("autosave", "OFF").unwrap();
+    assert!(!db.autosave());
+    db.set_option("autosave", "on").unwrap();
+    assert!(db.autosave());
+    assert!(db.set_option("autosave", "sometimes").is_err());
+    assert!(!db.has_unsaved_changes());
+
+    // 批量模式中的修改在写盘前属于未保存的修改
+    let dir = base_dir.join("batch");
+    let _ = fs::remove_dir_all(&dir);
+    let mut db = Database::new(StorageType::File(dir.clone()));
+    db.execute_sql("CREATE TABLE t (id INT)").unwrap();
+    assert!(!db.has_unsaved_changes());
+    db.begin_batch();
+    db.execute_sql("INSERT INTO t VALUES (1)").unwrap();
+    assert!(db.has_unsaved_changes());
+    db.end_batch().unwrap();
+    assert!(!db.has_unsaved_changes());
+
+    let _ = fs::remove_dir_all(&base_dir);
+    println!("退出时自动保存结果正确");
+}
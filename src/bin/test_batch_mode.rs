@@ -0,0 +1,81 @@
+use simple_db::core::sql::{SqlExecutor, SqlParser};
+use simple_db::core::storage::file::FileStorage;
+use simple_db::core::storage::Storage;
+use simple_db::core::types::DataType;
+use std::fs;
+
+// 在给定存储上执行一条SQL
+fn execute(storage: &mut FileStorage, sql: &str) {
+    let statement = SqlParser::new().parse(sql).expect("解析失败");
+    SqlExecutor::new(storage).execute(statement).expect("执行失败");
+}
+
+fn main() {
+    let base_dir = std::env::temp_dir().join("simple_db_test_batch_mode");
+    let _ = fs::remove_dir_all(&base_dir);
+
+    println!("=== 测试批量模式 ===");
+    let mut storage = FileStorage::new(base_dir.clone());
+    execute(&mut storage, "CREATE TABLE users (id INT PRIMARY KEY, name VARCHAR(20))");
+    execute(&mut storage, "CREATE TABLE logs (id INT, message VARCHAR(20))");
+
+    // 批量模式中的多次插入不写盘
+    let before = storage.files_written();
+    storage.begin_batch();
+    for i in 0..20 {
+        execute(&mut storage, &format!("INSERT INTO users VALUES ({}, 'user{}')", i, i));
+        execute(&mut storage, &format!("INSERT INTO logs VALUES ({}, 'insert')", i));
+    }
+    execute(&mut storage, "UPDATE users SET name = 'first' WHERE id = 0");
+    assert!(storage.has_unsaved_changes());
+    assert_eq!(storage.files_written(), before);
+
+    // 嵌套的批量模式在最外层结束时才写盘
+    storage.begin_batch();
+    execute(&mut storage, "INSERT INTO logs VALUES (20, 'nested')");
+    storage.end_batch().unwrap();
+    assert_eq!(storage.files_written(), before);
+
+    // 结束批量模式时每个修改过的表只写一次
+    storage.end_batch().unwrap();
+    println!("批量插入 41 行，写入表文件 {} 次", storage.files_written() - before);
+    assert_eq!(storage.files_written() - before, 2);
+    assert!(!storage.has_unsaved_changes());
+
+    // 没有修改时结束批量模式不写盘
+    let before = storage.files_written();
+    storage.begin_batch();
+    storage.end_batch().unwrap();
+    assert_eq!(storage.files_written(), before);
+
+    // 重新加载后批量期间的修改全部可见
+    let reloaded = FileStorage::new(base_dir.clone());
+    let users = reloaded.get_table("users").unwrap().unwrap();
+    assert_eq!(users.rows.len(), 20);
+    assert_eq!(users.rows[0], vec![DataType::Int(0), DataType::Varchar("first".to_string())]);
+    assert_eq!(users.rows[19], vec![DataType::Int(19), DataType::Varchar("user19".to_string())]);
+    let logs = reloaded.get_table("logs").unwrap().unwrap();
+    assert_eq!(logs.rows.len(), 21);
+    assert_eq!(logs.rows[20], vec![DataType::Int(20), DataType::Varchar("nested".to_string())]);
+    println!("重新加载后 users {} 行，logs {} 行", users.rows.len(), logs.rows.len());
+
+    // 写盘失败时修改仍视为未保存，下次批量结束时重新写入
+    let mut storage = reloaded;
+    let users_path = base_dir.join("tables").join("users.json");
+    storage.begin_batch();
+    execute(&mut storage, "INSERT INTO users VALUES (20, 'retry')");
+    fs::remove_file(&users_path).unwrap();
+    fs::create_dir(&users_path).unwrap(); // 表文件的位置被目录占用，写入失败
+    assert!(storage.end_batch().is_err());
+    assert!(storage.has_unsaved_changes());
+    fs::remove_dir(&users_path).unwrap();
+    storage.begin_batch();
+    storage.end_batch().unwrap();
+    assert!(!storage.has_unsaved_changes());
+    let reloaded = FileStorage::new(base_dir.clone());
+    assert_eq!(reloaded.get_table("users").unwrap().unwrap().rows.len(), 21);
+    println!("写盘失败后重试成功");
+
+    let _ = fs::remove_dir_all(&base_dir);
+    println!("测试通过");
+}
@@ -0,0 +1,66 @@
+use simple_db::core::sql::{SqlExecutor, SqlParser};
+use simple_db::core::storage::file::FileStorage;
+use simple_db::core::storage::Storage;
+use simple_db::core::types::{Column, ColumnType, DataType, Table};
+use std::fs;
+
+// 在给定存储上执行一条SQL
+fn execute(storage: &mut FileStorage, sql: &str) {
+    let statement = SqlParser::new().parse(sql).expect("解析失败");
+    SqlExecutor::new(storage).execute(statement).expect("执行失败");
+}
+
+fn main() {
+    let base_dir = std::env::temp_dir().join("simple_db_test_bulk_insert");
+    let _ = fs::remove_dir_all(&base_dir);
+
+    println!("=== 测试批量插入 ===");
+    let mut storage = FileStorage::new(base_dir.clone());
+    let columns = vec![
+        Column { name: "id".to_string(), data_type: ColumnType::Int(None), nullable: false, primary_key: true, comment: None, default: None },
+        Column { name: "name".to_string(), data_type: ColumnType::Varchar(20), nullable: true, primary_key: false, comment: None, default: None },
+    ];
+    storage.create_table(Table::new("items".to_string(), columns)).unwrap();
+
+    // 多行插入只写一次表文件
+    let rows: Vec<Vec<DataType>> = (0..100)
+        .map(|i| vec![DataType::Int(i), DataType::Varchar(format!("item{}", i))])
+        .collect();
+    let before = storage.files_written();
+    storage.insert_rows("items", rows).unwrap();
+    println!("插入 100 行，写入表文件 {} 次", storage.files_written() - before);
+    assert_eq!(storage.files_written() - before, 1);
+
+    // 逐行插入每行写一次
+    let before = storage.files_written();
+    for i in 100..110 {
+        storage.insert_row("items", vec![DataType::Int(i), DataType::Null]).unwrap();
+    }
+    println!("逐行插入 10 行，写入表文件 {} 次", storage.files_written() - before);
+    assert_eq!(storage.files_written() - before, 10);
+
+    // 任何一行失败时不插入任何行，也不写盘
+    let before = storage.files_written();
+    let result = storage.insert_rows("items", vec![
+        vec![DataType::Int(200), DataType::Null],
+        vec![DataType::Int(0), DataType::Null], // 主键重复
+    ]);
+    println!("包含重复主键的批量插入: {:?}", result.as_ref().map_err(|e| e.detailed_message()));
+    assert!(result.is_err());
+    assert_eq!(storage.get_table("items").unwrap().unwrap().rows.len(), 110);
+    assert_eq!(storage.files_written(), before);
+
+    // INSERT 语句插入多行同样只写一次
+    let before = storage.files_written();
+    execute(&mut storage, "INSERT INTO items VALUES (300, 'a'), (301, 'b'), (302, 'c')");
+    execute(&mut storage, "INSERT INTO items (id) VALUES (400), (401)");
+    println!("两条多行 INSERT 语句，写入表文件 {} 次", storage.files_written() - before);
+    assert_eq!(storage.files_written() - before, 2);
+
+    // 重新加载后数据完整
+    let reloaded = FileStorage::new(base_dir.clone());
+    assert_eq!(reloaded.get_table("items").unwrap().unwrap().rows.len(), 115);
+    println!("重新加载后共 115 行");
+
+    let _ = fs::remove_dir_all(&base_dir);
+}
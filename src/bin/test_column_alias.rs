@@ -0,0 +1,68 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::error::DbError;
+use simple_db::core::types::DataType;
+use simple_db::StatementResult;
+
+// 执行查询，返回表头和结果行
+fn query(db: &mut Database, sql: &str) -> (Vec<String>, Vec<Vec<DataType>>) {
+    match db.execute(sql).unwrap() {
+        StatementResult::ResultSet(result) => (result.columns, result.rows),
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+}
+
+fn headers(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| name.to_string()).collect()
+}
+
+// 两种执行方式下都应报告别名在定义之前被引用
+fn assert_forward_reference(db: &mut Database, sql: &str, alias: &str) {
+    let expected = format!("列别名 {} 在定义之前被引用", alias);
+    for error in [db.execute(sql).unwrap_err(), db.execute_sql(sql).unwrap_err()] {
+        match error {
+            DbError::SqlError(message) => assert_eq!(message, expected, "{}", sql),
+            other => panic!("{} 期望 SQL 错误，实际为 {:?}", sql, other),
+        }
+    }
+}
+
+fn main() {
+    println!("=== 测试引用前面定义的列别名 ===");
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql("CREATE TABLE products (id INT PRIMARY KEY, price INT)").unwrap();
+    db.execute_sql("INSERT INTO products VALUES (1, 10), (2, 25)").unwrap();
+
+    // 别名作为表头，后面的表达式可以引用前面的别名
+    let (columns, rows) = query(&mut db, "SELECT price AS p, p * 2 FROM products");
+    assert_eq!(columns, headers(&["p", "p * 2"]));
+    assert_eq!(rows, vec![
+        vec![DataType::Int(10), DataType::Int(20)],
+        vec![DataType::Int(25), DataType::Int(50)],
+    ]);
+    db.execute_sql("SELECT price AS p, p * 2 FROM products").unwrap();
+
+    // 别名可以逐个引用，也可以用于 ORDER BY
+    let (columns, rows) = query(&mut db, "SELECT price AS p, p * 2 AS d, d + p AS s FROM products ORDER BY s DESC");
+    assert_eq!(columns, headers(&["p", "d", "s"]));
+    assert_eq!(rows[0], vec![DataType::Int(25), DataType::Int(50), DataType::Int(75)]);
+
+    // 聚合结果的别名，以及没有 FROM 的表达式查询
+    let (_, rows) = query(&mut db, "SELECT SUM(price) AS total, total + 1 FROM products");
+    assert_eq!(rows, vec![vec![DataType::Int(35), DataType::Int(36)]]);
+    let (columns, rows) = query(&mut db, "SELECT 1 AS a, a + 1");
+    assert_eq!(columns, headers(&["a", "a + 1"]));
+    assert_eq!(rows, vec![vec![DataType::Int(1), DataType::Int(2)]]);
+
+    // 表中的同名列优先于别名
+    let (_, rows) = query(&mut db, "SELECT price AS id, id FROM products WHERE id = 1");
+    assert_eq!(rows, vec![vec![DataType::Int(10), DataType::Int(1)]]);
+
+    // 引用后面才定义的别名或自身的别名时报错
+    assert_forward_reference(&mut db, "SELECT p * 2, price AS p FROM products", "p");
+    assert_forward_reference(&mut db, "SELECT price + x AS x FROM products", "x");
+    assert_forward_reference(&mut db, "SELECT b AS a, 1 AS b", "b");
+
+    // AS 之后必须是别名
+    assert!(db.execute("SELECT price AS FROM products").is_err());
+    println!("列别名结果正确");
+}
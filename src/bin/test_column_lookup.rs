@@ -0,0 +1,45 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::types::DataType;
+use simple_db::StatementResult;
+
+fn main() {
+    println!("=== 测试查询中引用其他表的列 ===");
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql("CREATE TABLE users (id INT, name VARCHAR(10))").unwrap();
+    db.execute_sql("CREATE TABLE scores (id INT, score INT)").unwrap();
+    db.execute_sql("CREATE TABLE grades (id INT, score INT)").unwrap();
+    db.execute_sql("INSERT INTO users VALUES (1, 'Alice')").unwrap();
+    db.execute_sql("INSERT INTO scores VALUES (1, 99)").unwrap();
+
+    // score 只在其他表中存在，报错并提示包含该列的表，而不是返回 users 中同一位置的值
+    for sql in ["SELECT score FROM users", "SELECT id, score + 1 FROM users"] {
+        let message = db.execute(sql).unwrap_err().detailed_message();
+        println!("{} -> {}", sql, message);
+        assert!(message.contains("不在表 users 中"));
+        assert!(message.contains("grades、scores") || message.contains("scores、grades"));
+
+        let message = db.execute_sql(sql).unwrap_err().detailed_message();
+        assert!(message.contains("不在表 users 中"));
+    }
+
+    // 任何表中都没有的列
+    let message = db.execute("SELECT nme FROM users").unwrap_err().detailed_message();
+    println!("SELECT nme FROM users -> {}", message);
+    assert!(message.contains("列 nme 未找到"));
+
+    // 当前表中的列不受影响
+    match db.execute("SELECT id, name FROM users").unwrap() {
+        StatementResult::ResultSet(result) => {
+            assert_eq!(result.rows, vec![vec![DataType::Int(1), DataType::Varchar("Alice".to_string())]]);
+        }
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+
+    // 开启跨表查找后恢复原来的行为
+    db.set_option("cross_table_lookup", "on").unwrap();
+    assert!(db.execute_sql("SELECT score FROM users").is_ok());
+    db.set_option("cross_table_lookup", "off").unwrap();
+    assert!(db.execute_sql("SELECT score FROM users").is_err());
+    assert!(db.set_option("cross_table_lookup", "maybe").is_err());
+    println!("引用其他表的列时给出提示");
+}
@@ -0,0 +1,50 @@
+use simple_db::core::types::DataType;
+use simple_db::{Database, StatementResult, StorageType};
+use std::fs;
+
+fn text(s: &str) -> DataType {
+    DataType::Varchar(s.to_string())
+}
+
+// 执行 DESCRIBE，返回每列的列名和注释
+fn describe_comments(db: &mut Database, table: &str) -> Vec<(DataType, DataType)> {
+    match db.execute(&format!("DESCRIBE {}", table)).unwrap() {
+        StatementResult::ResultSet(result) => {
+            assert_eq!(result.columns[5], "Comment");
+            result.rows.into_iter().map(|row| (row[0].clone(), row[5].clone())).collect()
+        }
+        other => panic!("DESCRIBE 应返回结果集: {:?}", other),
+    }
+}
+
+fn main() {
+    let base_dir = std::env::temp_dir().join("simple_db_test_comment");
+    let _ = fs::remove_dir_all(&base_dir);
+
+    println!("=== 测试表和列的注释 ===");
+    let mut db = Database::new(StorageType::File(base_dir.clone()));
+    db.execute_sql("CREATE TABLE users (id INT PRIMARY KEY COMMENT 'user id', name VARCHAR(20) COMMENT 'it''s the name', age INT) COMMENT = 'registered users'").unwrap();
+
+    // DESCRIBE 显示每列的注释，没有注释的列为空字符串
+    let expected = vec![
+        (text("id"), text("user id")),
+        (text("name"), text("it's the name")),
+        (text("age"), text("")),
+    ];
+    assert_eq!(describe_comments(&mut db, "users"), expected);
+    assert_eq!(db.get_table("users").unwrap().unwrap().comment.as_deref(), Some("registered users"));
+
+    // 注释写入表文件，重新打开后仍然存在
+    drop(db);
+    let mut db = Database::new(StorageType::File(base_dir.clone()));
+    assert_eq!(describe_comments(&mut db, "users"), expected);
+    assert_eq!(db.get_table("users").unwrap().unwrap().comment.as_deref(), Some("registered users"));
+
+    // 显式重新加载同样保留注释
+    db.load().unwrap();
+    assert_eq!(describe_comments(&mut db, "users"), expected);
+    assert_eq!(db.get_table("users").unwrap().unwrap().comment.as_deref(), Some("registered users"));
+
+    let _ = fs::remove_dir_all(&base_dir);
+    println!("测试通过");
+}
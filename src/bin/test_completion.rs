@@ -0,0 +1,55 @@
+use simple_db::completion::Completer;
+use simple_db::core::db::{Database, StorageType};
+
+// 补全并打印结果
+fn complete(completer: &Completer, line: &str) -> (usize, Vec<String>) {
+    let result = completer.complete(line);
+    println!("{:?} -> {:?}", line, result);
+    result
+}
+
+fn strings(items: &[&str]) -> Vec<String> {
+    items.iter().map(|s| s.to_string()).collect()
+}
+
+fn main() {
+    println!("=== 测试补全 ===");
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql("CREATE TABLE users (id INT PRIMARY KEY, name VARCHAR(20), nickname VARCHAR(20), age INT)").unwrap();
+    db.execute_sql("CREATE TABLE notes (id INT, user_id INT, body VARCHAR(50))").unwrap();
+    let completer = Completer::from_database(&db).unwrap();
+
+    // 关键字，大小写跟随输入
+    assert_eq!(complete(&completer, "SEL"), (0, strings(&["SELECT"])));
+    assert_eq!(complete(&completer, "sel"), (0, strings(&["select"])));
+    assert_eq!(complete(&completer, "SELECT * FROM users WH"), (20, strings(&["WHERE"])));
+
+    // 行首同时补全Shell命令
+    assert_eq!(complete(&completer, "st"), (0, strings(&["stats"])));
+    assert_eq!(complete(&completer, "e"), (0, strings(&["error_mode", "exit", "explain"])));
+    assert_eq!(complete(&completer, "SELECT st"), (7, Vec::new()));
+
+    // FROM、INTO、UPDATE 等之后补全表名
+    assert_eq!(complete(&completer, "SELECT * FROM u"), (14, strings(&["users"])));
+    assert_eq!(complete(&completer, "SELECT * FROM "), (14, strings(&["notes", "users"])));
+    assert_eq!(complete(&completer, "INSERT INTO n"), (12, strings(&["notes"])));
+    assert_eq!(complete(&completer, "UPDATE us"), (7, strings(&["users"])));
+    assert_eq!(complete(&completer, "CREATE INDEX idx ON n"), (20, strings(&["notes"])));
+
+    // 语句中出现表名后补全该表的列名
+    assert_eq!(complete(&completer, "SELECT * FROM users WHERE n"), (26, strings(&["name", "nickname", "not", "nothing", "null", "numeric"])));
+    assert_eq!(complete(&completer, "SELECT * FROM notes WHERE user"), (26, strings(&["user_id"])));
+    assert_eq!(complete(&completer, "UPDATE users SET ag"), (17, strings(&["age"])));
+    assert_eq!(complete(&completer, "INSERT INTO notes (id, bo"), (23, strings(&["body"])));
+    assert_eq!(complete(&completer, "INSERT INTO notes VALUES (1, 2, 'x') ON CON"), (40, strings(&["CONFLICT"])));
+
+    // 字符串内部和没有匹配时不补全
+    assert_eq!(complete(&completer, "SELECT * FROM users WHERE name = 'na"), (34, Vec::new()));
+    assert_eq!(complete(&completer, "SELECT xyz"), (7, Vec::new()));
+
+    // 表结构变化后重新创建补全器
+    db.execute_sql("CREATE TABLE tags (label VARCHAR(10))").unwrap();
+    let completer = Completer::from_database(&db).unwrap();
+    assert_eq!(complete(&completer, "DESCRIBE t"), (9, strings(&["tags"])));
+    println!("补全结果正确");
+}
@@ -0,0 +1,82 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::types::DataType;
+use simple_db::StatementResult;
+use std::fs;
+
+fn text(s: &str) -> DataType {
+    DataType::Varchar(s.to_string())
+}
+
+// 执行查询并返回结果行
+fn rows(db: &mut Database, sql: &str) -> Vec<Vec<DataType>> {
+    match db.execute(sql).unwrap() {
+        StatementResult::ResultSet(result) => result.rows,
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+}
+
+fn main() {
+    println!("=== 测试导入 CSV ===");
+    let dir = std::env::temp_dir().join("simple_db_test_copy_from");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let csv_path = dir.join("users.csv");
+
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql("CREATE TABLE users (id INT PRIMARY KEY, name VARCHAR(30), score FLOAT, city VARCHAR(10) DEFAULT 'Paris')").unwrap();
+
+    // 加引号的字段可以包含逗号、换行和转义的引号；未加引号的空字段取列的默认值（没有时为 NULL），加引号的空字段为空字符串
+    fs::write(&csv_path, concat!(
+        "1,\"Smith, John\",1.5,Rome\n",
+        "2,\"say \"\"hi\"\"\",,\n",
+        "3,,2,\"\"\n",
+        "4,\"two\nlines\", 7 ,Oslo\n",
+    )).unwrap();
+    let copy = format!("COPY users FROM '{}'", csv_path.display());
+    assert_eq!(db.execute(&copy).unwrap(), StatementResult::RowsAffected(4));
+    assert_eq!(rows(&mut db, "SELECT * FROM users"), vec![
+        vec![DataType::Int(1), text("Smith, John"), DataType::Float(1.5), text("Rome")],
+        vec![DataType::Int(2), text("say \"hi\""), DataType::Null, text("Paris")],
+        vec![DataType::Int(3), DataType::Null, DataType::Float(2.0), text("")],
+        vec![DataType::Int(4), text("two\nlines"), DataType::Float(7.0), text("Oslo")],
+    ]);
+
+    // 按表头导入时，文件中没有的列使用默认值
+    fs::write(&csv_path, "id,name,score\n5,\"Lee, Ann\",\n").unwrap();
+    let copy = format!("COPY users FROM '{}' HEADER", csv_path.display());
+    assert_eq!(db.execute(&copy).unwrap(), StatementResult::RowsAffected(1));
+    assert_eq!(rows(&mut db, "SELECT * FROM users WHERE id = 5"), vec![
+        vec![DataType::Int(5), text("Lee, Ann"), DataType::Null, text("Paris")],
+    ]);
+
+    // 字段数量不对或类型错误时不导入任何行
+    for content in ["6,Bob\n", "6,Bob,1,Rome\n7,Eve,abc,Rome\n"] {
+        fs::write(&csv_path, content).unwrap();
+        let error = db.execute(&format!("COPY users FROM '{}'", csv_path.display())).unwrap_err();
+        println!("{:?}: {}", content, error.detailed_message());
+        assert_eq!(rows(&mut db, "SELECT id FROM users").len(), 5);
+    }
+
+    // 任何一行违反约束时整个文件都不导入，有无表头相同
+    for (content, header) in [("6,a,,\n7,b,,\n6,c,,\n", ""), ("id\n8\n1\n", " HEADER")] {
+        fs::write(&csv_path, content).unwrap();
+        let error = db.execute(&format!("COPY users FROM '{}'{}", csv_path.display(), header)).unwrap_err();
+        println!("{:?}: {}", content, error.detailed_message());
+        assert_eq!(rows(&mut db, "SELECT id FROM users").len(), 5);
+    }
+
+    // 非空列的空字段使用默认值
+    db.execute_sql("CREATE TABLE u (id INT, n INT NOT NULL DEFAULT 7)").unwrap();
+    fs::write(&csv_path, "5,\n6,8\n").unwrap();
+    assert_eq!(db.execute(&format!("COPY u FROM '{}'", csv_path.display())).unwrap(), StatementResult::RowsAffected(2));
+    fs::write(&csv_path, "n,id\n,9\n").unwrap();
+    assert_eq!(db.execute(&format!("COPY u FROM '{}' HEADER", csv_path.display())).unwrap(), StatementResult::RowsAffected(1));
+    assert_eq!(rows(&mut db, "SELECT * FROM u"), vec![
+        vec![DataType::Int(5), DataType::Int(7)],
+        vec![DataType::Int(6), DataType::Int(8)],
+        vec![DataType::Int(9), DataType::Int(7)],
+    ]);
+
+    let _ = fs::remove_dir_all(&dir);
+    println!("测试通过");
+}
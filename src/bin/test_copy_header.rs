@@ -0,0 +1,74 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::types::DataType;
+use simple_db::StatementResult;
+use std::fs;
+
+fn text(s: &str) -> DataType {
+    DataType::Varchar(s.to_string())
+}
+
+// 执行查询并返回结果行
+fn rows(db: &mut Database, sql: &str) -> Vec<Vec<DataType>> {
+    match db.execute(sql).unwrap() {
+        StatementResult::ResultSet(result) => result.rows,
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+}
+
+fn main() {
+    println!("=== 测试按表头导入 CSV ===");
+    let dir = std::env::temp_dir().join("simple_db_test_copy_header");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let csv_path = dir.join("users.csv");
+
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql("CREATE TABLE users (id INT PRIMARY KEY, name VARCHAR(20) NOT NULL, age INT, city VARCHAR(10) DEFAULT 'Paris')").unwrap();
+
+    // 表头的顺序与表的列顺序不同，未出现的列使用默认值
+    fs::write(&csv_path, "age,name,id\n30,Alice,1\n,Bob,2\n").unwrap();
+    let copy = format!("COPY users FROM '{}' HEADER", csv_path.display());
+    assert_eq!(db.execute(&copy).unwrap(), StatementResult::RowsAffected(2));
+    assert_eq!(rows(&mut db, "SELECT * FROM users"), vec![
+        vec![DataType::Int(1), text("Alice"), DataType::Int(30), text("Paris")],
+        vec![DataType::Int(2), text("Bob"), DataType::Null, text("Paris")],
+    ]);
+
+    // WITH HEADER 与 HEADER 相同，表头中的列名不区分前后空格
+    fs::write(&csv_path, " city , id ,name\nRome,3,Carol\n").unwrap();
+    let copy = format!("COPY users FROM '{}' WITH HEADER", csv_path.display());
+    assert_eq!(db.execute(&copy).unwrap(), StatementResult::RowsAffected(1));
+    assert_eq!(rows(&mut db, "SELECT city FROM users WHERE id = 3"), vec![vec![text("Rome")]]);
+
+    // 表头中的列不存在、重复或缺少必填列时不导入任何行
+    let failures = [
+        "id,name,email\n4,Dan,d@example.com\n",
+        "id,name,id\n4,Dan,5\n",
+        "id,age\n4,20\n",
+        "id,name\n4,Dan,extra\n",
+        "id,name\n4,Dan\nfive,Eve\n",
+        "",
+    ];
+    for content in failures {
+        fs::write(&csv_path, content).unwrap();
+        let error = db.execute(&format!("COPY users FROM '{}' HEADER", csv_path.display())).unwrap_err();
+        println!("{:?}: {}", content, error.detailed_message());
+        assert_eq!(rows(&mut db, "SELECT id FROM users").len(), 3);
+    }
+
+    // 导出的文件带有表头，可以按表头导入到列顺序不同的表中
+    let export_path = dir.join("export.csv");
+    db.execute_sql(&format!("COPY users TO '{}'", export_path.display())).unwrap();
+    db.execute_sql("CREATE TABLE people (city VARCHAR(10), name VARCHAR(20), id INT, age INT)").unwrap();
+    let copy = format!("COPY people FROM '{}' HEADER", export_path.display());
+    assert_eq!(db.execute(&copy).unwrap(), StatementResult::RowsAffected(3));
+    assert_eq!(rows(&mut db, "SELECT id, name, age, city FROM people"), rows(&mut db, "SELECT * FROM users"));
+
+    // 不带 HEADER 时仍按位置导入
+    fs::write(&csv_path, "London,Dan,4,40\n").unwrap();
+    db.execute_sql(&format!("COPY people FROM '{}'", csv_path.display())).unwrap();
+    assert_eq!(rows(&mut db, "SELECT name FROM people WHERE id = 4"), vec![vec![text("Dan")]]);
+
+    let _ = fs::remove_dir_all(&dir);
+    println!("按表头导入 CSV 结果正确");
+}
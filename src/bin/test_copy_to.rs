@@ -0,0 +1,60 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::types::DataType;
+use simple_db::StatementResult;
+use std::fs;
+
+fn text(s: &str) -> DataType {
+    DataType::Varchar(s.to_string())
+}
+
+// 执行查询并返回结果行
+fn rows(db: &mut Database, sql: &str) -> Vec<Vec<DataType>> {
+    match db.execute(sql).unwrap() {
+        StatementResult::ResultSet(result) => result.rows,
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+}
+
+fn main() {
+    println!("=== 测试 COPY ... TO 导出 CSV ===");
+    let dir = std::env::temp_dir().join("simple_db_test_copy_to");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let table_path = dir.join("items.csv");
+    let query_path = dir.join("query.csv");
+
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql("CREATE TABLE items (id INT, name VARCHAR(20), note VARCHAR(20))").unwrap();
+    db.execute_sql("INSERT INTO items VALUES (1, 'plain', NULL), (2, 'a, b', 'say \"hi\"'), (3, '', 'NULL')").unwrap();
+
+    // 导出整张表：NULL 为空字段，空字符串和需要转义的字段加引号，字符串 NULL 原样输出
+    let copy = format!("COPY items TO '{}'", table_path.display());
+    assert_eq!(db.execute(&copy).unwrap(), StatementResult::RowsAffected(3));
+    let csv = fs::read_to_string(&table_path).unwrap();
+    print!("{}", csv);
+    assert_eq!(csv, "id,name,note\n1,plain,\n2,\"a, b\",\"say \"\"hi\"\"\"\n3,\"\",NULL\n");
+
+    // 导出查询结果
+    let copy = format!("COPY (SELECT id, note FROM items WHERE id > 1) TO '{}'", query_path.display());
+    assert_eq!(db.execute(&copy).unwrap(), StatementResult::RowsAffected(2));
+    assert_eq!(fs::read_to_string(&query_path).unwrap(), "id,note\n2,\"say \"\"hi\"\"\"\n3,NULL\n");
+
+    // 表达式列的表头与直接查询时相同
+    let copy = format!("COPY (SELECT id * 10, id + 1 AS next FROM items WHERE id = 1) TO '{}'", query_path.display());
+    db.execute(&copy).unwrap();
+    assert_eq!(fs::read_to_string(&query_path).unwrap(), "id * 10,next\n10,2\n");
+
+    // 导入新表后数据一致，再次导出的 CSV 文本相同
+    db.execute_sql("CREATE TABLE copy_items LIKE items").unwrap();
+    db.execute_sql(&format!("COPY copy_items FROM '{}' HEADER", table_path.display())).unwrap();
+    assert_eq!(rows(&mut db, "SELECT * FROM copy_items"), vec![
+        vec![DataType::Int(1), text("plain"), DataType::Null],
+        vec![DataType::Int(2), text("a, b"), text("say \"hi\"")],
+        vec![DataType::Int(3), text(""), text("NULL")],
+    ]);
+    db.execute_sql(&format!("COPY copy_items TO '{}'", query_path.display())).unwrap();
+    assert_eq!(fs::read_to_string(&query_path).unwrap(), csv);
+
+    let _ = fs::remove_dir_all(&dir);
+    println!("COPY ... TO 导出结果正确");
+}
@@ -0,0 +1,78 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::types::DataType;
+use simple_db::StatementResult;
+use std::time::{Duration, Instant};
+
+// 执行查询并返回结果行
+fn rows(db: &mut Database, sql: &str) -> Vec<Vec<DataType>> {
+    match db.execute(sql).unwrap() {
+        StatementResult::ResultSet(result) => result.rows,
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+}
+
+fn count(db: &mut Database, sql: &str) -> i32 {
+    match rows(db, sql)[..] {
+        [ref row] => match row[..] {
+            [DataType::Int(n)] => n,
+            ref other => panic!("期望一个整数，实际为 {:?}", other),
+        },
+        ref other => panic!("期望一行结果，实际为 {:?}", other),
+    }
+}
+
+// 多次执行取最短耗时，减少偶然的干扰
+fn fastest(db: &mut Database, sql: &str) -> Duration {
+    (0..3).map(|_| {
+        let start = Instant::now();
+        db.execute(sql).unwrap();
+        start.elapsed()
+    }).min().unwrap()
+}
+
+fn main() {
+    println!("=== 测试 COUNT(*) 的快速路径 ===");
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql("CREATE TABLE t (id INT, grp INT, name VARCHAR(10))").unwrap();
+    assert_eq!(count(&mut db, "SELECT COUNT(*) FROM t"), 0);
+    assert_eq!(count(&mut db, "SELECT COUNT(*) FROM t WHERE id > 0"), 0);
+
+    db.execute_sql("INSERT INTO t VALUES (1, 1, 'a'), (2, 1, NULL), (3, 2, 'c'), (4, 2, 'd'), (5, 3, NULL)").unwrap();
+    assert_eq!(count(&mut db, "SELECT COUNT(*) FROM t"), 5);
+    assert_eq!(count(&mut db, "SELECT COUNT(*) FROM t WHERE grp = 2 OR id = 5"), 3);
+    assert_eq!(count(&mut db, "SELECT COUNT(*) FROM t WHERE name IS NULL"), 2);
+    assert_eq!(count(&mut db, "SELECT COUNT(*) FROM t WHERE id > 10"), 0);
+
+    // 其他聚合、分组和 HAVING 仍走原来的路径
+    assert_eq!(count(&mut db, "SELECT COUNT(name) FROM t"), 3);
+    assert_eq!(rows(&mut db, "SELECT COUNT(*), MAX(id) FROM t"), vec![vec![DataType::Int(5), DataType::Int(5)]]);
+    assert_eq!(rows(&mut db, "SELECT COUNT(*) FROM t GROUP BY grp").len(), 3);
+    assert_eq!(count(&mut db, "SELECT COUNT(*) FROM t HAVING COUNT(*) > 1"), 5);
+    assert!(rows(&mut db, "SELECT COUNT(*) FROM t HAVING COUNT(*) > 10").is_empty());
+
+    // 条件出错时仍然报错，删除后计数随之变化
+    assert!(db.execute("SELECT COUNT(*) FROM t WHERE missing = 1").is_err());
+    db.execute_sql("DELETE FROM t WHERE grp = 1").unwrap();
+    assert_eq!(count(&mut db, "SELECT COUNT(*) FROM t"), 3);
+    db.execute_sql("SELECT COUNT(*) FROM t WHERE grp = 2").unwrap();
+    assert!(db.execute("SELECT COUNT(*) FROM missing").is_err());
+
+    // 大表上没有 WHERE 的计数不需要遍历行，比取出所有行快得多
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql("CREATE TABLE big (id INT, value INT)").unwrap();
+    let total = 5_000;
+    for batch in 0..total / 1000 {
+        let values: Vec<String> = (0..1000).map(|i| format!("({}, {})", batch * 1000 + i, i % 7)).collect();
+        db.execute_sql(&format!("INSERT INTO big VALUES {}", values.join(", "))).unwrap();
+    }
+    assert_eq!(count(&mut db, "SELECT COUNT(*) FROM big"), total);
+    assert_eq!(count(&mut db, "SELECT COUNT(*) FROM big WHERE value = 0"), (0..total).filter(|i| i % 1000 % 7 == 0).count() as i32);
+
+    let count_time = fastest(&mut db, "SELECT COUNT(*) FROM big");
+    let filtered_count_time = fastest(&mut db, "SELECT COUNT(*) FROM big WHERE value = 0");
+    let select_time = fastest(&mut db, "SELECT id, value FROM big");
+    println!("COUNT(*): {:?}，带条件的 COUNT(*): {:?}，取出所有行: {:?}", count_time, filtered_count_time, select_time);
+    assert!(count_time * 10 < select_time);
+    assert!(filtered_count_time < select_time);
+    println!("COUNT(*) 的快速路径结果正确");
+}
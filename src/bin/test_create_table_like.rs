@@ -0,0 +1,48 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::types::DataType;
+use simple_db::StatementResult;
+
+fn main() {
+    println!("=== 测试 CREATE TABLE ... LIKE ===");
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql("CREATE TABLE users (id INT PRIMARY KEY, name VARCHAR(20) NOT NULL DEFAULT 'x' COMMENT 'name', age INT) MAX_ROWS = 10 COMMENT = 'people' ROW_VERSION = ON").unwrap();
+    db.execute_sql("INSERT INTO users VALUES (1, 'Alice', 30), (2, 'Bob', NULL)").unwrap();
+    db.execute_sql("CREATE INDEX idx_users_name ON users (name)").unwrap();
+
+    // 复制后的表结构与原表相同，但没有数据
+    assert_eq!(db.execute("CREATE TABLE users_staging LIKE users").unwrap(), StatementResult::Created);
+    let original = db.get_table("users").unwrap().unwrap().clone();
+    let copy = db.get_table("users_staging").unwrap().unwrap();
+    assert_eq!(copy.columns, original.columns);
+    assert_eq!(copy.max_rows, Some(10));
+    assert_eq!(copy.comment.as_deref(), Some("people"));
+    assert!(copy.has_row_versions());
+    assert!(copy.rows.is_empty());
+    assert!(copy.indexes.is_empty());
+    // 建表语句只有表名不同，原表的索引不复制
+    let original_sql = db.schema_sql(Some("users")).unwrap();
+    assert_eq!(
+        db.schema_sql(Some("users_staging")).unwrap(),
+        format!("{}\n", original_sql.lines().next().unwrap().replacen("users", "users_staging", 1))
+    );
+
+    // 原表不受影响，复制出的表可以正常写入并保留约束
+    assert_eq!(original.rows.len(), 2);
+    db.execute_sql("INSERT INTO users_staging (id, age) VALUES (7, 1)").unwrap();
+    assert_eq!(
+        db.get_row("users_staging", 0).unwrap(),
+        Some(vec![DataType::Int(7), DataType::Varchar("x".to_string()), DataType::Int(1)])
+    );
+    assert!(db.execute("INSERT INTO users_staging VALUES (7, 'dup', 2)").is_err());
+    assert_eq!(db.get_table("users").unwrap().unwrap().rows.len(), 2);
+
+    // 原表不存在或目标表已存在时报错
+    let error = db.execute("CREATE TABLE backup LIKE missing").unwrap_err();
+    println!("原表不存在: {}", error.detailed_message());
+    assert!(db.get_table("backup").unwrap().is_none());
+    let error = db.execute("CREATE TABLE users_staging LIKE users").unwrap_err();
+    println!("目标表已存在: {}", error.detailed_message());
+    assert_eq!(db.get_table("users_staging").unwrap().unwrap().rows.len(), 1);
+    assert!(db.execute("CREATE TABLE backup LIKE").is_err());
+    println!("CREATE TABLE ... LIKE 结果正确");
+}
@@ -0,0 +1,56 @@
+use simple_db::core::clock::fixed_clock;
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::types::DataType;
+use simple_db::StatementResult;
+use std::time::{Duration, UNIX_EPOCH};
+
+fn text(s: &str) -> DataType {
+    DataType::Varchar(s.to_string())
+}
+
+// 执行查询并返回结果行
+fn rows(db: &mut Database, sql: &str) -> Vec<Vec<DataType>> {
+    match db.execute(sql).unwrap() {
+        StatementResult::ResultSet(result) => result.rows,
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+}
+
+fn main() {
+    println!("=== 测试 CURRENT_DATE 和 CURRENT_TIMESTAMP ===");
+    let mut db = Database::new(StorageType::Memory);
+    // 2024-02-29 13:45:30 UTC
+    db.set_clock(fixed_clock(UNIX_EPOCH + Duration::from_secs(1_709_214_330)));
+
+    // 没有 FROM 的查询，可以带空括号
+    assert_eq!(
+        rows(&mut db, "SELECT CURRENT_DATE, CURRENT_TIMESTAMP, current_date()"),
+        vec![vec![text("2024-02-29"), text("2024-02-29 13:45:30"), text("2024-02-29")]]
+    );
+
+    // 在 INSERT、UPDATE 和 WHERE 中使用
+    db.execute_sql("CREATE TABLE log (id INT, day VARCHAR(10), at VARCHAR(19))").unwrap();
+    db.execute_sql("INSERT INTO log VALUES (1, CURRENT_DATE, NULL), (2, '2000-01-01', NULL)").unwrap();
+    assert_eq!(db.execute("UPDATE log SET at = CURRENT_TIMESTAMP WHERE day = CURRENT_DATE").unwrap(), StatementResult::RowsAffected(1));
+    assert_eq!(
+        rows(&mut db, "SELECT * FROM log"),
+        vec![
+            vec![DataType::Int(1), text("2024-02-29"), text("2024-02-29 13:45:30")],
+            vec![DataType::Int(2), text("2000-01-01"), DataType::Null],
+        ]
+    );
+    assert_eq!(rows(&mut db, "SELECT id, CURRENT_DATE FROM log WHERE id = 2"), vec![vec![DataType::Int(2), text("2024-02-29")]]);
+
+    // 打印路径和迭代器查询使用同一个时钟
+    db.execute_sql("SELECT CURRENT_TIMESTAMP").unwrap();
+    let row = db.query_iter("SELECT CURRENT_TIMESTAMP").unwrap().next().unwrap().unwrap();
+    assert_eq!(row, vec![text("2024-02-29 13:45:30")]);
+
+    // 日期跨天时按 UTC 计算
+    db.set_clock(fixed_clock(UNIX_EPOCH + Duration::from_secs(1_709_251_199)));
+    assert_eq!(rows(&mut db, "SELECT CURRENT_DATE, CURRENT_TIMESTAMP"), vec![vec![text("2024-02-29"), text("2024-02-29 23:59:59")]]);
+
+    // 列的 DEFAULT 只支持 CURRENT_TIMESTAMP
+    assert!(db.execute("CREATE TABLE bad (d VARCHAR(10) DEFAULT CURRENT_DATE)").is_err());
+    println!("CURRENT_DATE 和 CURRENT_TIMESTAMP 结果正确");
+}
@@ -0,0 +1,48 @@
+use simple_db::core::clock::fixed_clock;
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::types::{ColumnDefault, DataType};
+use std::fs;
+use std::time::{Duration, UNIX_EPOCH};
+
+fn text(s: &str) -> DataType {
+    DataType::Varchar(s.to_string())
+}
+
+fn main() {
+    println!("=== 测试 DEFAULT CURRENT_TIMESTAMP ===");
+    let dir = std::env::temp_dir().join("simple_db_test_default_timestamp");
+    let _ = fs::remove_dir_all(&dir);
+
+    let mut db = Database::new(StorageType::File(dir.clone()));
+    // 2023-11-14 22:13:20 UTC
+    db.set_clock(fixed_clock(UNIX_EPOCH + Duration::from_secs(1_700_000_000)));
+    db.execute_sql("CREATE TABLE events (id INT, created_at VARCHAR(19) DEFAULT CURRENT_TIMESTAMP)").unwrap();
+
+    // 省略该列时使用注入的时钟，显式给出的值不受影响
+    db.execute_sql("INSERT INTO events (id) VALUES (1)").unwrap();
+    db.execute_sql("INSERT INTO events VALUES (2)").unwrap();
+    db.execute_sql("INSERT INTO events VALUES (3, '2000-01-01 00:00:00')").unwrap();
+    db.set_clock(fixed_clock(UNIX_EPOCH + Duration::from_secs(1_700_000_061)));
+    db.execute_sql("INSERT INTO events (id) VALUES (4)").unwrap();
+    let expected = vec![
+        vec![DataType::Int(1), text("2023-11-14 22:13:20")],
+        vec![DataType::Int(2), text("2023-11-14 22:13:20")],
+        vec![DataType::Int(3), text("2000-01-01 00:00:00")],
+        vec![DataType::Int(4), text("2023-11-14 22:14:21")],
+    ];
+    assert_eq!(db.get_table("events").unwrap().unwrap().rows, expected);
+
+    // 列的默认值和插入的时间在保存后重新加载时保持不变
+    drop(db);
+    let mut db = Database::new(StorageType::File(dir.clone()));
+    db.load().unwrap();
+    let table = db.get_table("events").unwrap().unwrap();
+    assert_eq!(table.columns[1].default, Some(ColumnDefault::CurrentTimestamp));
+    assert_eq!(table.rows, expected);
+    db.set_clock(fixed_clock(UNIX_EPOCH));
+    db.execute_sql("INSERT INTO events (id) VALUES (5)").unwrap();
+    assert_eq!(db.get_table("events").unwrap().unwrap().rows[4], vec![DataType::Int(5), text("1970-01-01 00:00:00")]);
+
+    let _ = fs::remove_dir_all(&dir);
+    println!("DEFAULT CURRENT_TIMESTAMP 使用注入的时钟");
+}
@@ -0,0 +1,76 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::sql::{parse_delimiter_command, split_statements_with_delimiter, ScriptItem, ScriptSplitter};
+use simple_db::core::types::DataType;
+use simple_db::{run_script, ScriptSummary, StatementResult};
+
+const SCRIPT: &str = "\
+CREATE TABLE notes (id INT, body VARCHAR(50));
+DELIMITER //
+INSERT INTO notes VALUES (1, 'a; b')//
+INSERT INTO notes VALUES (2, 'x // y') //
+DELIMITER ;
+INSERT INTO notes VALUES (3, 'back');
+";
+
+// 查询全部行
+fn rows(db: &mut Database, sql: &str) -> Vec<Vec<DataType>> {
+    match db.execute(sql).unwrap() {
+        StatementResult::ResultSet(result) => result.rows,
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+}
+
+fn main() {
+    println!("=== 测试修改语句分隔符 ===");
+    // 多字符分隔符，引号中的分隔符不拆分
+    assert_eq!(
+        split_statements_with_delimiter("SELECT 1; SELECT 2 // SELECT '//' //", "//"),
+        vec!["SELECT 1; SELECT 2", "SELECT '//'"]
+    );
+    assert_eq!(split_statements_with_delimiter("a;b", ";"), vec!["a", "b"]);
+
+    // DELIMITER 命令
+    assert_eq!(parse_delimiter_command("DELIMITER //"), Some("//"));
+    assert_eq!(parse_delimiter_command("  set delimiter $$ "), Some("$$"));
+    assert_eq!(parse_delimiter_command("SET DELIMITER = //"), None);
+    assert_eq!(parse_delimiter_command("DELIMITER"), None);
+    assert_eq!(parse_delimiter_command("DELIMITERS //"), None);
+    assert_eq!(parse_delimiter_command("SELECT * FROM delimiter"), None);
+
+    // 拆分器在调用方确认后才切换分隔符
+    let mut splitter = ScriptSplitter::new("DELIMITER //\nSELECT 1; SELECT 2//", ";");
+    assert_eq!(splitter.next(), Some(ScriptItem::Delimiter("//".to_string())));
+    splitter.set_delimiter("//");
+    assert_eq!(splitter.next(), Some(ScriptItem::Statement("SELECT 1; SELECT 2".to_string())));
+    assert_eq!(splitter.next(), None);
+
+    // 脚本中切换到 // 再恢复为分号
+    let mut db = Database::new(StorageType::Memory);
+    let summary = run_script(&mut db, SCRIPT, true);
+    println!("{:?}", summary);
+    assert_eq!(summary, ScriptSummary { succeeded: 6, failed: 0, first_error: None });
+    assert_eq!(db.delimiter(), ";");
+    assert_eq!(rows(&mut db, "SELECT body FROM notes"), vec![
+        vec![DataType::Varchar("a; b".to_string())],
+        vec![DataType::Varchar("x // y".to_string())],
+        vec![DataType::Varchar("back".to_string())],
+    ]);
+
+    // SET delimiter 同样对之后的语句生效，execute_many 使用当前的分隔符
+    let mut db = Database::new(StorageType::Memory);
+    let summary = run_script(&mut db, "SET delimiter = $$;\nCREATE TABLE t (id INT)$$ INSERT INTO t VALUES (1)$$", true);
+    assert!(summary.is_success());
+    assert_eq!(db.delimiter(), "$$");
+    let results = db.execute_many("INSERT INTO t VALUES (2)$$ INSERT INTO t VALUES (3)", true);
+    assert!(results.iter().all(|r| r.is_ok()));
+    assert_eq!(rows(&mut db, "SELECT * FROM t").len(), 3);
+
+    // 无效的分隔符不生效
+    assert!(db.set_delimiter("").is_err());
+    assert!(db.set_delimiter("' '").is_err());
+    assert!(db.set_option("delimiter", "a b").is_err());
+    assert_eq!(db.delimiter(), "$$");
+    db.set_option("delimiter", ";").unwrap();
+    assert_eq!(db.delimiter(), ";");
+    println!("语句分隔符切换正确");
+}
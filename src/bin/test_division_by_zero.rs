@@ -0,0 +1,39 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::types::DataType;
+
+// 执行失败时返回详细错误信息
+fn error_of(db: &mut Database, sql: &str) -> String {
+    let message = db.execute(sql)
+        .expect_err(&format!("期望执行失败: {}", sql))
+        .detailed_message();
+    println!("{} -> {}", sql, message);
+    message
+}
+
+fn main() {
+    println!("=== 测试除数为零 ===");
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql("CREATE TABLE t (x INT, y INT, f FLOAT)").unwrap();
+    db.execute_sql("INSERT INTO t VALUES (4, 2, 1.5), (6, 3, 2.5)").unwrap();
+
+    // WHERE 中的除零错误指出出错的表达式
+    assert_eq!(error_of(&mut db, "SELECT * FROM t WHERE x / (y - y) = 1"), "SQL语法错误: 除数不能为零: x / (y - y)");
+    assert_eq!(error_of(&mut db, "SELECT x FROM t WHERE f / 0.0 > 1.0"), "SQL语法错误: 除数不能为零: f / 0.0");
+    assert!(error_of(&mut db, "SELECT x FROM t WHERE 1 + x / (y * 0) = 1").ends_with("x / (y * 0)"));
+
+    // 打印结果的执行路径同样返回错误
+    let message = db.execute_sql("SELECT * FROM t WHERE x / (y - y) = 1").unwrap_err().detailed_message();
+    assert_eq!(message, "SQL语法错误: 除数不能为零: x / (y - y)");
+
+    // 投影、UPDATE 和 DELETE 中出错时不修改数据
+    assert!(error_of(&mut db, "SELECT x / (y - 2) FROM t").ends_with("x / (y - 2)"));
+    assert!(error_of(&mut db, "UPDATE t SET x = 0 WHERE x / (y - y) = 1").contains("除数不能为零"));
+    assert!(error_of(&mut db, "DELETE FROM t WHERE x / (y - y) = 1").contains("除数不能为零"));
+    let rows = db.query_iter("SELECT x FROM t").unwrap().collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(rows, vec![vec![DataType::Int(4)], vec![DataType::Int(6)]]);
+
+    // 除数不为零时正常计算
+    let rows = db.query_iter("SELECT x FROM t WHERE x / (y - 1) = 3").unwrap().collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(rows, vec![vec![DataType::Int(6)]]);
+    println!("除数为零时返回错误");
+}
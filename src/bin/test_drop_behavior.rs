@@ -0,0 +1,43 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::error::DbError;
+use simple_db::StatementResult;
+
+fn main() {
+    println!("=== 测试 DROP TABLE 的 CASCADE / RESTRICT ===");
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql("CREATE TABLE keep (id INT)").unwrap();
+
+    // 单个表和多个表都可以带 CASCADE 或 RESTRICT，关键字不区分大小写
+    for (create, drop) in [
+        (vec!["tmp"], "DROP TABLE tmp CASCADE"),
+        (vec!["tmp"], "DROP TABLE tmp RESTRICT"),
+        (vec!["tmp"], "drop table tmp cascade"),
+        (vec!["a", "b"], "DROP TABLE a, b CASCADE"),
+        (vec!["a", "b"], "DROP TABLE a, b RESTRICT;"),
+    ] {
+        for name in &create {
+            db.execute_sql(&format!("CREATE TABLE {} (id INT)", name)).unwrap();
+        }
+        assert_eq!(db.execute(drop).unwrap(), StatementResult::Dropped, "{}", drop);
+        assert_eq!(db.list_tables().unwrap(), vec!["keep"], "{}", drop);
+        println!("{} -> 已删除", drop);
+    }
+
+    // 删除后的表不能再查询
+    assert!(matches!(db.execute("SELECT * FROM tmp"), Err(DbError::TableError(_))));
+
+    // 无法识别的选项或多个选项报错，表不被删除
+    for sql in [
+        "DROP TABLE keep PURGE",
+        "DROP TABLE keep CASCADE RESTRICT",
+        "DROP TABLE keep CASCADE CASCADE",
+    ] {
+        match db.execute(sql) {
+            Err(DbError::SqlError(message)) => println!("{} -> {}", sql, message),
+            other => panic!("{} 应返回语法错误: {:?}", sql, other),
+        }
+        assert_eq!(db.list_tables().unwrap(), vec!["keep"], "{}", sql);
+    }
+
+    println!("测试通过");
+}
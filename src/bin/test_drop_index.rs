@@ -0,0 +1,96 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::error::DbError;
+use simple_db::core::types::DataType;
+use simple_db::StatementResult;
+use std::fs;
+
+fn text(s: &str) -> DataType {
+    DataType::Varchar(s.to_string())
+}
+
+// 执行查询并返回结果行
+fn rows(db: &mut Database, sql: &str) -> Vec<Vec<DataType>> {
+    match db.execute(sql).unwrap_or_else(|e| panic!("执行失败 {}: {}", sql, e.detailed_message())) {
+        StatementResult::ResultSet(result) => result.rows,
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+}
+
+// EXPLAIN 中读取表的方式
+fn scan(db: &mut Database, sql: &str) -> Vec<DataType> {
+    rows(db, &format!("EXPLAIN {}", sql)).remove(0)[1..].to_vec()
+}
+
+fn main() {
+    println!("=== 测试列出和删除索引 ===");
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql("CREATE TABLE items (id INT PRIMARY KEY, price INT, name VARCHAR(20))").unwrap();
+    db.execute_sql("INSERT INTO items VALUES (1, 10, 'pen'), (2, 25, 'book'), (3, 40, 'lamp')").unwrap();
+    db.execute_sql("CREATE INDEX idx_price ON items (price)").unwrap();
+    db.execute_sql("CREATE INDEX idx_name ON items (name)").unwrap();
+
+    // 按创建顺序列出索引
+    let result = db.execute("SHOW INDEXES FROM items").unwrap();
+    println!("{:?}", result);
+    match result {
+        StatementResult::ResultSet(result) => {
+            assert_eq!(result.columns, vec!["Name", "Column", "Type"]);
+            assert_eq!(result.rows, vec![
+                vec![text("idx_price"), text("price"), text("BTREE")],
+                vec![text("idx_name"), text("name"), text("BTREE")],
+            ]);
+        }
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+    assert_eq!(rows(&mut db, "show index in items").len(), 2);
+    db.execute_sql("SHOW INDEXES FROM items").unwrap();
+
+    // 删除前使用索引
+    let query = "SELECT name FROM items WHERE price > 20";
+    assert_eq!(scan(&mut db, query), vec![text("index range scan"), text("items using idx_price")]);
+    assert_eq!(rows(&mut db, query), vec![vec![text("book")], vec![text("lamp")]]);
+
+    // 删除后回到全表扫描，结果不变
+    assert_eq!(db.execute("DROP INDEX idx_price").unwrap(), StatementResult::Dropped);
+    assert_eq!(rows(&mut db, "SHOW INDEXES FROM items"), vec![vec![text("idx_name"), text("name"), text("BTREE")]]);
+    assert_eq!(scan(&mut db, query), vec![text("full scan"), text("items")]);
+    assert_eq!(rows(&mut db, query), vec![vec![text("book")], vec![text("lamp")]]);
+
+    // 删除后数据修改，查询结果仍然正确
+    db.execute_sql("UPDATE items SET price = 50 WHERE id = 1").unwrap();
+    assert_eq!(rows(&mut db, query), vec![vec![text("pen")], vec![text("book")], vec![text("lamp")]]);
+
+    // 同名索引可以重新创建
+    db.execute_sql("CREATE INDEX idx_price ON items (price)").unwrap();
+    assert_eq!(scan(&mut db, query), vec![text("index range scan"), text("items using idx_price")]);
+    assert_eq!(rows(&mut db, "SELECT id FROM items WHERE price >= 40"), vec![vec![DataType::Int(1)], vec![DataType::Int(3)]]);
+
+    // 删除不存在的索引报错
+    db.execute_sql("DROP INDEX idx_price").unwrap();
+    let error = db.execute("DROP INDEX idx_price").unwrap_err();
+    println!("删除不存在的索引: {}", error.detailed_message());
+    assert!(matches!(&error, DbError::TableError(message) if message == "索引 idx_price 不存在"));
+    assert!(db.execute("SHOW INDEXES FROM missing").is_err());
+    assert!(db.execute("SHOW TABLES").is_err());
+    assert!(db.execute("DROP INDEX").is_err());
+
+    // 没有索引的表
+    db.execute_sql("CREATE TABLE plain (id INT)").unwrap();
+    assert!(rows(&mut db, "SHOW INDEXES FROM plain").is_empty());
+
+    // 删除索引后重新打开数据库，索引不再存在
+    let base_dir = std::env::temp_dir().join("simple_db_test_drop_index");
+    let _ = fs::remove_dir_all(&base_dir);
+    let mut db = Database::new(StorageType::File(base_dir.clone()));
+    db.execute_sql("CREATE TABLE items (id INT, price INT)").unwrap();
+    db.execute_sql("CREATE INDEX idx_a ON items (id)").unwrap();
+    db.execute_sql("CREATE INDEX idx_b ON items (price)").unwrap();
+    db.execute_sql("DROP INDEX idx_a").unwrap();
+    drop(db);
+    let mut db = Database::new(StorageType::File(base_dir.clone()));
+    db.load().unwrap();
+    assert_eq!(rows(&mut db, "SHOW INDEXES FROM items"), vec![vec![text("idx_b"), text("price"), text("BTREE")]]);
+
+    let _ = fs::remove_dir_all(&base_dir);
+    println!("列出和删除索引结果正确");
+}
@@ -0,0 +1,45 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::types::DataType;
+use simple_db::StatementResult;
+
+fn text(s: &str) -> DataType {
+    DataType::Varchar(s.to_string())
+}
+
+// 执行查询并返回结果行
+fn rows(db: &mut Database, sql: &str) -> Vec<Vec<DataType>> {
+    match db.execute(sql).unwrap() {
+        StatementResult::ResultSet(result) => result.rows,
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+}
+
+fn main() {
+    println!("=== 测试字符串中的引号转义 ===");
+    let mut db = Database::new(StorageType::Memory);
+    assert_eq!(rows(&mut db, "SELECT 'it''s', \"say \"\"hi\"\"\", ''''"), vec![vec![text("it's"), text("say \"hi\""), text("'")]]);
+    assert_eq!(DataType::Varchar("say \"it's\"".to_string()).to_sql_literal(), "'say \"it''s\"'");
+    println!("引号转义正确");
+
+    println!("\n=== 测试导出后导入 ===");
+    db.execute_sql("CREATE TABLE quotes (id INT PRIMARY KEY, body VARCHAR(50), score FLOAT)").unwrap();
+    db.execute_sql("CREATE INDEX idx_score ON quotes (score)").unwrap();
+    db.execute_sql("CREATE TABLE empty (id INT)").unwrap();
+    db.execute_sql("INSERT INTO quotes VALUES (1, 'say \"it''s\"', 1.5), (2, 'end;\nnext', NULL), (3, NULL, 2.0), (4, '', 0.25)").unwrap();
+
+    let dump = db.dump_sql().unwrap();
+    print!("{}", dump);
+    let mut restored = Database::new(StorageType::Memory);
+    for result in restored.execute_many(&dump, true) {
+        result.expect("导入导出语句失败");
+    }
+    for name in ["empty", "quotes"] {
+        let original = db.get_table(name).unwrap().unwrap();
+        let copy = restored.get_table(name).unwrap().unwrap();
+        assert_eq!(original.columns, copy.columns, "表 {} 结构不一致", name);
+        assert_eq!(original.rows, copy.rows, "表 {} 数据不一致", name);
+        assert_eq!(original.indexes, copy.indexes, "表 {} 索引不一致", name);
+    }
+    assert_eq!(restored.dump_sql().unwrap(), dump);
+    println!("导出导入结果一致");
+}
@@ -0,0 +1,52 @@
+use simple_db::core::db::{Database, ErrorDisplayMode, StorageType};
+use simple_db::core::error::{DbError, Locale};
+use simple_db::core::types::TypeError;
+
+fn main() {
+    println!("=== 测试错误信息的语言 ===");
+    let mut db = Database::new(StorageType::Memory);
+    let error = db.execute("SELECT * FROM missing").unwrap_err();
+
+    // 表不存在的错误在两种语言下的信息
+    assert_eq!(error.detailed_message_in(Locale::English), "Table error: table missing does not exist");
+    assert_eq!(error.detailed_message_in(Locale::Chinese), "表错误: 表 missing 不存在");
+    assert_eq!(error.brief_message_in(Locale::English), "Error: Table error");
+    assert_eq!(error.brief_message_in(Locale::Chinese), "错误: 表错误");
+
+    // 未设置语言时保持原有信息
+    assert_eq!(db.locale(), None);
+    assert_eq!(db.format_error(&error), "Error: Table error");
+    db.set_error_mode(ErrorDisplayMode::Detailed);
+    assert_eq!(db.format_error(&error), "表错误: 表 missing 不存在");
+
+    // 通过选项切换语言
+    db.set_option("locale", "en").unwrap();
+    assert_eq!(db.locale(), Some(Locale::English));
+    assert_eq!(db.format_error(&error), "Table error: table missing does not exist");
+    db.set_error_mode(ErrorDisplayMode::Brief);
+    assert_eq!(db.format_error(&error), "Error: Table error");
+    db.set_option("locale", "ZH").unwrap();
+    assert_eq!(db.format_error(&error), "错误: 表错误");
+    db.set_option("locale", "default").unwrap();
+    assert_eq!(db.locale(), None);
+    assert!(db.set_option("locale", "fr").is_err());
+
+    // 带多个占位符的内容和语法错误
+    let error = DbError::SqlError("列 age 在表 users 中不存在".to_string());
+    assert_eq!(error.detailed_message_in(Locale::English), "Syntax error: column age does not exist in table users");
+    assert_eq!(error.brief_message_in(Locale::English), "Error: Syntax error");
+    assert_eq!(error.detailed_message_in(Locale::Chinese), "SQL语法错误: 列 age 在表 users 中不存在");
+
+    // 原本是英文的信息在中文下翻译，不认识的内容原样保留
+    let error = DbError::TransactionError("Transaction is not active".to_string());
+    assert_eq!(error.detailed_message_in(Locale::Chinese), "事务错误: 事务未激活");
+    assert_eq!(error.detailed_message_in(Locale::English), "Transaction error: Transaction is not active");
+    let error = DbError::SqlError("期望MAX_ROWS的行数".to_string());
+    assert_eq!(error.detailed_message_in(Locale::English), "Syntax error: 期望MAX_ROWS的行数");
+
+    // 类型错误
+    let error = DbError::TypeError(TypeError::PrimaryKeyViolation("1".to_string()));
+    assert_eq!(error.brief_message_in(Locale::English), "Error: Duplicate entry '1' for key 'PRIMARY'");
+    assert_eq!(error.brief_message_in(Locale::Chinese), "错误: 主键 '1' 重复");
+    println!("错误信息的语言切换正确");
+}
@@ -0,0 +1,54 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::types::DataType;
+use simple_db::StatementResult;
+
+// 执行 EXPLAIN，返回每一步的 (操作, 说明)
+fn plan(db: &mut Database, sql: &str) -> Vec<(String, String)> {
+    match db.execute(&format!("EXPLAIN {}", sql)).unwrap() {
+        StatementResult::ResultSet(result) => {
+            assert_eq!(result.columns, vec!["id", "operation", "detail"]);
+            result.rows.into_iter().enumerate().map(|(i, row)| {
+                assert_eq!(row[0], DataType::Int(i as i32 + 1));
+                (row[1].to_string(), row[2].to_string())
+            }).collect()
+        }
+        other => panic!("EXPLAIN 应返回结果集: {:?}", other),
+    }
+}
+
+fn step(operation: &str, detail: &str) -> (String, String) {
+    (operation.to_string(), detail.to_string())
+}
+
+fn main() {
+    println!("=== 测试 EXPLAIN ===");
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql("CREATE TABLE users (id INT PRIMARY KEY, name VARCHAR(20), age INT)").unwrap();
+    db.execute_sql("INSERT INTO users VALUES (1, 'Alice', 30), (2, 'Bob', 25)").unwrap();
+
+    // 没有索引时按条件过滤需要全表扫描
+    let query = "SELECT name FROM users WHERE age > 26";
+    let without_index = plan(&mut db, query);
+    println!("{:?}", without_index);
+    assert_eq!(without_index, vec![
+        step("full scan", "users"),
+        step("filter", "age > 26"),
+        step("project", "name"),
+    ]);
+
+    // 建立索引后同一查询改用索引区间扫描
+    db.execute_sql("CREATE INDEX idx_age ON users (age)").unwrap();
+    let with_index = plan(&mut db, query);
+    println!("{:?}", with_index);
+    assert_eq!(with_index[0], step("index range scan", "users using idx_age"));
+
+    // 主键等值条件直接按主键查找，没有条件时仍是全表扫描
+    assert_eq!(plan(&mut db, "SELECT * FROM users WHERE id = 2")[0], step("primary key lookup", "users using id"));
+    assert_eq!(plan(&mut db, "SELECT * FROM users")[0], step("full scan", "users"));
+
+    // 删除索引后回到全表扫描
+    db.execute_sql("DROP INDEX idx_age").unwrap();
+    assert_eq!(plan(&mut db, query)[0], step("full scan", "users"));
+
+    println!("测试通过");
+}
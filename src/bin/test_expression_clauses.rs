@@ -0,0 +1,28 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::error::DbError;
+
+// 没有 FROM 的表达式查询带子句时，两种执行方式都返回同样的语法错误
+fn assert_clause_rejected(db: &mut Database, sql: &str, expected: &str) {
+    for error in [db.execute(sql).unwrap_err(), db.execute_sql(sql).unwrap_err()] {
+        match error {
+            DbError::SqlError(message) => assert_eq!(message, expected, "{}", sql),
+            other => panic!("{} 期望 SQL 错误，实际为 {:?}", sql, other),
+        }
+    }
+    assert!(db.query_iter(sql).is_err(), "{}", sql);
+    println!("{} -> {}", sql, expected);
+}
+
+fn main() {
+    println!("=== 测试表达式查询不支持的子句 ===");
+    let mut db = Database::new(StorageType::Memory);
+
+    assert_clause_rejected(&mut db, "SELECT 1 WHERE x = 1", "表达式查询不支持 WHERE 子句");
+    assert_clause_rejected(&mut db, "SELECT 1 + 2, 'a' WHERE 1 = 1", "表达式查询不支持 WHERE 子句");
+    assert_clause_rejected(&mut db, "SELECT 1 ORDER BY 1", "表达式查询不支持 ORDER BY 子句");
+    assert_clause_rejected(&mut db, "SELECT 1 GROUP BY 1", "表达式查询不支持 GROUP BY 子句");
+
+    // 不带子句的表达式查询不受影响
+    assert!(db.execute("SELECT 1").is_ok());
+    println!("测试通过");
+}
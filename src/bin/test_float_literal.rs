@@ -0,0 +1,39 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::sql::{Lexer, Token};
+use simple_db::core::types::DataType;
+
+// 词法分析得到的第一个记号
+fn first_token(sql: &str) -> Token {
+    let tokens = Lexer::new().tokenize(sql).unwrap_or_else(|e| panic!("词法分析失败 {}: {}", sql, e.detailed_message()));
+    println!("{} -> {:?}", sql, tokens[0]);
+    tokens[0].clone()
+}
+
+fn main() {
+    println!("=== 测试科学计数法浮点数 ===");
+    assert_eq!(first_token("1.5e3"), Token::Float(1500.0));
+    assert_eq!(first_token("2E-4"), Token::Float(0.0002));
+    assert_eq!(first_token("3e+2"), Token::Float(300.0));
+    assert_eq!(first_token("1e0"), Token::Float(1.0));
+    assert_eq!(first_token(".5e1"), Token::Float(5.0));
+    assert_eq!(first_token("12"), Token::Number(12));
+
+    // 指数部分缺少数字时报错
+    for sql in ["1e", "1e+", "2.5E-", "1ex"] {
+        let result = Lexer::new().tokenize(sql);
+        println!("{} -> {:?}", sql, result.as_ref().map_err(|e| e.detailed_message()));
+        assert!(result.is_err());
+    }
+
+    // 在语句中使用
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql("CREATE TABLE plants (id INT, height FLOAT)").unwrap();
+    db.execute_sql("INSERT INTO plants VALUES (1, 1.2e2), (2, 3.5E-1), (3, 8e1)").unwrap();
+    let rows = db.query_iter("SELECT id, height FROM plants WHERE height > 1e2")
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    println!("height > 1e2 -> {:?}", rows);
+    assert_eq!(rows, vec![vec![DataType::Int(1), DataType::Float(120.0)]]);
+    println!("科学计数法浮点数解析正确");
+}
@@ -0,0 +1,192 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::sql::{OutputFormat, TableFormatter, TableStyle};
+use simple_db::core::types::DataType;
+
+fn strings(items: &[&str]) -> Vec<String> {
+    items.iter().map(|s| s.to_string()).collect()
+}
+
+fn main() {
+    println!("=== 测试表格最大列宽 ===");
+    let headers = strings(&["id", "description"]);
+    let long = "a very long description that does not fit";
+    let rows = vec![strings(&["1", long]), strings(&["2", "short"]), strings(&["3", "NULL"])];
+
+    // 不限制宽度时完整输出
+    let table = TableFormatter::format_table(&headers, &rows);
+    print!("{}", table);
+    assert!(table.contains(long));
+
+    // 超过最大宽度的单元格截断为恰好 max_width 列，并以 '…' 结尾，较短的单元格不变
+    let table = TableFormatter::format_table_with_max_width(&headers, &rows, Some(10));
+    print!("{}", table);
+    let lines: Vec<&str> = table.lines().collect();
+    assert_eq!(lines[0], "| id  | descripti… |");
+    assert_eq!(lines[1], "| --- | ---------- |");
+    assert_eq!(lines[2], "| 1   | a very lo… |");
+    assert_eq!(lines[3], "| 2   | short      |");
+    assert_eq!(lines[4], "| 3   |            |");
+    assert!(lines.iter().all(|line| TableFormatter::display_width(line) == TableFormatter::display_width(lines[0])));
+
+    // 宽度按显示宽度计算，全角字符占2列
+    assert_eq!(TableFormatter::display_width("数据库"), 6);
+    let rows = vec![strings(&["1", "简单的数据库实现"]), strings(&["2", "数据库"])];
+    let table = TableFormatter::format_table_with_max_width(&headers, &rows, Some(11));
+    print!("{}", table);
+    let lines: Vec<&str> = table.lines().collect();
+    assert_eq!(lines[2], "| 1   | 简单的数据… |");
+    assert_eq!(lines[3], "| 2   | 数据库      |");
+    assert!(lines.iter().all(|line| TableFormatter::display_width(line) == TableFormatter::display_width(lines[0])));
+
+    // CSV 输出保留完整的值
+    let csv = TableFormatter::format_csv(&headers, &[vec![DataType::Int(1), DataType::Varchar(long.to_string())]]);
+    assert!(csv.contains(long));
+
+    // 通过数据库设置最大列宽
+    let mut db = Database::new(StorageType::Memory);
+    db.set_max_column_width(Some(8));
+    assert_eq!(db.max_column_width(), Some(8));
+    db.execute_sql("CREATE TABLE notes (id INT, body VARCHAR(100))").unwrap();
+    db.execute_sql(&format!("INSERT INTO notes VALUES (1, '{}')", long)).unwrap();
+    db.execute_sql("SELECT * FROM notes").unwrap();
+    let rows: Vec<_> = db.query_iter("SELECT body FROM notes").unwrap().collect::<Result<_, _>>().unwrap();
+    assert_eq!(rows[0][0].to_string(), long);
+    println!("最大列宽截断正确");
+
+    println!("\n=== 测试表格样式 ===");
+    let headers = strings(&["id", "name"]);
+    let rows = vec![strings(&["1", "Alice"]), strings(&["2", "NULL"]), strings(&["10", "数据库"])];
+
+    // 默认样式保持不变
+    assert_eq!(TableFormatter::format_table_styled(&headers, &rows, None, TableStyle::default()), TableFormatter::format_table(&headers, &rows));
+    let minimal = TableFormatter::format_table_styled(&headers, &rows, None, TableStyle::Minimal);
+    print!("{}", minimal);
+    assert_eq!(minimal, "\
+| id  | name   |
+| --- | ------ |
+| 1   | Alice  |
+| 2   |        |
+| 10  | 数据库 |
+");
+
+    // 带边框的样式
+    let boxed = TableFormatter::format_table_styled(&headers, &rows, None, TableStyle::Box { row_separators: false });
+    print!("{}", boxed);
+    assert_eq!(boxed, "\
+┌─────┬────────┐
+│ id  │ name   │
+├─────┼────────┤
+│ 1   │ Alice  │
+│ 2   │        │
+│ 10  │ 数据库 │
+└─────┴────────┘
+");
+
+    // 带边框和行分隔线的样式
+    let boxed_rows = TableFormatter::format_table_styled(&headers, &rows, None, TableStyle::Box { row_separators: true });
+    print!("{}", boxed_rows);
+    assert_eq!(boxed_rows, "\
+┌─────┬────────┐
+│ id  │ name   │
+├─────┼────────┤
+│ 1   │ Alice  │
+├─────┼────────┤
+│ 2   │        │
+├─────┼────────┤
+│ 10  │ 数据库 │
+└─────┴────────┘
+");
+
+    // 没有数据行时只画表头
+    let empty = TableFormatter::format_table_styled(&headers, &[], None, TableStyle::Box { row_separators: true });
+    print!("{}", empty);
+    assert_eq!(empty, "\
+┌─────┬──────┐
+│ id  │ name │
+└─────┴──────┘
+");
+
+    // 与最大列宽一起使用
+    let truncated = TableFormatter::format_table_styled(&headers, &[strings(&["1", "Alexander"])], Some(5), TableStyle::Box { row_separators: false });
+    print!("{}", truncated);
+    assert_eq!(truncated, "\
+┌─────┬───────┐
+│ id  │ name  │
+├─────┼───────┤
+│ 1   │ Alex… │
+└─────┴───────┘
+");
+
+    // 通过数据库设置样式
+    db.set_max_column_width(None);
+    db.set_table_style(TableStyle::Box { row_separators: true });
+    assert_eq!(db.table_style(), TableStyle::Box { row_separators: true });
+    db.execute_sql("INSERT INTO notes VALUES (2, 'second')").unwrap();
+    db.execute_sql("SELECT id, body FROM notes WHERE id = 2").unwrap();
+    println!("表格样式输出正确");
+
+    println!("\n=== 测试JSON输出 ===");
+    let rows = vec![
+        vec![DataType::Int(1), DataType::Varchar("first".to_string())],
+        vec![DataType::Float(2.5), DataType::Null],
+        vec![DataType::Int(3), DataType::Varchar("NULL".to_string())],
+    ];
+    let compact = TableFormatter::format_json(&headers, &rows, false);
+    let pretty = TableFormatter::format_json(&headers, &rows, true);
+    print!("{}{}", compact, pretty);
+    // 数字输出为JSON数字，NULL 输出为 null，内容为 "NULL" 的字符串仍是字符串
+    assert_eq!(compact, "{\"columns\":[\"id\",\"name\"],\"rows\":[[1,\"first\"],[2.5,null],[3,\"NULL\"]]}\n");
+
+    // 两种格式解析后结构相同，只有空白不同
+    let parsed_compact: serde_json::Value = serde_json::from_str(&compact).unwrap();
+    let parsed_pretty: serde_json::Value = serde_json::from_str(&pretty).unwrap();
+    assert_eq!(parsed_compact, parsed_pretty);
+    assert_ne!(compact, pretty);
+    assert_eq!(compact.lines().count(), 1);
+    assert!(pretty.lines().count() > 1);
+    let without_whitespace: String = pretty.chars().filter(|c| !c.is_whitespace()).collect();
+    assert_eq!(without_whitespace, compact.trim_end());
+
+    // 通过 SET 选项切换输出格式
+    db.set_option("output", "json").unwrap();
+    assert_eq!(db.output_format(), OutputFormat::Json { pretty: false });
+    db.set_option("output", "json_pretty").unwrap();
+    assert_eq!(db.output_format(), OutputFormat::Json { pretty: true });
+    db.execute_sql("SELECT id, body FROM notes WHERE id = 2").unwrap();
+    assert!(db.set_option("output", "xml").is_err());
+    db.set_option("output", "table").unwrap();
+    assert_eq!(db.output_format(), OutputFormat::Table);
+    println!("JSON输出正确");
+
+    println!("\n=== 测试行号 ===");
+    // 行号从1开始递增，行号变为两位数后各行仍然对齐
+    let rows: Vec<Vec<String>> = (0..10).map(|i| strings(&[&(i * 7).to_string(), "x"])).collect();
+    let (numbered_headers, numbered_rows) = TableFormatter::with_row_numbers(&headers, &rows);
+    assert_eq!(numbered_headers, strings(&["#", "id", "name"]));
+    for (i, row) in numbered_rows.iter().enumerate() {
+        assert_eq!(row[0], (i + 1).to_string());
+        assert_eq!(row[1..], rows[i][..]);
+    }
+    let numbered = TableFormatter::format_table(&numbered_headers, &numbered_rows);
+    print!("{}", numbered);
+    let lines: Vec<&str> = numbered.lines().collect();
+    assert_eq!(lines[0], "| #   | id  | name |");
+    assert_eq!(lines[2], "| 1   | 0   | x    |");
+    assert_eq!(lines[11], "| 10  | 63  | x    |");
+    assert!(lines.iter().all(|line| line.len() == lines[0].len()));
+
+    // 通过 SET rownum 开启，JSON 输出中不加行号
+    assert!(!db.row_numbers());
+    db.set_option("rownum", "on").unwrap();
+    assert!(db.row_numbers());
+    db.execute_sql("SELECT id, body FROM notes").unwrap();
+    db.set_option("output", "json").unwrap();
+    db.execute_sql("SELECT id, body FROM notes").unwrap();
+    let values: Vec<Vec<DataType>> = (0..10).map(|i| vec![DataType::Int(i * 7), DataType::Varchar("x".to_string())]).collect();
+    let csv = TableFormatter::format_csv(&headers, &values);
+    assert!(csv.starts_with("id,name\n0,x\n"));
+    assert!(db.set_option("rownum", "maybe").is_err());
+    db.set_option("rownum", "off").unwrap();
+    assert!(!db.row_numbers());
+    println!("行号输出正确");
+}
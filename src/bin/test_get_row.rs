@@ -0,0 +1,40 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::types::DataType;
+
+fn text(s: &str) -> DataType {
+    DataType::Varchar(s.to_string())
+}
+
+fn main() {
+    println!("=== 测试按行号读取行 ===");
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql("CREATE TABLE users (id INT PRIMARY KEY, name VARCHAR(10))").unwrap();
+    db.execute_sql("INSERT INTO users VALUES (1, 'Ann'), (2, 'Bob'), (3, 'Cid')").unwrap();
+
+    // 行号按插入顺序从0开始
+    assert_eq!(db.get_row("users", 0).unwrap(), Some(vec![DataType::Int(1), text("Ann")]));
+    assert_eq!(db.get_row("users", 2).unwrap(), Some(vec![DataType::Int(3), text("Cid")]));
+
+    // 超出范围时返回 None
+    assert_eq!(db.get_row("users", 3).unwrap(), None);
+    assert_eq!(db.get_row("users", usize::MAX).unwrap(), None);
+
+    // 表不存在时报错
+    assert!(db.get_row("missing", 0).is_err());
+
+    // 与按行号修改和删除的接口一致
+    db.update_row("users", 1, vec![DataType::Int(20), text("Bea")]).unwrap();
+    assert_eq!(db.get_row("users", 1).unwrap(), Some(vec![DataType::Int(20), text("Bea")]));
+    db.delete_row("users", 0).unwrap();
+    assert_eq!(db.get_row("users", 0).unwrap(), Some(vec![DataType::Int(20), text("Bea")]));
+    assert_eq!(db.get_row("users", 2).unwrap(), None);
+
+    // 逐行读取直到 None
+    let mut index = 0;
+    while let Some(row) = db.get_row("users", index).unwrap() {
+        println!("第 {} 行: {:?}", index, row);
+        index += 1;
+    }
+    assert_eq!(index, 2);
+    println!("按行号读取正确");
+}
@@ -0,0 +1,45 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::error::DbError;
+use simple_db::core::types::{Column, ColumnDefault, ColumnType, DataType};
+
+fn column(name: &str, data_type: ColumnType, nullable: bool, primary_key: bool, default: Option<ColumnDefault>) -> Column {
+    Column { name: name.to_string(), data_type, nullable, primary_key, comment: None, default }
+}
+
+fn main() {
+    println!("=== 测试获取表结构 ===");
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql(concat!(
+        "CREATE TABLE users (id INT PRIMARY KEY, name VARCHAR(20) NOT NULL, ",
+        "age INT(3) DEFAULT 18, score FLOAT NOT NULL DEFAULT 0.5, city VARCHAR(10) DEFAULT 'Paris', ",
+        "created VARCHAR(30) DEFAULT CURRENT_TIMESTAMP, note VARCHAR(50) COMMENT 'free text')",
+    )).unwrap();
+
+    // 列的顺序、类型、可否为空、主键和默认值与建表语句一致；主键列不可为空
+    let mut note = column("note", ColumnType::Varchar(50), true, false, None);
+    note.comment = Some("free text".to_string());
+    let expected = vec![
+        column("id", ColumnType::Int(None), false, true, None),
+        column("name", ColumnType::Varchar(20), false, false, None),
+        column("age", ColumnType::Int(Some(3)), true, false, Some(ColumnDefault::Value(DataType::Int(18)))),
+        column("score", ColumnType::Float(None), false, false, Some(ColumnDefault::Value(DataType::Float(0.5)))),
+        column("city", ColumnType::Varchar(10), true, false, Some(ColumnDefault::Value(DataType::Varchar("Paris".to_string())))),
+        column("created", ColumnType::Varchar(30), true, false, Some(ColumnDefault::CurrentTimestamp)),
+        note,
+    ];
+    let schema = db.get_schema("users").unwrap();
+    println!("{:#?}", schema);
+    assert_eq!(schema, expected);
+
+    // 修改表结构后返回新的列定义
+    db.execute_sql("ALTER TABLE users RENAME COLUMN age TO years").unwrap();
+    assert_eq!(db.get_schema("users").unwrap()[2].name, "years");
+
+    // 表不存在时报错
+    match db.get_schema("missing") {
+        Err(DbError::TableError(message)) => assert_eq!(message, "表 missing 不存在"),
+        other => panic!("应返回表不存在错误: {:?}", other),
+    }
+
+    println!("测试通过");
+}
@@ -0,0 +1,56 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::types::DataType;
+use simple_db::StatementResult;
+
+// 执行查询并返回结果行
+fn rows(db: &mut Database, sql: &str) -> Vec<Vec<DataType>> {
+    match db.execute(sql).unwrap() {
+        StatementResult::ResultSet(result) => {
+            println!("{} -> {:?}", sql, result.rows);
+            result.rows
+        }
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+}
+
+fn int_rows(values: &[[i32; 2]]) -> Vec<Vec<DataType>> {
+    values.iter().map(|row| row.iter().map(|&v| DataType::Int(v)).collect()).collect()
+}
+
+fn main() {
+    println!("=== 测试 GROUP BY 与 HAVING ===");
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql("CREATE TABLE orders (order_id INT, user_id INT, amount INT)").unwrap();
+    // 用户 1 有 3 个订单共 60，用户 2 有 1 个订单共 500，用户 3 有 2 个订单共 101
+    db.execute_sql("INSERT INTO orders VALUES (1, 1, 10), (2, 1, 20), (3, 2, 500), (4, 3, 100), (5, 1, 30), (6, 3, 1)").unwrap();
+
+    // 按订单数过滤：只保留订单多于 1 个的用户
+    assert_eq!(
+        rows(&mut db, "SELECT user_id, COUNT(*) FROM orders GROUP BY user_id HAVING COUNT(*) > 1 ORDER BY user_id"),
+        int_rows(&[[1, 3], [3, 2]])
+    );
+    // 阈值本身不满足 >，满足 >=
+    assert_eq!(
+        rows(&mut db, "SELECT user_id, COUNT(*) FROM orders GROUP BY user_id HAVING COUNT(*) > 2"),
+        int_rows(&[[1, 3]])
+    );
+    assert_eq!(
+        rows(&mut db, "SELECT user_id, COUNT(*) FROM orders GROUP BY user_id HAVING COUNT(*) >= 2 ORDER BY user_id"),
+        int_rows(&[[1, 3], [3, 2]])
+    );
+
+    // 按金额合计过滤：60 不满足，101 和 500 满足
+    assert_eq!(
+        rows(&mut db, "SELECT user_id, SUM(amount) FROM orders GROUP BY user_id HAVING SUM(amount) > 100 ORDER BY user_id"),
+        int_rows(&[[2, 500], [3, 101]])
+    );
+    assert_eq!(
+        rows(&mut db, "SELECT user_id, SUM(amount) FROM orders GROUP BY user_id HAVING SUM(amount) > 101"),
+        int_rows(&[[2, 500]])
+    );
+
+    // 没有分组满足条件时结果为空
+    assert!(rows(&mut db, "SELECT user_id, SUM(amount) FROM orders GROUP BY user_id HAVING SUM(amount) > 1000").is_empty());
+
+    println!("测试通过");
+}
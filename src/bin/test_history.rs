@@ -0,0 +1,50 @@
+use simple_db::history::History;
+use std::fs;
+
+fn main() {
+    let base_dir = std::env::temp_dir().join("simple_db_test_history");
+    let _ = fs::remove_dir_all(&base_dir);
+    fs::create_dir_all(&base_dir).unwrap();
+    let path = base_dir.join(".history");
+
+    println!("=== 测试命令历史 ===");
+
+    // 历史文件不存在时从空记录开始
+    let mut history = History::load(&path, 3).unwrap();
+    assert!(history.entries().is_empty());
+
+    // 空输入和连续重复的输入不记录
+    assert!(history.add("list").unwrap());
+    assert!(!history.add("   ").unwrap());
+    assert!(!history.add("list").unwrap());
+    assert!(history.add("SELECT * FROM users;").unwrap());
+    assert!(history.add("list").unwrap());
+    println!("记录: {:?}", history.entries());
+    assert_eq!(history.entries(), ["list", "SELECT * FROM users;", "list"]);
+
+    // 多行SQL合并为一行
+    assert!(history.add("SELECT name\n  FROM users\n\n  WHERE id = 1;\n").unwrap());
+    assert_eq!(history.entries().last().unwrap(), "SELECT name FROM users WHERE id = 1;");
+
+    // 超过上限时丢弃最早的记录
+    println!("记录: {:?}", history.entries());
+    assert_eq!(history.entries(), ["SELECT * FROM users;", "list", "SELECT name FROM users WHERE id = 1;"]);
+
+    // 每次添加都写入历史文件，重新加载后记录一致
+    let content = fs::read_to_string(&path).unwrap();
+    assert_eq!(content, "SELECT * FROM users;\nlist\nSELECT name FROM users WHERE id = 1;\n");
+    let reloaded = History::load(&path, 3).unwrap();
+    assert_eq!(reloaded.entries(), history.entries());
+
+    // 加载时同样应用上限
+    let reloaded = History::load(&path, 2).unwrap();
+    assert_eq!(reloaded.entries(), ["list", "SELECT name FROM users WHERE id = 1;"]);
+
+    // 不关联文件的历史只保存在内存中
+    let mut memory = History::new(10);
+    assert!(memory.add("stats").unwrap());
+    assert_eq!(memory.entries(), ["stats"]);
+    println!("历史记录的添加和持久化正确");
+
+    let _ = fs::remove_dir_all(&base_dir);
+}
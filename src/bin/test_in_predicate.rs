@@ -0,0 +1,48 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::types::DataType;
+
+// 执行查询并返回结果中的 id 列
+fn ids(db: &Database, sql: &str) -> Vec<i32> {
+    let rows = db.query_iter(sql)
+        .unwrap_or_else(|e| panic!("查询失败 {}: {}", sql, e.detailed_message()))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap_or_else(|e| panic!("查询失败 {}: {}", sql, e.detailed_message()));
+    let ids = rows.iter()
+        .map(|row| match &row[0] {
+            DataType::Int(id) => *id,
+            other => panic!("期望整数，实际为 {:?}", other),
+        })
+        .collect();
+    println!("{} -> {:?}", sql, ids);
+    ids
+}
+
+fn main() {
+    println!("=== 测试 IN 谓词 ===");
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql("CREATE TABLE users (id INT, age INT)").unwrap();
+    db.execute_sql("CREATE TABLE orders (id INT, user_id INT, price FLOAT)").unwrap();
+    db.execute_sql("INSERT INTO users VALUES (1, 25), (2, 31), (3, NULL), (4, 40)").unwrap();
+    db.execute_sql("INSERT INTO orders VALUES (10, 1, 4.0), (11, 2, 5.0), (12, 4, 4.5), (13, NULL, 6.0), (14, 2, NULL)").unwrap();
+
+    // 值列表
+    assert_eq!(ids(&db, "SELECT id FROM users WHERE id IN (1, 3, 9)"), vec![1, 3]);
+    assert_eq!(ids(&db, "SELECT id FROM users WHERE id NOT IN (1, 3)"), vec![2, 4]);
+
+    // 用另一个表中的 id 过滤
+    assert_eq!(ids(&db, "SELECT id FROM orders WHERE user_id IN (SELECT id FROM users WHERE age > 30)"), vec![11, 12, 14]);
+    assert_eq!(ids(&db, "SELECT id FROM orders WHERE user_id NOT IN (SELECT id FROM users WHERE age > 30)"), vec![10]);
+
+    // 与 = 一样，整数可以与浮点数相等
+    assert_eq!(ids(&db, "SELECT id FROM orders WHERE price = 4"), vec![10]);
+    assert_eq!(ids(&db, "SELECT id FROM orders WHERE price IN (4, 5)"), vec![10, 11]);
+    assert_eq!(ids(&db, "SELECT id FROM orders WHERE price NOT IN (4, 5)"), vec![12, 13]);
+
+    // 三值逻辑：NULL 值不属于任何列表，列表中有 NULL 时没有匹配的值结果未知
+    assert!(ids(&db, "SELECT id FROM users WHERE age IN (NULL)").is_empty());
+    assert_eq!(ids(&db, "SELECT id FROM users WHERE id IN (1, NULL)"), vec![1]);
+    assert!(ids(&db, "SELECT id FROM users WHERE id NOT IN (1, NULL)").is_empty());
+    assert!(ids(&db, "SELECT id FROM users WHERE id NOT IN (SELECT user_id FROM orders)").is_empty());
+
+    println!("IN 谓词结果正确");
+}
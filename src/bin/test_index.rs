@@ -0,0 +1,114 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::types::DataType;
+
+// 执行查询并收集全部结果行
+fn query(db: &Database, sql: &str) -> Vec<Vec<DataType>> {
+    db.query_iter(sql)
+        .unwrap_or_else(|e| panic!("查询失败 {}: {}", sql, e.detailed_message()))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap_or_else(|e| panic!("查询失败 {}: {}", sql, e.detailed_message()))
+}
+
+fn main() {
+    println!("=== 测试索引区间查询 ===");
+
+    // 两个表数据完全相同，只有 indexed 表建立索引，全表扫描的结果作为参照
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql("CREATE TABLE scanned (id INT NOT NULL, score INT, price FLOAT, name VARCHAR(20))").unwrap();
+    db.execute_sql("CREATE TABLE indexed (id INT NOT NULL, score INT, price FLOAT, name VARCHAR(20))").unwrap();
+
+    // 使用固定种子的线性同余生成器产生可重复的数据，包含重复值和 NULL
+    let mut seed: u32 = 12345;
+    let mut next = || {
+        seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+        (seed >> 16) % 1000
+    };
+    for id in 0..500 {
+        let score = next();
+        let score = if score % 10 == 0 { "NULL".to_string() } else { (score % 100).to_string() };
+        let price = format!("{}.{}", next() % 50, next() % 10);
+        let name = format!("'n{}'", next() % 40);
+        for table in ["scanned", "indexed"] {
+            db.execute_sql(&format!("INSERT INTO {} VALUES ({}, {}, {}, {})", table, id, score, price, name)).unwrap();
+        }
+    }
+
+    db.execute_sql("CREATE INDEX idx_score ON indexed (score)").unwrap();
+    db.execute_sql("CREATE INDEX idx_price ON indexed (price)").unwrap();
+    db.execute_sql("CREATE INDEX idx_name ON indexed (name)").unwrap();
+
+    let conditions = [
+        "score > 50",
+        "score < 20",
+        "score >= 50",
+        "score <= 20",
+        "score = 42",
+        "score BETWEEN 30 AND 40",
+        "score BETWEEN 40 AND 30",
+        "score > 30 AND score < 40",
+        "score > 40 AND score <= 40",
+        "70 < score",
+        "score > 2.5",
+        "price > 25",
+        "price BETWEEN 10 AND 12.5",
+        "name > 'n3'",
+        "name BETWEEN 'n1' AND 'n2'",
+        "score > 50 AND id < 100",
+        "id < 100 AND price < 5",
+        "score > 90 OR price < 1",
+        "score IS NULL",
+    ];
+
+    let check = |db: &Database| {
+        for condition in &conditions {
+            let expected = query(db, &format!("SELECT * FROM scanned WHERE {}", condition));
+            let actual = query(db, &format!("SELECT * FROM indexed WHERE {}", condition));
+            assert_eq!(expected, actual, "条件 {} 的结果不一致", condition);
+
+            let expected = query(db, &format!("SELECT id, price * 2 FROM scanned WHERE {} ORDER BY id", condition));
+            let actual = query(db, &format!("SELECT id, price * 2 FROM indexed WHERE {} ORDER BY id", condition));
+            assert_eq!(expected, actual, "条件 {} 的表达式查询结果不一致", condition);
+
+            let expected = query(db, &format!("SELECT COUNT(*), SUM(score) FROM scanned WHERE {}", condition));
+            let actual = query(db, &format!("SELECT COUNT(*), SUM(score) FROM indexed WHERE {}", condition));
+            assert_eq!(expected, actual, "条件 {} 的聚合结果不一致", condition);
+        }
+    };
+
+    check(&db);
+    println!("索引查询与全表扫描结果一致");
+
+    // 修改数据后索引需要重新建立
+    for table in ["scanned", "indexed"] {
+        db.execute_sql(&format!("UPDATE {} SET score = 99 WHERE id < 50", table)).unwrap();
+        db.execute_sql(&format!("DELETE FROM {} WHERE score < 10", table)).unwrap();
+        db.execute_sql(&format!("INSERT INTO {} VALUES (1000, 55, 1.5, 'new')", table)).unwrap();
+    }
+    check(&db);
+    println!("修改数据后索引查询结果一致");
+
+    println!("\n查询计划:");
+    for sql in [
+        "EXPLAIN SELECT * FROM indexed WHERE score BETWEEN 30 AND 40",
+        "EXPLAIN SELECT id FROM indexed WHERE id < 10",
+    ] {
+        println!("执行: {}", sql);
+        if let Err(e) = db.execute_sql(sql) {
+            println!("失败: {}", e);
+        }
+    }
+
+    // 错误情况
+    println!("\n错误处理:");
+    for sql in [
+        "CREATE INDEX idx_score ON scanned (score)",
+        "CREATE INDEX idx_other ON indexed (score)",
+        "CREATE INDEX idx_missing ON indexed (missing)",
+        "CREATE INDEX idx_missing ON missing (score)",
+    ] {
+        match db.execute_sql(sql) {
+            Ok(_) => println!("{} 意外成功", sql),
+            Err(e) => println!("{} -> {}", sql, e.detailed_message()),
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use std::ops::Bound;
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::index::{Index, IndexKey};
+use simple_db::core::types::{Column, ColumnType, DataType, Table};
+
+// 执行查询并收集全部结果行
+fn query(db: &Database, sql: &str) -> Vec<Vec<DataType>> {
+    db.query_iter(sql)
+        .unwrap_or_else(|e| panic!("查询失败 {}: {}", sql, e.detailed_message()))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap_or_else(|e| panic!("查询失败 {}: {}", sql, e.detailed_message()))
+}
+
+// 通过索引查找 score 在 [low, high] 内的行号，与逐行扫描的结果比较
+fn check_table(table: &Table, low: i32, high: i32) {
+    let (low_key, high_key) = (IndexKey::from_value(&DataType::Int(low)).unwrap(), IndexKey::from_value(&DataType::Int(high)).unwrap());
+    let mut actual = table.index_range("score", Bound::Included(&low_key), Bound::Included(&high_key)).unwrap();
+    actual.sort();
+    let expected: Vec<usize> = table.rows.iter().enumerate()
+        .filter(|(_, row)| matches!(row[1], DataType::Int(score) if score >= low && score <= high))
+        .map(|(i, _)| i)
+        .collect();
+    assert_eq!(actual, expected, "score 在 [{}, {}] 内的行号不一致", low, high);
+}
+
+fn check_all(table: &Table) {
+    for (low, high) in [(0, 100), (10, 10), (20, 40), (41, 41), (99, 200)] {
+        check_table(table, low, high);
+    }
+    // 主键索引同样保持一致
+    for row in &table.rows {
+        assert_eq!(table.find_by_primary_key(&row[0]), Some(row.as_slice()));
+    }
+}
+
+fn main() {
+    println!("=== 测试修改表数据时同步更新已建立的索引 ===");
+    let columns = vec![
+        Column { name: "id".to_string(), data_type: ColumnType::Int(None), nullable: false, primary_key: true, comment: None, default: None },
+        Column { name: "score".to_string(), data_type: ColumnType::Int(None), nullable: true, primary_key: false, comment: None, default: None },
+    ];
+    let mut table = Table::new("items".to_string(), columns);
+    table.indexes.push(Index { name: "idx_score".to_string(), column: "score".to_string() });
+    for id in 0..50 {
+        let score = if id % 7 == 0 { DataType::Null } else { DataType::Int(id * 3 % 50) };
+        table.insert_row(vec![DataType::Int(id), score]).unwrap();
+    }
+    // 先查询一次，使索引被建立并缓存，之后的修改都作用在已缓存的索引上
+    check_all(&table);
+
+    table.insert_row(vec![DataType::Int(100), DataType::Int(41)]).unwrap();
+    check_all(&table);
+    table.replace_row(3, vec![DataType::Int(3), DataType::Int(41)]);
+    table.replace_row(4, vec![DataType::Int(4), DataType::Null]);
+    table.replace_row(7, vec![DataType::Int(7), DataType::Int(10)]);
+    check_all(&table);
+    table.remove_row(0);
+    check_all(&table);
+    table.remove_rows(&[2, 5, 6, 30]);
+    check_all(&table);
+
+    // 批量插入失败时撤销已插入的行，索引同样恢复
+    let before = table.rows.len();
+    assert!(table.insert_rows(vec![
+        vec![DataType::Int(200), DataType::Int(10)],
+        vec![DataType::Int(201), DataType::Int(41)],
+        vec![DataType::Int(1), DataType::Int(20)], // 主键重复
+    ]).is_err());
+    assert_eq!(table.rows.len(), before);
+    check_all(&table);
+    assert_eq!(table.find_by_primary_key(&DataType::Int(200)), None);
+
+    // 通过 SQL 修改时，查询结果与没有索引的表一致
+    let path = std::env::temp_dir().join("simple_db_test_index_maintenance");
+    for use_file in [false, true] {
+        let _ = std::fs::remove_dir_all(&path);
+        let storage = if use_file { StorageType::File(path.clone()) } else { StorageType::Memory };
+        let mut db = Database::new(storage);
+        for name in ["scanned", "indexed"] {
+            db.execute_sql(&format!("CREATE TABLE {} (id INT PRIMARY KEY, score INT, name VARCHAR(10))", name)).unwrap();
+            for id in 0..30 {
+                db.execute_sql(&format!("INSERT INTO {} VALUES ({}, {}, 'n{}')", name, id, id * 7 % 20, id % 4)).unwrap();
+            }
+        }
+        db.execute_sql("CREATE INDEX idx_score ON indexed (score)").unwrap();
+        db.execute_sql("CREATE INDEX idx_name ON indexed (name)").unwrap();
+
+        let check = |db: &Database| {
+            for condition in ["score > 10", "score BETWEEN 3 AND 8", "score = 5", "name >= 'n2'", "id = 12"] {
+                let expected = query(db, &format!("SELECT * FROM scanned WHERE {}", condition));
+                let actual = query(db, &format!("SELECT * FROM indexed WHERE {}", condition));
+                assert_eq!(expected, actual, "条件 {} 的结果不一致", condition);
+            }
+        };
+        check(&db);
+
+        // 每条修改之后立即查询，索引不会被整体丢弃重建
+        for statement in [
+            "INSERT INTO {} VALUES (100, 5, 'n9')",
+            "INSERT INTO {} VALUES (101, 9, 'n0'), (102, 15, 'n3')",
+            "UPDATE {} SET score = 5 WHERE id < 5",
+            "UPDATE {} SET name = 'n1' WHERE score = 5",
+            "DELETE FROM {} WHERE score > 12",
+            "DELETE FROM {} WHERE id = 0",
+            "ALTER TABLE {} MODIFY COLUMN score FLOAT",
+            "UPDATE {} SET score = 6.5 WHERE id = 3",
+            "ALTER TABLE {} RENAME COLUMN name TO label; ALTER TABLE {} RENAME COLUMN label TO name",
+        ] {
+            for table in ["scanned", "indexed"] {
+                for sql in statement.split(';') {
+                    db.execute_sql(&sql.replace("{}", table)).unwrap();
+                }
+            }
+            check(&db);
+        }
+        assert!(db.execute("INSERT INTO indexed VALUES (200, 1, 'a'), (1, 2, 'b')").is_err());
+        check(&db);
+        println!("{:?}: 修改后索引查询与全表扫描结果一致", db.storage_kind());
+    }
+    let _ = std::fs::remove_dir_all(&path);
+
+    println!("测试通过");
+}
@@ -0,0 +1,68 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::error::DbError;
+use simple_db::core::types::{ColumnType, DataType, TypeError};
+use simple_db::{QueryResult, StatementResult};
+
+fn main() {
+    println!("=== 测试按列名插入时的类型检查 ===");
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql("CREATE TABLE people (id INT PRIMARY KEY, name VARCHAR(5), age INT)").unwrap();
+
+    // 列的顺序与表定义不同
+    db.execute_sql("INSERT INTO people (name, age, id) VALUES ('Ann', 30, 1)").unwrap();
+
+    // 字符串插入到调换了顺序的整数列
+    let error = db.execute("INSERT INTO people (age, name, id) VALUES ('old', 'Bob', 2)").unwrap_err();
+    println!("字符串插入整数列: {}", error.detailed_message());
+    match &error {
+        DbError::TypeError(TypeError::ColumnTypeMismatch { column, expected, actual }) => {
+            assert_eq!(column, "age");
+            assert_eq!(expected, &ColumnType::Int(None));
+            assert_eq!(actual, &DataType::Varchar("old".to_string()));
+        }
+        other => panic!("期望列类型不匹配错误，实际为 {:?}", other),
+    }
+    assert_eq!(error.detailed_message(), "列 age 的类型不匹配: 期望 INT, 实际值 'old'");
+
+    // 超出长度的字符串
+    let error = db.execute("INSERT INTO people (id, name) VALUES (3, 'Christina')").unwrap_err();
+    println!("超长字符串: {}", error.detailed_message());
+    assert_eq!(error.detailed_message(), "列 name 的类型不匹配: 期望 VARCHAR(5), 实际值 'Christina'");
+
+    // 多行插入中任何一行类型不匹配时不插入任何行
+    assert!(db.execute("INSERT INTO people (id, age) VALUES (4, 20), (5, 'x')").is_err());
+    assert_eq!(
+        db.execute("SELECT id, name, age FROM people").unwrap(),
+        StatementResult::ResultSet(QueryResult {
+            columns: vec!["id".to_string(), "name".to_string(), "age".to_string()],
+            rows: vec![vec![DataType::Int(1), DataType::Varchar("Ann".to_string()), DataType::Int(30)]],
+        })
+    );
+
+    // NULL 可以插入任何可为空的列
+    db.execute_sql("INSERT INTO people (age, id) VALUES (NULL, 6)").unwrap();
+    println!("类型检查结果正确");
+
+    println!("\n=== 测试空的 VALUES ===");
+    let cases = [
+        ("INSERT INTO people VALUES ()", "VALUES 的第 1 行没有值"),
+        ("INSERT INTO people (id) VALUES ()", "VALUES 的第 1 行没有值"),
+        ("INSERT INTO people VALUES (7, 'Gus', 1), ()", "VALUES 的第 2 行没有值"),
+        ("INSERT INTO people VALUES", "VALUES 后缺少要插入的行"),
+        ("INSERT INTO people VALUES;", "VALUES 后缺少要插入的行"),
+        ("INSERT INTO people (id) VALUES ON CONFLICT DO NOTHING", "VALUES 后缺少要插入的行"),
+        ("INSERT INTO people () VALUES (7)", "列名列表不能为空"),
+        ("INSERT INTO people VALUES (7, 'Gus', 1),", "多行插入时期望左括号"),
+    ];
+    for (sql, message) in cases {
+        let error = db.execute(sql).unwrap_err();
+        println!("{} -> {}", sql, error.detailed_message());
+        assert_eq!(error.detailed_message(), format!("SQL语法错误: {}", message));
+    }
+    // 出错的语句没有插入任何行
+    match db.execute("SELECT COUNT(*) FROM people").unwrap() {
+        StatementResult::ResultSet(result) => assert_eq!(result.rows, vec![vec![DataType::Int(2)]]),
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+    println!("空的 VALUES 报错正确");
+}
@@ -0,0 +1,69 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::types::DataType;
+use simple_db::StatementResult;
+use std::panic::{self, AssertUnwindSafe};
+
+// 执行语句，发生 panic 时报告是哪条语句
+fn execute_without_panic(db: &mut Database, sql: &str) -> bool {
+    match panic::catch_unwind(AssertUnwindSafe(|| db.execute(sql))) {
+        Ok(result) => result.is_ok(),
+        Err(_) => panic!("执行时发生 panic: {:?}", sql),
+    }
+}
+
+fn count(db: &mut Database) -> DataType {
+    match db.execute("SELECT COUNT(*) FROM t").unwrap() {
+        StatementResult::ResultSet(result) => result.rows[0][0].clone(),
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+}
+
+fn main() {
+    println!("=== 测试异常的 INSERT 语句 ===");
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql("CREATE TABLE t (id INT PRIMARY KEY, name VARCHAR(5))").unwrap();
+    db.execute_sql("INSERT INTO t VALUES (100, 'seed')").unwrap();
+
+    // 这些语句都应返回错误，且不插入任何行
+    let malformed = [
+        "INSERT",
+        "INSERT INTO",
+        "INSERT INTO t",
+        "INSERT INTO t VALUES",
+        "INSERT INTO t VALUES ()",
+        "INSERT INTO t VALUES (), ()",
+        "INSERT INTO t VALUES (1, 'a'), ()",
+        "INSERT INTO t VALUES (1, 'a'),",
+        "INSERT INTO t VALUES (1, 'a') (2, 'b')",
+        "INSERT INTO t VALUES (1,)",
+        "INSERT INTO t VALUES (,)",
+        "INSERT INTO t VALUES ((1))",
+        "INSERT INTO t VALUES (1, 'a', 3)",
+        "INSERT INTO t VALUES (1, 'a') ON",
+        "INSERT INTO t VALUES (1, 'a') ON CONFLICT",
+        "INSERT INTO t VALUES (1, 'a') extra",
+        "INSERT INTO t ()",
+        "INSERT INTO t () VALUES ()",
+        "INSERT INTO t (id",
+        "INSERT INTO t (id,) VALUES (1)",
+        "INSERT INTO t (id) VALUES",
+        "INSERT INTO t (id) VALUES (1, 2)",
+        "INSERT INTO t (id, name) VALUES (1)",
+        "INSERT INTO t (nope) VALUES (1)",
+        "INSERT INTO t (id) VALUES (1), (2, 3)",
+        "INSERT INTO missing VALUES (1)",
+        "INSERT INTO t VALUES ('x', 'y')",
+    ];
+    for sql in malformed {
+        assert!(!execute_without_panic(&mut db, sql), "应当报错: {}", sql);
+    }
+    assert_eq!(count(&mut db), DataType::Int(1));
+
+    // 合法语句在任意位置截断后执行，只会报错或成功，不会 panic
+    let valid = "INSERT INTO t (id, name) VALUES (1, 'a'), (2, 'ü') ON CONFLICT DO NOTHING;";
+    for (end, _) in valid.char_indices().skip(1) {
+        execute_without_panic(&mut db, &valid[..end]);
+    }
+    assert!(execute_without_panic(&mut db, valid));
+    println!("异常的 INSERT 语句均返回错误");
+}
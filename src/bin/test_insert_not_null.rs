@@ -0,0 +1,46 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::error::{DbError, Locale};
+use simple_db::core::types::TypeError;
+
+fn count(db: &Database) -> usize {
+    db.get_table("users").unwrap().unwrap().rows.len()
+}
+
+// 多行插入违反非空约束时，错误指出出错的行号和列名，且不插入任何行
+fn check_null_at_row(db: &mut Database, sql: &str, column: &str, row: usize) {
+    let err = db.execute(sql).unwrap_err();
+    println!("{} -> {}", sql, err.detailed_message());
+    match &err {
+        DbError::TypeError(TypeError::NullValueAtRow { column: c, row: r }) => {
+            assert_eq!((c.as_str(), *r), (column, row));
+        }
+        other => panic!("应为第 {} 行的非空错误: {:?}", row, other),
+    }
+    assert_eq!(err.detailed_message_in(Locale::Chinese), format!("第 {} 行的字段 '{}' 没有默认值", row, column));
+    assert_eq!(err.detailed_message_in(Locale::English), format!("Field '{}' doesn't have a default value at row {}", column, row));
+    assert_eq!(count(db), 1);
+}
+
+fn main() {
+    println!("=== 测试多行插入的非空约束错误 ===");
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql("CREATE TABLE users (id INT PRIMARY KEY, name VARCHAR(20) NOT NULL, age INT)").unwrap();
+    db.execute_sql("INSERT INTO users VALUES (100, 'seed', 1)").unwrap();
+
+    // 第 3 个元组的 name 为 NULL
+    check_null_at_row(&mut db, "INSERT INTO users VALUES (1, 'a', 10), (2, 'b', 20), (3, NULL, 30), (4, 'd', 40)", "name", 3);
+    // 指定列名的插入同样报告行号，省略的非空列视为 NULL
+    check_null_at_row(&mut db, "INSERT INTO users (id, age) VALUES (1, 10), (2, 20), (3, 30)", "name", 1);
+    check_null_at_row(&mut db, "INSERT INTO users (id, name) VALUES (1, 'a'), (2, 'b'), (3, NULL)", "name", 3);
+    // 省略末尾列时同样检查
+    check_null_at_row(&mut db, "INSERT INTO users VALUES (1, 'a'), (2, 'b'), (3)", "name", 3);
+
+    // 单行插入不附带行号
+    match db.execute("INSERT INTO users VALUES (5, NULL, 50)").unwrap_err() {
+        DbError::TypeError(TypeError::NullValue(column)) => assert_eq!(column, "name"),
+        other => panic!("应为非空错误: {:?}", other),
+    }
+    assert_eq!(count(&db), 1);
+
+    println!("测试通过");
+}
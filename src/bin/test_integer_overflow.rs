@@ -0,0 +1,57 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::error::DbError;
+use simple_db::core::types::DataType;
+use simple_db::StatementResult;
+
+// 三种执行方式都返回同一个结果
+fn value(db: &mut Database, sql: &str) -> DataType {
+    db.execute_sql(sql).unwrap_or_else(|e| panic!("执行失败 {}: {}", sql, e.detailed_message()));
+    let iterated = db.query_iter(sql).unwrap().collect::<Result<Vec<_>, _>>().unwrap();
+    match db.execute(sql).unwrap() {
+        StatementResult::ResultSet(result) => {
+            assert_eq!(result.rows, iterated, "{}", sql);
+            result.rows[0][0].clone()
+        }
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+}
+
+// 三种执行方式都返回整数溢出错误
+fn assert_overflow(db: &mut Database, sql: &str) {
+    let iterated = db.query_iter(sql).and_then(|rows| rows.collect::<Result<Vec<_>, _>>());
+    for error in [db.execute(sql).unwrap_err(), db.execute_sql(sql).unwrap_err(), iterated.unwrap_err()] {
+        match error {
+            DbError::SqlError(message) => assert!(message.starts_with("整数溢出"), "{}: {}", sql, message),
+            other => panic!("{} 期望整数溢出，实际为 {:?}", sql, other),
+        }
+    }
+    println!("{} -> 整数溢出", sql);
+}
+
+fn main() {
+    println!("=== 测试整数运算溢出 ===");
+    let mut db = Database::new(StorageType::Memory);
+
+    // 没有 FROM 的表达式：恰好到达边界时成功，越过边界时报错
+    assert_eq!(value(&mut db, "SELECT 2147483646 + 1"), DataType::Int(i32::MAX));
+    assert_eq!(value(&mut db, "SELECT (0 - 2147483647) - 1"), DataType::Int(i32::MIN));
+    assert_eq!(value(&mut db, "SELECT 46340 * 46340"), DataType::Int(46340 * 46340));
+    assert_overflow(&mut db, "SELECT 2147483647 + 1");
+    assert_overflow(&mut db, "SELECT (0 - 2147483647) - 2");
+    assert_overflow(&mut db, "SELECT 2147483647 * 2");
+    assert_overflow(&mut db, "SELECT 65536 * 32768");
+
+    // 对表中的值计算：min 为 i32::MIN，max 为 i32::MAX
+    db.execute_sql("CREATE TABLE t (id INT, min INT, max INT)").unwrap();
+    db.insert_row("t", vec![DataType::Int(1), DataType::Int(i32::MIN), DataType::Int(i32::MAX)]).unwrap();
+    assert_eq!(value(&mut db, "SELECT (max - 1) + 1 FROM t"), DataType::Int(i32::MAX));
+    assert_eq!(value(&mut db, "SELECT (min + 1) - 1 FROM t"), DataType::Int(i32::MIN));
+    assert_eq!(value(&mut db, "SELECT max * 1, min * 1 FROM t"), DataType::Int(i32::MAX));
+    assert_overflow(&mut db, "SELECT max + 1 FROM t");
+    assert_overflow(&mut db, "SELECT min - 1 FROM t");
+    assert_overflow(&mut db, "SELECT max * 2 FROM t");
+    assert_overflow(&mut db, "SELECT min * (0 - 1) FROM t");
+    assert_overflow(&mut db, "SELECT id FROM t WHERE max + id > 0");
+
+    println!("测试通过");
+}
@@ -0,0 +1,66 @@
+use simple_db::core::db::{Database, StorageType};
+use std::fs;
+
+// 按名称排序的表名
+fn table_names(db: &Database) -> Vec<String> {
+    let mut names = db.list_tables().unwrap();
+    names.sort();
+    names
+}
+
+fn main() {
+    let base_dir = std::env::temp_dir().join("simple_db_test_integrity");
+    let _ = fs::remove_dir_all(&base_dir);
+
+    println!("=== 测试表文件完整性检查 ===");
+    let mut db = Database::new(StorageType::File(base_dir.clone()));
+    db.execute_sql("CREATE TABLE users (id INT PRIMARY KEY, name VARCHAR(20))").unwrap();
+    db.execute_sql("INSERT INTO users VALUES (1, 'Alice'), (2, 'Bob')").unwrap();
+    db.execute_sql("CREATE TABLE notes (id INT, body VARCHAR(50))").unwrap();
+    db.execute_sql("INSERT INTO notes VALUES (1, 'hello')").unwrap();
+    db.execute_sql("CREATE TABLE tags (name VARCHAR(10))").unwrap();
+    let results = db.check_integrity().unwrap();
+    println!("{:?}", results);
+    assert_eq!(results, vec![
+        ("notes".to_string(), Ok(())),
+        ("tags".to_string(), Ok(())),
+        ("users".to_string(), Ok(())),
+    ]);
+    drop(db);
+
+    // 写坏一个表文件，整个数据库无法加载
+    let tables_dir = base_dir.join("tables");
+    fs::write(tables_dir.join("notes.json"), "{ not json").unwrap();
+    let mut db = Database::new(StorageType::File(base_dir.clone()));
+    assert!(db.load().is_err());
+
+    // 检查结果指出损坏的表，其他表正常
+    let results = db.check_integrity().unwrap();
+    println!("{:?}", results);
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].0, "notes");
+    assert!(results[0].1.as_ref().unwrap_err().starts_with("序列化错误"));
+    assert_eq!(results[1], ("tags".to_string(), Ok(())));
+    assert_eq!(results[2], ("users".to_string(), Ok(())));
+
+    // 修复后损坏的文件被移到 .broken 目录，其余的表正常加载
+    assert_eq!(db.repair().unwrap(), vec!["notes"]);
+    assert_eq!(table_names(&db), vec!["tags", "users"]);
+    assert!(db.get_row("users", 1).unwrap().is_some());
+    assert!(!tables_dir.join("notes.json").exists());
+    assert_eq!(fs::read_to_string(tables_dir.join(".broken").join("notes.json")).unwrap(), "{ not json");
+    assert!(db.check_integrity().unwrap().iter().all(|(_, result)| result.is_ok()));
+
+    // 重新打开数据库不再出错，再次修复没有需要移走的文件
+    let mut db = Database::new(StorageType::File(base_dir.clone()));
+    db.load().unwrap();
+    assert_eq!(table_names(&db), vec!["tags", "users"]);
+    assert!(db.repair().unwrap().is_empty());
+
+    // 内存存储没有表文件
+    let db = Database::new(StorageType::Memory);
+    assert!(db.check_integrity().unwrap().is_empty());
+
+    let _ = fs::remove_dir_all(&base_dir);
+    println!("表文件完整性检查结果正确");
+}
@@ -0,0 +1,89 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::sql::{Expression, Operator, SqlParser, SqlStatement, WhereClause};
+use simple_db::core::types::DataType;
+use simple_db::StatementResult;
+
+// 把条件树写成完全加括号的形式，IS [NOT] NULL 写在操作数之后
+fn render(clause: &WhereClause) -> String {
+    let unary = |operand: String, operator: &Operator| match operator {
+        Operator::IsNull => format!("{} IS NULL", operand),
+        Operator::IsNotNull => format!("{} IS NOT NULL", operand),
+        other => format!("{} {:?}", operand, other),
+    };
+    match clause {
+        WhereClause::Simple { column, operator, .. } if operator.is_unary() => unary(column.clone(), operator),
+        WhereClause::Simple { column, value, .. } => format!("{}={}", column, value),
+        WhereClause::Expression { left, operator, .. } if operator.is_unary() => unary(format!("{:?}", left), operator),
+        WhereClause::Expression { left, right, .. } => match (left.as_ref(), right.as_ref()) {
+            (Expression::Column(column), Expression::Literal(value)) => format!("{}={}", column, value),
+            other => format!("{:?}", other),
+        },
+        WhereClause::And { left, right } => format!("({} AND {})", render(left), render(right)),
+        WhereClause::Or { left, right } => format!("({} OR {})", render(left), render(right)),
+        other => format!("{:?}", other),
+    }
+}
+
+fn parse_where(predicate: &str) -> String {
+    let sql = format!("SELECT * FROM t WHERE {}", predicate);
+    match SqlParser::new().parse(&sql).unwrap() {
+        SqlStatement::Select { where_clause: Some(clause), .. } => render(&clause),
+        other => panic!("期望带 WHERE 的 SELECT，实际为 {:?}", other),
+    }
+}
+
+// 满足条件的行的 id
+fn ids(db: &mut Database, predicate: &str) -> Vec<i32> {
+    match db.execute(&format!("SELECT id FROM t WHERE {}", predicate)).unwrap() {
+        StatementResult::ResultSet(result) => result.rows.iter()
+            .map(|row| match row[0] {
+                DataType::Int(id) => id,
+                ref other => panic!("期望整数 id，实际为 {:?}", other),
+            })
+            .collect(),
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+}
+
+fn main() {
+    println!("=== 测试 IS [NOT] NULL 与 AND/OR 的优先级 ===");
+    // IS [NOT] NULL 先与操作数结合，再按 AND 高于 OR 组合
+    let shapes = [
+        ("a IS NULL AND b IS NOT NULL OR c = 1", "((a IS NULL AND b IS NOT NULL) OR c=1)"),
+        ("c = 1 OR a IS NULL AND b IS NOT NULL", "(c=1 OR (a IS NULL AND b IS NOT NULL))"),
+        ("a IS NULL AND (b IS NOT NULL OR c = 1)", "(a IS NULL AND (b IS NOT NULL OR c=1))"),
+    ];
+    for (predicate, expected) in shapes {
+        let actual = parse_where(predicate);
+        println!("{} => {}", predicate, actual);
+        assert_eq!(actual, expected, "{}", predicate);
+    }
+
+    // a、b 取 NULL 或 1，c 取 0 或 1 的全部组合，id = a * 4 + b * 2 + c（NULL 记为 0）
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql("CREATE TABLE t (id INT PRIMARY KEY, a INT, b INT, c INT)").unwrap();
+    let value = |bit: i32| if bit == 1 { "1" } else { "NULL" };
+    for id in 0..8 {
+        db.execute_sql(&format!("INSERT INTO t VALUES ({}, {}, {}, {})", id, value(id / 4), value(id / 2 % 2), id % 2)).unwrap();
+    }
+
+    // 查询结果与按 SQL 优先级计算的结果一致
+    type Oracle = fn(bool, bool, i32) -> bool;
+    let cases: [(&str, Oracle); 5] = [
+        ("a IS NULL AND b IS NOT NULL OR c = 1", |a, b, c| (!a && b) || c == 1),
+        ("c = 1 OR a IS NULL AND b IS NOT NULL", |a, b, c| c == 1 || (!a && b)),
+        ("a IS NULL AND (b IS NOT NULL OR c = 1)", |a, b, c| !a && (b || c == 1)),
+        ("(a IS NULL AND b IS NOT NULL) OR c = 1", |a, b, c| (!a && b) || c == 1),
+        ("a IS NOT NULL OR b IS NULL AND c = 0", |a, b, c| a || (!b && c == 0)),
+    ];
+    for (predicate, oracle) in cases {
+        let expected: Vec<i32> = (0..8).filter(|id| oracle(id / 4 == 1, id / 2 % 2 == 1, id % 2)).collect();
+        let actual = ids(&mut db, predicate);
+        println!("{} -> {:?}", predicate, actual);
+        assert_eq!(actual, expected, "{}", predicate);
+    }
+
+    // 表达式上的 IS NULL 同样先结合：NULL 参与运算结果为 NULL
+    assert_eq!(ids(&mut db, "(a + 1) IS NULL AND b IS NOT NULL OR c = 1"), vec![1, 2, 3, 5, 7]);
+    println!("IS [NOT] NULL 的优先级正确");
+}
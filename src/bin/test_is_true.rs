@@ -0,0 +1,66 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::types::DataType;
+use simple_db::StatementResult;
+
+// 执行查询并返回结果中的 id 列
+fn ids(db: &Database, sql: &str) -> Vec<i32> {
+    let rows = db.query_iter(sql)
+        .unwrap_or_else(|e| panic!("查询失败 {}: {}", sql, e.detailed_message()))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap_or_else(|e| panic!("查询失败 {}: {}", sql, e.detailed_message()));
+    let ids = rows.iter()
+        .map(|row| match &row[0] {
+            DataType::Int(id) => *id,
+            other => panic!("期望整数，实际为 {:?}", other),
+        })
+        .collect();
+    println!("{} -> {:?}", sql, ids);
+    ids
+}
+
+fn setup() -> Database {
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql("CREATE TABLE flags (id INT PRIMARY KEY, active INT, score FLOAT, name VARCHAR(10))").unwrap();
+    db.execute_sql("INSERT INTO flags VALUES (1, 1, 0.5, 'a'), (2, 0, 0.0, 'b'), (3, NULL, NULL, 'c'), (4, 5, -1.5, 'd')").unwrap();
+    db
+}
+
+fn main() {
+    println!("=== 测试 IS [NOT] TRUE / FALSE ===");
+    let mut db = setup();
+
+    // 非零为真，零为假，NULL 既不是真也不是假
+    assert_eq!(ids(&db, "SELECT id FROM flags WHERE active IS TRUE"), vec![1, 4]);
+    assert_eq!(ids(&db, "SELECT id FROM flags WHERE active IS FALSE"), vec![2]);
+    assert_eq!(ids(&db, "SELECT id FROM flags WHERE active IS NOT TRUE"), vec![2, 3]);
+    assert_eq!(ids(&db, "SELECT id FROM flags WHERE active IS NOT FALSE"), vec![1, 3, 4]);
+    assert_eq!(ids(&db, "SELECT id FROM flags WHERE score IS TRUE"), vec![1, 4]);
+    assert_eq!(ids(&db, "SELECT id FROM flags WHERE score is false"), vec![2]);
+
+    // 可以用于表达式，并与其他条件组合
+    assert_eq!(ids(&db, "SELECT id FROM flags WHERE id - 1 IS FALSE"), vec![1]);
+    assert_eq!(ids(&db, "SELECT id, active FROM flags WHERE active IS NOT TRUE AND id > 2"), vec![3]);
+    assert_eq!(ids(&db, "SELECT id FROM flags WHERE active IS TRUE OR name = 'c'"), vec![1, 3, 4]);
+
+    // EXPLAIN 中显示条件
+    match db.execute("EXPLAIN SELECT id FROM flags WHERE active IS NOT TRUE") {
+        Ok(StatementResult::ResultSet(result)) => {
+            println!("{:?}", result.rows);
+            assert!(result.rows.iter().any(|row| row[2] == DataType::Varchar("active IS NOT TRUE".to_string())));
+        }
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+
+    // 字符串不能判断真假
+    let error = db.query_iter("SELECT id FROM flags WHERE name IS TRUE").unwrap().collect::<Result<Vec<_>, _>>().unwrap_err();
+    println!("字符串: {}", error.detailed_message());
+    assert!(db.query_iter("SELECT id FROM flags WHERE active IS MAYBE").is_err());
+
+    // UPDATE 和 DELETE 中同样可用
+    let mut db = setup();
+    db.execute_sql("UPDATE flags SET name = 'off' WHERE active IS NOT TRUE").unwrap();
+    assert_eq!(ids(&db, "SELECT id FROM flags WHERE name = 'off'"), vec![2, 3]);
+    db.execute_sql("DELETE FROM flags WHERE active IS FALSE").unwrap();
+    assert_eq!(ids(&db, "SELECT id FROM flags"), vec![1, 3, 4]);
+    println!("IS [NOT] TRUE / FALSE 的结果正确");
+}
@@ -0,0 +1,47 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::error::DbError;
+use simple_db::core::types::DataType;
+use std::fs;
+
+// 执行查询并收集全部结果行
+fn query(db: &Database, sql: &str) -> Vec<Vec<DataType>> {
+    db.query_iter(sql)
+        .unwrap_or_else(|e| panic!("查询失败 {}: {}", sql, e.detailed_message()))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap_or_else(|e| panic!("查询失败 {}: {}", sql, e.detailed_message()))
+}
+
+fn main() {
+    let base_dir = std::env::temp_dir().join("simple_db_test_load_error");
+    let _ = fs::remove_dir_all(&base_dir);
+
+    println!("=== 测试加载失败时保留现有的表 ===");
+    let mut db = Database::new(StorageType::File(base_dir.clone()));
+    db.execute_sql("CREATE TABLE users (id INT PRIMARY KEY, name VARCHAR(20))").unwrap();
+    db.execute_sql("INSERT INTO users VALUES (1, 'Alice'), (2, 'Bob')").unwrap();
+    db.execute_sql("CREATE TABLE orders (id INT, amount INT)").unwrap();
+    db.execute_sql("INSERT INTO orders VALUES (10, 100)").unwrap();
+    db.load().unwrap();
+    let users = query(&db, "SELECT * FROM users");
+    let orders = query(&db, "SELECT * FROM orders");
+    assert_eq!(users.len(), 2);
+    assert_eq!(orders.len(), 1);
+
+    // 第一次加载之后破坏一个表文件，再次加载必须失败
+    fs::write(base_dir.join("tables").join("orders.json"), "{ not valid json").unwrap();
+    let err = db.load().unwrap_err();
+    println!("加载错误: {}", err.detailed_message());
+    assert!(matches!(err, DbError::Serialization(_)), "应为反序列化错误: {:?}", err);
+
+    // 加载失败后，之前已加载的表仍然完整可用
+    let mut tables = db.list_tables().unwrap();
+    tables.sort();
+    assert_eq!(tables, vec!["orders", "users"]);
+    assert_eq!(query(&db, "SELECT * FROM users"), users);
+    assert_eq!(query(&db, "SELECT * FROM orders"), orders);
+    assert_eq!(db.reload().unwrap_err().to_string(), err.to_string());
+    assert_eq!(query(&db, "SELECT * FROM users"), users);
+
+    let _ = fs::remove_dir_all(&base_dir);
+    println!("测试通过");
+}
@@ -0,0 +1,46 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::error::DbError;
+use simple_db::core::types::TypeError;
+use std::fs;
+
+// 填满 MAX_ROWS 限制后，下一次插入必须失败且不改变表中的数据
+fn check_max_rows(db: &mut Database) {
+    db.execute_sql("CREATE TABLE limited (id INT, name VARCHAR(10)) MAX_ROWS = 3").unwrap();
+    assert_eq!(db.get_table("limited").unwrap().unwrap().max_rows, Some(3));
+
+    db.execute_sql("INSERT INTO limited VALUES (1, 'a'), (2, 'b')").unwrap();
+    db.execute_sql("INSERT INTO limited VALUES (3, 'c')").unwrap();
+    assert_eq!(db.get_table("limited").unwrap().unwrap().rows.len(), 3);
+
+    let err = db.execute_sql("INSERT INTO limited VALUES (4, 'd')").unwrap_err();
+    println!("插入错误: {}", err.detailed_message());
+    assert!(matches!(err, DbError::TypeError(TypeError::TableFull(ref name)) if name == "limited"), "应为表已满错误: {:?}", err);
+    assert_eq!(db.get_table("limited").unwrap().unwrap().rows.len(), 3);
+
+    // 删除一行后可以再插入一行
+    db.execute_sql("DELETE FROM limited WHERE id = 1").unwrap();
+    db.execute_sql("INSERT INTO limited VALUES (4, 'd')").unwrap();
+    assert_eq!(db.get_table("limited").unwrap().unwrap().rows.len(), 3);
+    assert!(db.execute_sql("INSERT INTO limited VALUES (5, 'e')").is_err());
+}
+
+fn main() {
+    println!("=== 测试内存存储的 MAX_ROWS ===");
+    let mut db = Database::new(StorageType::Memory);
+    check_max_rows(&mut db);
+
+    println!("=== 测试文件存储的 MAX_ROWS ===");
+    let base_dir = std::env::temp_dir().join("simple_db_test_max_rows");
+    let _ = fs::remove_dir_all(&base_dir);
+    let mut db = Database::new(StorageType::File(base_dir.clone()));
+    check_max_rows(&mut db);
+
+    // 重新加载后限制仍然生效
+    db.load().unwrap();
+    assert_eq!(db.get_table("limited").unwrap().unwrap().max_rows, Some(3));
+    assert!(db.execute_sql("INSERT INTO limited VALUES (6, 'f')").is_err());
+    assert_eq!(db.get_table("limited").unwrap().unwrap().rows.len(), 3);
+
+    let _ = fs::remove_dir_all(&base_dir);
+    println!("测试通过");
+}
@@ -0,0 +1,29 @@
+use simple_db::core::db::{Database, StorageKind, StorageType};
+use simple_db::{execute_script, execute_sql_with_path};
+use std::fs;
+
+const SCRIPT: &str = "CREATE TABLE t (id INT);\nINSERT INTO t VALUES (1);\nSELECT * FROM t;\n";
+
+fn main() {
+    println!("=== 测试内存数据库执行脚本不写入文件 ===");
+    let dir = std::env::temp_dir().join("simple_db_test_memory_script");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    std::env::set_current_dir(&dir).unwrap();
+
+    // 不提供路径时使用内存存储
+    assert!(execute_sql_with_path(SCRIPT, None, true));
+
+    // 按内存数据库的存储配置新建数据库，仍然是内存存储
+    let mut db = Database::new(StorageType::Memory);
+    assert!(execute_script(&mut db, SCRIPT, true));
+    let mut copy = Database::new(db.storage_type());
+    assert_eq!(copy.storage_kind(), StorageKind::Memory);
+    assert!(execute_script(&mut copy, SCRIPT, true));
+
+    assert_eq!(fs::read_dir(&dir).unwrap().count(), 0, "内存数据库不应创建任何文件");
+
+    std::env::set_current_dir(std::env::temp_dir()).unwrap();
+    let _ = fs::remove_dir_all(&dir);
+    println!("内存数据库执行脚本没有创建文件");
+}
@@ -0,0 +1,58 @@
+use simple_db::core::error::DbError;
+use simple_db::core::types::{ColumnType, DataType, TypeError};
+use simple_db::{Database, StorageType};
+
+fn text(s: &str) -> DataType {
+    DataType::Varchar(s.to_string())
+}
+
+fn column_type(db: &Database, column: &str) -> ColumnType {
+    db.get_table("items").unwrap().unwrap().columns.iter()
+        .find(|c| c.name == column)
+        .unwrap()
+        .data_type
+        .clone()
+}
+
+fn rows(db: &Database) -> Vec<Vec<DataType>> {
+    db.get_table("items").unwrap().unwrap().rows.clone()
+}
+
+fn main() {
+    println!("=== 测试修改列类型 ===");
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql("CREATE TABLE items (id INT PRIMARY KEY, code INT, label VARCHAR(10))").unwrap();
+    db.execute_sql("INSERT INTO items VALUES (1, 42, '7'), (2, NULL, 'abc'), (3, -5, '12')").unwrap();
+
+    // INT 放宽为 VARCHAR：每个值转换为字符串，NULL 保持为 NULL
+    db.execute_sql("ALTER TABLE items MODIFY COLUMN code VARCHAR(10)").unwrap();
+    assert_eq!(column_type(&db, "code"), ColumnType::Varchar(10));
+    assert_eq!(rows(&db), vec![
+        vec![DataType::Int(1), text("42"), text("7")],
+        vec![DataType::Int(2), DataType::Null, text("abc")],
+        vec![DataType::Int(3), text("-5"), text("12")],
+    ]);
+    db.execute_sql("INSERT INTO items VALUES (4, 'x9', 'y')").unwrap();
+    db.execute_sql("DELETE FROM items WHERE id = 4").unwrap();
+    let before = rows(&db);
+
+    // VARCHAR 收窄为 INT：'abc' 无法转换，整个修改失败，表保持不变
+    let err = db.execute("ALTER TABLE items MODIFY COLUMN label INT").unwrap_err();
+    println!("收窄失败: {}", err.detailed_message());
+    match err {
+        DbError::TypeError(TypeError::TypeMismatch { expected, actual }) => {
+            assert_eq!(expected, ColumnType::Int(None));
+            assert_eq!(actual, text("abc"));
+        }
+        other => panic!("应为类型不匹配错误: {:?}", other),
+    }
+    assert_eq!(column_type(&db, "label"), ColumnType::Varchar(10));
+    assert_eq!(rows(&db), before);
+
+    // 收窄长度时超长的值同样使修改失败
+    assert!(db.execute("ALTER TABLE items MODIFY COLUMN label VARCHAR(2)").is_err());
+    assert_eq!(column_type(&db, "label"), ColumnType::Varchar(10));
+    assert_eq!(rows(&db), before);
+
+    println!("测试通过");
+}
@@ -0,0 +1,54 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::types::DataType;
+
+// 执行查询并返回结果中的 id 列
+fn ids(db: &Database, sql: &str) -> Vec<i32> {
+    let rows = db.query_iter(sql)
+        .unwrap_or_else(|e| panic!("查询失败 {}: {}", sql, e.detailed_message()))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap_or_else(|e| panic!("查询失败 {}: {}", sql, e.detailed_message()));
+    let ids = rows.iter()
+        .map(|row| match &row[0] {
+            DataType::Int(id) => *id,
+            other => panic!("期望整数，实际为 {:?}", other),
+        })
+        .collect();
+    println!("{} -> {:?}", sql, ids);
+    ids
+}
+
+fn setup() -> Database {
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql("CREATE TABLE items (id INT PRIMARY KEY, x INT, name VARCHAR(10))").unwrap();
+    db.execute_sql("INSERT INTO items VALUES (1, 5, 'a'), (2, 3, 'b'), (3, NULL, 'c'), (4, 7, NULL), (5, 5, 'e')").unwrap();
+    db
+}
+
+fn main() {
+    println!("=== 测试不等于运算符 <> ===");
+    let db = setup();
+
+    // <> 与 != 的结果相同
+    let conditions = ["x {} 5", "x{}5", "name {} 'a'", "id * 2 {} 10", "x {} 5 AND name {} 'b'"];
+    for condition in conditions {
+        let with_ne = ids(&db, &format!("SELECT id FROM items WHERE {}", condition.replace("{}", "!=")));
+        let with_angle = ids(&db, &format!("SELECT id FROM items WHERE {}", condition.replace("{}", "<>")));
+        assert_eq!(with_angle, with_ne, "{}", condition);
+    }
+    assert_eq!(ids(&db, "SELECT id FROM items WHERE x <> 5"), vec![2, 3, 4]);
+    assert_eq!(ids(&db, "SELECT id, id * 2 FROM items WHERE x <> 5"), vec![2, 3, 4]);
+
+    // < 、<= 和 <=> 不受影响
+    assert_eq!(ids(&db, "SELECT id FROM items WHERE x < 5"), vec![2]);
+    assert_eq!(ids(&db, "SELECT id FROM items WHERE x <= 5"), vec![1, 2, 5]);
+    assert_eq!(ids(&db, "SELECT id FROM items WHERE x <=> NULL"), vec![3]);
+    assert_eq!(ids(&db, "SELECT id FROM items WHERE x<5"), vec![2]);
+
+    // UPDATE 和 DELETE 中同样可用
+    let mut db = setup();
+    db.execute_sql("UPDATE items SET name = 'z' WHERE x <> 5").unwrap();
+    assert_eq!(ids(&db, "SELECT id FROM items WHERE name = 'z'"), vec![2, 3, 4]);
+    db.execute_sql("DELETE FROM items WHERE x <> 5").unwrap();
+    assert_eq!(ids(&db, "SELECT id FROM items"), vec![1, 5]);
+    println!("不等于运算符 <> 的结果正确");
+}
@@ -0,0 +1,41 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::types::DataType;
+use simple_db::StatementResult;
+
+// 执行查询并返回结果行
+fn rows(db: &mut Database, sql: &str) -> Vec<Vec<DataType>> {
+    match db.execute(sql).unwrap() {
+        StatementResult::ResultSet(result) => result.rows,
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+}
+
+fn main() {
+    println!("=== 测试 NULL 参与算术运算 ===");
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql("CREATE TABLE products (id INT PRIMARY KEY, price FLOAT, qty INT, name VARCHAR(10))").unwrap();
+    db.execute_sql("INSERT INTO products VALUES (1, 2.5, 4, 'pen'), (2, NULL, 3, 'ink'), (3, 1.0, NULL, NULL)").unwrap();
+
+    // 不带表的表达式
+    assert_eq!(rows(&mut db, "SELECT NULL * 5"), vec![vec![DataType::Null]]);
+    assert_eq!(rows(&mut db, "SELECT 1 + NULL, NULL / 0, (NULL + 1) * 2"), vec![vec![DataType::Null; 3]]);
+
+    // 列与 NULL 运算，以及值为 NULL 的列参与运算
+    assert_eq!(rows(&mut db, "SELECT price + NULL FROM products"), vec![vec![DataType::Null]; 3]);
+    assert_eq!(rows(&mut db, "SELECT id, price * qty FROM products"), vec![
+        vec![DataType::Int(1), DataType::Float(10.0)],
+        vec![DataType::Int(2), DataType::Null],
+        vec![DataType::Int(3), DataType::Null],
+    ]);
+    // 字符串列为 NULL 时同样得到 NULL
+    assert_eq!(rows(&mut db, "SELECT name + 1 FROM products WHERE id = 3"), vec![vec![DataType::Null]]);
+
+    // WHERE 中的算术结果为 NULL 时条件不成立
+    assert_eq!(rows(&mut db, "SELECT id FROM products WHERE price * qty > 0"), vec![vec![DataType::Int(1)]]);
+    assert_eq!(rows(&mut db, "SELECT id FROM products WHERE qty + NULL > 0"), Vec::<Vec<DataType>>::new());
+    assert_eq!(rows(&mut db, "SELECT id FROM products WHERE price + 1 IS NULL"), vec![vec![DataType::Int(2)]]);
+
+    // 非 NULL 的字符串仍然不能参与运算
+    assert!(db.execute("SELECT name + 1 FROM products WHERE id = 1").is_err());
+    println!("NULL 参与算术运算的结果正确");
+}
@@ -0,0 +1,56 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::sql::{ArithmeticOperator, Expression, SqlParser, SqlStatement};
+use simple_db::core::types::DataType;
+use simple_db::StatementResult;
+
+// 执行查询并返回表头和结果行
+fn query(db: &mut Database, sql: &str) -> (Vec<String>, Vec<Vec<DataType>>) {
+    match db.execute(sql).unwrap() {
+        StatementResult::ResultSet(result) => (result.columns, result.rows),
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+}
+
+fn main() {
+    println!("=== 测试 NULL 作为表达式中的值 ===");
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql("CREATE TABLE t (id INT PRIMARY KEY, c INT, s VARCHAR(10))").unwrap();
+    db.execute_sql("INSERT INTO t VALUES (1, 5, 'a'), (2, 7, 'b')").unwrap();
+
+    // SELECT 列表中可以直接写 NULL
+    let (headers, rows) = query(&mut db, "SELECT NULL");
+    assert_eq!(headers, vec!["NULL".to_string()]);
+    assert_eq!(rows, vec![vec![DataType::Null]]);
+    let (_, rows) = query(&mut db, "SELECT id, NULL FROM t");
+    assert_eq!(rows, vec![
+        vec![DataType::Int(1), DataType::Null],
+        vec![DataType::Int(2), DataType::Null],
+    ]);
+
+    // NULL 可以作为算术表达式的操作数
+    match SqlParser::new().parse("SELECT NULL + 1").unwrap() {
+        SqlStatement::SelectExpression { expressions, .. } => match &expressions[0] {
+            Expression::Binary { left, operator: ArithmeticOperator::Add, right } => {
+                assert!(matches!(**left, Expression::Literal(DataType::Null)));
+                assert!(matches!(**right, Expression::Literal(DataType::Int(1))));
+            }
+            other => panic!("期望加法表达式，实际为 {:?}", other),
+        },
+        other => panic!("期望表达式查询，实际为 {:?}", other),
+    }
+    let (_, rows) = query(&mut db, "SELECT NULL + 1");
+    assert_eq!(rows, vec![vec![DataType::Null]]);
+
+    // UPDATE 和 INSERT 中的 NULL
+    db.execute_sql("UPDATE t SET c = NULL WHERE id = 1").unwrap();
+    db.execute_sql("INSERT INTO t VALUES (3, NULL, NULL)").unwrap();
+    let (_, rows) = query(&mut db, "SELECT id FROM t WHERE c IS NULL");
+    assert_eq!(rows, vec![vec![DataType::Int(1)], vec![DataType::Int(3)]]);
+    let (_, rows) = query(&mut db, "SELECT s FROM t WHERE id = 3");
+    assert_eq!(rows, vec![vec![DataType::Null]]);
+
+    // 条件左侧也可以是 NULL
+    let (_, rows) = query(&mut db, "SELECT id FROM t WHERE NULL IS NULL");
+    assert_eq!(rows.len(), 3);
+    println!("NULL 作为值的结果正确");
+}
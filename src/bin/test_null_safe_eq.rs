@@ -0,0 +1,51 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::types::DataType;
+
+// 执行查询并返回结果中的 id 列
+fn ids(db: &Database, sql: &str) -> Vec<i32> {
+    let rows = db.query_iter(sql)
+        .unwrap_or_else(|e| panic!("查询失败 {}: {}", sql, e.detailed_message()))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap_or_else(|e| panic!("查询失败 {}: {}", sql, e.detailed_message()));
+    let ids = rows.iter()
+        .map(|row| match &row[0] {
+            DataType::Int(id) => *id,
+            other => panic!("期望整数，实际为 {:?}", other),
+        })
+        .collect();
+    println!("{} -> {:?}", sql, ids);
+    ids
+}
+
+fn main() {
+    println!("=== 测试 NULL 安全的相等比较 <=> ===");
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql("CREATE TABLE pairs (id INT, a INT, b INT, label VARCHAR(10))").unwrap();
+    db.execute_sql("INSERT INTO pairs VALUES (1, NULL, NULL, 'x'), (2, NULL, 5, NULL), (3, 5, NULL, 'y'), (4, 5, 5, 'x'), (5, 5, 6, 'z'), (6, 2, 2, NULL)").unwrap();
+
+    // NULL 与 NULL 相等，NULL 与非 NULL 不相等，两个非 NULL 值按值比较
+    assert_eq!(ids(&db, "SELECT id FROM pairs WHERE a <=> b"), vec![1, 4, 6]);
+    assert_eq!(ids(&db, "SELECT id FROM pairs WHERE a <=> NULL"), vec![1, 2]);
+    assert_eq!(ids(&db, "SELECT id FROM pairs WHERE b <=> NULL"), vec![1, 3]);
+    assert_eq!(ids(&db, "SELECT id FROM pairs WHERE a <=> 5"), vec![3, 4, 5]);
+    assert_eq!(ids(&db, "SELECT id FROM pairs WHERE label <=> 'x'"), vec![1, 4]);
+    assert_eq!(ids(&db, "SELECT id FROM pairs WHERE label <=> NULL"), vec![2, 6]);
+    assert_eq!(ids(&db, "SELECT id FROM pairs WHERE id + 1 <=> b"), vec![4, 5]);
+    assert_eq!(ids(&db, "SELECT id FROM pairs WHERE a <=> b AND label <=> 'x'"), vec![1, 4]);
+
+    // 使用索引时结果相同
+    db.execute_sql("CREATE INDEX idx_a ON pairs (a)").unwrap();
+    assert_eq!(ids(&db, "SELECT id FROM pairs WHERE a <=> 5"), vec![3, 4, 5]);
+    assert_eq!(ids(&db, "SELECT id FROM pairs WHERE 5 <=> a"), vec![3, 4, 5]);
+    assert_eq!(ids(&db, "SELECT id FROM pairs WHERE a <=> NULL"), vec![1, 2]);
+
+    // 修改和删除同样可以使用
+    db.execute_sql("UPDATE pairs SET label = 'null' WHERE b <=> NULL").unwrap();
+    assert_eq!(ids(&db, "SELECT id FROM pairs WHERE label = 'null'"), vec![1, 3]);
+    db.execute_sql("DELETE FROM pairs WHERE a <=> NULL").unwrap();
+    assert_eq!(ids(&db, "SELECT id FROM pairs"), vec![3, 4, 5, 6]);
+    println!("<=> 的比较结果正确");
+
+    println!("\n执行: EXPLAIN SELECT id FROM pairs WHERE a <=> 5");
+    db.execute_sql("EXPLAIN SELECT id FROM pairs WHERE a <=> 5").unwrap();
+}
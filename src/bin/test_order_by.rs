@@ -0,0 +1,124 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::types::DataType;
+use simple_db::StatementResult;
+use std::fs;
+use std::process::Command;
+
+// 执行查询并收集全部结果行
+fn query(db: &Database, sql: &str) -> Vec<Vec<DataType>> {
+    db.query_iter(sql)
+        .unwrap_or_else(|e| panic!("查询失败 {}: {}", sql, e.detailed_message()))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap_or_else(|e| panic!("查询失败 {}: {}", sql, e.detailed_message()))
+}
+
+// 在临时目录中用 simple_db 执行脚本，返回标准输出
+fn run_script(sql: &str) -> String {
+    let dir = std::env::temp_dir().join("simple_db_test_order_by");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("script.sql"), sql).unwrap();
+
+    // simple_db 与当前测试程序位于同一目录
+    let binary = std::env::current_exe().unwrap().with_file_name("simple_db");
+    let output = Command::new(&binary)
+        .arg("script.sql")
+        .current_dir(&dir)
+        .output()
+        .unwrap_or_else(|e| panic!("无法运行 {}: {}", binary.display(), e));
+    let _ = fs::remove_dir_all(&dir);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+// 取出结果中第一列的整数
+fn ids(rows: &[Vec<DataType>]) -> Vec<i32> {
+    rows.iter()
+        .map(|row| match &row[0] {
+            DataType::Int(id) => *id,
+            other => panic!("期望整数，实际为 {:?}", other),
+        })
+        .collect()
+}
+
+fn main() {
+    println!("=== 测试 ORDER BY 的稳定性 ===");
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql("CREATE TABLE tasks (id INT, grp VARCHAR(10), priority INT)").unwrap();
+
+    // 排序列只有少数几个取值，插入顺序与 id 顺序一致
+    let groups = ["b", "a", "c", "a", "b", "c", "a", "b", "a", "c"];
+    for (id, grp) in groups.iter().enumerate() {
+        let priority = if id % 3 == 0 { "NULL".to_string() } else { (id % 2).to_string() };
+        db.execute_sql(&format!("INSERT INTO tasks VALUES ({}, '{}', {})", id, grp, priority)).unwrap();
+    }
+
+    // 同一分组内的行保持插入顺序
+    let rows = query(&db, "SELECT id, grp FROM tasks ORDER BY grp");
+    println!("ORDER BY grp: {:?}", ids(&rows));
+    assert_eq!(ids(&rows), vec![1, 3, 6, 8, 0, 4, 7, 2, 5, 9]);
+
+    // DESC 只反转分组的顺序，分组内仍然保持插入顺序
+    let rows = query(&db, "SELECT id, grp FROM tasks ORDER BY grp DESC");
+    println!("ORDER BY grp DESC: {:?}", ids(&rows));
+    assert_eq!(ids(&rows), vec![2, 5, 9, 0, 4, 7, 1, 3, 6, 8]);
+
+    // 数值列和 NULL 相同的行同样保持插入顺序
+    let rows = query(&db, "SELECT id, priority FROM tasks ORDER BY priority");
+    println!("ORDER BY priority: {:?}", ids(&rows));
+    assert_eq!(ids(&rows), vec![0, 3, 6, 9, 2, 4, 8, 1, 5, 7]);
+
+    let rows = query(&db, "SELECT * FROM tasks WHERE id > 2 ORDER BY grp");
+    println!("WHERE id > 2 ORDER BY grp: {:?}", ids(&rows));
+    assert_eq!(ids(&rows), vec![3, 6, 8, 4, 7, 5, 9]);
+    println!("排序键相同的行保持原有顺序");
+
+    // 打印结果的执行路径使用同样的稳定排序
+    println!("\n执行: SELECT id, grp FROM tasks ORDER BY grp");
+    db.execute_sql("SELECT id, grp FROM tasks ORDER BY grp").unwrap();
+
+    // 按不在结果中的表列排序，结果只包含投影的列
+    println!("\n=== 测试按未投影的列排序 ===");
+    let rows = query(&db, "SELECT id FROM tasks ORDER BY grp DESC");
+    println!("SELECT id ORDER BY grp DESC: {:?}", ids(&rows));
+    assert_eq!(ids(&rows), vec![2, 5, 9, 0, 4, 7, 1, 3, 6, 8]);
+    assert!(rows.iter().all(|row| row.len() == 1));
+
+    let rows = query(&db, "SELECT id, id * 10 FROM tasks WHERE id < 6 ORDER BY priority");
+    println!("SELECT id, id * 10 ORDER BY priority: {:?}", rows);
+    assert_eq!(ids(&rows), vec![0, 3, 2, 4, 1, 5]);
+    assert!(rows.iter().all(|row| row.len() == 2));
+
+    // 表中也不存在的列仍然报错
+    assert!(db.query_iter("SELECT id FROM tasks ORDER BY missing").is_err());
+    assert!(db.execute_sql("SELECT id FROM tasks ORDER BY missing").is_err());
+
+    println!("\n执行: SELECT id FROM tasks WHERE grp = 'a' ORDER BY priority DESC");
+    db.execute_sql("SELECT id FROM tasks WHERE grp = 'a' ORDER BY priority DESC").unwrap();
+    println!("按未投影的列排序正确");
+
+    // 打印结果与返回结果使用同样的比较规则：NULL 最小，数值按大小而不是按字符串排序
+    println!("\n=== 测试各执行路径的排序一致 ===");
+    let setup = "CREATE TABLE t (label VARCHAR(10), f FLOAT);\n\
+        INSERT INTO t VALUES ('hundred', 100.0), ('neg', -1.5), ('none', NULL), ('ten', 10.5), ('nine', 9.25);\n";
+    let mut db = Database::new(StorageType::Memory);
+    for result in db.execute_many(setup, true) {
+        result.unwrap();
+    }
+    let labels = |rows: Vec<Vec<DataType>>| rows.into_iter().map(|row| row[0].to_string()).collect::<Vec<_>>();
+    for (sql, expected) in [
+        ("SELECT * FROM t ORDER BY f", ["none", "neg", "nine", "ten", "hundred"]),
+        ("SELECT label FROM t ORDER BY f DESC", ["hundred", "ten", "nine", "neg", "none"]),
+    ] {
+        assert_eq!(labels(query(&db, sql)), expected, "{}", sql);
+        match db.execute(sql).unwrap() {
+            StatementResult::ResultSet(result) => assert_eq!(labels(result.rows), expected, "{}", sql),
+            other => panic!("期望结果集，实际为 {:?}", other),
+        }
+        let output = run_script(&format!("{}{};\n", setup, sql));
+        let positions: Vec<usize> = expected.iter()
+            .map(|label| output.find(&format!(" {} ", label)).unwrap_or_else(|| panic!("输出中没有 {}: {}", label, output)))
+            .collect();
+        assert!(positions.windows(2).all(|pair| pair[0] < pair[1]), "{} 的打印顺序不一致:\n{}", sql, output);
+    }
+    println!("各执行路径的排序结果一致");
+}
@@ -0,0 +1,82 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::error::DbError;
+use simple_db::core::types::{ColumnType, DataType, TypeError};
+use simple_db::StatementResult;
+use std::fs;
+
+fn text(s: &str) -> DataType {
+    DataType::Varchar(s.to_string())
+}
+
+// 解析失败时应返回类型不匹配错误
+fn assert_mismatch(s: &str, column_type: ColumnType) {
+    match DataType::parse_as(s, &column_type) {
+        Err(DbError::TypeError(TypeError::TypeMismatch { expected, actual })) => {
+            assert_eq!(expected, column_type);
+            assert_eq!(actual, text(s));
+        }
+        other => panic!("{:?} 解析为 {:?} 时期望类型不匹配，实际为 {:?}", s, column_type, other),
+    }
+}
+
+fn main() {
+    println!("=== 测试按列类型解析字符串 ===");
+    // 整数
+    let int = ColumnType::Int(None);
+    assert_eq!(DataType::parse_as("42", &int).unwrap(), DataType::Int(42));
+    assert_eq!(DataType::parse_as(" -7 ", &int).unwrap(), DataType::Int(-7));
+    assert_eq!(DataType::parse_as("", &int).unwrap(), DataType::Null);
+    assert_mismatch("abc", int.clone());
+    assert_mismatch("1.5", int.clone());
+    assert_mismatch("99999999999", int.clone());
+    assert_mismatch("   ", int.clone());
+
+    // 浮点数
+    let float = ColumnType::Float(None);
+    assert_eq!(DataType::parse_as("2.5", &float).unwrap(), DataType::Float(2.5));
+    assert_eq!(DataType::parse_as("3", &float).unwrap(), DataType::Float(3.0));
+    assert_eq!(DataType::parse_as("-1e3", &float).unwrap(), DataType::Float(-1000.0));
+    assert_eq!(DataType::parse_as("", &float).unwrap(), DataType::Null);
+    assert_mismatch("1,5", float.clone());
+    assert_mismatch("one", float);
+
+    // 字符串按原样保留，超出长度时报错
+    let varchar = ColumnType::Varchar(5);
+    assert_eq!(DataType::parse_as("hello", &varchar).unwrap(), text("hello"));
+    assert_eq!(DataType::parse_as(" a b ", &varchar).unwrap(), text(" a b "));
+    assert_eq!(DataType::parse_as("", &varchar).unwrap(), DataType::Null);
+    assert_mismatch("toolong", varchar);
+
+    // COPY FROM 使用同样的规则，加引号的空字段仍是空字符串
+    let dir = std::env::temp_dir().join("simple_db_test_parse_as");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let csv_path = dir.join("items.csv");
+    fs::write(&csv_path, "1,2.5,pen\n2,,\"\"\n3, 4 ,\n").unwrap();
+
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql("CREATE TABLE items (id INT, price FLOAT, name VARCHAR(5))").unwrap();
+    let copy = format!("COPY items FROM '{}'", csv_path.display());
+    assert_eq!(db.execute(&copy).unwrap(), StatementResult::RowsAffected(3));
+    match db.execute("SELECT * FROM items").unwrap() {
+        StatementResult::ResultSet(result) => assert_eq!(result.rows, vec![
+            vec![DataType::Int(1), DataType::Float(2.5), text("pen")],
+            vec![DataType::Int(2), DataType::Null, text("")],
+            vec![DataType::Int(3), DataType::Float(4.0), DataType::Null],
+        ]),
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+
+    // 任何字段无法解析时不导入任何行
+    fs::write(&csv_path, "4,1.0,ok\n5,cheap,bad\n").unwrap();
+    let error = db.execute(&copy).unwrap_err();
+    println!("无法解析的字段: {}", error.detailed_message());
+    assert!(matches!(error, DbError::TypeError(TypeError::TypeMismatch { .. })));
+    match db.execute("SELECT id FROM items").unwrap() {
+        StatementResult::ResultSet(result) => assert_eq!(result.rows.len(), 3),
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+
+    let _ = fs::remove_dir_all(&dir);
+    println!("按列类型解析字符串结果正确");
+}
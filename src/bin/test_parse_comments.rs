@@ -0,0 +1,33 @@
+use simple_db::core::sql::{SqlParser, SqlStatement, Token};
+
+fn main() {
+    println!("=== 测试解析时保留注释 ===");
+    let sql = "-- 查询成年用户\nSELECT id, name /* 只需要两列 */ FROM users -- 结尾的注释\nWHERE age >= 18";
+
+    let mut parser = SqlParser::new();
+    let output = parser.parse_with_comments(sql).unwrap();
+    println!("{:?}", output.comments);
+    assert_eq!(output.comments, vec![
+        Token::Comment(" 查询成年用户".to_string()),
+        Token::MultiLineComment(" 只需要两列 ".to_string()),
+        Token::Comment(" 结尾的注释".to_string()),
+    ]);
+    match output.statement {
+        SqlStatement::SelectWithExpressions { expressions, table, where_clause, .. } => {
+            assert_eq!(expressions.len(), 2);
+            assert_eq!(table, "users");
+            assert!(where_clause.is_some());
+        }
+        other => panic!("期望 SELECT 语句，实际为 {:?}", other),
+    }
+
+    // 没有注释时为空，语句与 parse 的结果相同
+    let output = parser.parse_with_comments("DELETE FROM users WHERE id = 1").unwrap();
+    assert!(output.comments.is_empty());
+    assert_eq!(format!("{:?}", output.statement), format!("{:?}", parser.parse("DELETE FROM users WHERE id = 1").unwrap()));
+
+    // 默认的 parse 仍然丢弃注释，只有注释时同样报错
+    assert!(matches!(parser.parse(sql).unwrap(), SqlStatement::SelectWithExpressions { .. }));
+    assert!(parser.parse_with_comments("-- 只有注释").is_err());
+    println!("注释保留结果正确");
+}
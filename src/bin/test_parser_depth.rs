@@ -0,0 +1,66 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::error::DbError;
+use simple_db::core::sql::{SqlParser, DEFAULT_MAX_DEPTH};
+use simple_db::core::types::DataType;
+use simple_db::StatementResult;
+
+// 用 depth 层括号包住的表达式
+fn nested(depth: usize, inner: &str) -> String {
+    format!("{}{}{}", "(".repeat(depth), inner, ")".repeat(depth))
+}
+
+fn assert_too_deep(result: Result<impl std::fmt::Debug, DbError>) {
+    match result {
+        Err(DbError::SqlError(message)) => assert_eq!(message, "表达式嵌套过深"),
+        other => panic!("期望嵌套过深的错误，实际为 {:?}", other),
+    }
+}
+
+fn main() {
+    println!("=== 测试解析器的嵌套深度限制 ===");
+    let mut parser = SqlParser::new();
+
+    // 极深的括号返回错误而不是栈溢出
+    assert_too_deep(parser.parse(&format!("SELECT {}", nested(2_000, "1"))));
+    assert_too_deep(parser.parse(&format!("SELECT * FROM t WHERE {}", nested(2_000, "a = 1"))));
+    assert_too_deep(parser.parse(&format!("SELECT * FROM t WHERE a = {}", nested(2_000, "1"))));
+
+    // 超过限制后同一个解析器仍能解析正常的语句
+    assert!(parser.parse("SELECT (1 + 2) * 3").is_ok());
+
+    // 限制以内的嵌套可以正常执行
+    let mut db = Database::new(StorageType::Memory);
+    let sql = format!("SELECT {}", nested(DEFAULT_MAX_DEPTH / 2, "1 + 1"));
+    match db.execute(&sql).unwrap() {
+        StatementResult::ResultSet(result) => assert_eq!(result.rows, vec![vec![DataType::Int(2)]]),
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+    db.execute_sql("CREATE TABLE t (a INT)").unwrap();
+    db.execute_sql("INSERT INTO t VALUES (1), (2)").unwrap();
+    let sql = format!("SELECT a FROM t WHERE {}", nested(DEFAULT_MAX_DEPTH / 2, "a = 2"));
+    match db.execute(&sql).unwrap() {
+        StatementResult::ResultSet(result) => assert_eq!(result.rows, vec![vec![DataType::Int(2)]]),
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+
+    // 平铺的运算链和条件链没有嵌套，不受深度限制
+    let sum = (1..=300).map(|n| n.to_string()).collect::<Vec<_>>().join(" + ");
+    match db.execute(&format!("SELECT {}", sum)).unwrap() {
+        StatementResult::ResultSet(result) => assert_eq!(result.rows, vec![vec![DataType::Int(45_150)]]),
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+    let conditions = (0..300).map(|n| format!("a = {}", n)).collect::<Vec<_>>().join(" OR ");
+    match db.execute(&format!("SELECT a FROM t WHERE {}", conditions)).unwrap() {
+        StatementResult::ResultSet(result) => assert_eq!(result.rows, vec![vec![DataType::Int(1)], vec![DataType::Int(2)]]),
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+    let conditions = vec!["a > 0"; 1_000].join(" AND ");
+    assert!(parser.parse(&format!("SELECT * FROM t WHERE {}", conditions)).is_ok());
+    assert!(parser.parse(&format!("SELECT {}", vec!["1"; 1_000].join(" * "))).is_ok());
+
+    // 最大深度可以调整
+    parser.set_max_depth(4);
+    assert!(parser.parse("SELECT ((1))").is_ok());
+    assert_too_deep(parser.parse("SELECT ((((1))))"));
+    println!("嵌套深度限制结果正确");
+}
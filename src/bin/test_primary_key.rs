@@ -1,4 +1,6 @@
 use simple_db::core::db::{Database, StorageType};
+use simple_db::core::error::DbError;
+use simple_db::core::types::{Column, ColumnType, DataType, Table, TypeError};
 use std::path::PathBuf;
 
 fn main() {
@@ -26,7 +28,27 @@ fn main() {
         "CREATE TABLE animals (id INT PRIMARY KEY, name VARCHAR(50) NOT NULL, species VARCHAR(100))",
         "INSERT INTO animals VALUES (1, 'Leo', 'Lion')",
         "INSERT INTO animals VALUES (2, 'Max', 'Dog')",
-        "SELECT * FROM animals"
+        "SELECT * FROM animals",
+        
+        // 主键列隐式为 NOT NULL（应该失败）
+        "INSERT INTO animals VALUES (NULL, 'Tom', 'Cat')",
+        
+        // 忽略冲突的行，其余行正常插入
+        "INSERT INTO animals VALUES (1, 'Tom', 'Cat'), (3, 'Jerry', 'Mouse'), (2, 'Rex', 'Dog') ON CONFLICT DO NOTHING",
+        "SELECT * FROM animals",
+        
+        // 按主键查找
+        "SELECT * FROM animals WHERE id = 3",
+        "EXPLAIN SELECT name FROM animals WHERE id = 3 AND species = 'Mouse'",
+        
+        // 省略末尾可为空的列，以 NULL 填充
+        "INSERT INTO animals VALUES (4, 'Nemo')",
+        
+        // 省略了必填列（应该失败）
+        "INSERT INTO animals VALUES (5)",
+        
+        // 没有任何列的表（应该失败）
+        "CREATE TABLE empty_table ()"
     ];
     
     for (i, &cmd) in test_commands.iter().enumerate() {
@@ -36,4 +58,47 @@ fn main() {
             Err(e) => println!("执行失败: {}", e),
         }
     }
+    
+    // 直接按主键查找行
+    println!("\n=== 测试按主键查找 ===");
+    let row = db.find_by_primary_key("animals", &DataType::Int(3)).unwrap();
+    assert_eq!(row, Some(&[DataType::Int(3), DataType::Varchar("Jerry".to_string()), DataType::Varchar("Mouse".to_string())][..]));
+    assert_eq!(db.find_by_primary_key("animals", &DataType::Int(42)).unwrap(), None);
+    assert_eq!(db.find_by_primary_key("animals", &DataType::Null).unwrap(), None);
+    assert!(db.find_by_primary_key("missing", &DataType::Int(1)).is_err());
+    println!("按主键查找结果正确");
+    
+    // 未写 NOT NULL 的主键列在建表时即标记为不可为空
+    println!("\n=== 测试主键列不能为 NULL ===");
+    let id_column = &db.get_table("animals").unwrap().unwrap().columns[0];
+    assert!(id_column.primary_key && !id_column.nullable);
+    let inserts = [
+        ("INSERT INTO animals VALUES (NULL, 'Tom', 'Cat')", TypeError::NullValue("id".to_string())),
+        ("INSERT INTO animals (name) VALUES ('Tom')", TypeError::NullValue("id".to_string())),
+        (
+            "INSERT INTO animals VALUES (6, 'Tom', 'Cat'), (NULL, 'Rex', 'Dog')",
+            TypeError::NullValueAtRow { column: "id".to_string(), row: 2 },
+        ),
+    ];
+    for (sql, expected) in inserts {
+        match db.execute(sql) {
+            Err(DbError::TypeError(error)) => assert_eq!(error.to_string(), expected.to_string(), "{}", sql),
+            other => panic!("{} 期望主键为空的错误，实际为 {:?}", sql, other),
+        }
+    }
+    assert_eq!(db.get_table("animals").unwrap().unwrap().rows.len(), 4);
+    assert!(db.execute("UPDATE animals SET id = NULL WHERE id = 1").is_err());
+    
+    // 直接构造的表同样如此
+    let table = Table::new("t".to_string(), vec![Column {
+        name: "id".to_string(),
+        data_type: ColumnType::Int(None),
+        nullable: true,
+        primary_key: true,
+        comment: None,
+        default: None,
+    }]);
+    assert!(!table.columns[0].nullable);
+    assert_eq!(table.create_table_sql(), "CREATE TABLE t (id INT PRIMARY KEY)");
+    println!("主键列不能为 NULL");
 } 
\ No newline at end of file
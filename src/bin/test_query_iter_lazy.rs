@@ -0,0 +1,34 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::types::DataType;
+
+fn main() {
+    println!("=== 测试逐行查询只计算取出的行 ===");
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql("CREATE TABLE t (id INT, x INT)").unwrap();
+    // 第二行的 x 为 0，计算 100 / x 会报除数为零
+    db.execute_sql("INSERT INTO t VALUES (1, 4), (2, 0), (3, 5)").unwrap();
+
+    // 完整执行时第二行出错
+    let sql = "SELECT id, 100 / x FROM t";
+    assert!(db.execute(sql).is_err());
+    let all: Result<Vec<_>, _> = db.query_iter(sql).unwrap().collect();
+    assert!(all.unwrap_err().detailed_message().contains("除数不能为零"));
+
+    // 只取第一行时不会计算后面出错的行
+    let first: Vec<_> = db.query_iter(sql).unwrap().take(1).collect::<Result<_, _>>().unwrap();
+    assert_eq!(first, vec![vec![DataType::Int(1), DataType::Int(25)]]);
+
+    // 逐行读取：第一行成功，第二行出错
+    let mut rows = db.query_iter(sql).unwrap();
+    assert_eq!(rows.next().unwrap().unwrap(), vec![DataType::Int(1), DataType::Int(25)]);
+    assert!(rows.next().unwrap().is_err());
+    drop(rows);
+
+    // WHERE 条件同样按需计算：第一行满足条件后不再检查出错的行
+    let where_sql = "SELECT id FROM t WHERE 100 / x > 1";
+    assert!(db.execute(where_sql).is_err());
+    let mut rows = db.query_iter(where_sql).unwrap();
+    assert_eq!(rows.next().unwrap().unwrap(), vec![DataType::Int(1)]);
+
+    println!("测试通过");
+}
@@ -0,0 +1,61 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::types::DataType;
+use std::fs;
+
+// 执行查询并收集全部结果行
+fn query(db: &Database, sql: &str) -> Vec<Vec<DataType>> {
+    db.query_iter(sql)
+        .unwrap_or_else(|e| panic!("查询失败 {}: {}", sql, e.detailed_message()))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap_or_else(|e| panic!("查询失败 {}: {}", sql, e.detailed_message()))
+}
+
+fn main() {
+    let base_dir = std::env::temp_dir().join("simple_db_test_reload");
+    let _ = fs::remove_dir_all(&base_dir);
+
+    println!("=== 测试重新加载 ===");
+    let mut db = Database::new(StorageType::File(base_dir.clone()));
+    db.execute_sql("CREATE TABLE users (id INT PRIMARY KEY, name VARCHAR(20))").unwrap();
+    db.execute_sql("INSERT INTO users VALUES (1, 'Alice'), (2, 'Bob')").unwrap();
+    let saved = query(&db, "SELECT * FROM users");
+
+    // 批量模式中的修改只在内存中，尚未写盘
+    db.begin_batch();
+    db.execute_sql("INSERT INTO users VALUES (3, 'Carol')").unwrap();
+    db.execute_sql("UPDATE users SET name = 'Alicia' WHERE id = 1").unwrap();
+    db.execute_sql("DELETE FROM users WHERE id = 2").unwrap();
+    db.execute_sql("CREATE TABLE scratch (id INT)").unwrap();
+    println!("未保存的修改: {:?}", query(&db, "SELECT * FROM users"));
+    assert_ne!(query(&db, "SELECT * FROM users"), saved);
+
+    // 重新加载后恢复为磁盘上的状态
+    assert_eq!(db.reload().unwrap(), 1);
+    println!("重新加载后: {:?}", query(&db, "SELECT * FROM users"));
+    assert_eq!(query(&db, "SELECT * FROM users"), saved);
+    assert_eq!(db.list_tables().unwrap(), vec!["users"]);
+    assert_eq!(db.find_by_primary_key("users", &DataType::Int(2)).unwrap().map(|row| row.to_vec()), Some(saved[1].clone()));
+
+    // 结束批量模式时被丢弃的修改不会写盘
+    db.end_batch().unwrap();
+    let mut reopened = Database::new(StorageType::File(base_dir.clone()));
+    reopened.load().unwrap();
+    assert_eq!(query(&reopened, "SELECT * FROM users"), saved);
+    assert_eq!(reopened.list_tables().unwrap(), vec!["users"]);
+
+    // 重新加载后的修改照常写盘
+    db.execute_sql("INSERT INTO users VALUES (4, 'Dave')").unwrap();
+    reopened.reload().unwrap();
+    assert_eq!(query(&reopened, "SELECT name FROM users WHERE id = 4"), vec![vec![DataType::Varchar("Dave".to_string())]]);
+    println!("重新加载丢弃了未保存的修改");
+
+    // 内存存储没有磁盘状态，重新加载不改变数据
+    let mut memory = Database::new(StorageType::Memory);
+    memory.execute_sql("CREATE TABLE t (id INT)").unwrap();
+    memory.execute_sql("INSERT INTO t VALUES (1)").unwrap();
+    assert_eq!(memory.reload().unwrap(), 1);
+    assert_eq!(query(&memory, "SELECT * FROM t"), vec![vec![DataType::Int(1)]]);
+    println!("内存存储重新加载后数据不变");
+
+    let _ = fs::remove_dir_all(&base_dir);
+}
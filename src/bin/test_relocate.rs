@@ -0,0 +1,79 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::types::DataType;
+use simple_db::StatementResult;
+use std::fs;
+
+// 执行查询并返回结果行
+fn rows(db: &mut Database, sql: &str) -> Vec<Vec<DataType>> {
+    match db.execute(sql).unwrap() {
+        StatementResult::ResultSet(result) => result.rows,
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+}
+
+fn main() {
+    let base_dir = std::env::temp_dir().join("simple_db_test_relocate");
+    let _ = fs::remove_dir_all(&base_dir);
+    let setup_dir = base_dir.join("setup");
+    let final_dir = base_dir.join("final").join("db");
+
+    println!("=== 测试移动数据库目录 ===");
+    let mut db = Database::new(StorageType::File(setup_dir.clone()));
+    db.execute_sql("CREATE TABLE users (id INT PRIMARY KEY, name VARCHAR(20))").unwrap();
+    db.execute_sql("INSERT INTO users VALUES (1, 'Alice'), (2, 'Bob')").unwrap();
+    db.execute_sql("CREATE INDEX idx_name ON users (name)").unwrap();
+
+    // 移动到不存在的目录，原目录被删除
+    db.relocate(&final_dir).unwrap();
+    assert_eq!(db.get_storage_path(), Some(final_dir.clone()));
+    assert!(!setup_dir.exists());
+    assert!(final_dir.join("tables").join("users.json").exists());
+
+    // 移动后查询和写入都使用新目录
+    assert_eq!(
+        rows(&mut db, "SELECT name FROM users WHERE name = 'Bob'"),
+        vec![vec![DataType::Varchar("Bob".to_string())]]
+    );
+    db.execute_sql("INSERT INTO users VALUES (3, 'Carol')").unwrap();
+    assert!(!setup_dir.exists());
+    drop(db);
+
+    let mut db = Database::new(StorageType::File(final_dir.clone()));
+    db.load().unwrap();
+    assert_eq!(rows(&mut db, "SELECT id FROM users").len(), 3);
+    assert_eq!(rows(&mut db, "SHOW INDEXES FROM users").len(), 1);
+
+    // 目标是非空目录时不移动
+    let occupied = base_dir.join("occupied");
+    fs::create_dir_all(&occupied).unwrap();
+    fs::write(occupied.join("keep.txt"), "keep").unwrap();
+    let error = db.relocate(&occupied).unwrap_err();
+    println!("目标目录非空: {}", error.detailed_message());
+    assert_eq!(db.get_storage_path(), Some(final_dir.clone()));
+    assert_eq!(fs::read_to_string(occupied.join("keep.txt")).unwrap(), "keep");
+    assert!(final_dir.join("tables").join("users.json").exists());
+
+    // 目标是空目录时可以移动
+    let empty = base_dir.join("empty");
+    fs::create_dir_all(&empty).unwrap();
+    db.relocate(&empty).unwrap();
+    assert!(!final_dir.exists());
+    assert_eq!(rows(&mut db, "SELECT id FROM users").len(), 3);
+
+    // 不能移动到自身的子目录，移动到当前位置什么也不做
+    assert!(db.relocate(empty.join("nested")).is_err());
+    // 被拒绝时不在数据库目录中留下新建的目录
+    assert!(db.relocate(empty.join("sub").join("new")).is_err());
+    assert!(db.relocate(empty.join("tables").join("sub").join("new")).is_err());
+    assert!(!empty.join("sub").exists());
+    assert!(!empty.join("tables").join("sub").exists());
+    db.relocate(&empty).unwrap();
+    assert_eq!(rows(&mut db, "SELECT id FROM users").len(), 3);
+
+    // 内存存储没有目录
+    let mut db = Database::new(StorageType::Memory);
+    assert!(db.relocate(base_dir.join("memory")).is_err());
+
+    let _ = fs::remove_dir_all(&base_dir);
+    println!("移动数据库目录结果正确");
+}
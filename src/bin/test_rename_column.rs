@@ -0,0 +1,55 @@
+use simple_db::core::error::DbError;
+use simple_db::core::types::DataType;
+use simple_db::{Database, StorageType};
+
+// 执行查询并收集全部结果行
+fn query(db: &Database, sql: &str) -> Vec<Vec<DataType>> {
+    db.query_iter(sql)
+        .unwrap_or_else(|e| panic!("查询失败 {}: {}", sql, e.detailed_message()))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap_or_else(|e| panic!("查询失败 {}: {}", sql, e.detailed_message()))
+}
+
+// 执行SQL，返回语法错误的消息
+fn sql_error(db: &mut Database, sql: &str) -> String {
+    match db.execute(sql) {
+        Err(DbError::SqlError(message)) => message,
+        other => panic!("{} 应返回语法错误: {:?}", sql, other),
+    }
+}
+
+fn column_names(db: &Database) -> Vec<String> {
+    db.get_table("users").unwrap().unwrap().columns.iter().map(|c| c.name.clone()).collect()
+}
+
+fn main() {
+    println!("=== 测试重命名列 ===");
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql("CREATE TABLE users (id INT PRIMARY KEY, name VARCHAR(20), age INT)").unwrap();
+    db.execute_sql("INSERT INTO users VALUES (1, 'Alice', 30), (2, 'Bob', 25)").unwrap();
+    db.execute_sql("CREATE INDEX idx_age ON users (age)").unwrap();
+    let before = query(&db, "SELECT * FROM users");
+
+    // 重命名后列的位置和数据不变，索引跟随新列名
+    db.execute_sql("ALTER TABLE users RENAME COLUMN age TO years").unwrap();
+    assert_eq!(column_names(&db), vec!["id", "name", "years"]);
+    assert_eq!(query(&db, "SELECT * FROM users"), before);
+    assert_eq!(query(&db, "SELECT name FROM users WHERE years = 25"), vec![vec![DataType::Varchar("Bob".to_string())]]);
+    assert_eq!(db.get_table("users").unwrap().unwrap().indexes[0].column, "years");
+    assert!(db.query_iter("SELECT age FROM users").and_then(|rows| rows.collect::<Result<Vec<_>, _>>()).is_err());
+
+    // 新列名与已有列重名时报错，表不变
+    let message = sql_error(&mut db, "ALTER TABLE users RENAME COLUMN years TO name");
+    println!("重名错误: {}", message);
+    assert_eq!(message, "列 name 在表 users 中已存在");
+    assert_eq!(column_names(&db), vec!["id", "name", "years"]);
+
+    // 原列不存在时报错，表不变
+    let message = sql_error(&mut db, "ALTER TABLE users RENAME COLUMN email TO mail");
+    println!("列不存在错误: {}", message);
+    assert_eq!(message, "列 email 在表 users 中不存在");
+    assert_eq!(column_names(&db), vec!["id", "name", "years"]);
+    assert_eq!(query(&db, "SELECT * FROM users"), before);
+
+    println!("测试通过");
+}
@@ -0,0 +1,74 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::types::DataType;
+use simple_db::StatementResult;
+
+fn text(s: &str) -> DataType {
+    DataType::Varchar(s.to_string())
+}
+
+// 执行语句并返回结果集的列名和数据行
+fn returning(db: &mut Database, sql: &str) -> (Vec<String>, Vec<Vec<DataType>>) {
+    match db.execute(sql).unwrap() {
+        StatementResult::ResultSet(result) => (result.columns, result.rows),
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+}
+
+fn main() {
+    println!("=== 测试 INSERT ... RETURNING ===");
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql("CREATE TABLE users (id INT PRIMARY KEY, name VARCHAR(20), status VARCHAR(10) DEFAULT 'active')").unwrap();
+
+    // 返回的行包含插入时补全的默认值
+    let (columns, rows) = returning(&mut db, "INSERT INTO users (id, name) VALUES (1, 'alice') RETURNING *");
+    assert_eq!(columns, vec!["id", "name", "status"]);
+    assert_eq!(rows, vec![vec![DataType::Int(1), text("alice"), text("active")]]);
+
+    // 指定列，多行插入按插入顺序返回
+    let (columns, rows) = returning(&mut db, "INSERT INTO users VALUES (2, 'bob'), (3, 'carol', 'idle') RETURNING status, id");
+    assert_eq!(columns, vec!["status", "id"]);
+    assert_eq!(rows, vec![vec![text("active"), DataType::Int(2)], vec![text("idle"), DataType::Int(3)]]);
+
+    // 忽略冲突时只返回实际插入的行
+    let (_, rows) = returning(&mut db, "INSERT INTO users VALUES (3, 'dup'), (4, 'dave') ON CONFLICT DO NOTHING RETURNING id");
+    assert_eq!(rows, vec![vec![DataType::Int(4)]]);
+
+    // RETURNING 中的列不存在时报错，且不插入数据
+    assert!(db.execute("INSERT INTO users VALUES (5, 'eve') RETURNING missing").is_err());
+    assert_eq!(db.get_table("users").unwrap().unwrap().rows.len(), 4);
+    assert!(db.execute("INSERT INTO users VALUES (5, 'eve') RETURNING").is_err());
+
+    // 打印路径同样输出插入的行
+    db.execute_sql("INSERT INTO users VALUES (5, 'eve') RETURNING id, name").unwrap();
+    assert_eq!(db.get_table("users").unwrap().unwrap().rows.len(), 5);
+
+    println!("INSERT ... RETURNING 结果正确");
+
+    println!("=== 测试 UPDATE / DELETE ... RETURNING ===");
+    // UPDATE 返回修改后的行
+    let (columns, rows) = returning(&mut db, "UPDATE users SET status = 'idle' WHERE id >= 4 RETURNING id, status");
+    assert_eq!(columns, vec!["id", "status"]);
+    assert_eq!(rows, vec![vec![DataType::Int(4), text("idle")], vec![DataType::Int(5), text("idle")]]);
+
+    // DELETE 返回删除前的行
+    let (columns, rows) = returning(&mut db, "DELETE FROM users WHERE status = 'idle' AND id > 3 RETURNING *");
+    assert_eq!(columns, vec!["id", "name", "status"]);
+    assert_eq!(rows, vec![
+        vec![DataType::Int(4), text("dave"), text("idle")],
+        vec![DataType::Int(5), text("eve"), text("idle")],
+    ]);
+    assert_eq!(db.get_table("users").unwrap().unwrap().rows.len(), 3);
+
+    // WHERE 没有匹配的行时返回空结果集
+    let (columns, rows) = returning(&mut db, "UPDATE users SET name = 'x' WHERE id = 99 RETURNING name");
+    assert_eq!(columns, vec!["name"]);
+    assert!(rows.is_empty());
+    let (_, rows) = returning(&mut db, "DELETE FROM users WHERE id = 99 RETURNING *");
+    assert!(rows.is_empty());
+
+    // 违反约束时不修改数据，也不返回结果
+    assert!(db.execute("UPDATE users SET id = 1 WHERE id = 2 RETURNING *").is_err());
+    assert_eq!(returning(&mut db, "SELECT id FROM users WHERE id = 2").1, vec![vec![DataType::Int(2)]]);
+
+    println!("UPDATE / DELETE ... RETURNING 结果正确");
+}
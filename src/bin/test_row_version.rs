@@ -0,0 +1,89 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::types::DataType;
+use simple_db::StatementResult;
+use std::fs;
+
+fn text(s: &str) -> DataType {
+    DataType::Varchar(s.to_string())
+}
+
+// 执行查询并返回结果行
+fn rows(db: &mut Database, sql: &str) -> Vec<Vec<DataType>> {
+    match db.execute(sql).unwrap() {
+        StatementResult::ResultSet(result) => result.rows,
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+}
+
+fn main() {
+    println!("=== 测试行版本号 ===");
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql("CREATE TABLE accounts (id INT PRIMARY KEY, balance INT) ROW_VERSION = ON").unwrap();
+    db.execute_sql("INSERT INTO accounts VALUES (1, 100), (2, 50)").unwrap();
+    assert_eq!(db.get_row_version("accounts", 0).unwrap(), Some(1));
+    assert_eq!(db.get_row_version("accounts", 2).unwrap(), None);
+
+    // 版本号匹配时更新成功，版本号加 1
+    let result = db.execute("UPDATE accounts SET balance = 90 WHERE id = 1 AND version = 1").unwrap();
+    assert_eq!(result, StatementResult::RowsAffected(1));
+    assert_eq!(db.get_row_version("accounts", 0).unwrap(), Some(2));
+    assert_eq!(db.get_row_version("accounts", 1).unwrap(), Some(1));
+
+    // 使用过期的版本号时不更新任何行
+    let result = db.execute("UPDATE accounts SET balance = 0 WHERE id = 1 AND version = 1").unwrap();
+    assert_eq!(result, StatementResult::RowsAffected(0));
+    assert_eq!(db.get_row("accounts", 0).unwrap(), Some(vec![DataType::Int(1), DataType::Int(90)]));
+
+    // 不带版本条件的更新同样增加版本号；DELETE 也可以检查版本号
+    db.execute_sql("UPDATE accounts SET balance = 51 WHERE id = 2").unwrap();
+    assert_eq!(db.get_row_version("accounts", 1).unwrap(), Some(2));
+    assert_eq!(db.execute("DELETE FROM accounts WHERE id = 2 AND version = 1").unwrap(), StatementResult::RowsAffected(0));
+    assert_eq!(db.execute("DELETE FROM accounts WHERE id = 1 AND version = 2").unwrap(), StatementResult::RowsAffected(1));
+    // 删除后剩余行的版本号随行移动
+    assert_eq!(db.get_row_version("accounts", 0).unwrap(), Some(2));
+
+    // 版本号不能直接修改，也不出现在 SELECT * 中
+    assert!(db.execute("UPDATE accounts SET version = 1 WHERE id = 2").is_err());
+    assert_eq!(rows(&mut db, "SELECT * FROM accounts"), vec![vec![DataType::Int(2), DataType::Int(51)]]);
+
+    // DESCRIBE 在最后一行列出行版本号
+    let describe = rows(&mut db, "DESCRIBE accounts");
+    assert_eq!(describe.len(), 3);
+    assert_eq!(describe[2][..3], [text("version"), text("INT"), text("NO")]);
+
+    // 建表语句保留 ROW_VERSION 选项，名为 version 的列与之冲突
+    assert_eq!(db.schema_sql(Some("accounts")).unwrap(), "CREATE TABLE accounts (id INT PRIMARY KEY, balance INT) ROW_VERSION = ON;\n");
+    assert!(db.execute("CREATE TABLE bad (id INT, version INT) ROW_VERSION = ON").is_err());
+    assert!(db.execute("ALTER TABLE accounts RENAME COLUMN balance TO version").is_err());
+
+    // 未开启时没有版本号，version 不是可用的列
+    db.execute_sql("CREATE TABLE plain (id INT)").unwrap();
+    db.execute_sql("INSERT INTO plain VALUES (1)").unwrap();
+    assert_eq!(db.get_row_version("plain", 0).unwrap(), None);
+    assert!(db.execute("UPDATE plain SET id = 2 WHERE version = 1").is_err());
+
+    // 两个进程共享同一个数据库文件：第二个进程读取后，第一个进程先完成了更新
+    let base_dir = std::env::temp_dir().join("simple_db_test_row_version");
+    let _ = fs::remove_dir_all(&base_dir);
+    let mut writer = Database::new(StorageType::File(base_dir.clone()));
+    writer.execute_sql("CREATE TABLE stock (id INT PRIMARY KEY, qty INT) ROW_VERSION = ON").unwrap();
+    writer.execute_sql("INSERT INTO stock VALUES (1, 10)").unwrap();
+
+    let mut stale = Database::new(StorageType::File(base_dir.clone()));
+    stale.load().unwrap();
+    let seen_version = stale.get_row_version("stock", 0).unwrap().unwrap();
+    assert_eq!(seen_version, 1);
+
+    let sql = format!("UPDATE stock SET qty = 9 WHERE id = 1 AND version = {}", seen_version);
+    assert_eq!(writer.execute(&sql).unwrap(), StatementResult::RowsAffected(1));
+
+    // 重新读取最新数据后，基于旧版本号的更新被拒绝
+    stale.reload().unwrap();
+    let sql = format!("UPDATE stock SET qty = 8 WHERE id = 1 AND version = {}", seen_version);
+    assert_eq!(stale.execute(&sql).unwrap(), StatementResult::RowsAffected(0));
+    assert_eq!(stale.get_row("stock", 0).unwrap(), Some(vec![DataType::Int(1), DataType::Int(9)]));
+    assert_eq!(stale.get_row_version("stock", 0).unwrap(), Some(2));
+
+    let _ = fs::remove_dir_all(&base_dir);
+    println!("行版本号检查结果正确");
+}
@@ -0,0 +1,72 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::types::DataType;
+
+// 执行查询并返回结果中的 id 列
+fn ids(db: &Database, sql: &str) -> Vec<i32> {
+    let rows = db.query_iter(sql)
+        .unwrap_or_else(|e| panic!("查询失败 {}: {}", sql, e.detailed_message()))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap_or_else(|e| panic!("查询失败 {}: {}", sql, e.detailed_message()));
+    let ids = rows.iter()
+        .map(|row| match &row[0] {
+            DataType::Int(id) => *id,
+            other => panic!("期望整数，实际为 {:?}", other),
+        })
+        .collect();
+    println!("{} -> {:?}", sql, ids);
+    ids
+}
+
+fn main() {
+    println!("=== 测试 SAMPLE 抽样查询 ===");
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql("CREATE TABLE plants (id INT PRIMARY KEY, height FLOAT)").unwrap();
+    for id in 1..=100 {
+        db.execute_sql(&format!("INSERT INTO plants VALUES ({}, {}.5)", id, id % 30)).unwrap();
+    }
+
+    // 固定种子时抽样结果可以复现
+    db.set_sample_seed(42);
+    let first = ids(&db, "SELECT id FROM plants SAMPLE 10");
+    let second = ids(&db, "SELECT id FROM plants SAMPLE 10");
+    assert_eq!(first, vec![24, 29, 32, 36, 41, 47, 49, 81, 91, 95]);
+    db.set_sample_seed(42);
+    assert_eq!(ids(&db, "SELECT id FROM plants SAMPLE 10"), first);
+    assert_eq!(ids(&db, "SELECT id FROM plants SAMPLE 10"), second);
+    assert_ne!(first, second);
+
+    // 抽取的行互不相同，并保持原来的顺序
+    assert_eq!(first.len(), 10);
+    assert!(first.windows(2).all(|w| w[0] < w[1]));
+
+    // 先按 WHERE 过滤再抽样
+    let filtered = ids(&db, "SELECT id, height FROM plants WHERE id > 90 SAMPLE 5");
+    assert_eq!(filtered.len(), 5);
+    assert!(filtered.iter().all(|id| *id > 90));
+
+    // 抽样行数不少于结果行数时返回全部行
+    assert_eq!(ids(&db, "SELECT id FROM plants WHERE id <= 3 SAMPLE 10"), vec![1, 2, 3]);
+    assert!(ids(&db, "SELECT id FROM plants SAMPLE 0").is_empty());
+
+    // 按百分比抽样，行数大致为总数的对应比例
+    db.set_sample_seed(7);
+    let percent = ids(&db, "SELECT * FROM plants SAMPLE 20 PERCENT");
+    db.set_sample_seed(7);
+    assert_eq!(ids(&db, "SELECT * FROM plants SAMPLE 20 PERCENT"), percent);
+    assert!((5..=40).contains(&percent.len()));
+    assert_eq!(ids(&db, "SELECT id FROM plants SAMPLE 100 PERCENT").len(), 100);
+    assert!(ids(&db, "SELECT id FROM plants SAMPLE 0 PERCENT").is_empty());
+
+    // 非法的抽样大小
+    for sql in [
+        "SELECT id FROM plants SAMPLE",
+        "SELECT id FROM plants SAMPLE 1.5",
+        "SELECT id FROM plants SAMPLE 150 PERCENT",
+        "SELECT 1 SAMPLE 2",
+    ] {
+        let result = db.execute(sql);
+        println!("{} -> {:?}", sql, result.as_ref().map_err(|e| e.detailed_message()));
+        assert!(result.is_err());
+    }
+    println!("抽样查询结果可以复现");
+}
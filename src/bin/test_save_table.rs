@@ -0,0 +1,60 @@
+use simple_db::core::db::{Database, StorageType};
+use std::fs;
+use std::path::Path;
+use std::time::SystemTime;
+
+// 表文件的内容和修改时间
+fn file_state(path: &Path) -> (String, SystemTime) {
+    (fs::read_to_string(path).unwrap(), fs::metadata(path).unwrap().modified().unwrap())
+}
+
+fn main() {
+    let base_dir = std::env::temp_dir().join("simple_db_test_save_table");
+    let _ = fs::remove_dir_all(&base_dir);
+    let users_path = base_dir.join("tables").join("users.json");
+    let orders_path = base_dir.join("tables").join("orders.json");
+
+    println!("=== 测试只保存一个表 ===");
+    let mut db = Database::new(StorageType::File(base_dir.clone()));
+    db.execute_sql("CREATE TABLE users (id INT PRIMARY KEY, name VARCHAR(20))").unwrap();
+    db.execute_sql("CREATE TABLE orders (id INT PRIMARY KEY, total INT)").unwrap();
+    let users_before = file_state(&users_path);
+    let orders_before = file_state(&orders_path);
+    // 保证修改时间可以区分
+    std::thread::sleep(std::time::Duration::from_millis(20));
+
+    // 批量模式中的修改还没有写盘，只保存 users
+    db.begin_batch();
+    db.execute_sql("INSERT INTO users VALUES (1, 'Alice')").unwrap();
+    db.execute_sql("INSERT INTO orders VALUES (1, 100)").unwrap();
+    db.save_table("users").unwrap();
+
+    let users_after = file_state(&users_path);
+    assert_ne!(users_after.0, users_before.0);
+    assert!(users_after.0.contains("Alice"));
+    assert!(users_after.1 > users_before.1);
+    assert_eq!(file_state(&orders_path), orders_before);
+
+    // 结束批量时其余修改照常写入
+    db.end_batch().unwrap();
+    assert!(file_state(&orders_path).0.contains("100"));
+    drop(db);
+
+    // 重新打开后两个表的数据都在
+    let mut db = Database::new(StorageType::File(base_dir.clone()));
+    db.load().unwrap();
+    assert!(db.get_row("users", 0).unwrap().is_some());
+    assert!(db.get_row("orders", 0).unwrap().is_some());
+
+    // 表不存在时报错
+    assert!(db.save_table("missing").is_err());
+
+    // 内存存储不做任何操作
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql("CREATE TABLE t (id INT)").unwrap();
+    db.save_table("t").unwrap();
+    assert!(db.save_table("missing").is_err());
+
+    let _ = fs::remove_dir_all(&base_dir);
+    println!("只保存一个表的结果正确");
+}
@@ -0,0 +1,78 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::error::DbError;
+use simple_db::core::types::DataType;
+use simple_db::StatementResult;
+
+fn text(s: &str) -> DataType {
+    DataType::Varchar(s.to_string())
+}
+
+// 执行查询并返回结果行
+fn rows(db: &mut Database, sql: &str) -> Vec<Vec<DataType>> {
+    match db.execute(sql).unwrap() {
+        StatementResult::ResultSet(result) => {
+            println!("{} -> {:?}", sql, result.rows);
+            result.rows
+        }
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+}
+
+// 执行SQL，返回语法错误的消息
+fn sql_error(db: &mut Database, sql: &str) -> String {
+    match db.execute(sql) {
+        Err(DbError::SqlError(message)) => {
+            println!("{} -> {}", sql, message);
+            message
+        }
+        other => panic!("{} 应返回语法错误: {:?}", sql, other),
+    }
+}
+
+fn main() {
+    println!("=== 测试关联标量子查询 ===");
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql("CREATE TABLE users (id INT, name VARCHAR(10))").unwrap();
+    db.execute_sql("CREATE TABLE orders (order_id INT, user_id INT, amount INT)").unwrap();
+    db.execute_sql("INSERT INTO users VALUES (1, 'Alice'), (2, 'Bob'), (3, 'Carol')").unwrap();
+    db.execute_sql("INSERT INTO orders VALUES (10, 1, 5), (11, 1, 7), (12, 2, 100)").unwrap();
+
+    // 子查询对外层的每一行分别计算
+    assert_eq!(
+        rows(&mut db, "SELECT name, (SELECT COUNT(*) FROM orders WHERE orders.user_id = users.id) FROM users"),
+        vec![
+            vec![text("Alice"), DataType::Int(2)],
+            vec![text("Bob"), DataType::Int(1)],
+            vec![text("Carol"), DataType::Int(0)],
+        ]
+    );
+    // 未限定表名的外层列同样按外层行取值，子查询可以参与运算
+    assert_eq!(
+        rows(&mut db, "SELECT id, (SELECT SUM(amount) FROM orders WHERE user_id = id) + 1 FROM users WHERE id < 3"),
+        vec![
+            vec![DataType::Int(1), DataType::Int(13)],
+            vec![DataType::Int(2), DataType::Int(101)],
+        ]
+    );
+    // 非关联子查询对每一行的值相同
+    assert_eq!(
+        rows(&mut db, "SELECT id, (SELECT MAX(amount) FROM orders) FROM users"),
+        vec![
+            vec![DataType::Int(1), DataType::Int(100)],
+            vec![DataType::Int(2), DataType::Int(100)],
+            vec![DataType::Int(3), DataType::Int(100)],
+        ]
+    );
+
+    // 子查询返回多行或多列时报错
+    assert_eq!(
+        sql_error(&mut db, "SELECT name, (SELECT order_id FROM orders WHERE orders.user_id = users.id) FROM users"),
+        "标量子查询返回了多行"
+    );
+    assert_eq!(
+        sql_error(&mut db, "SELECT name, (SELECT order_id, amount FROM orders WHERE orders.user_id = users.id) FROM users"),
+        "标量子查询只能返回一列，实际返回 2 列"
+    );
+
+    println!("测试通过");
+}
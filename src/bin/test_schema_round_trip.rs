@@ -0,0 +1,56 @@
+use simple_db::core::db::{Database, StorageType};
+
+// 比较两个数据库中同名表的结构：列定义、索引、行数限制、注释和行版本设置
+fn assert_same_schema(original: &Database, restored: &Database) {
+    let mut names = original.list_tables().unwrap();
+    let mut restored_names = restored.list_tables().unwrap();
+    names.sort();
+    restored_names.sort();
+    assert_eq!(names, restored_names);
+
+    for name in names {
+        let a = original.get_table(&name).unwrap().unwrap();
+        let b = restored.get_table(&name).unwrap().unwrap();
+        assert_eq!(a.columns, b.columns, "表 {} 的列不同", name);
+        assert_eq!(a.indexes, b.indexes, "表 {} 的索引不同", name);
+        assert_eq!(a.max_rows, b.max_rows, "表 {} 的行数限制不同", name);
+        assert_eq!(a.comment, b.comment, "表 {} 的注释不同", name);
+        assert_eq!(a.has_row_versions(), b.has_row_versions(), "表 {} 的行版本设置不同", name);
+        assert!(b.rows.is_empty(), "结构导出不应包含数据");
+    }
+}
+
+fn main() {
+    println!("=== 测试结构导出后重建 ===");
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql(concat!(
+        "CREATE TABLE users (id INT PRIMARY KEY COMMENT 'user id', name VARCHAR(20) NOT NULL DEFAULT 'anon', ",
+        "score FLOAT DEFAULT 1.5, note VARCHAR(30) COMMENT 'it''s a note', joined VARCHAR(30) DEFAULT CURRENT_TIMESTAMP) ",
+        "MAX_ROWS = 100 COMMENT = 'people'",
+    )).unwrap();
+    db.execute_sql("CREATE INDEX idx_name ON users (name)").unwrap();
+    db.execute_sql("CREATE TABLE accounts (id INT PRIMARY KEY, balance INT NOT NULL DEFAULT 0) ROW_VERSION = ON").unwrap();
+    db.execute_sql("CREATE TABLE logs (message VARCHAR(50))").unwrap();
+    db.execute_sql("INSERT INTO users (id) VALUES (1)").unwrap();
+    db.execute_sql("INSERT INTO logs VALUES ('hello')").unwrap();
+
+    let schema = db.schema_sql(None).unwrap();
+    println!("{}", schema);
+
+    // 在新数据库中执行导出的语句，得到相同的表结构
+    let mut restored = Database::new(StorageType::Memory);
+    assert!(restored.execute_many(&schema, true).iter().all(|result| result.is_ok()));
+    assert_same_schema(&db, &restored);
+
+    // 重建后的数据库再次导出，结果与第一次相同
+    assert_eq!(restored.schema_sql(None).unwrap(), schema);
+
+    // 只导出一个表时同样可以重建
+    let mut single = Database::new(StorageType::Memory);
+    let users_schema = db.schema_sql(Some("users")).unwrap();
+    assert!(single.execute_many(&users_schema, true).iter().all(|result| result.is_ok()));
+    assert_eq!(single.list_tables().unwrap(), vec!["users"]);
+    assert_eq!(single.schema_sql(Some("users")).unwrap(), users_schema);
+
+    println!("测试通过");
+}
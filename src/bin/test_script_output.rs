@@ -0,0 +1,65 @@
+use std::fs;
+use std::process::Command;
+
+// 在临时目录中用 simple_db 执行脚本，返回标准输出
+fn run_script(name: &str, sql: &str) -> String {
+    let dir = std::env::temp_dir().join("simple_db_test_script_output").join(name);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("script.sql"), sql).unwrap();
+
+    // simple_db 与当前测试程序位于同一目录
+    let binary = std::env::current_exe().unwrap().with_file_name("simple_db");
+    let output = Command::new(&binary)
+        .arg("script.sql")
+        .current_dir(&dir)
+        .output()
+        .unwrap_or_else(|e| panic!("无法运行 {}: {}", binary.display(), e));
+    let _ = fs::remove_dir_all(&dir);
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    println!("--- {} ---\n{}", name, stdout);
+    stdout
+}
+
+const EMPTY: &str = "There are no results to be displayed.";
+
+fn main() {
+    println!("=== 测试空结果提示 ===");
+    let setup = "CREATE TABLE users (id INT, name VARCHAR(20));\nINSERT INTO users VALUES (1, 'Alice');\n";
+
+    // 过滤后为空的查询提示一次
+    let output = run_script("empty_select", &format!("{}SELECT * FROM users WHERE id > 10;", setup));
+    assert_eq!(output, format!("{}\n", EMPTY));
+
+    // 没有 FROM 的常量查询不提示
+    let output = run_script("constant_select", "SELECT 1 + 1;");
+    assert!(!output.contains(EMPTY));
+    assert!(output.contains("| 2 "));
+
+    // 空结果紧跟在对应语句之后，与其他查询的输出一起出现
+    let output = run_script("mixed", &format!(
+        "{}SELECT name FROM users WHERE id = 2;\nSELECT 1 + 1;\nSELECT id, name FROM users WHERE id * 2 > 100;\nSELECT name FROM users;",
+        setup
+    ));
+    assert_eq!(output.matches(EMPTY).count(), 2);
+    let blocks: Vec<&str> = output.split("\n\n").collect();
+    assert_eq!(blocks.len(), 4);
+    assert_eq!(blocks[0], EMPTY);
+    assert!(blocks[1].contains("| 2 "));
+    assert_eq!(blocks[2], EMPTY);
+    assert!(blocks[3].contains("Alice"));
+
+    // 空的 UNION 和聚合查询
+    let output = run_script("union_and_aggregate", &format!(
+        "{}SELECT name FROM users WHERE id = 5 UNION SELECT name FROM users WHERE id = 6;\nSELECT COUNT(*) FROM users WHERE id > 10;",
+        setup
+    ));
+    assert_eq!(output.matches(EMPTY).count(), 1);
+    assert!(output.contains("| 0 "));
+
+    // 只有修改语句时不提示
+    let output = run_script("no_select", setup);
+    assert_eq!(output, "");
+    println!("空结果提示正确");
+}
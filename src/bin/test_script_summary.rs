@@ -0,0 +1,63 @@
+use simple_db::{run_script_file, Database, ScriptSummary, StorageType};
+use std::fs;
+use std::process::Command;
+
+const SCRIPT: &str = "\
+CREATE TABLE users (id INT PRIMARY KEY, name VARCHAR(20));
+INSERT INTO users VALUES (1, 'Alice');
+INSERT INTO users VALUES (1, 'Bob');
+SET verbose = maybe;
+INSERT INTO missing VALUES (1);
+SELECT * FROM users;
+";
+
+fn main() {
+    let dir = std::env::temp_dir().join("simple_db_test_script_summary");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let script = dir.join("script.sql");
+    fs::write(&script, SCRIPT).unwrap();
+
+    println!("=== 测试脚本执行统计 ===");
+    // 遇到错误继续执行时统计全部语句
+    let mut db = Database::new(StorageType::Memory);
+    let summary = run_script_file(&mut db, &script, false).unwrap();
+    println!("{:?}", summary);
+    assert_eq!(summary.succeeded, 3);
+    assert_eq!(summary.failed, 3);
+    assert_eq!(summary.first_error.as_deref(), Some("Error: Duplicate entry '1' for key 'PRIMARY'"));
+    assert!(!summary.is_success());
+
+    // 遇错停止时之后的语句不计入
+    let mut db = Database::new(StorageType::Memory);
+    let summary = run_script_file(&mut db, &script, true).unwrap();
+    println!("{:?}", summary);
+    assert_eq!(summary, ScriptSummary {
+        succeeded: 2,
+        failed: 1,
+        first_error: Some("Error: Duplicate entry '1' for key 'PRIMARY'".to_string()),
+    });
+
+    // 全部成功
+    fs::write(&script, "CREATE TABLE t (id INT);\nINSERT INTO t VALUES (1);\n").unwrap();
+    let mut db = Database::new(StorageType::Memory);
+    let summary = run_script_file(&mut db, &script, true).unwrap();
+    assert_eq!(summary, ScriptSummary { succeeded: 2, failed: 0, first_error: None });
+    assert!(summary.is_success());
+
+    // 文件不存在
+    assert!(run_script_file(&mut db, dir.join("missing.sql"), true).is_err());
+
+    // simple_db 执行脚本时以退出码表示是否全部成功
+    let binary = std::env::current_exe().unwrap().with_file_name("simple_db");
+    let status = |sql: &str| {
+        let _ = fs::remove_dir_all(dir.join("db"));
+        fs::write(&script, sql).unwrap();
+        Command::new(&binary).arg("script.sql").current_dir(&dir).output().unwrap().status
+    };
+    assert!(status("CREATE TABLE t (id INT);\n").success());
+    assert_eq!(status(SCRIPT).code(), Some(1));
+
+    let _ = fs::remove_dir_all(&dir);
+    println!("脚本执行统计正确");
+}
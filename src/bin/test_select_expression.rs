@@ -0,0 +1,44 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::error::DbError;
+use simple_db::core::types::DataType;
+use simple_db::StatementResult;
+
+// 没有 FROM 的聚合查询在两种执行方式下都应报错
+fn assert_aggregate_rejected(db: &mut Database, sql: &str, function: &str) {
+    let expected = format!("聚合函数 {} 需要 FROM 子句", function);
+    for error in [db.execute(sql).unwrap_err(), db.execute_sql(sql).unwrap_err()] {
+        match error {
+            DbError::SqlError(message) => assert_eq!(message, expected, "{}", sql),
+            other => panic!("{} 期望 SQL 错误，实际为 {:?}", sql, other),
+        }
+    }
+}
+
+fn main() {
+    println!("=== 测试没有 FROM 的表达式查询 ===");
+    let mut db = Database::new(StorageType::Memory);
+
+    // 标量表达式产出单行单列的结果
+    match db.execute("SELECT 1 + 1").unwrap() {
+        StatementResult::ResultSet(result) => {
+            assert_eq!(result.columns, vec!["1 + 1".to_string()]);
+            assert_eq!(result.rows, vec![vec![DataType::Int(2)]]);
+        }
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+    db.execute_sql("SELECT 1 + 1").unwrap();
+
+    // 聚合函数需要 FROM 子句，嵌套在算术表达式中时同样报错
+    assert_aggregate_rejected(&mut db, "SELECT COUNT(*)", "COUNT");
+    assert_aggregate_rejected(&mut db, "SELECT 1, SUM(2) + 1", "SUM");
+    assert_aggregate_rejected(&mut db, "SELECT MAX(3)", "MAX");
+
+    // 有 FROM 时聚合照常计算
+    db.execute_sql("CREATE TABLE t (id INT)").unwrap();
+    db.execute_sql("INSERT INTO t VALUES (1), (2)").unwrap();
+    match db.execute("SELECT COUNT(*) FROM t").unwrap() {
+        StatementResult::ResultSet(result) => assert_eq!(result.rows, vec![vec![DataType::Int(2)]]),
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+    println!("没有 FROM 的表达式查询结果正确");
+}
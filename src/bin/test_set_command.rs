@@ -0,0 +1,56 @@
+use simple_db::core::sql::TableStyle;
+use simple_db::{execute_script, parse_set_command, Database, ErrorDisplayMode, StorageType};
+
+fn pair(name: &str, value: &str) -> Option<(String, String)> {
+    Some((name.to_string(), value.to_string()))
+}
+
+fn main() {
+    println!("=== 测试 SET 命令 ===");
+
+    // 解析 SET 命令
+    assert_eq!(parse_set_command("SET error_mode = detailed"), pair("error_mode", "detailed"));
+    assert_eq!(parse_set_command("set max_width=20;"), pair("max_width", "20"));
+    assert_eq!(parse_set_command("  SET table_style = 'box_rows'  "), pair("table_style", "box_rows"));
+    assert_eq!(parse_set_command("SET verbose = \"on\""), pair("verbose", "on"));
+    assert_eq!(parse_set_command("SELECT 1"), None);
+    assert_eq!(parse_set_command("SET = 1"), None);
+    assert_eq!(parse_set_command("SET verbose on"), None);
+    assert_eq!(parse_set_command("SET verbose = on; SELECT 1"), None);
+    println!("SET 命令解析正确");
+
+    // 设置后数据库选项随之改变
+    let mut db = Database::new(StorageType::Memory);
+    let (name, value) = parse_set_command("SET error_mode = DETAILED").unwrap();
+    db.set_option(&name, &value).unwrap();
+    assert_eq!(db.get_error_mode(), ErrorDisplayMode::Detailed);
+
+    let (name, value) = parse_set_command("SET max_width = 12").unwrap();
+    db.set_option(&name, &value).unwrap();
+    assert_eq!(db.max_column_width(), Some(12));
+    db.set_option("max_width", "off").unwrap();
+    assert_eq!(db.max_column_width(), None);
+
+    db.set_option("table_style", "box").unwrap();
+    assert_eq!(db.table_style(), TableStyle::Box { row_separators: false });
+    db.set_option("verbose", "on").unwrap();
+    assert!(db.is_verbose());
+    db.set_option("verbose", "off").unwrap();
+    assert!(!db.is_verbose());
+
+    // 未知的选项或无效的取值报错，选项保持不变
+    for (name, value) in [("null_string", "N/A"), ("error_mode", "loud"), ("max_width", "wide"), ("table_style", "fancy")] {
+        let result = db.set_option(name, value);
+        println!("SET {} = {} -> {:?}", name, value, result.as_ref().map_err(|e| e.detailed_message()));
+        assert!(result.is_err());
+    }
+    assert_eq!(db.get_error_mode(), ErrorDisplayMode::Detailed);
+    assert_eq!(db.table_style(), TableStyle::Box { row_separators: false });
+
+    // 脚本中的 SET 命令对后续语句生效
+    let mut db = Database::new(StorageType::Memory);
+    assert!(execute_script(&mut db, "SET table_style = box_rows; CREATE TABLE t (id INT); INSERT INTO t VALUES (1), (2); SELECT * FROM t;", true));
+    assert_eq!(db.table_style(), TableStyle::Box { row_separators: true });
+    assert!(!execute_script(&mut db, "SET unknown = 1;", true));
+    println!("SET 命令修改数据库选项正确");
+}
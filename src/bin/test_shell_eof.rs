@@ -0,0 +1,56 @@
+use std::fs;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+// 在临时目录中启动交互式 simple_db，写入输入后关闭标准输入，返回标准输出
+// 程序在限定时间内没有退出时视为失败
+fn run_shell(name: &str, input: &str) -> String {
+    let dir = std::env::temp_dir().join("simple_db_test_shell_eof").join(name);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+
+    // simple_db 与当前测试程序位于同一目录
+    let binary = std::env::current_exe().unwrap().with_file_name("simple_db");
+    let mut child = Command::new(&binary)
+        .current_dir(&dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap_or_else(|e| panic!("无法运行 {}: {}", binary.display(), e));
+    // 写完后丢弃句柄，交互式Shell随即读到输入结束
+    child.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+
+    let start = Instant::now();
+    while child.try_wait().unwrap().is_none() {
+        if start.elapsed() > Duration::from_secs(10) {
+            child.kill().unwrap();
+            panic!("{}: 输入结束后交互式Shell没有退出", name);
+        }
+        std::thread::sleep(Duration::from_millis(20));
+    }
+    let output = child.wait_with_output().unwrap();
+    let _ = fs::remove_dir_all(&dir);
+    assert!(output.status.success(), "{}: 退出状态 {}", name, output.status);
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    println!("--- {} ---\n{}", name, stdout);
+    stdout
+}
+
+fn main() {
+    println!("=== 测试交互式Shell在输入结束时退出 ===");
+    // 没有任何输入
+    run_shell("empty", "");
+
+    // 执行完所有语句后因输入结束退出，输出以换行结束
+    let output = run_shell("statements", "CREATE TABLE t (id INT);\nINSERT INTO t VALUES (42);\nSELECT * FROM t;\n");
+    assert!(output.contains("| 42 "));
+    assert!(output.ends_with('\n'));
+
+    // 最后一行没有换行，或者停在未结束的语句中
+    let output = run_shell("no_trailing_newline", "SELECT 1 + 1;");
+    assert!(output.contains("| 2 "));
+    run_shell("unfinished_statement", "SELECT 1\n+ 1\n");
+    println!("交互式Shell在输入结束时正常退出");
+}
@@ -0,0 +1,68 @@
+use simple_db::core::types::DataType;
+use simple_db::{Database, StorageType};
+use std::fs;
+
+// 查询结果的全部行
+fn rows(db: &Database, sql: &str) -> Vec<Vec<DataType>> {
+    db.query_iter(sql)
+        .unwrap_or_else(|e| panic!("查询失败 {}: {}", sql, e.detailed_message()))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap()
+}
+
+// 创建快照、修改数据后恢复，检查数据回到快照时的状态
+fn check_restore(db: &mut Database) {
+    db.execute_sql("CREATE TABLE users (id INT PRIMARY KEY, name VARCHAR(10))").unwrap();
+    db.execute_sql("CREATE INDEX idx_name ON users (name)").unwrap();
+    db.execute_sql("CREATE TABLE logs (id INT)").unwrap();
+    db.execute_sql("INSERT INTO users VALUES (1, 'Ann'), (2, 'Bob')").unwrap();
+    let original = rows(db, "SELECT * FROM users");
+
+    let snapshot = db.snapshot().unwrap();
+    assert_eq!(snapshot.table_names(), vec!["logs", "users"]);
+
+    // 修改数据、删除表、新建表
+    db.execute_sql("UPDATE users SET name = 'Zed' WHERE id = 1").unwrap();
+    db.execute_sql("DELETE FROM users WHERE id = 2").unwrap();
+    db.execute_sql("INSERT INTO users VALUES (3, 'Cid')").unwrap();
+    db.execute_sql("DROP TABLE logs").unwrap();
+    db.execute_sql("CREATE TABLE extra (id INT)").unwrap();
+    assert_ne!(rows(db, "SELECT * FROM users"), original);
+
+    db.restore(snapshot.clone()).unwrap();
+    let mut tables = db.list_tables().unwrap();
+    tables.sort();
+    println!("恢复后的表: {:?}", tables);
+    assert_eq!(tables, vec!["logs", "users"]);
+    assert_eq!(rows(db, "SELECT * FROM users"), original);
+
+    // 恢复后索引和主键仍然有效
+    assert_eq!(rows(db, "SELECT id FROM users WHERE name = 'Bob'"), vec![vec![DataType::Int(2)]]);
+    assert!(db.execute_sql("INSERT INTO users VALUES (1, 'Dup')").is_err());
+
+    // 同一个快照可以多次恢复
+    db.execute_sql("DELETE FROM users").unwrap();
+    db.restore(snapshot).unwrap();
+    assert_eq!(rows(db, "SELECT * FROM users"), original);
+}
+
+fn main() {
+    println!("=== 测试内存存储的快照和恢复 ===");
+    let mut db = Database::new(StorageType::Memory);
+    check_restore(&mut db);
+
+    println!("\n=== 测试文件存储的快照和恢复 ===");
+    let dir = std::env::temp_dir().join("simple_db_test_snapshot");
+    let _ = fs::remove_dir_all(&dir);
+    let mut db = Database::new(StorageType::File(dir.clone()));
+    check_restore(&mut db);
+
+    // 表文件同样恢复
+    let reopened = Database::new(StorageType::File(dir.clone()));
+    let mut tables = reopened.list_tables().unwrap();
+    tables.sort();
+    assert_eq!(tables, vec!["logs", "users"]);
+    assert_eq!(rows(&reopened, "SELECT COUNT(*) FROM users"), vec![vec![DataType::Int(2)]]);
+    let _ = fs::remove_dir_all(&dir);
+    println!("快照恢复后数据回到原来的状态");
+}
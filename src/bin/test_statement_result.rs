@@ -0,0 +1,184 @@
+use simple_db::core::types::DataType;
+use simple_db::{Database, QueryResult, StatementResult, StorageType};
+
+// 执行SQL，失败时直接报错
+fn execute(db: &mut Database, sql: &str) -> StatementResult {
+    let result = db.execute(sql).unwrap_or_else(|e| panic!("执行失败 {}: {}", sql, e.detailed_message()));
+    println!("{} -> {:?}", sql, result);
+    result
+}
+
+fn text(s: &str) -> DataType {
+    DataType::Varchar(s.to_string())
+}
+
+fn main() {
+    println!("=== 测试语句执行结果 ===");
+    let mut db = Database::new(StorageType::Memory);
+
+    assert_eq!(execute(&mut db, "CREATE TABLE users (id INT PRIMARY KEY, name VARCHAR(20) NOT NULL, age INT)"), StatementResult::Created);
+    assert_eq!(execute(&mut db, "CREATE INDEX idx_age ON users (age)"), StatementResult::Created);
+
+    // 插入、修改和删除返回影响的行数
+    assert_eq!(execute(&mut db, "INSERT INTO users VALUES (1, 'Alice', 30)"), StatementResult::RowsAffected(1));
+    assert_eq!(execute(&mut db, "INSERT INTO users VALUES (2, 'Bob', 25), (3, 'Carol', 35)"), StatementResult::RowsAffected(2));
+    assert_eq!(execute(&mut db, "INSERT INTO users (id, name) VALUES (4, 'Dave')"), StatementResult::RowsAffected(1));
+    assert_eq!(
+        execute(&mut db, "INSERT INTO users VALUES (1, 'Eve', 20), (5, 'Frank', 40) ON CONFLICT DO NOTHING"),
+        StatementResult::RowsAffected(1)
+    );
+    assert_eq!(execute(&mut db, "UPDATE users SET age = 31 WHERE age > 28"), StatementResult::RowsAffected(3));
+    assert_eq!(execute(&mut db, "UPDATE users SET age = 1 WHERE id = 100"), StatementResult::RowsAffected(0));
+    assert_eq!(execute(&mut db, "DELETE FROM users WHERE id = 5"), StatementResult::RowsAffected(1));
+
+    // 查询返回结果集
+    assert_eq!(
+        execute(&mut db, "SELECT id, name FROM users WHERE age = 31"),
+        StatementResult::ResultSet(QueryResult {
+            columns: vec!["id".to_string(), "name".to_string()],
+            rows: vec![vec![DataType::Int(1), text("Alice")], vec![DataType::Int(3), text("Carol")]],
+        })
+    );
+    match execute(&mut db, "SELECT * FROM users") {
+        StatementResult::ResultSet(result) => {
+            assert_eq!(result.columns, vec!["id", "name", "age"]);
+            assert_eq!(result.rows.len(), 4);
+        }
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+    match execute(&mut db, "SELECT COUNT(*) FROM users") {
+        StatementResult::ResultSet(result) => assert_eq!(result.rows, vec![vec![DataType::Int(4)]]),
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+    match execute(&mut db, "SELECT 1 + 2") {
+        StatementResult::ResultSet(result) => assert_eq!(result.rows, vec![vec![DataType::Int(3)]]),
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+    match execute(&mut db, "SELECT name FROM users WHERE id = 1 UNION SELECT name FROM users WHERE id = 2") {
+        StatementResult::ResultSet(result) => assert_eq!(result.rows, vec![vec![text("Alice")], vec![text("Bob")]]),
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+
+    // * 与表达式混用时展开为全部列，表头取自表结构
+    match execute(&mut db, "SELECT *, age * 2 FROM users WHERE id = 1") {
+        StatementResult::ResultSet(result) => {
+            assert_eq!(result.columns, vec!["id", "name", "age", "age * 2"]);
+            assert_eq!(result.rows, vec![vec![DataType::Int(1), text("Alice"), DataType::Int(31), DataType::Int(62)]]);
+        }
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+    match execute(&mut db, "SELECT id + 100, * FROM users WHERE id = 2") {
+        StatementResult::ResultSet(result) => {
+            assert_eq!(result.columns, vec!["id + 100", "id", "name", "age"]);
+            assert_eq!(result.rows, vec![vec![DataType::Int(102), DataType::Int(2), text("Bob"), DataType::Int(25)]]);
+        }
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+
+    // 表名限定的 users.* 展开为以表名限定的列
+    match execute(&mut db, "SELECT users.*, age * 2 FROM users WHERE id = 1") {
+        StatementResult::ResultSet(result) => {
+            assert_eq!(result.columns, vec!["users.id", "users.name", "users.age", "age * 2"]);
+            assert_eq!(result.rows, vec![vec![DataType::Int(1), text("Alice"), DataType::Int(31), DataType::Int(62)]]);
+        }
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+    assert!(db.execute("SELECT orders.* FROM users").is_err());
+
+    // 表头与打印的表格相同，保留原始 SQL 中的写法；UNION 的表头取自左侧
+    match execute(&mut db, "SELECT id, age  *  2 AS doubled, age/2 FROM users WHERE id = 1") {
+        StatementResult::ResultSet(result) => assert_eq!(result.columns, vec!["id", "doubled", "age/2"]),
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+    match execute(&mut db, "SELECT age + 1, name FROM users WHERE id = 1 UNION SELECT age*3, name FROM users WHERE id = 2") {
+        StatementResult::ResultSet(result) => assert_eq!(result.columns, vec!["age + 1", "name"]),
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+
+    // EXPLAIN 和 DESCRIBE 同样返回结果集
+    match execute(&mut db, "EXPLAIN SELECT name FROM users WHERE age > 30") {
+        StatementResult::ResultSet(result) => {
+            assert_eq!(result.columns, vec!["id", "operation", "detail"]);
+            assert_eq!(result.rows[0], vec![DataType::Int(1), text("index range scan"), text("users using idx_age")]);
+        }
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+    match execute(&mut db, "DESCRIBE users") {
+        StatementResult::ResultSet(result) => {
+            assert_eq!(result.columns, vec!["Field", "Type", "Null", "Key", "Default", "Comment"]);
+            assert_eq!(result.rows[0][0..4], [text("id"), text("INT"), text("NO"), text("PRI")]);
+        }
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+
+    assert_eq!(execute(&mut db, "ALTER TABLE users RENAME COLUMN age TO years"), StatementResult::Altered);
+    assert_eq!(execute(&mut db, "DELETE FROM users"), StatementResult::RowsAffected(4));
+    assert_eq!(execute(&mut db, "DROP TABLE users"), StatementResult::Dropped);
+
+    // 出错时返回错误
+    assert!(db.execute("SELECT * FROM users").is_err());
+    println!("所有语句的执行结果正确");
+
+    // 一次执行多条语句，第二条语句出错
+    println!("\n=== 测试多条语句执行 ===");
+    let script = "CREATE TABLE notes (id INT, body VARCHAR(50)); \
+                  INSERT INTO missing VALUES (1); \
+                  INSERT INTO notes VALUES (1, 'a; b') -- 字符串和注释中的分号不分割语句; \n";
+
+    // 遇到错误继续执行
+    let results = db.execute_many(script, false);
+    println!("继续执行: {:?}", results);
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].as_ref().unwrap(), &StatementResult::Created);
+    assert!(results[1].is_err());
+    assert_eq!(results[2].as_ref().unwrap(), &StatementResult::RowsAffected(1));
+    match db.execute("SELECT body FROM notes") {
+        Ok(StatementResult::ResultSet(result)) => assert_eq!(result.rows, vec![vec![text("a; b")]]),
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+
+    // 遇到错误立即停止
+    db.execute("DROP TABLE notes").unwrap();
+    let results = db.execute_many(script, true);
+    println!("遇错停止: {:?}", results);
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].as_ref().unwrap(), &StatementResult::Created);
+    assert!(results[1].is_err());
+    match db.execute("SELECT COUNT(*) FROM notes") {
+        Ok(StatementResult::ResultSet(result)) => assert_eq!(result.rows, vec![vec![DataType::Int(0)]]),
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+    println!("多条语句的执行结果正确");
+
+    // 部分关键字可以作为列名
+    println!("\n=== 测试关键字作为列名 ===");
+    execute(&mut db, "CREATE TABLE steps (id INT PRIMARY KEY, order INT, key VARCHAR(10))");
+    execute(&mut db, "INSERT INTO steps (id, order, key) VALUES (1, 2, 'b'), (2, 1, 'a'), (3, 3, NULL)");
+    match execute(&mut db, "SELECT key, order FROM steps WHERE order > 1 ORDER BY order DESC") {
+        StatementResult::ResultSet(result) => {
+            assert_eq!(result.columns, vec!["key", "order"]);
+            assert_eq!(result.rows, vec![vec![DataType::Null, DataType::Int(3)], vec![text("b"), DataType::Int(2)]]);
+        }
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+    assert_eq!(execute(&mut db, "UPDATE steps SET key = 'c' WHERE key IS NULL"), StatementResult::RowsAffected(1));
+    match execute(&mut db, "SELECT id FROM steps WHERE key = 'c'") {
+        StatementResult::ResultSet(result) => assert_eq!(result.rows, vec![vec![DataType::Int(3)]]),
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+    match execute(&mut db, "SELECT * FROM steps ORDER BY order") {
+        StatementResult::ResultSet(result) => {
+            assert_eq!(result.columns, vec!["id", "order", "key"]);
+            assert_eq!(result.rows[0], vec![DataType::Int(2), DataType::Int(1), text("a")]);
+        }
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+    // 导出的建表语句可以重新执行
+    let schema = db.schema_sql(Some("steps")).unwrap();
+    assert!(schema.contains("order INT"));
+    execute(&mut db, "DROP TABLE steps");
+    execute(&mut db, schema.trim().trim_end_matches(';'));
+    // 其他关键字仍然不能作为列名
+    assert!(db.execute("CREATE TABLE bad (select INT)").is_err());
+    println!("关键字作为列名的查询结果正确");
+}
@@ -0,0 +1,51 @@
+use std::fs;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+// 在临时目录中运行交互式 simple_db，写入输入后关闭标准输入，返回标准输出
+fn run_shell(input: &str) -> String {
+    let dir = std::env::temp_dir().join("simple_db_test_stats_command");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+
+    // simple_db 与当前测试程序位于同一目录
+    let binary = std::env::current_exe().unwrap().with_file_name("simple_db");
+    let mut child = Command::new(&binary)
+        .current_dir(&dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap_or_else(|e| panic!("无法运行 {}: {}", binary.display(), e));
+    child.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+    let output = child.wait_with_output().unwrap();
+    let _ = fs::remove_dir_all(&dir);
+    assert!(output.status.success(), "退出状态 {}", output.status);
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    println!("{}", stdout);
+    stdout
+}
+
+fn main() {
+    println!("=== 测试 stats 命令 ===");
+    let output = run_shell(concat!(
+        "stats\n",
+        "CREATE TABLE users (id INT PRIMARY KEY, name VARCHAR(20));\n",
+        "CREATE TABLE logs (msg VARCHAR(5));\n",
+        "INSERT INTO users VALUES (1, 'Alice'), (2, NULL);\n",
+        "stats\n",
+    ));
+
+    // 没有表时给出提示
+    assert!(output.contains("> 没有表\n"));
+
+    // 每个表一行，按表名排序，依次为行数、列数和估算的字节数（整数 4 字节，字符串按长度，NULL 不计）
+    let expected = concat!(
+        "| table | rows | columns | bytes |\n",
+        "| ----- | ---- | ------- | ----- |\n",
+        "| logs  | 0    | 1       | 0     |\n",
+        "| users | 2    | 2       | 13    |\n",
+    );
+    assert!(output.contains(expected), "stats 输出不符合预期");
+    println!("测试通过");
+}
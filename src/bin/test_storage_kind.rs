@@ -0,0 +1,23 @@
+use simple_db::core::db::{Database, StorageKind, StorageType};
+use std::fs;
+
+fn main() {
+    println!("=== 测试查询存储类型和路径 ===");
+    // 内存存储没有路径
+    let db = Database::new(StorageType::Memory);
+    assert_eq!(db.storage_kind(), StorageKind::Memory);
+    assert_eq!(db.get_storage_path(), None);
+
+    // 文件存储返回存储目录，移动目录后返回新路径
+    let base_dir = std::env::temp_dir().join("simple_db_test_storage_kind");
+    let _ = fs::remove_dir_all(&base_dir);
+    let mut db = Database::new(StorageType::File(base_dir.join("first")));
+    assert_eq!(db.storage_kind(), StorageKind::File);
+    assert_eq!(db.get_storage_path(), Some(base_dir.join("first")));
+    db.relocate(base_dir.join("second")).unwrap();
+    assert_eq!(db.storage_kind(), StorageKind::File);
+    assert_eq!(db.get_storage_path(), Some(base_dir.join("second")));
+
+    let _ = fs::remove_dir_all(&base_dir);
+    println!("存储类型和路径结果正确");
+}
@@ -0,0 +1,77 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::types::{DataType, RowDiff, Table};
+use std::fs;
+
+fn text(s: &str) -> DataType {
+    DataType::Varchar(s.to_string())
+}
+
+fn table(db: &Database, name: &str) -> Table {
+    db.get_table(name).unwrap().unwrap().clone()
+}
+
+fn main() {
+    println!("=== 测试表的比较 ===");
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql("CREATE TABLE users (id INT PRIMARY KEY, name VARCHAR(20))").unwrap();
+    db.execute_sql("INSERT INTO users VALUES (1, 'Alice'), (2, 'Bob'), (3, 'Carol')").unwrap();
+    let before = table(&db, "users");
+
+    // 相同的表没有差异；表名不参与比较
+    db.execute_sql("CREATE TABLE backup LIKE users").unwrap();
+    db.execute_sql("INSERT INTO backup VALUES (1, 'Alice'), (2, 'Bob'), (3, 'Carol')").unwrap();
+    assert_eq!(before, table(&db, "backup"));
+    assert!(before.diff(&table(&db, "backup")).is_empty());
+
+    // 按主键对应行，行的顺序不影响结果
+    db.execute_sql("UPDATE users SET name = 'Robert' WHERE id = 2").unwrap();
+    db.execute_sql("DELETE FROM users WHERE id = 1").unwrap();
+    db.execute_sql("INSERT INTO users VALUES (4, 'Dave')").unwrap();
+    let after = table(&db, "users");
+    assert_ne!(before, after);
+    assert_eq!(before.diff(&after), vec![
+        RowDiff::Removed(vec![DataType::Int(1), text("Alice")]),
+        RowDiff::Changed {
+            old: vec![DataType::Int(2), text("Bob")],
+            new: vec![DataType::Int(2), text("Robert")],
+        },
+        RowDiff::Added(vec![DataType::Int(4), text("Dave")]),
+    ]);
+    assert_eq!(after.diff(&before), vec![
+        RowDiff::Changed {
+            old: vec![DataType::Int(2), text("Robert")],
+            new: vec![DataType::Int(2), text("Bob")],
+        },
+        RowDiff::Removed(vec![DataType::Int(4), text("Dave")]),
+        RowDiff::Added(vec![DataType::Int(1), text("Alice")]),
+    ]);
+
+    // 没有主键时按位置对应
+    db.execute_sql("CREATE TABLE a (v INT)").unwrap();
+    db.execute_sql("CREATE TABLE b (v INT)").unwrap();
+    db.execute_sql("INSERT INTO a VALUES (1), (2)").unwrap();
+    db.execute_sql("INSERT INTO b VALUES (1), (5), (6)").unwrap();
+    assert_eq!(table(&db, "a").diff(&table(&db, "b")), vec![
+        RowDiff::Changed { old: vec![DataType::Int(2)], new: vec![DataType::Int(5)] },
+        RowDiff::Added(vec![DataType::Int(6)]),
+    ]);
+
+    // 列定义不同时不相等
+    db.execute_sql("CREATE TABLE c (w INT)").unwrap();
+    db.execute_sql("INSERT INTO c VALUES (1), (2)").unwrap();
+    assert_ne!(table(&db, "a"), table(&db, "c"));
+
+    // 文件存储重新加载后的表与保存前相同
+    let dir = std::env::temp_dir().join("simple_db_test_table_diff");
+    let _ = fs::remove_dir_all(&dir);
+    let mut file_db = Database::new(StorageType::File(dir.clone()));
+    file_db.execute_sql("CREATE TABLE users (id INT PRIMARY KEY, name VARCHAR(20))").unwrap();
+    file_db.execute_sql("INSERT INTO users VALUES (2, 'Robert'), (3, 'Carol'), (4, 'Dave')").unwrap();
+    drop(file_db);
+    let mut file_db = Database::new(StorageType::File(dir.clone()));
+    file_db.load().unwrap();
+    assert_eq!(table(&file_db, "users"), after);
+
+    let _ = fs::remove_dir_all(&dir);
+    println!("表的比较结果正确");
+}
@@ -0,0 +1,53 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::storage::{file::FileStorage, memory::MemoryStorage, Storage};
+use simple_db::core::types::Table;
+use std::fs;
+
+const NAMES: [&str; 8] = ["orders", "users", "a_log", "zeta", "items", "b", "carts", "m"];
+
+// 按乱序建表，检查 get_tables 与 get_table_by_index 均按表名排序
+fn check_order(storage: &mut dyn Storage) {
+    for name in NAMES {
+        storage.create_table(Table::new(name.to_string(), Vec::new())).unwrap();
+    }
+    let mut expected: Vec<&str> = NAMES.to_vec();
+    expected.sort();
+
+    let names: Vec<&str> = storage.get_tables().unwrap().into_iter().map(|t| t.name.as_str()).collect();
+    println!("{:?}", names);
+    assert_eq!(names, expected);
+    for (i, name) in expected.iter().enumerate() {
+        assert_eq!(storage.get_table_by_index(i).unwrap().map(|t| t.name.as_str()), Some(*name));
+    }
+    assert!(storage.get_table_by_index(expected.len()).unwrap().is_none());
+
+    // 删除和新建表后仍然有序
+    storage.drop_table("items").unwrap();
+    storage.create_table(Table::new("d".to_string(), Vec::new())).unwrap();
+    let names: Vec<String> = storage.get_tables().unwrap().into_iter().map(|t| t.name.clone()).collect();
+    let mut sorted = names.clone();
+    sorted.sort();
+    assert_eq!(names, sorted);
+}
+
+fn main() {
+    println!("=== 测试表的遍历顺序 ===");
+    check_order(&mut MemoryStorage::new());
+
+    let base_dir = std::env::temp_dir().join("simple_db_test_table_order");
+    let _ = fs::remove_dir_all(&base_dir);
+    check_order(&mut FileStorage::new(base_dir.clone()));
+    let _ = fs::remove_dir_all(&base_dir);
+
+    // 错误提示中其他表的顺序固定
+    let mut db = Database::new(StorageType::Memory);
+    for name in ["zoo", "farm", "pets"] {
+        db.execute_sql(&format!("CREATE TABLE {} (id INT, legs INT)", name)).unwrap();
+    }
+    db.execute_sql("CREATE TABLE people (id INT)").unwrap();
+    db.execute_sql("INSERT INTO people VALUES (1)").unwrap();
+    let error = db.execute("SELECT legs FROM people").unwrap_err();
+    println!("{}", error.detailed_message());
+    assert_eq!(error.detailed_message(), "SQL语法错误: 列 legs 不在表 people 中，表 farm、pets、zoo 中有同名的列");
+    println!("表的遍历顺序正确");
+}
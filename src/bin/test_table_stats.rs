@@ -0,0 +1,46 @@
+use simple_db::core::db::{Database, StorageType, TableStats};
+
+fn stats(db: &Database, name: &str) -> TableStats {
+    db.table_stats().unwrap().into_iter().find(|s| s.name == name).unwrap()
+}
+
+fn main() {
+    println!("=== 测试表的统计信息 ===");
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql("CREATE TABLE users (id INT PRIMARY KEY, name VARCHAR(20), score FLOAT)").unwrap();
+    db.execute_sql("CREATE TABLE empty (id INT)").unwrap();
+
+    // 空表大小为 0，结果按表名排序
+    let all = db.table_stats().unwrap();
+    println!("{:?}", all);
+    assert_eq!(all, vec![
+        TableStats { name: "empty".to_string(), row_count: 0, column_count: 1, size_bytes: 0 },
+        TableStats { name: "users".to_string(), row_count: 0, column_count: 3, size_bytes: 0 },
+    ]);
+
+    // 整数 4 字节，浮点数 8 字节，字符串按字节长度，NULL 不计
+    db.execute_sql("INSERT INTO users VALUES (1, 'Alice', 1.5)").unwrap();
+    assert_eq!(stats(&db, "users").size_bytes, 4 + 5 + 8);
+    db.execute_sql("INSERT INTO users VALUES (2, NULL, NULL)").unwrap();
+    assert_eq!(stats(&db, "users").size_bytes, 4 + 5 + 8 + 4);
+
+    // 插入越多估算值越大
+    let mut last = stats(&db, "users").size_bytes;
+    for id in 3..10 {
+        db.execute_sql(&format!("INSERT INTO users VALUES ({}, 'user{}', 0.5)", id, id)).unwrap();
+        let size = stats(&db, "users").size_bytes;
+        assert!(size > last, "插入第 {} 行后大小应增加: {} -> {}", id, last, size);
+        last = size;
+    }
+    assert_eq!(stats(&db, "users").row_count, 9);
+
+    // 修改为更长的字符串后变大，删除后变小
+    db.execute_sql("UPDATE users SET name = 'a much longer name' WHERE id = 1").unwrap();
+    assert!(stats(&db, "users").size_bytes > last);
+    last = stats(&db, "users").size_bytes;
+    db.execute_sql("DELETE FROM users WHERE id > 5").unwrap();
+    assert!(stats(&db, "users").size_bytes < last);
+    assert_eq!(stats(&db, "empty").size_bytes, 0);
+    println!("{:?}", db.table_stats().unwrap());
+    println!("表的统计信息正确");
+}
@@ -0,0 +1,43 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::error::DbError;
+use simple_db::core::sql::SqlParser;
+
+fn main() {
+    println!("=== 测试语句结束后的多余内容 ===");
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql("CREATE TABLE users (id INT PRIMARY KEY, name VARCHAR(20), age INT)").unwrap();
+    db.execute_sql("INSERT INTO users VALUES (1, 'Alice', 30)").unwrap();
+
+    // 完整语句之后的多余 token 报错，错误中指出第一个多余的 token
+    let cases = [
+        ("SELECT * FROM users garbage", "Identifier(\"garbage\")"),
+        ("SELECT name FROM users WHERE id = 1 ORDER BY name extra", "Identifier(\"extra\")"),
+        ("INSERT INTO users VALUES (10, 'Extra', 40) garbage", "Identifier(\"garbage\")"),
+        ("SELECT 1 + 1 2", "Number(2)"),
+        ("DELETE FROM users WHERE id = 5 6", "Number(6)"),
+        ("SELECT * FROM users;;", "Semicolon"),
+        ("SELECT * FROM users; SELECT 1", "Select"),
+    ];
+    for (sql, token) in cases {
+        match SqlParser::new().parse(sql) {
+            Err(DbError::SqlError(message)) => {
+                println!("{} -> {}", sql, message);
+                assert_eq!(message, format!("语句结束后存在多余的内容: {}", token), "{}", sql);
+            }
+            other => panic!("{} 应返回语法错误: {:?}", sql, other),
+        }
+    }
+
+    // 出错的语句不执行
+    assert!(db.execute("INSERT INTO users VALUES (10, 'Extra', 40) garbage").is_err());
+    assert!(db.execute("DELETE FROM users WHERE id = 1 garbage").is_err());
+    assert_eq!(db.get_table("users").unwrap().unwrap().rows.len(), 1);
+
+    // 允许一个结尾的分号，分号前后可以有空白
+    for sql in ["SELECT * FROM users;", "SELECT * FROM users ;  ", "SELECT 1 + 1;", "INSERT INTO users VALUES (2, 'Bob', 25);"] {
+        db.execute(sql).unwrap_or_else(|e| panic!("{} 应执行成功: {}", sql, e.detailed_message()));
+    }
+    assert_eq!(db.get_table("users").unwrap().unwrap().rows.len(), 2);
+
+    println!("测试通过");
+}
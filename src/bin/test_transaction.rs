@@ -0,0 +1,179 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::error::DbError;
+use simple_db::core::types::{DataType, TypeError};
+use std::fs;
+
+fn text(s: &str) -> DataType {
+    DataType::Varchar(s.to_string())
+}
+
+// 查询结果中的 id 列
+fn ids(db: &Database, sql: &str) -> Vec<i32> {
+    db.query_iter(sql)
+        .unwrap()
+        .map(|row| match row.unwrap()[0] {
+            DataType::Int(id) => id,
+            ref other => panic!("期望整数，实际为 {:?}", other),
+        })
+        .collect()
+}
+
+fn main() {
+    println!("=== 测试事务内读取未提交的修改 ===");
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql("CREATE TABLE accounts (id INT PRIMARY KEY, owner VARCHAR(10))").unwrap();
+    db.execute_sql("CREATE TABLE vips (id INT)").unwrap();
+    db.execute_sql("INSERT INTO accounts VALUES (1, 'Ann'), (2, 'Bob')").unwrap();
+
+    // 插入后在同一事务中查询
+    let mut transaction = db.begin_transaction();
+    transaction.insert_row("accounts", vec![DataType::Int(3), text("Cid")]).unwrap();
+    transaction.insert_row("vips", vec![DataType::Int(3)]).unwrap();
+    let result = transaction.query("SELECT id, owner FROM accounts WHERE id > 1").unwrap();
+    println!("事务内查询: {:?}", result.rows);
+    assert_eq!(result.columns, vec!["id", "owner"]);
+    assert_eq!(result.rows, vec![vec![DataType::Int(2), text("Bob")], vec![DataType::Int(3), text("Cid")]]);
+
+    // 子查询引用的其他表同样能看到未提交的修改
+    let result = transaction.query("SELECT owner FROM accounts WHERE id IN (SELECT id FROM vips)").unwrap();
+    assert_eq!(result.rows, vec![vec![text("Cid")]]);
+
+    // 修改和删除同样可见
+    transaction.update_row("accounts", 0, vec![DataType::Int(1), text("Amy")]).unwrap();
+    transaction.delete_row("accounts", 1).unwrap();
+    let table = transaction.get_table("accounts").unwrap().unwrap();
+    println!("事务内的表: {:?}", table.rows);
+    assert_eq!(table.rows, vec![vec![DataType::Int(1), text("Amy")], vec![DataType::Int(3), text("Cid")]]);
+    let result = transaction.query("SELECT COUNT(*) FROM accounts").unwrap();
+    assert_eq!(result.rows, vec![vec![DataType::Int(2)]]);
+    assert!(transaction.get_table("missing").unwrap().is_none());
+
+    // 回滚后存储中的数据不变
+    transaction.rollback().unwrap();
+    assert_eq!(ids(&db, "SELECT id FROM accounts"), vec![1, 2]);
+    assert!(ids(&db, "SELECT id FROM vips").is_empty());
+
+    // 提交后的结果与事务内看到的一致
+    let mut transaction = db.begin_transaction();
+    transaction.insert_row("accounts", vec![DataType::Int(4), text("Dan")]).unwrap();
+    transaction.delete_row("accounts", 0).unwrap();
+    let seen = transaction.query("SELECT id FROM accounts").unwrap().rows;
+    transaction.commit().unwrap();
+    assert_eq!(seen, vec![vec![DataType::Int(2)], vec![DataType::Int(4)]]);
+    assert_eq!(ids(&db, "SELECT id FROM accounts"), vec![2, 4]);
+    println!("事务内可以读取未提交的修改");
+
+    println!("=== 测试提交失败时不应用任何修改 ===");
+    // 第二条插入违反主键约束，三条插入都不生效
+    let mut transaction = db.begin_transaction();
+    transaction.insert_row("accounts", vec![DataType::Int(5), text("Eve")]).unwrap();
+    transaction.insert_row("accounts", vec![DataType::Int(2), text("Dup")]).unwrap();
+    transaction.insert_row("accounts", vec![DataType::Int(6), text("Fay")]).unwrap();
+    let error = transaction.commit().unwrap_err();
+    println!("提交失败: {}", error);
+    assert_eq!(ids(&db, "SELECT id FROM accounts"), vec![2, 4]);
+
+    // 其他表上的修改同样不生效
+    let mut transaction = db.begin_transaction();
+    transaction.insert_row("vips", vec![DataType::Int(4)]).unwrap();
+    transaction.insert_row("accounts", vec![DataType::Int(4), text("Dup")]).unwrap();
+    assert!(transaction.commit().is_err());
+    assert!(ids(&db, "SELECT id FROM vips").is_empty());
+    assert_eq!(ids(&db, "SELECT id FROM accounts"), vec![2, 4]);
+
+    // 修改或删除不存在的行号时提交失败，同一事务中的其他修改也不生效
+    let mut transaction = db.begin_transaction();
+    transaction.insert_row("vips", vec![DataType::Int(2)]).unwrap();
+    transaction.update_row("accounts", 5, vec![DataType::Int(9), text("Ivy")]).unwrap();
+    assert!(matches!(transaction.commit(), Err(DbError::TableError(_))));
+    let mut transaction = db.begin_transaction();
+    transaction.delete_row("accounts", 0).unwrap();
+    transaction.delete_row("accounts", 1).unwrap(); // 删除第一行后只剩一行
+    assert!(matches!(transaction.commit(), Err(DbError::TableError(_))));
+    assert!(ids(&db, "SELECT id FROM vips").is_empty());
+    assert_eq!(ids(&db, "SELECT id FROM accounts"), vec![2, 4]);
+
+    // 修改后的行违反主键或非空约束时提交失败，表保持不变
+    db.execute_sql("CREATE TABLE t (id INT PRIMARY KEY, name VARCHAR(10) NOT NULL)").unwrap();
+    db.execute_sql("INSERT INTO t VALUES (1, 'a'), (2, 'b')").unwrap();
+    let mut transaction = db.begin_transaction();
+    transaction.update_row("t", 0, vec![DataType::Int(2), text("c")]).unwrap();
+    assert!(matches!(transaction.commit(), Err(DbError::TypeError(TypeError::PrimaryKeyViolation(_)))));
+    let mut transaction = db.begin_transaction();
+    transaction.insert_row("vips", vec![DataType::Int(2)]).unwrap();
+    transaction.update_row("t", 0, vec![DataType::Int(3), DataType::Null]).unwrap();
+    assert!(matches!(transaction.commit(), Err(DbError::TypeError(TypeError::NullValue(_)))));
+    let mut transaction = db.begin_transaction();
+    transaction.update_row("t", 1, vec![text("x"), text("c")]).unwrap();
+    assert!(matches!(transaction.commit(), Err(DbError::TypeError(TypeError::TypeMismatch { .. }))));
+    assert_eq!(db.get_table("t").unwrap().unwrap().rows, vec![
+        vec![DataType::Int(1), text("a")],
+        vec![DataType::Int(2), text("b")],
+    ]);
+    assert!(ids(&db, "SELECT id FROM vips").is_empty());
+
+    // 同一事务中先改走原主键值，再由另一行使用，提交成功
+    let mut transaction = db.begin_transaction();
+    transaction.update_row("t", 1, vec![DataType::Int(3), text("b")]).unwrap();
+    transaction.update_row("t", 0, vec![DataType::Int(2), text("a")]).unwrap();
+    transaction.commit().unwrap();
+    assert_eq!(ids(&db, "SELECT id FROM t"), vec![2, 3]);
+    println!("提交失败时没有应用任何修改");
+
+    println!("=== 测试结束后的事务不能再使用 ===");
+    let is_inactive = |result: Result<(), DbError>| matches!(result, Err(DbError::TransactionError(_)));
+
+    // 提交后不能再修改、提交或回滚
+    let mut transaction = db.begin_transaction();
+    transaction.insert_row("vips", vec![DataType::Int(7)]).unwrap();
+    transaction.commit().unwrap();
+    assert!(is_inactive(transaction.insert_row("vips", vec![DataType::Int(8)])));
+    assert!(is_inactive(transaction.delete_row("vips", 0)));
+    assert!(is_inactive(transaction.commit()));
+    assert!(is_inactive(transaction.rollback()));
+    assert_eq!(ids(&db, "SELECT id FROM vips"), vec![7]);
+
+    // 回滚后同样不能再使用，之前缓存的修改被丢弃
+    let mut transaction = db.begin_transaction();
+    transaction.insert_row("vips", vec![DataType::Int(8)]).unwrap();
+    transaction.rollback().unwrap();
+    assert!(is_inactive(transaction.update_row("vips", 0, vec![DataType::Int(9)])));
+    assert!(is_inactive(transaction.commit()));
+    assert!(is_inactive(transaction.rollback()));
+    assert_eq!(ids(&db, "SELECT id FROM vips"), vec![7]);
+
+    // 提交失败后事务也已结束，不能重试
+    let mut transaction = db.begin_transaction();
+    transaction.insert_row("vips", vec![DataType::Int(8)]).unwrap();
+    transaction.insert_row("accounts", vec![DataType::Int(2), text("Dup")]).unwrap();
+    assert!(transaction.commit().is_err());
+    assert!(is_inactive(transaction.commit()));
+    assert!(is_inactive(transaction.insert_row("vips", vec![DataType::Int(9)])));
+    assert_eq!(ids(&db, "SELECT id FROM vips"), vec![7]);
+    println!("结束后的事务拒绝再次使用");
+
+    println!("=== 测试文件存储中提交的修改写入磁盘 ===");
+    let base_dir = std::env::temp_dir().join("simple_db_test_transaction");
+    let _ = fs::remove_dir_all(&base_dir);
+    let mut db = Database::new(StorageType::File(base_dir.clone()));
+    db.execute_sql("CREATE TABLE accounts (id INT PRIMARY KEY, owner VARCHAR(10))").unwrap();
+    db.execute_sql("CREATE TABLE vips (id INT)").unwrap();
+    db.execute_sql("INSERT INTO accounts VALUES (1, 'Ann')").unwrap();
+    let mut transaction = db.begin_transaction();
+    transaction.insert_row("accounts", vec![DataType::Int(2), text("Bob")]).unwrap();
+    transaction.update_row("accounts", 0, vec![DataType::Int(1), text("Amy")]).unwrap();
+    transaction.insert_row("vips", vec![DataType::Int(2)]).unwrap();
+    transaction.commit().unwrap();
+    assert!(!db.has_unsaved_changes());
+
+    // 不手动保存，重新打开后提交的修改仍然存在
+    drop(db);
+    let db = Database::new(StorageType::File(base_dir.clone()));
+    assert_eq!(db.get_table("accounts").unwrap().unwrap().rows, vec![
+        vec![DataType::Int(1), text("Amy")],
+        vec![DataType::Int(2), text("Bob")],
+    ]);
+    assert_eq!(ids(&db, "SELECT id FROM vips"), vec![2]);
+    let _ = fs::remove_dir_all(&base_dir);
+    println!("提交的修改在重新打开后仍然存在");
+}
@@ -0,0 +1,59 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::types::{ColumnType, DataType};
+
+fn main() {
+    println!("=== 测试数据类型别名 ===");
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql(
+        "CREATE TABLE measurements (\
+            id INT PRIMARY KEY, \
+            height REAL, \
+            weight DOUBLE PRECISION, \
+            ratio DOUBLE, \
+            price DECIMAL(10, 2), \
+            amount NUMERIC(8), \
+            total NUMERIC, \
+            notes TEXT, \
+            code CHAR(3), \
+            flag CHARACTER)",
+    ).unwrap();
+
+    let types: Vec<(String, ColumnType)> = db.get_schema("measurements").unwrap()
+        .into_iter()
+        .map(|c| (c.name, c.data_type))
+        .collect();
+    println!("{:?}", types);
+    let expected = [
+        ("id", ColumnType::Int(None)),
+        ("height", ColumnType::Float(None)),
+        ("weight", ColumnType::Float(None)),
+        ("ratio", ColumnType::Float(None)),
+        ("price", ColumnType::Float(None)),
+        ("amount", ColumnType::Float(None)),
+        ("total", ColumnType::Float(None)),
+        ("notes", ColumnType::Varchar(65535)),
+        ("code", ColumnType::Varchar(3)),
+        ("flag", ColumnType::Varchar(1)),
+    ];
+    assert_eq!(types.len(), expected.len());
+    for ((name, data_type), (expected_name, expected_type)) in types.iter().zip(expected.iter()) {
+        assert_eq!(name, expected_name);
+        assert_eq!(data_type, expected_type, "列 {}", name);
+    }
+
+    // 别名类型的列按映射后的类型检查
+    db.execute_sql("INSERT INTO measurements VALUES (1, 1.5, 2.5, 0.5, 9.99, 1.0, 2.0, 'long text', 'abc', 'y')").unwrap();
+    assert!(db.execute_sql("INSERT INTO measurements (id, code) VALUES (2, 'abcd')").is_err());
+    let rows = db.query_iter("SELECT price, notes FROM measurements").unwrap().collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(rows, vec![vec![DataType::Float(9.99), DataType::Varchar("long text".to_string())]]);
+
+    // 语法错误
+    for sql in [
+        "CREATE TABLE bad (x DECIMAL(10,))",
+        "CREATE TABLE bad (x CHAR())",
+        "CREATE TABLE bad (x NUMERIC(a))",
+    ] {
+        assert!(db.execute_sql(sql).is_err(), "{}", sql);
+    }
+    println!("类型别名映射正确");
+}
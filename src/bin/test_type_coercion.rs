@@ -0,0 +1,77 @@
+use simple_db::core::error::DbError;
+use simple_db::core::types::{ArithmeticOperator, DataType};
+use std::cmp::Ordering;
+
+fn text(s: &str) -> DataType {
+    DataType::Varchar(s.to_string())
+}
+
+// 两个方向的比较结果互为相反
+fn assert_cmp(a: &DataType, b: &DataType, expected: Option<Ordering>) {
+    assert_eq!(a.partial_cmp_coerced(b), expected, "{:?} 与 {:?}", a, b);
+    assert_eq!(b.partial_cmp_coerced(a), expected.map(Ordering::reverse), "{:?} 与 {:?}", b, a);
+}
+
+// 错误中的说明文字
+fn message(error: DbError) -> String {
+    match error {
+        DbError::SqlError(message) => message,
+        other => panic!("期望SQL错误，实际为 {:?}", other),
+    }
+}
+
+// 两个方向的运算结果
+fn arithmetic(a: &DataType, operator: ArithmeticOperator, b: &DataType) -> (Result<DataType, String>, Result<DataType, String>) {
+    let run = |x: &DataType, y: &DataType| x.arithmetic(&operator, y).map_err(message);
+    (run(a, b), run(b, a))
+}
+
+fn main() {
+    println!("=== 测试不同类型之间的比较 ===");
+    let (int, float, varchar, null) = (DataType::Int(2), DataType::Float(2.5), text("2"), DataType::Null);
+
+    // 数值之间按数值比较，整数与浮点数自动转换
+    assert_cmp(&int, &DataType::Int(3), Some(Ordering::Less));
+    assert_cmp(&int, &DataType::Int(2), Some(Ordering::Equal));
+    assert_cmp(&int, &float, Some(Ordering::Less));
+    assert_cmp(&int, &DataType::Float(2.0), Some(Ordering::Equal));
+    assert_cmp(&float, &DataType::Float(1.5), Some(Ordering::Greater));
+    assert_cmp(&float, &DataType::Float(f64::NAN), None);
+    // 字符串之间按字典序比较
+    assert_cmp(&varchar, &text("10"), Some(Ordering::Greater));
+    assert_cmp(&varchar, &text("2"), Some(Ordering::Equal));
+    // 字符串与数值不能比较，NULL 与任何值（包括 NULL）都不能比较
+    assert_cmp(&varchar, &int, None);
+    assert_cmp(&varchar, &float, None);
+    for value in [&int, &float, &varchar, &null] {
+        assert_cmp(&null, value, None);
+    }
+    println!("比较结果正确");
+
+    println!("\n=== 测试不同类型之间的算术运算 ===");
+    // 整数之间结果为整数，整数除法截断
+    assert_eq!(arithmetic(&int, ArithmeticOperator::Add, &DataType::Int(5)), (Ok(DataType::Int(7)), Ok(DataType::Int(7))));
+    assert_eq!(arithmetic(&int, ArithmeticOperator::Subtract, &DataType::Int(5)), (Ok(DataType::Int(-3)), Ok(DataType::Int(3))));
+    assert_eq!(arithmetic(&DataType::Int(7), ArithmeticOperator::Divide, &int), (Ok(DataType::Int(3)), Ok(DataType::Int(0))));
+    // 整数与浮点数混合时结果为浮点数
+    assert_eq!(arithmetic(&int, ArithmeticOperator::Add, &float), (Ok(DataType::Float(4.5)), Ok(DataType::Float(4.5))));
+    assert_eq!(arithmetic(&int, ArithmeticOperator::Subtract, &float), (Ok(DataType::Float(-0.5)), Ok(DataType::Float(0.5))));
+    assert_eq!(arithmetic(&int, ArithmeticOperator::Multiply, &float), (Ok(DataType::Float(5.0)), Ok(DataType::Float(5.0))));
+    assert_eq!(arithmetic(&DataType::Int(5), ArithmeticOperator::Divide, &float), (Ok(DataType::Float(2.0)), Ok(DataType::Float(0.5))));
+    assert_eq!(arithmetic(&float, ArithmeticOperator::Multiply, &float), (Ok(DataType::Float(6.25)), Ok(DataType::Float(6.25))));
+    // 除数为零
+    let zero_division = Err("除数不能为零".to_string());
+    assert_eq!(DataType::Int(1).arithmetic(&ArithmeticOperator::Divide, &DataType::Int(0)).map_err(message), zero_division);
+    assert_eq!(float.arithmetic(&ArithmeticOperator::Divide, &DataType::Int(0)).map_err(message), zero_division);
+    assert_eq!(int.arithmetic(&ArithmeticOperator::Divide, &DataType::Float(0.0)).map_err(message), zero_division);
+    // 任一操作数为 NULL 时结果为 NULL
+    for value in [&int, &float, &null] {
+        assert_eq!(arithmetic(value, ArithmeticOperator::Add, &null), (Ok(DataType::Null), Ok(DataType::Null)));
+    }
+    // 字符串不能参与算术运算
+    let string_error = Err("字符串不能参与算术运算".to_string());
+    for value in [&int, &float, &varchar] {
+        assert_eq!(arithmetic(value, ArithmeticOperator::Multiply, &varchar), (string_error.clone(), string_error.clone()));
+    }
+    println!("算术运算结果正确");
+}
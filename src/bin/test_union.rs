@@ -0,0 +1,72 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::error::DbError;
+use simple_db::core::types::DataType;
+use simple_db::StatementResult;
+
+// 执行查询并返回结果行
+fn rows(db: &mut Database, sql: &str) -> Vec<Vec<DataType>> {
+    match db.execute(sql).unwrap() {
+        StatementResult::ResultSet(result) => {
+            println!("{} -> {:?}", sql, result.rows);
+            result.rows
+        }
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+}
+
+fn ints(values: &[i32]) -> Vec<Vec<DataType>> {
+    values.iter().map(|&v| vec![DataType::Int(v)]).collect()
+}
+
+fn main() {
+    println!("=== 测试 UNION 与 UNION ALL ===");
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql("CREATE TABLE users (id INT, name VARCHAR(10))").unwrap();
+    db.execute_sql("CREATE TABLE orders (order_id INT, user_id INT)").unwrap();
+    db.execute_sql("INSERT INTO users VALUES (1, 'Alice'), (2, 'Bob'), (3, 'Carol')").unwrap();
+    db.execute_sql("INSERT INTO orders VALUES (10, 1), (11, 1), (12, 2), (13, 4)").unwrap();
+
+    // UNION ALL 保留两侧的全部行，包括重复行
+    assert_eq!(
+        rows(&mut db, "SELECT user_id FROM orders UNION ALL SELECT id FROM users"),
+        ints(&[1, 1, 2, 4, 1, 2, 3])
+    );
+
+    // UNION 去除重复行，保留每行首次出现的位置
+    assert_eq!(
+        rows(&mut db, "SELECT user_id FROM orders UNION SELECT id FROM users"),
+        ints(&[1, 2, 4, 3])
+    );
+
+    // 多列时整行相同才视为重复，表头取自左侧查询
+    match db.execute("SELECT id, name FROM users UNION SELECT user_id, 'Alice' FROM orders").unwrap() {
+        StatementResult::ResultSet(result) => {
+            assert_eq!(result.columns, vec!["id", "name"]);
+            let text = |s: &str| DataType::Varchar(s.to_string());
+            assert_eq!(result.rows, vec![
+                vec![DataType::Int(1), text("Alice")],
+                vec![DataType::Int(2), text("Bob")],
+                vec![DataType::Int(3), text("Carol")],
+                vec![DataType::Int(2), text("Alice")],
+                vec![DataType::Int(4), text("Alice")],
+            ]);
+        }
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+
+    // 两侧列数不一致时报错
+    for sql in [
+        "SELECT id, name FROM users UNION SELECT user_id FROM orders",
+        "SELECT id FROM users UNION ALL SELECT order_id, user_id FROM orders",
+    ] {
+        match db.execute(sql) {
+            Err(DbError::SqlError(message)) => {
+                println!("{} -> {}", sql, message);
+                assert!(message.starts_with("UNION 两侧的列数不一致"), "{}", message);
+            }
+            other => panic!("{} 应返回列数不一致错误: {:?}", sql, other),
+        }
+    }
+
+    println!("测试通过");
+}
@@ -0,0 +1,84 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::sql::{query_rows, SqlExecutor, SqlParser, SqlStatement};
+use simple_db::core::storage::memory::MemoryStorage;
+use simple_db::core::types::DataType;
+use simple_db::StatementResult;
+
+fn text(s: &str) -> DataType {
+    DataType::Varchar(s.to_string())
+}
+
+// 执行查询并返回结果行，打印结果的执行方式同样不能出错
+fn rows(db: &mut Database, sql: &str) -> Vec<Vec<DataType>> {
+    db.execute_sql(sql).unwrap_or_else(|e| panic!("执行失败 {}: {}", sql, e.detailed_message()));
+    let iterated = db.query_iter(sql).unwrap().collect::<Result<Vec<_>, _>>().unwrap();
+    match db.execute(sql).unwrap() {
+        StatementResult::ResultSet(result) => {
+            println!("{} -> {:?}", sql, result.rows);
+            assert_eq!(result.rows, iterated, "{}", sql);
+            result.rows
+        }
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+}
+
+// 把 SELECT * 的查询改为只投影指定的列，得到普通（不含表达式）的查询
+fn select_columns(sql: &str, projected: &[&str]) -> SqlStatement {
+    match SqlParser::new().parse(sql).unwrap() {
+        SqlStatement::Select { table, where_clause, order_by, .. } => SqlStatement::Select {
+            columns: projected.iter().map(|c| c.to_string()).collect(),
+            table,
+            where_clause,
+            order_by,
+        },
+        other => panic!("期望普通查询，实际为 {:?}", other),
+    }
+}
+
+const SETUP: [&str; 2] = [
+    "CREATE TABLE users (id INT, name VARCHAR(10), age INT)",
+    "INSERT INTO users VALUES (1, 'Alice', 25), (2, 'Bob', 31), (3, 'Carol', NULL), (4, 'Dave', 35)",
+];
+
+fn main() {
+    println!("=== 测试按未投影的列过滤 ===");
+
+    // 普通查询：过滤列不在投影中，age 为 NULL 的行不满足比较条件
+    let mut storage = MemoryStorage::new();
+    for sql in SETUP {
+        SqlExecutor::new(&mut storage).execute(SqlParser::new().parse(sql).unwrap()).unwrap();
+    }
+    let cases = [
+        ("SELECT * FROM users WHERE age > 28", vec!["name"], vec![vec![text("Bob")], vec![text("Dave")]]),
+        ("SELECT * FROM users WHERE age <= 28 OR name = 'Carol'", vec!["id"], vec![vec![DataType::Int(1)], vec![DataType::Int(3)]]),
+        ("SELECT * FROM users WHERE age IS NULL", vec!["id", "name"], vec![vec![DataType::Int(3), text("Carol")]]),
+    ];
+    for (sql, projected, expected) in cases {
+        let (headers, result) = query_rows(&storage, select_columns(sql, &projected)).unwrap();
+        let result = result.collect::<Result<Vec<_>, _>>().unwrap();
+        println!("{} 投影 {:?} -> {:?}", sql, projected, result);
+        assert_eq!(headers, projected);
+        assert_eq!(result, expected, "{}", sql);
+        // 打印结果的执行方式同样可以按未投影的列过滤
+        SqlExecutor::new(&mut storage).execute(select_columns(sql, &projected)).unwrap();
+    }
+
+    // 带表达式的查询：过滤列同样不需要出现在投影中
+    let mut db = Database::new(StorageType::Memory);
+    for sql in SETUP {
+        db.execute_sql(sql).unwrap();
+    }
+    let sql = "SELECT name, id * 10 FROM users WHERE age > 28";
+    assert!(matches!(SqlParser::new().parse(sql).unwrap(), SqlStatement::SelectWithExpressions { .. }));
+    assert_eq!(rows(&mut db, sql), vec![
+        vec![text("Bob"), DataType::Int(20)],
+        vec![text("Dave"), DataType::Int(40)],
+    ]);
+    assert_eq!(rows(&mut db, "SELECT name FROM users WHERE age > 28"), vec![vec![text("Bob")], vec![text("Dave")]]);
+    assert_eq!(rows(&mut db, "SELECT *, id * 10 FROM users WHERE age < 30"), vec![
+        vec![DataType::Int(1), text("Alice"), DataType::Int(25), DataType::Int(10)],
+    ]);
+    assert_eq!(rows(&mut db, "SELECT id + 1 FROM users WHERE age IS NULL"), vec![vec![DataType::Int(4)]]);
+
+    println!("测试通过");
+}
@@ -0,0 +1,78 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::error::DbError;
+use simple_db::core::types::{DataType, TypeError};
+use simple_db::StatementResult;
+
+fn text(s: &str) -> DataType {
+    DataType::Varchar(s.to_string())
+}
+
+// 查询全部行，用于确认失败的 UPDATE 没有修改数据
+fn all_rows(db: &mut Database) -> Vec<Vec<DataType>> {
+    match db.execute("SELECT id, name, age FROM people").unwrap() {
+        StatementResult::ResultSet(result) => result.rows,
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+}
+
+fn main() {
+    println!("=== 测试 UPDATE 的约束检查 ===");
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql("CREATE TABLE people (id INT PRIMARY KEY, name VARCHAR(5) NOT NULL, age INT)").unwrap();
+    db.execute_sql("INSERT INTO people VALUES (1, 'Ann', 30), (2, 'Bob', 25), (3, 'Cat', 40)").unwrap();
+    let original = all_rows(&mut db);
+
+    // 非空列设为 NULL
+    let error = db.execute("UPDATE people SET name = NULL WHERE id = 2").unwrap_err();
+    println!("非空列设为 NULL: {}", error.detailed_message());
+    assert!(matches!(&error, DbError::TypeError(TypeError::NullValue(column)) if column == "name"));
+    assert_eq!(all_rows(&mut db), original);
+
+    // 超出长度的字符串
+    let error = db.execute("UPDATE people SET name = 'Christina' WHERE id = 1").unwrap_err();
+    println!("超长字符串: {}", error.detailed_message());
+    assert!(matches!(&error, DbError::TypeError(TypeError::TypeMismatch { .. })));
+    assert_eq!(all_rows(&mut db), original);
+
+    // 类型不匹配
+    let error = db.execute("UPDATE people SET age = 'old' WHERE id = 3").unwrap_err();
+    println!("类型不匹配: {}", error.detailed_message());
+    assert!(matches!(&error, DbError::TypeError(TypeError::TypeMismatch { .. })));
+    assert_eq!(all_rows(&mut db), original);
+
+    // 主键设为 NULL
+    let error = db.execute("UPDATE people SET id = NULL WHERE id = 3").unwrap_err();
+    println!("主键设为 NULL: {}", error.detailed_message());
+    assert!(matches!(&error, DbError::TypeError(TypeError::NullValue(column)) if column == "id"));
+    assert_eq!(all_rows(&mut db), original);
+
+    // 主键与其他行重复
+    let error = db.execute("UPDATE people SET id = 1 WHERE id = 2").unwrap_err();
+    println!("主键重复: {}", error.detailed_message());
+    assert!(matches!(&error, DbError::TypeError(TypeError::PrimaryKeyViolation(_))));
+    assert_eq!(all_rows(&mut db), original);
+
+    // 多行更新为同一个主键，第一行单独看不冲突，也不更新任何行
+    let error = db.execute("UPDATE people SET id = 9 WHERE age > 26").unwrap_err();
+    println!("多行主键重复: {}", error.detailed_message());
+    assert!(matches!(&error, DbError::TypeError(TypeError::PrimaryKeyViolation(_))));
+    assert_eq!(all_rows(&mut db), original);
+
+    // 主键保持原值或改为未使用的值都可以
+    assert_eq!(db.execute("UPDATE people SET id = 2, age = 26 WHERE id = 2").unwrap(), StatementResult::RowsAffected(1));
+    assert_eq!(db.execute("UPDATE people SET id = 4 WHERE id = 3").unwrap(), StatementResult::RowsAffected(1));
+    assert_eq!(
+        all_rows(&mut db),
+        vec![
+            vec![DataType::Int(1), text("Ann"), DataType::Int(30)],
+            vec![DataType::Int(2), text("Bob"), DataType::Int(26)],
+            vec![DataType::Int(4), text("Cat"), DataType::Int(40)],
+        ]
+    );
+
+    // 直接修改行时同样不与自身的主键冲突
+    db.update_row("people", 0, vec![DataType::Int(1), text("Amy"), DataType::Int(31)]).unwrap();
+    assert!(db.update_row("people", 0, vec![DataType::Int(2), text("Amy"), DataType::Int(31)]).is_err());
+    assert_eq!(db.get_row("people", 0).unwrap(), Some(vec![DataType::Int(1), text("Amy"), DataType::Int(31)]));
+    println!("UPDATE 的约束检查结果正确");
+}
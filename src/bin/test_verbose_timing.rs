@@ -0,0 +1,30 @@
+use simple_db::core::db::{Database, StorageType};
+use std::time::Duration;
+
+fn main() {
+    println!("=== 测试 verbose 模式的执行耗时 ===");
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql("CREATE TABLE t (id INT)").unwrap();
+
+    // 默认关闭，不记录耗时
+    assert!(!db.is_verbose());
+    db.execute_sql("INSERT INTO t VALUES (1)").unwrap();
+    assert_eq!(db.last_elapsed(), None);
+
+    // 开启后每条语句都记录耗时，包括 execute 和失败的语句
+    db.set_verbose(true);
+    db.execute_sql("SELECT * FROM t").unwrap();
+    let elapsed = db.last_elapsed().expect("verbose 模式下应记录耗时");
+    assert!(elapsed > Duration::ZERO);
+    db.execute("INSERT INTO t VALUES (2)").unwrap();
+    assert!(db.last_elapsed().is_some_and(|elapsed| elapsed > Duration::ZERO));
+    assert!(db.execute_sql("SELECT * FROM missing").is_err());
+    assert!(db.last_elapsed().is_some_and(|elapsed| elapsed > Duration::ZERO));
+
+    // 关闭后清除记录，之后的语句也不再记录
+    db.set_verbose(false);
+    assert_eq!(db.last_elapsed(), None);
+    db.execute_sql("SELECT * FROM t").unwrap();
+    assert_eq!(db.last_elapsed(), None);
+    println!("执行耗时记录正确");
+}
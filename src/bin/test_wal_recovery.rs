@@ -0,0 +1,65 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::storage::wal::{Wal, WalRecord};
+use simple_db::core::types::DataType;
+use std::fs;
+
+// 查询结果中的 id 列
+fn ids(db: &Database, sql: &str) -> Vec<i32> {
+    db.query_iter(sql)
+        .unwrap()
+        .map(|row| match row.unwrap()[0] {
+            DataType::Int(id) => id,
+            ref other => panic!("期望整数，实际为 {:?}", other),
+        })
+        .collect()
+}
+
+fn main() {
+    println!("=== 测试预写日志的崩溃恢复 ===");
+    let dir = std::env::temp_dir().join("simple_db_test_wal_recovery");
+    let _ = fs::remove_dir_all(&dir);
+
+    let mut db = Database::new(StorageType::File(dir.clone()));
+    db.execute_sql("CREATE TABLE accounts (id INT PRIMARY KEY)").unwrap();
+    db.execute_sql("CREATE TABLE audit (id INT)").unwrap();
+    db.execute_sql("INSERT INTO accounts VALUES (1)").unwrap();
+    db.execute_sql("INSERT INTO audit VALUES (1)").unwrap();
+    let mut accounts = db.get_table("accounts").unwrap().unwrap().clone();
+    let mut audit = db.get_table("audit").unwrap().unwrap().clone();
+    drop(db);
+
+    // 模拟批量提交中途崩溃：日志已完整写入，表文件还没有更新
+    let accounts_file = dir.join("tables").join("accounts.json");
+    let accounts_before = fs::read_to_string(&accounts_file).unwrap();
+    accounts.insert_row(vec![DataType::Int(2)]).unwrap();
+    audit.insert_row(vec![DataType::Int(2)]).unwrap();
+    let wal = Wal::new(dir.join("wal.log"));
+    wal.append_group(&[WalRecord::Put { table: accounts.clone() }, WalRecord::Put { table: audit }]).unwrap();
+    assert_eq!(fs::read_to_string(&accounts_file).unwrap(), accounts_before);
+
+    // 之后一组修改没有写完提交标记就崩溃，不应被重放
+    accounts.insert_row(vec![DataType::Int(3)]).unwrap();
+    let mut log = fs::read_to_string(dir.join("wal.log")).unwrap();
+    log.push_str(&serde_json::to_string(&WalRecord::Put { table: accounts }).unwrap());
+    log.push_str("\n{\"op\":\"Dro");
+    fs::write(dir.join("wal.log"), log).unwrap();
+
+    // 重新打开时重放已提交的组，两个表的修改都生效
+    let db = Database::new(StorageType::File(dir.clone()));
+    assert_eq!(ids(&db, "SELECT id FROM accounts"), vec![1, 2]);
+    assert_eq!(ids(&db, "SELECT id FROM audit"), vec![1, 2]);
+    assert_ne!(fs::read_to_string(&accounts_file).unwrap(), accounts_before);
+    assert_eq!(fs::read_to_string(dir.join("wal.log")).unwrap(), "");
+
+    // 显式调用 load 同样重放日志
+    let mut db = Database::new(StorageType::File(dir.clone()));
+    let mut audit = db.get_table("audit").unwrap().unwrap().clone();
+    audit.insert_row(vec![DataType::Int(3)]).unwrap();
+    Wal::new(dir.join("wal.log")).append_group(&[WalRecord::Put { table: audit }]).unwrap();
+    db.load().unwrap();
+    assert_eq!(ids(&db, "SELECT id FROM audit"), vec![1, 2, 3]);
+    assert_eq!(fs::read_to_string(dir.join("wal.log")).unwrap(), "");
+
+    let _ = fs::remove_dir_all(&dir);
+    println!("崩溃后重放已提交的修改，丢弃未提交的修改");
+}
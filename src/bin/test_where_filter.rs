@@ -0,0 +1,92 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::types::DataType;
+use simple_db::StatementResult;
+
+// 查询结果中的 id 列
+fn ids(db: &Database, sql: &str) -> Vec<i32> {
+    db.query_iter(sql)
+        .unwrap_or_else(|e| panic!("查询失败 {}: {}", sql, e.detailed_message()))
+        .map(|row| match row.unwrap()[0] {
+            DataType::Int(id) => id,
+            ref other => panic!("期望整数，实际为 {:?}", other),
+        })
+        .collect()
+}
+
+// 创建测试表，包含 NULL 值
+fn setup() -> Database {
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql("CREATE TABLE items (id INT PRIMARY KEY, qty INT, name VARCHAR(10), flag INT)").unwrap();
+    db.execute_sql("CREATE INDEX idx_qty ON items (qty)").unwrap();
+    db.execute_sql(
+        "INSERT INTO items VALUES (1, 5, 'a', 0), (2, NULL, 'b', 0), (3, 10, NULL, 0), (4, 5, 'd', 0), (5, 20, 'e', 0), (6, NULL, NULL, 0)",
+    ).unwrap();
+    db
+}
+
+fn rows_affected(db: &mut Database, sql: &str) -> usize {
+    match db.execute(sql).unwrap_or_else(|e| panic!("执行失败 {}: {}", sql, e.detailed_message())) {
+        StatementResult::RowsAffected(n) => n,
+        other => panic!("期望影响行数，实际为 {:?}", other),
+    }
+}
+
+fn main() {
+    println!("=== 测试各类语句的 WHERE 过滤一致 ===");
+    let predicates = [
+        "qty = 5",
+        "qty > 5",
+        "qty IS NULL",
+        "qty IS NOT NULL",
+        "name = 'b' OR qty >= 10",
+        "qty != 5 AND name IS NOT NULL",
+        "id = 3",
+        "qty <=> NULL",
+        "id * 2 > 7",
+        "id + flag = 3",
+        "(id + 1) * 2 >= 8 AND id < 5",
+        "id - 1 IN (2, 4)",
+    ];
+
+    for predicate in predicates {
+        // 普通查询和带表达式的查询
+        let db = setup();
+        let selected = ids(&db, &format!("SELECT id FROM items WHERE {}", predicate));
+        let with_expressions = ids(&db, &format!("SELECT id, id * 2 FROM items WHERE {}", predicate));
+
+        // UPDATE 修改的行
+        let mut db = setup();
+        let updated = rows_affected(&mut db, &format!("UPDATE items SET flag = 1 WHERE {}", predicate));
+        let updated_ids = ids(&db, "SELECT id FROM items WHERE flag = 1");
+
+        // DELETE 删除的行
+        let mut db = setup();
+        let deleted = rows_affected(&mut db, &format!("DELETE FROM items WHERE {}", predicate));
+        let remaining = ids(&db, "SELECT id FROM items");
+        let deleted_ids: Vec<i32> = (1..=6).filter(|id| !remaining.contains(id)).collect();
+
+        println!("{} -> {:?}", predicate, selected);
+        assert_eq!(with_expressions, selected, "{}", predicate);
+        assert_eq!(updated_ids, selected, "{}", predicate);
+        assert_eq!(updated, selected.len(), "{}", predicate);
+        assert_eq!(deleted_ids, selected, "{}", predicate);
+        assert_eq!(deleted, selected.len(), "{}", predicate);
+    }
+
+    // 没有 WHERE 时删除所有行
+    let mut db = setup();
+    assert_eq!(rows_affected(&mut db, "DELETE FROM items"), 6);
+    assert!(ids(&db, "SELECT id FROM items").is_empty());
+
+    // 条件两侧都是表达式
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql("CREATE TABLE orders (id INT PRIMARY KEY, price INT, qty INT, total INT)").unwrap();
+    db.execute_sql("INSERT INTO orders VALUES (1, 100, 20, 0), (2, 10, 3, 30), (3, 50, 30, 0), (4, 7, 2, 9)").unwrap();
+    assert_eq!(rows_affected(&mut db, "UPDATE orders SET total = 999 WHERE price * qty = total"), 1);
+    assert_eq!(ids(&db, "SELECT id FROM orders WHERE total = 999"), vec![2]);
+    assert_eq!(rows_affected(&mut db, "UPDATE orders SET total = 1 WHERE price + qty = total"), 1);
+    assert_eq!(ids(&db, "SELECT id FROM orders WHERE total = 1"), vec![4]);
+    assert_eq!(rows_affected(&mut db, "DELETE FROM orders WHERE price * qty > 1000"), 2);
+    assert_eq!(ids(&db, "SELECT id FROM orders"), vec![2, 4]);
+    println!("各类语句的 WHERE 过滤结果一致");
+}
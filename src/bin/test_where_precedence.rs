@@ -0,0 +1,90 @@
+use simple_db::core::db::{Database, StorageType};
+use simple_db::core::sql::{Expression, SqlParser, SqlStatement, WhereClause};
+use simple_db::core::types::DataType;
+use simple_db::StatementResult;
+
+// 把条件树写成完全加括号的形式，便于检查结合方式
+fn render(clause: &WhereClause) -> String {
+    match clause {
+        WhereClause::Simple { column, value, .. } => format!("{}={}", column, value),
+        WhereClause::Expression { left, right, .. } => match (left.as_ref(), right.as_ref()) {
+            (Expression::Column(column), Expression::Literal(value)) => format!("{}={}", column, value),
+            other => format!("{:?}", other),
+        },
+        WhereClause::And { left, right } => format!("({} AND {})", render(left), render(right)),
+        WhereClause::Or { left, right } => format!("({} OR {})", render(left), render(right)),
+        other => format!("{:?}", other),
+    }
+}
+
+fn parse_where(predicate: &str) -> String {
+    let sql = format!("SELECT * FROM t WHERE {}", predicate);
+    match SqlParser::new().parse(&sql).unwrap() {
+        SqlStatement::Select { where_clause: Some(clause), .. } => render(&clause),
+        other => panic!("期望带 WHERE 的 SELECT，实际为 {:?}", other),
+    }
+}
+
+// 满足条件的行的 id
+fn ids(db: &mut Database, predicate: &str) -> Vec<i32> {
+    match db.execute(&format!("SELECT id FROM t WHERE {}", predicate)).unwrap() {
+        StatementResult::ResultSet(result) => result.rows.iter()
+            .map(|row| match row[0] {
+                DataType::Int(id) => id,
+                ref other => panic!("期望整数 id，实际为 {:?}", other),
+            })
+            .collect(),
+        other => panic!("期望结果集，实际为 {:?}", other),
+    }
+}
+
+fn main() {
+    println!("=== 测试 WHERE 中 AND 与 OR 的优先级 ===");
+    // AND 的优先级高于 OR，括号可以改变结合方式
+    let shapes = [
+        ("a = 1 OR b = 1 AND c = 1", "(a=1 OR (b=1 AND c=1))"),
+        ("a = 1 AND b = 1 OR c = 1", "((a=1 AND b=1) OR c=1)"),
+        ("a = 1 AND b = 1 OR c = 1 AND a = 0", "((a=1 AND b=1) OR (c=1 AND a=0))"),
+        ("(a = 1 OR b = 1) AND c = 1", "((a=1 OR b=1) AND c=1)"),
+        ("a = 1 AND (b = 1 OR c = 1)", "(a=1 AND (b=1 OR c=1))"),
+        ("((((a = 1))))", "a=1"),
+        ("(a = 1 OR (b = 1 AND (c = 1 OR (a = 0 AND b = 0))))", "(a=1 OR (b=1 AND (c=1 OR (a=0 AND b=0))))"),
+    ];
+    for (predicate, expected) in shapes {
+        let actual = parse_where(predicate);
+        println!("{} => {}", predicate, actual);
+        assert_eq!(actual, expected, "{}", predicate);
+    }
+
+    // a、b、c 取 0 和 1 的全部组合，id = a * 4 + b * 2 + c
+    let mut db = Database::new(StorageType::Memory);
+    db.execute_sql("CREATE TABLE t (id INT PRIMARY KEY, a INT, b INT, c INT)").unwrap();
+    for id in 0..8 {
+        db.execute_sql(&format!("INSERT INTO t VALUES ({}, {}, {}, {})", id, id / 4, id / 2 % 2, id % 2)).unwrap();
+    }
+
+    // 查询结果与按 SQL 优先级计算的结果一致
+    type Oracle = fn(i32, i32, i32) -> bool;
+    let cases: [(&str, Oracle); 10] = [
+        ("a = 1 OR b = 1 AND c = 1", |a, b, c| a == 1 || (b == 1 && c == 1)),
+        ("a = 1 AND b = 1 OR c = 1", |a, b, c| (a == 1 && b == 1) || c == 1),
+        ("(a = 1 OR b = 1) AND c = 1", |a, b, c| (a == 1 || b == 1) && c == 1),
+        ("a = 1 AND (b = 1 OR c = 1)", |a, b, c| a == 1 && (b == 1 || c == 1)),
+        ("a = 0 OR b = 0 OR c = 0 AND a = 1", |a, b, c| a == 0 || b == 0 || (c == 0 && a == 1)),
+        ("a = 1 AND b = 0 OR a = 0 AND b = 1", |a, b, _| (a == 1 && b == 0) || (a == 0 && b == 1)),
+        ("(a = 1 OR b = 1) AND (b = 0 OR c = 1)", |a, b, c| (a == 1 || b == 1) && (b == 0 || c == 1)),
+        ("((((a = 1))))", |a, _, _| a == 1),
+        ("(a = 1 OR (b = 1 AND (c = 1 OR (a = 0 AND b = 0))))", |a, b, c| a == 1 || (b == 1 && (c == 1 || (a == 0 && b == 0)))),
+        ("((a = 1 AND (b = 1)) OR ((c = 0) AND (a = 0 OR b = 0)))", |a, b, c| (a == 1 && b == 1) || (c == 0 && (a == 0 || b == 0))),
+    ];
+    for (predicate, oracle) in cases {
+        let expected: Vec<i32> = (0..8).filter(|id| oracle(id / 4, id / 2 % 2, id % 2)).collect();
+        let actual = ids(&mut db, predicate);
+        println!("{} -> {:?}", predicate, actual);
+        assert_eq!(actual, expected, "{}", predicate);
+    }
+
+    // 括号内是算术表达式时不作为条件分组
+    assert_eq!(ids(&mut db, "(a + b) * 2 = 4 OR c = 1 AND a = 1"), vec![5, 6, 7]);
+    println!("AND 与 OR 的优先级正确");
+}
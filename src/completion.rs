@@ -0,0 +1,127 @@
+use crate::core::db::Database;
+use crate::core::error::DbError;
+
+/// 可补全的SQL关键字，与解析器支持的语法保持一致
+const KEYWORDS: &[&str] = &[
+    "ALL", "ALTER", "AND", "AS", "ASC", "AVG", "BETWEEN", "BY", "CASCADE", "CHAR",
+    "COLUMN", "COMMENT", "CONFLICT", "COPY", "COUNT", "CREATE", "CURRENT_DATE", "CURRENT_TIMESTAMP", "DECIMAL",
+    "DEFAULT", "DELETE", "DELIMITER", "DESC", "DESCRIBE", "DISTINCT", "DO", "DOUBLE", "DROP", "EXPLAIN", "FALSE", "FLOAT",
+    "FROM", "GROUP", "HAVING", "HEADER", "IN", "INDEX", "INDEXES", "INSERT", "INT", "INTO", "IS", "KEY", "LIKE", "MAX",
+    "MAX_ROWS", "MIN", "MODIFY", "NOT", "NOTHING", "NULL", "NUMERIC", "ON", "OR", "ORDER",
+    "PERCENT", "PRECISION", "PRIMARY", "REAL", "RENAME", "RESTRICT", "RETURNING", "ROW_VERSION", "SAMPLE", "SELECT", "SET", "SHOW",
+    "SUM", "TABLE", "TEXT", "TO", "TRUE", "UNION", "UPDATE", "VALUES", "VARCHAR", "WHERE", "WITH",
+];
+
+/// 交互式Shell中不需要分号的命令，只在行首补全
+const SHELL_COMMANDS: &[&str] = &[
+    "check", "clear", "error_mode", "exit", "help", "history", "list", "load", "reload", "repair", "save", "schema",
+    "stats",
+    "toggle_error_mode",
+];
+
+/// 其后跟表名的关键字
+const TABLE_KEYWORDS: &[&str] = &["FROM", "INTO", "UPDATE", "TABLE", "DESCRIBE", "ON", "COPY"];
+
+/// 根据表结构补全关键字、表名和列名，与终端无关
+#[derive(Debug, Clone, Default)]
+pub struct Completer {
+    // 表名和对应的列名
+    tables: Vec<(String, Vec<String>)>,
+}
+
+impl Completer {
+    /// 使用给定的表结构创建补全器
+    pub fn new(tables: Vec<(String, Vec<String>)>) -> Self {
+        Completer { tables }
+    }
+
+    /// 读取数据库当前的表结构，表结构变化后需要重新创建
+    pub fn from_database(db: &Database) -> Result<Self, DbError> {
+        let mut tables = Vec::new();
+        for name in db.list_tables()? {
+            let columns = db.get_schema(&name)?.into_iter().map(|c| c.name).collect();
+            tables.push((name, columns));
+        }
+        Ok(Completer::new(tables))
+    }
+
+    /// 补全光标前的最后一个单词
+    ///
+    /// 返回被补全单词的起始位置和按字母排序（忽略大小写）的候选项：
+    /// FROM、INTO 等关键字之后补全表名，语句中已出现表名时补全这些表的列名和关键字，
+    /// 其余情况补全关键字，行首还会补全Shell命令。字符串内部不补全。
+    pub fn complete(&self, line: &str) -> (usize, Vec<String>) {
+        let start = line
+            .char_indices()
+            .rev()
+            .take_while(|(_, c)| c.is_alphanumeric() || *c == '_')
+            .last()
+            .map(|(i, _)| i)
+            .unwrap_or(line.len());
+        let prefix = &line[start..];
+        let before = &line[..start];
+
+        if in_string(before) {
+            return (start, Vec::new());
+        }
+
+        let words: Vec<&str> = before
+            .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .filter(|w| !w.is_empty())
+            .collect();
+
+        let mut candidates: Vec<String> = Vec::new();
+        if !words.is_empty() && is_table_keyword(&words, words.len() - 1) {
+            candidates.extend(self.tables.iter().map(|(name, _)| name.clone()));
+        } else {
+            // 语句中出现过的表，补全它们的列名
+            for (name, columns) in &self.tables {
+                if words.iter().enumerate().any(|(i, w)| w == name && i > 0 && is_table_keyword(&words, i - 1)) {
+                    candidates.extend(columns.iter().cloned());
+                }
+            }
+
+            // 关键字的大小写跟随输入
+            let lowercase = !prefix.is_empty() && prefix.chars().all(|c| !c.is_uppercase());
+            candidates.extend(KEYWORDS.iter().map(|k| {
+                if lowercase { k.to_lowercase() } else { k.to_string() }
+            }));
+
+            if before.trim().is_empty() {
+                candidates.extend(SHELL_COMMANDS.iter().map(|c| c.to_string()));
+            }
+        }
+
+        let mut candidates: Vec<String> = candidates
+            .into_iter()
+            .filter(|c| c.get(..prefix.len()).is_some_and(|p| p.eq_ignore_ascii_case(prefix)))
+            .collect();
+        // 忽略大小写排序，列名和关键字混合时更容易查找
+        candidates.sort_by(|a, b| a.to_lowercase().cmp(&b.to_lowercase()).then_with(|| a.cmp(b)));
+        candidates.dedup();
+        (start, candidates)
+    }
+}
+
+// 判断第 i 个单词之后是否应该跟表名，ON 只在 CREATE INDEX 中跟表名（INSERT 中是 ON CONFLICT）
+fn is_table_keyword(words: &[&str], i: usize) -> bool {
+    let word = words[i];
+    if word.eq_ignore_ascii_case("ON") {
+        return words.iter().any(|w| w.eq_ignore_ascii_case("INDEX"));
+    }
+    TABLE_KEYWORDS.iter().any(|k| k.eq_ignore_ascii_case(word))
+}
+
+// 判断位置之前是否有未闭合的引号
+fn in_string(before: &str) -> bool {
+    let mut quote: Option<char> = None;
+    for c in before.chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '\'' || c == '"' => quote = Some(c),
+            None => {}
+        }
+    }
+    quote.is_some()
+}
@@ -0,0 +1,54 @@
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 返回当前时间的时钟，测试时可以注入固定时间
+pub type Clock = Arc<dyn Fn() -> SystemTime + Send + Sync>;
+
+/// 使用系统时间的默认时钟
+pub fn system_clock() -> Clock {
+    Arc::new(SystemTime::now)
+}
+
+/// 始终返回同一时间的时钟
+pub fn fixed_clock(time: SystemTime) -> Clock {
+    Arc::new(move || time)
+}
+
+/// 将时间格式化为 UTC 的 "YYYY-MM-DD HH:MM:SS"
+pub fn format_timestamp(time: SystemTime) -> String {
+    let secs = time.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0) as i64;
+    let days = secs.div_euclid(86400);
+    let seconds_of_day = secs.rem_euclid(86400);
+    let (year, month, day) = civil_from_days(days);
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+        year,
+        month,
+        day,
+        seconds_of_day / 3600,
+        seconds_of_day % 3600 / 60,
+        seconds_of_day % 60
+    )
+}
+
+/// 将时间格式化为 UTC 的 "YYYY-MM-DD"
+pub fn format_date(time: SystemTime) -> String {
+    let secs = time.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0) as i64;
+    let (year, month, day) = civil_from_days(secs.div_euclid(86400));
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+// 将 1970-01-01 起的天数换算为公历年月日
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let day_of_era = z.rem_euclid(146097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
@@ -0,0 +1,70 @@
+use crate::core::error::DbError;
+
+/// 解析CSV文本，返回每条记录的字段列表
+/// 未加引号的空字段解析为 None（对应 NULL），加引号的字段可以包含逗号、换行和 "" 转义的引号
+pub fn parse_csv(content: &str) -> Result<Vec<Vec<Option<String>>>, DbError> {
+    let mut records = Vec::new();
+    let mut record: Vec<Option<String>> = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;   // 当前字段是否以引号开头
+    let mut in_quotes = false; // 当前是否处于引号内
+    let mut chars = content.chars().peekable();
+
+    // 结束当前字段并加入记录
+    fn finish_field(record: &mut Vec<Option<String>>, field: &mut String, quoted: &mut bool) {
+        if field.is_empty() && !*quoted {
+            record.push(None);
+        } else {
+            record.push(Some(std::mem::take(field)));
+        }
+        *quoted = false;
+    }
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            if c == '"' {
+                if chars.peek() == Some(&'"') {
+                    // "" 表示字段内的一个引号
+                    chars.next();
+                    field.push('"');
+                } else {
+                    in_quotes = false;
+                }
+            } else {
+                field.push(c);
+            }
+            continue;
+        }
+
+        match c {
+            '"' if field.is_empty() && !quoted => {
+                quoted = true;
+                in_quotes = true;
+            }
+            ',' => finish_field(&mut record, &mut field, &mut quoted),
+            '\r' => {}
+            '\n' => {
+                finish_field(&mut record, &mut field, &mut quoted);
+                // 跳过空行
+                if !(record.len() == 1 && record[0].is_none()) {
+                    records.push(std::mem::take(&mut record));
+                } else {
+                    record.clear();
+                }
+            }
+            _ => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(DbError::SqlError("CSV中存在未闭合的引号".to_string()));
+    }
+
+    // 处理最后一行没有换行符的情况
+    if !field.is_empty() || quoted || !record.is_empty() {
+        finish_field(&mut record, &mut field, &mut quoted);
+        records.push(record);
+    }
+
+    Ok(records)
+}
@@ -1,15 +1,35 @@
-use crate::core::error::DbError;
-use crate::core::sql::{SqlExecutor, SqlParser};
-use crate::core::storage::{file::FileStorage, memory::MemoryStorage, Storage};
+use crate::core::clock::{system_clock, Clock};
+use crate::core::error::{DbError, Locale};
+use crate::core::sql::{query_rows, split_statements_with_delimiter, RowIter, SampleRng, OutputFormat, SqlExecutor, SqlParser, SqlStatement, TableStyle};
+use crate::core::storage::{file::FileStorage, memory::MemoryStorage, IntegrityReport, Storage};
 use crate::core::transaction::Transaction;
 use crate::core::types::{Column, DataType, Table};
+use std::cell::Cell;
 use std::path::PathBuf;
+use std::time::{Duration, Instant};
 
 pub enum StorageType {
     File(PathBuf),
     Memory,
 }
 
+// 没有路径时使用内存存储，避免在默认目录下意外写入文件
+impl From<Option<PathBuf>> for StorageType {
+    fn from(path: Option<PathBuf>) -> Self {
+        match path {
+            Some(path) => StorageType::File(path),
+            None => StorageType::Memory,
+        }
+    }
+}
+
+// 数据库当前使用的存储类型
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StorageKind {
+    File,   // 文件存储，表保存在存储目录中
+    Memory, // 内存存储，没有存储目录
+}
+
 // 错误显示模式
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ErrorDisplayMode {
@@ -17,10 +37,61 @@ pub enum ErrorDisplayMode {
     Detailed, // 详细错误信息
 }
 
+// 单个表的统计信息
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableStats {
+    pub name: String,
+    pub row_count: usize,
+    pub column_count: usize,
+    pub size_bytes: usize, // 数据占用内存的估算值，见 Table::approximate_size_bytes
+}
+
+// 数据库某一时刻所有表的副本，用于 snapshot / restore
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    tables: Vec<Table>,
+}
+
+impl Snapshot {
+    // 快照中的表名，按字母排序
+    pub fn table_names(&self) -> Vec<String> {
+        self.tables.iter().map(|t| t.name.clone()).collect()
+    }
+}
+
+// 查询返回的列名和数据行
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryResult {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<DataType>>,
+}
+
+// 执行一条语句的结果
+#[derive(Debug, Clone, PartialEq)]
+pub enum StatementResult {
+    RowsAffected(usize),    // INSERT、UPDATE、DELETE 和 COPY 影响的行数
+    ResultSet(QueryResult), // SELECT、EXPLAIN、DESCRIBE、SHOW INDEXES 和带 RETURNING 的语句的结果
+    Created,                // CREATE TABLE、CREATE INDEX
+    Dropped,                // DROP TABLE、DROP INDEX
+    Altered,                // ALTER TABLE
+}
+
 pub struct Database {
     storage: Box<dyn Storage>,
     sql_parser: SqlParser,
     error_mode: ErrorDisplayMode, // 错误显示模式
+    locale: Option<Locale>, // 错误信息的语言，None 时保持原有的中英文混合信息
+    verbose: bool, // 是否输出每条语句的执行耗时
+    last_elapsed: Option<Duration>, // 最近一条语句的执行耗时（仅在verbose模式下记录）
+    clock: Clock, // 当前时间来源，测试时可替换为固定时钟
+    max_column_width: Option<usize>, // 表格输出中单元格的最大显示宽度
+    table_style: TableStyle, // 表格输出的样式
+    output_format: OutputFormat, // 查询结果的输出格式
+    row_numbers: bool, // 表格输出是否在最前面加上行号列
+    autosave: bool, // 交互式Shell退出前是否保存数据库
+    delimiter: String, // 脚本和交互式Shell中的语句分隔符
+    cross_table_lookup: bool, // 列不在查询的表中时，是否到其他表中查找同名列
+    sample_rng: Cell<SampleRng>, // 为 SAMPLE 子句生成随机种子，测试时可设置固定种子
 }
 
 impl Database {
@@ -34,9 +105,198 @@ impl Database {
             storage,
             sql_parser: SqlParser::new(),
             error_mode: ErrorDisplayMode::Brief, // 默认使用简略模式
+            locale: None,
+            verbose: false,
+            last_elapsed: None,
+            clock: system_clock(),
+            max_column_width: None,
+            table_style: TableStyle::default(),
+            output_format: OutputFormat::default(),
+            row_numbers: false,
+            autosave: true, // 默认退出前保存
+            delimiter: ";".to_string(),
+            cross_table_lookup: false,
+            sample_rng: Cell::new(SampleRng::from_time()),
         }
     }
     
+    // 设置是否输出每条语句的执行耗时
+    pub fn set_verbose(&mut self, verbose: bool) {
+        self.verbose = verbose;
+        if !verbose {
+            self.last_elapsed = None;
+        }
+    }
+    
+    // 设置获取当前时间的时钟，用于 DEFAULT CURRENT_TIMESTAMP、CURRENT_DATE 等
+    pub fn set_clock(&mut self, clock: Clock) {
+        self.sql_parser.set_clock(clock.clone());
+        self.clock = clock;
+    }
+    
+    // 设置表格输出中单元格的最大显示宽度，超出部分以 '…' 截断；None 表示不截断
+    pub fn set_max_column_width(&mut self, max_column_width: Option<usize>) {
+        self.max_column_width = max_column_width;
+    }
+    
+    // 获取表格输出中单元格的最大显示宽度
+    pub fn max_column_width(&self) -> Option<usize> {
+        self.max_column_width
+    }
+    
+    // 设置表格输出的样式，默认只在表头下画分隔线
+    pub fn set_table_style(&mut self, table_style: TableStyle) {
+        self.table_style = table_style;
+    }
+    
+    // 获取表格输出的样式
+    pub fn table_style(&self) -> TableStyle {
+        self.table_style
+    }
+    
+    // 设置查询结果的输出格式，默认输出为表格
+    pub fn set_output_format(&mut self, output_format: OutputFormat) {
+        self.output_format = output_format;
+    }
+    
+    // 获取查询结果的输出格式
+    pub fn output_format(&self) -> OutputFormat {
+        self.output_format
+    }
+    
+    // 设置表格输出是否在最前面加上从1开始的行号列，JSON 输出不受影响
+    pub fn set_row_numbers(&mut self, row_numbers: bool) {
+        self.row_numbers = row_numbers;
+    }
+    
+    // 获取表格输出是否显示行号
+    pub fn row_numbers(&self) -> bool {
+        self.row_numbers
+    }
+    
+    // 设置交互式Shell退出前是否先保存数据库，默认开启
+    pub fn set_autosave(&mut self, autosave: bool) {
+        self.autosave = autosave;
+    }
+    
+    // 获取交互式Shell退出前是否保存数据库
+    pub fn autosave(&self) -> bool {
+        self.autosave
+    }
+    
+    // 设置语句分隔符，分隔符不能为空，也不能包含空白或引号
+    pub fn set_delimiter(&mut self, delimiter: &str) -> Result<(), DbError> {
+        if delimiter.is_empty() || delimiter.chars().any(|c| c.is_whitespace() || c == '\'' || c == '"') {
+            return Err(DbError::SqlError(format!("无效的语句分隔符: '{}'", delimiter)));
+        }
+        self.delimiter = delimiter.to_string();
+        Ok(())
+    }
+    
+    // 获取当前的语句分隔符
+    pub fn delimiter(&self) -> &str {
+        &self.delimiter
+    }
+    
+    // 设置列不在查询的表中时是否到其他表中查找同名列，默认关闭，此时报错并提示包含该列的表
+    pub fn set_cross_table_lookup(&mut self, cross_table_lookup: bool) {
+        self.cross_table_lookup = cross_table_lookup;
+    }
+    
+    // 设置 SAMPLE 子句的随机种子，之后执行的抽样查询结果可以复现
+    pub fn set_sample_seed(&mut self, seed: u64) {
+        self.sample_rng.set(SampleRng::new(seed));
+    }
+    
+    // 为语句中的每个 SAMPLE 子句分配新的随机种子
+    fn seed_samples(&self, statement: &mut SqlStatement) {
+        match statement {
+            SqlStatement::Sample { statement, seed, .. } => {
+                let mut rng = self.sample_rng.get();
+                *seed = rng.next_u64();
+                self.sample_rng.set(rng);
+                self.seed_samples(statement);
+            }
+            SqlStatement::Union { left, right, .. } => {
+                self.seed_samples(left);
+                self.seed_samples(right);
+            }
+            SqlStatement::Explain { statement } | SqlStatement::CopyTo { query: statement, .. } => {
+                self.seed_samples(statement);
+            }
+            _ => {}
+        }
+    }
+    
+    // 按名称设置运行时选项，供交互式Shell和脚本中的 SET 命令使用
+    // 名称和取值不区分大小写
+    pub fn set_option(&mut self, name: &str, value: &str) -> Result<(), DbError> {
+        let invalid = || DbError::SqlError(format!("选项 {} 的取值无效: {}", name, value));
+        let value_lower = value.to_lowercase();
+        match name.to_lowercase().as_str() {
+            "error_mode" => match value_lower.as_str() {
+                "brief" => self.set_error_mode(ErrorDisplayMode::Brief),
+                "detailed" => self.set_error_mode(ErrorDisplayMode::Detailed),
+                _ => return Err(invalid()),
+            },
+            "locale" => match value_lower.as_str() {
+                "zh" | "chinese" => self.set_locale(Some(Locale::Chinese)),
+                "en" | "english" => self.set_locale(Some(Locale::English)),
+                "default" | "off" => self.set_locale(None),
+                _ => return Err(invalid()),
+            },
+            "verbose" => match value_lower.as_str() {
+                "on" | "true" | "1" => self.set_verbose(true),
+                "off" | "false" | "0" => self.set_verbose(false),
+                _ => return Err(invalid()),
+            },
+            "max_width" => match value_lower.as_str() {
+                "off" | "none" => self.set_max_column_width(None),
+                _ => self.set_max_column_width(Some(value.parse::<usize>().map_err(|_| invalid())?)),
+            },
+            "table_style" => match value_lower.as_str() {
+                "minimal" => self.set_table_style(TableStyle::Minimal),
+                "box" => self.set_table_style(TableStyle::Box { row_separators: false }),
+                "box_rows" => self.set_table_style(TableStyle::Box { row_separators: true }),
+                _ => return Err(invalid()),
+            },
+            "output" => match value_lower.as_str() {
+                "table" => self.set_output_format(OutputFormat::Table),
+                "json" => self.set_output_format(OutputFormat::Json { pretty: false }),
+                "json_pretty" => self.set_output_format(OutputFormat::Json { pretty: true }),
+                _ => return Err(invalid()),
+            },
+            "rownum" => match value_lower.as_str() {
+                "on" | "true" | "1" => self.set_row_numbers(true),
+                "off" | "false" | "0" => self.set_row_numbers(false),
+                _ => return Err(invalid()),
+            },
+            "autosave" => match value_lower.as_str() {
+                "on" | "true" | "1" => self.set_autosave(true),
+                "off" | "false" | "0" => self.set_autosave(false),
+                _ => return Err(invalid()),
+            },
+            "delimiter" => self.set_delimiter(value)?,
+            "cross_table_lookup" => match value_lower.as_str() {
+                "on" | "true" | "1" => self.set_cross_table_lookup(true),
+                "off" | "false" | "0" => self.set_cross_table_lookup(false),
+                _ => return Err(invalid()),
+            },
+            _ => return Err(DbError::SqlError(format!("未知的选项: {}", name))),
+        }
+        Ok(())
+    }
+    
+    // 获取当前是否为verbose模式
+    pub fn is_verbose(&self) -> bool {
+        self.verbose
+    }
+    
+    // 获取最近一条语句的执行耗时，非verbose模式下为None
+    pub fn last_elapsed(&self) -> Option<Duration> {
+        self.last_elapsed
+    }
+    
     // 设置错误显示模式
     pub fn set_error_mode(&mut self, mode: ErrorDisplayMode) {
         self.error_mode = mode;
@@ -56,25 +316,131 @@ impl Database {
         self.error_mode
     }
     
-    // 根据当前模式格式化错误信息
+    // 设置错误信息的语言，None 表示使用默认的错误信息
+    pub fn set_locale(&mut self, locale: Option<Locale>) {
+        self.locale = locale;
+    }
+    
+    // 获取错误信息的语言
+    pub fn locale(&self) -> Option<Locale> {
+        self.locale
+    }
+    
+    // 根据当前模式和语言格式化错误信息
     pub fn format_error(&self, error: &DbError) -> String {
-        match self.error_mode {
-            ErrorDisplayMode::Brief => error.brief_message(),
-            ErrorDisplayMode::Detailed => error.detailed_message(),
+        match (self.error_mode, self.locale) {
+            (ErrorDisplayMode::Brief, None) => error.brief_message(),
+            (ErrorDisplayMode::Detailed, None) => error.detailed_message(),
+            (ErrorDisplayMode::Brief, Some(locale)) => error.brief_message_in(locale),
+            (ErrorDisplayMode::Detailed, Some(locale)) => error.detailed_message_in(locale),
         }
     }
 
     // SQL操作
     pub fn execute_sql(&mut self, sql: &str) -> Result<(), DbError> {
-        let statement = self.sql_parser.parse(sql)?;
-        let mut executor = SqlExecutor::new(&mut *self.storage);
-        executor.execute(statement)
+        self.execute_sql_with_output(sql).map(|_| ())
+    }
+    
+    // 以迭代器方式执行查询，逐行产出结果而不物化整个结果集
+    pub fn query_iter(&self, sql: &str) -> Result<RowIter<'_>, DbError> {
+        let mut parser = SqlParser::new();
+        parser.set_clock(self.clock.clone());
+        let mut statement = parser.parse(sql)?;
+        self.seed_samples(&mut statement);
+        query_rows(&*self.storage, statement).map(|(_, rows)| rows)
     }
     
     // 执行SQL并返回是否有输出
     pub fn execute_sql_with_output(&mut self, sql: &str) -> Result<bool, DbError> {
-        let statement = self.sql_parser.parse(sql)?;
+        let start = Instant::now();
+        let result = self.execute_statement(sql);
+        self.record_elapsed(start);
+        result
+    }
+    
+    // 执行一条SQL并返回类型化的结果，查询结果作为数据返回而不输出
+    pub fn execute(&mut self, sql: &str) -> Result<StatementResult, DbError> {
+        let start = Instant::now();
+        let result = self.execute_with_result(sql);
+        self.record_elapsed(start);
+        result
+    }
+    
+    // 依次执行多条以当前分隔符（默认为分号）分隔的SQL，每条语句对应一个结果；stop_on_error 为 true 时在第一个错误后停止
+    pub fn execute_many(&mut self, sql: &str, stop_on_error: bool) -> Vec<Result<StatementResult, DbError>> {
+        let mut results = Vec::new();
+        for statement in split_statements_with_delimiter(sql, &self.delimiter) {
+            let result = self.execute(&statement);
+            let failed = result.is_err();
+            results.push(result);
+            if failed && stop_on_error {
+                break;
+            }
+        }
+        results
+    }
+    
+    // verbose模式下记录并输出耗时，失败的语句同样计时
+    fn record_elapsed(&mut self, start: Instant) {
+        if self.verbose {
+            let elapsed = start.elapsed();
+            self.last_elapsed = Some(elapsed);
+            println!("执行耗时: {:.3} ms", elapsed.as_secs_f64() * 1000.0);
+        }
+    }
+    
+    fn execute_with_result(&mut self, sql: &str) -> Result<StatementResult, DbError> {
+        let mut statement = self.sql_parser.parse(sql)?;
+        self.seed_samples(&mut statement);
+        let mut executor = SqlExecutor::new(&mut *self.storage);
+        executor.set_clock(self.clock.clone());
+        executor.set_max_column_width(self.max_column_width);
+        executor.set_table_style(self.table_style);
+        executor.set_output_format(self.output_format);
+        executor.set_row_numbers(self.row_numbers);
+        executor.set_cross_table_lookup(self.cross_table_lookup);
+        
+        let (columns, rows) = match statement {
+            SqlStatement::Explain { statement } => executor.explain_result(&statement)?,
+            SqlStatement::Describe { table } => executor.describe_result(&table)?,
+            SqlStatement::ShowIndexes { table } => executor.show_indexes_result(&table)?,
+            SqlStatement::Returning { statement, columns } => executor.execute_returning(*statement, &columns)?,
+            SqlStatement::Select { .. }
+            | SqlStatement::SelectExpression { .. }
+            | SqlStatement::SelectWithExpressions { .. }
+            | SqlStatement::Union { .. }
+            | SqlStatement::Sample { .. } => {
+                let (columns, rows) = query_rows(&*self.storage, statement)?;
+                (columns, rows.collect::<Result<Vec<_>, DbError>>()?)
+            }
+            statement => {
+                let schema_change = match statement {
+                    SqlStatement::CreateTable { .. } | SqlStatement::CreateTableLike { .. } | SqlStatement::CreateIndex { .. } => {
+                        Some(StatementResult::Created)
+                    }
+                    SqlStatement::DropTable { .. } | SqlStatement::DropTables { .. } | SqlStatement::DropIndex { .. } => {
+                        Some(StatementResult::Dropped)
+                    }
+                    SqlStatement::AlterTable { .. } => Some(StatementResult::Altered),
+                    _ => None,
+                };
+                executor.execute(statement)?;
+                return Ok(schema_change.unwrap_or(StatementResult::RowsAffected(executor.rows_affected())));
+            }
+        };
+        Ok(StatementResult::ResultSet(QueryResult { columns, rows }))
+    }
+    
+    fn execute_statement(&mut self, sql: &str) -> Result<bool, DbError> {
+        let mut statement = self.sql_parser.parse(sql)?;
+        self.seed_samples(&mut statement);
         let mut executor = SqlExecutor::new(&mut *self.storage);
+        executor.set_clock(self.clock.clone());
+        executor.set_max_column_width(self.max_column_width);
+        executor.set_table_style(self.table_style);
+        executor.set_output_format(self.output_format);
+        executor.set_row_numbers(self.row_numbers);
+        executor.set_cross_table_lookup(self.cross_table_lookup);
         executor.execute(statement)?;
         Ok(executor.has_output())
     }
@@ -93,10 +459,95 @@ impl Database {
         self.storage.get_table(table_name)
     }
 
+    // 只返回表结构（列定义），不包含行数据
+    pub fn get_schema(&self, table_name: &str) -> Result<Vec<Column>, DbError> {
+        self.storage.get_table(table_name)?
+            .map(|table| table.columns.clone())
+            .ok_or_else(|| DbError::TableError(format!("表 {} 不存在", table_name)))
+    }
+
+    // 按行号读取一行，行号与 update_row / delete_row 使用的一致；超出范围时返回 None
+    pub fn get_row(&self, table_name: &str, index: usize) -> Result<Option<Vec<DataType>>, DbError> {
+        self.storage.get_table(table_name)?
+            .map(|table| table.get_row(index))
+            .ok_or_else(|| DbError::TableError(format!("表 {} 不存在", table_name)))
+    }
+
+    // 按行号读取行版本号，表未开启 ROW_VERSION 或行号超出范围时返回 None
+    pub fn get_row_version(&self, table_name: &str, index: usize) -> Result<Option<u64>, DbError> {
+        self.storage.get_table(table_name)?
+            .map(|table| table.row_version(index))
+            .ok_or_else(|| DbError::TableError(format!("表 {} 不存在", table_name)))
+    }
+
+    pub fn find_by_primary_key(&self, table_name: &str, key: &DataType) -> Result<Option<&[DataType]>, DbError> {
+        self.storage.find_by_primary_key(table_name, key)
+    }
+
     pub fn list_tables(&self) -> Result<Vec<String>, DbError> {
         self.storage.list_tables()
     }
 
+    // 重建建表语句，指定表名时只输出该表，否则按表名顺序输出所有表
+    pub fn schema_sql(&self, table_name: Option<&str>) -> Result<String, DbError> {
+        let names = match table_name {
+            Some(name) => vec![name.to_string()],
+            None => {
+                let mut names = self.storage.list_tables()?;
+                names.sort();
+                names
+            }
+        };
+        
+        let mut sql = String::new();
+        for name in names {
+            let table = self.storage.get_table(&name)?
+                .ok_or_else(|| DbError::TableError(format!("表 {} 不存在", name)))?;
+            sql.push_str(&table.create_table_sql());
+            sql.push_str(";\n");
+            for index in &table.indexes {
+                sql.push_str(&format!("CREATE INDEX {} ON {} ({});\n", index.name, table.name, index.column));
+            }
+        }
+        Ok(sql)
+    }
+
+    // 导出建表语句和重建全部数据的 INSERT 语句，按表名顺序输出
+    // 读取存储出错时返回错误，不会得到不完整的导出结果
+    pub fn dump_sql(&self) -> Result<String, DbError> {
+        let mut names = self.storage.list_tables()?;
+        names.sort();
+        
+        let mut sql = String::new();
+        for name in names {
+            let table = self.storage.get_table(&name)?
+                .ok_or_else(|| DbError::TableError(format!("表 {} 不存在", name)))?;
+            sql.push_str(&table.create_table_sql());
+            sql.push_str(";\n");
+            for index in &table.indexes {
+                sql.push_str(&format!("CREATE INDEX {} ON {} ({});\n", index.name, table.name, index.column));
+            }
+            for row in &table.rows {
+                let values: Vec<String> = row.iter().map(|value| value.to_sql_literal()).collect();
+                sql.push_str(&format!("INSERT INTO {} VALUES ({});\n", table.name, values.join(", ")));
+            }
+        }
+        Ok(sql)
+    }
+
+    // 所有表的行数、列数和估算的数据大小，按表名排序
+    pub fn table_stats(&self) -> Result<Vec<TableStats>, DbError> {
+        Ok(self.storage.get_tables()?
+            .into_iter()
+            .map(|table| TableStats {
+                name: table.name.clone(),
+                row_count: table.rows.len(),
+                column_count: table.columns.len(),
+                size_bytes: table.approximate_size_bytes(),
+            })
+            .collect())
+    }
+
     // 数据操作
     pub fn insert_row(&mut self, table_name: &str, row: Vec<DataType>) -> Result<(), DbError> {
         self.storage.insert_row(table_name, row)
@@ -115,20 +566,112 @@ impl Database {
         self.storage.save()
     }
 
+    // 是否有尚未写盘的修改（如批量模式中的修改），内存存储总是返回 false
+    pub fn has_unsaved_changes(&self) -> bool {
+        self.storage.has_unsaved_changes()
+    }
+
+    // 只将一个表写入磁盘，不重写其他表；批量模式中其他表的修改仍在结束时写入
+    // 内存存储不做任何操作，表不存在时返回错误
+    pub fn save_table(&self, name: &str) -> Result<(), DbError> {
+        if self.storage.get_table(name)?.is_none() {
+            return Err(DbError::TableError(format!("表 {} 不存在", name)));
+        }
+        self.storage.save_table(name)
+    }
+
     pub fn load(&mut self) -> Result<(), DbError> {
         self.storage.load()
     }
 
+    // 丢弃内存中尚未写盘的修改（如批量模式中的修改），从磁盘重新读取全部表
+    // 内存存储没有磁盘状态，重新加载不做任何操作
+    // 返回重新加载后表的数量
+    pub fn reload(&mut self) -> Result<usize, DbError> {
+        self.storage.load()?;
+        Ok(self.storage.list_tables()?.len())
+    }
+
+    // 检查磁盘上的每个表文件能否读取，返回按表名排序的 (表名, 检查结果)，损坏的文件附带错误信息
+    // 内存存储没有表文件，返回空列表
+    pub fn check_integrity(&self) -> Result<IntegrityReport, DbError> {
+        self.storage.check_integrity()
+    }
+
+    // 将损坏的表文件移到表目录下的 .broken 目录，然后重新加载其余的表
+    // 返回被移走的表名，未保存的修改会被丢弃
+    pub fn repair(&mut self) -> Result<Vec<String>, DbError> {
+        self.storage.repair()
+    }
+
+    // 复制当前所有表（包括表结构、数据和索引定义），之后可以用 restore 恢复
+    pub fn snapshot(&self) -> Result<Snapshot, DbError> {
+        let mut tables: Vec<Table> = self.storage.get_tables()?.into_iter().cloned().collect();
+        for table in &mut tables {
+            table.invalidate_indexes();
+        }
+        Ok(Snapshot { tables })
+    }
+    
+    // 恢复到快照时的状态：快照之后创建的表被删除，修改和删除的表恢复原样
+    // 文件存储会同时改写表文件
+    pub fn restore(&mut self, snapshot: Snapshot) -> Result<(), DbError> {
+        self.storage.begin_batch();
+        let result = self.replace_tables(snapshot.tables);
+        let end_result = self.storage.end_batch();
+        result.and(end_result)
+    }
+    
+    fn replace_tables(&mut self, tables: Vec<Table>) -> Result<(), DbError> {
+        for name in self.storage.list_tables()? {
+            self.storage.drop_table(&name)?;
+        }
+        for table in tables {
+            self.storage.create_table(table)?;
+        }
+        Ok(())
+    }
+    
+    // 批量模式：期间的修改只作用于内存，end_batch 时一次性写盘
+    pub fn begin_batch(&mut self) {
+        self.storage.begin_batch();
+    }
+
+    pub fn end_batch(&mut self) -> Result<(), DbError> {
+        self.storage.end_batch()
+    }
+
     // 事务
-    pub fn begin_transaction(&mut self) -> Transaction {
+    pub fn begin_transaction(&mut self) -> Transaction<'_> {
         Transaction::new(&mut *self.storage)
     }
 
-    // 获取数据库存储路径
-    pub fn get_storage_path(&self) -> PathBuf {
-        match self.storage.as_ref() {
-            storage if storage.is_file_storage() => storage.get_path(),
-            _ => PathBuf::from("db"), // 如果是内存存储，返回默认路径
+    // 将数据库目录（表文件、预写日志和隔离的损坏文件）移动到新路径，之后的读写都使用新路径
+    // 目标已存在时必须是空目录；不能重命名时（如跨文件系统）复制后删除原目录
+    // 内存存储没有目录，返回错误
+    pub fn relocate(&mut self, new_path: impl Into<PathBuf>) -> Result<(), DbError> {
+        self.storage.relocate(new_path.into())
+    }
+
+    // 获取数据库使用的存储类型
+    pub fn storage_kind(&self) -> StorageKind {
+        if self.storage.is_file_storage() {
+            StorageKind::File
+        } else {
+            StorageKind::Memory
+        }
+    }
+    
+    // 获取数据库存储路径，内存存储没有路径，返回 None
+    pub fn get_storage_path(&self) -> Option<PathBuf> {
+        match self.storage_kind() {
+            StorageKind::File => Some(self.storage.get_path()),
+            StorageKind::Memory => None,
         }
     }
+
+    // 获取与当前数据库相同的存储配置，内存存储得到 StorageType::Memory
+    pub fn storage_type(&self) -> StorageType {
+        StorageType::from(self.get_storage_path())
+    }
 } 
\ No newline at end of file
@@ -2,6 +2,124 @@ use crate::core::types::TypeError;
 use std::io;
 use thiserror::Error;
 
+// 错误信息使用的语言
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Locale {
+    Chinese, // 中文
+    English, // 英文
+}
+
+// 错误中常见的具体内容及其英文翻译，{} 为占位符，两种语言中占位符的顺序相同
+const MESSAGES: &[(&str, &str)] = &[
+    ("表 {} 不存在", "table {} does not exist"),
+    ("表 {} 已存在", "table {} already exists"),
+    ("表 {} 不在查询中", "table {} is not in the query"),
+    ("表必须至少有一个列", "a table must have at least one column"),
+    ("表中只能有一个主键", "a table can only have one primary key"),
+    ("列 {} 在表 {} 中不存在", "column {} does not exist in table {}"),
+    ("列 {} 不存在", "column {} does not exist"),
+    ("列 {} 未找到", "column {} not found"),
+    ("索引 {} 已存在", "index {} already exists"),
+    ("索引 {} 不存在", "index {} does not exist"),
+    ("行索引 {} 超出范围", "row index {} is out of range"),
+    ("期望表名", "expected a table name"),
+    ("期望列名", "expected a column name"),
+    ("期望索引名", "expected an index name"),
+    ("期望列名或表达式", "expected a column name or expression"),
+    ("期望表达式", "expected an expression"),
+    ("期望操作符", "expected an operator"),
+    ("期望值", "expected a value"),
+    ("期望数据类型", "expected a data type"),
+    ("期望逗号或右括号", "expected a comma or closing parenthesis"),
+    ("期望 {}, 实际 {}", "expected {}, found {}"),
+    ("期望 {}, 但已到结尾", "expected {}, but reached the end"),
+    ("意外的语句开始: {}", "unexpected start of statement: {}"),
+    ("语句结束后存在多余的内容: {}", "unexpected content after the statement: {}"),
+    ("未知数据类型: {}", "unknown data type: {}"),
+    ("未知字符: {}", "unknown character: {}"),
+    ("未知的选项: {}", "unknown option: {}"),
+    ("选项 {} 的取值无效: {}", "invalid value for option {}: {}"),
+    ("空语句", "empty statement"),
+    ("类型不匹配", "type mismatch"),
+    ("整数溢出", "integer overflow"),
+    ("除数不能为零: {}", "division by zero: {}"),
+    ("除数不能为零", "division by zero"),
+    ("字符串不能参与算术运算", "strings cannot be used in arithmetic"),
+    ("事务未激活", "Transaction is not active"),
+];
+
+// 按模板匹配信息，返回各占位符对应的内容
+fn match_template(template: &str, message: &str) -> Option<Vec<String>> {
+    let mut pieces = template.split("{}");
+    let mut rest = message.strip_prefix(pieces.next()?)?;
+    let pieces: Vec<&str> = pieces.collect();
+    if pieces.is_empty() {
+        return rest.is_empty().then(Vec::new);
+    }
+    let mut args = Vec::new();
+    for (i, piece) in pieces.iter().enumerate() {
+        // 最后一段必须在结尾，中间的段取第一次出现的位置
+        let end = if i + 1 == pieces.len() {
+            rest.strip_suffix(piece)?.len()
+        } else {
+            rest.find(piece)?
+        };
+        args.push(rest[..end].to_string());
+        rest = &rest[end + piece.len()..];
+    }
+    Some(args)
+}
+
+// 将具体的错误内容翻译为指定语言，不认识的内容原样返回
+fn translate(message: &str, locale: Locale) -> String {
+    for (chinese, english) in MESSAGES {
+        let (from, to) = match locale {
+            Locale::Chinese => (english, chinese),
+            Locale::English => (chinese, english),
+        };
+        if let Some(args) = match_template(from, message) {
+            let mut pieces = to.split("{}");
+            let mut result = pieces.next().unwrap_or("").to_string();
+            for (arg, piece) in args.iter().zip(pieces) {
+                result.push_str(arg);
+                result.push_str(piece);
+            }
+            return result;
+        }
+    }
+    message.to_string()
+}
+
+// 类型错误在指定语言下的信息
+fn type_error_message(err: &TypeError, locale: Locale) -> String {
+    match (err, locale) {
+        (TypeError::TypeMismatch { expected, actual }, Locale::Chinese) => format!("类型不匹配: 期望 {:?}, 实际 {:?}", expected, actual),
+        (TypeError::TypeMismatch { expected, actual }, Locale::English) => format!("Type mismatch: expected {:?}, found {:?}", expected, actual),
+        (TypeError::ColumnTypeMismatch { column, expected, actual }, Locale::Chinese) => {
+            format!("列 {} 的类型不匹配: 期望 {}, 实际值 {}", column, expected, actual.to_sql_literal())
+        }
+        (TypeError::ColumnTypeMismatch { column, expected, actual }, Locale::English) => {
+            format!("Type mismatch for column {}: expected {}, found {}", column, expected, actual.to_sql_literal())
+        }
+        (TypeError::StringLengthExceeded { max_length, actual_length }, Locale::Chinese) => {
+            format!("字符串长度超出限制: 最大长度 {}, 实际长度 {}", max_length, actual_length)
+        }
+        (TypeError::StringLengthExceeded { max_length, actual_length }, Locale::English) => {
+            format!("String too long: maximum length {}, actual length {}", max_length, actual_length)
+        }
+        (TypeError::NullValue(column), Locale::Chinese) => format!("字段 '{}' 没有默认值", column),
+        (TypeError::NullValue(column), Locale::English) => format!("Field '{}' doesn't have a default value", column),
+        (TypeError::NullValueAtRow { column, row }, Locale::Chinese) => format!("第 {} 行的字段 '{}' 没有默认值", row, column),
+        (TypeError::NullValueAtRow { column, row }, Locale::English) => {
+            format!("Field '{}' doesn't have a default value at row {}", column, row)
+        }
+        (TypeError::PrimaryKeyViolation(key), Locale::Chinese) => format!("主键 '{}' 重复", key),
+        (TypeError::PrimaryKeyViolation(key), Locale::English) => format!("Duplicate entry '{}' for key 'PRIMARY'", key),
+        (TypeError::TableFull(table), Locale::Chinese) => format!("表 '{}' 已满", table),
+        (TypeError::TableFull(table), Locale::English) => format!("The table '{}' is full", table),
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum DbError {
     #[error("IO错误: {0}")]
@@ -48,4 +166,42 @@ impl DbError {
             DbError::TransactionError(_) => "Error: Transaction error".to_string(),
         }
     }
-} 
\ No newline at end of file
+    
+    // 获取指定语言的详细错误信息
+    pub fn detailed_message_in(&self, locale: Locale) -> String {
+        match (self, locale) {
+            (DbError::IoError(err), Locale::Chinese) => format!("IO错误: {}", err),
+            (DbError::IoError(err), Locale::English) => format!("IO error: {}", err),
+            (DbError::Serialization(msg), Locale::Chinese) => format!("序列化错误: {}", msg),
+            (DbError::Serialization(msg), Locale::English) => format!("Serialization error: {}", msg),
+            (DbError::TableError(msg), Locale::Chinese) => format!("表错误: {}", translate(msg, locale)),
+            (DbError::TableError(msg), Locale::English) => format!("Table error: {}", translate(msg, locale)),
+            (DbError::TypeError(err), _) => type_error_message(err, locale),
+            (DbError::SqlError(msg), Locale::Chinese) => format!("SQL语法错误: {}", translate(msg, locale)),
+            (DbError::SqlError(msg), Locale::English) => format!("Syntax error: {}", translate(msg, locale)),
+            (DbError::TransactionError(msg), Locale::Chinese) => format!("事务错误: {}", translate(msg, locale)),
+            (DbError::TransactionError(msg), Locale::English) => format!("Transaction error: {}", translate(msg, locale)),
+        }
+    }
+    
+    // 获取指定语言的简略错误信息
+    pub fn brief_message_in(&self, locale: Locale) -> String {
+        let kind = match (self, locale) {
+            (DbError::IoError(_), Locale::Chinese) => "IO错误".to_string(),
+            (DbError::IoError(_), Locale::English) => "IO error".to_string(),
+            (DbError::Serialization(_), Locale::Chinese) => "序列化错误".to_string(),
+            (DbError::Serialization(_), Locale::English) => "Serialization error".to_string(),
+            (DbError::TableError(_), Locale::Chinese) => "表错误".to_string(),
+            (DbError::TableError(_), Locale::English) => "Table error".to_string(),
+            (DbError::TypeError(err), _) => type_error_message(err, locale),
+            (DbError::SqlError(_), Locale::Chinese) => "SQL语法错误".to_string(),
+            (DbError::SqlError(_), Locale::English) => "Syntax error".to_string(),
+            (DbError::TransactionError(_), Locale::Chinese) => "事务错误".to_string(),
+            (DbError::TransactionError(_), Locale::English) => "Transaction error".to_string(),
+        };
+        match locale {
+            Locale::Chinese => format!("错误: {}", kind),
+            Locale::English => format!("Error: {}", kind),
+        }
+    }
+}
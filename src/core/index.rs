@@ -0,0 +1,137 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::ops::Bound;
+use serde::{Serialize, Deserialize};
+use crate::core::types::DataType;
+
+/// 单列索引的定义，随表结构一起持久化
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Index {
+    pub name: String,
+    pub column: String,
+}
+
+/// 索引键：整数和浮点数统一按数值比较，与 WHERE 中两者可以互相比较保持一致
+#[derive(Debug, Clone)]
+pub enum IndexKey {
+    Number(f64),
+    Text(String),
+}
+
+impl IndexKey {
+    /// NULL 不进入索引，与 NULL 的大小比较总是不匹配
+    pub fn from_value(value: &DataType) -> Option<IndexKey> {
+        match value {
+            // -0.0 与 0.0 相等，统一为 0.0
+            DataType::Int(n) => Some(IndexKey::Number(*n as f64 + 0.0)),
+            DataType::Float(f) => Some(IndexKey::Number(*f + 0.0)),
+            DataType::Varchar(s) => Some(IndexKey::Text(s.clone())),
+            DataType::Null => None,
+        }
+    }
+}
+
+impl PartialEq for IndexKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for IndexKey {}
+
+impl PartialOrd for IndexKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for IndexKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (IndexKey::Number(a), IndexKey::Number(b)) => a.total_cmp(b),
+            (IndexKey::Text(a), IndexKey::Text(b)) => a.cmp(b),
+            (IndexKey::Number(_), IndexKey::Text(_)) => Ordering::Less,
+            (IndexKey::Text(_), IndexKey::Number(_)) => Ordering::Greater,
+        }
+    }
+}
+
+/// 列值到行号的有序映射
+#[derive(Debug, Clone, Default)]
+pub struct BTreeIndex {
+    entries: BTreeMap<IndexKey, Vec<usize>>,
+}
+
+impl BTreeIndex {
+    /// 扫描一列数据建立索引
+    pub fn build(rows: &[Vec<DataType>], column_index: usize) -> Self {
+        let mut entries: BTreeMap<IndexKey, Vec<usize>> = BTreeMap::new();
+        for (row_index, row) in rows.iter().enumerate() {
+            if let Some(key) = IndexKey::from_value(&row[column_index]) {
+                entries.entry(key).or_default().push(row_index);
+            }
+        }
+        BTreeIndex { entries }
+    }
+
+    /// 将一行的值加入索引，同一个键下的行号保持升序
+    pub fn insert(&mut self, value: &DataType, row_index: usize) {
+        if let Some(key) = IndexKey::from_value(value) {
+            let rows = self.entries.entry(key).or_default();
+            let position = rows.partition_point(|&row| row < row_index);
+            rows.insert(position, row_index);
+        }
+    }
+
+    /// 从索引中去掉一行的值，其他行的行号不变
+    pub fn remove(&mut self, value: &DataType, row_index: usize) {
+        if let Some(key) = IndexKey::from_value(value) {
+            if let Some(rows) = self.entries.get_mut(&key) {
+                rows.retain(|&row| row != row_index);
+                if rows.is_empty() {
+                    self.entries.remove(&key);
+                }
+            }
+        }
+    }
+
+    /// 删除给定的多行（行号按升序排列），之后的行号依次前移
+    pub fn remove_rows(&mut self, removed: &[usize]) {
+        for rows in self.entries.values_mut() {
+            rows.retain(|row| removed.binary_search(row).is_err());
+            for row in rows.iter_mut() {
+                *row -= removed.partition_point(|&r| r < *row);
+            }
+        }
+        self.entries.retain(|_, rows| !rows.is_empty());
+    }
+
+    /// 只保留行号小于 len 的行
+    pub fn truncate(&mut self, len: usize) {
+        for rows in self.entries.values_mut() {
+            rows.retain(|&row| row < len);
+        }
+        self.entries.retain(|_, rows| !rows.is_empty());
+    }
+
+    /// 返回键等于给定值的第一行的行号
+    pub fn get(&self, key: &IndexKey) -> Option<usize> {
+        self.entries.get(key)?.first().copied()
+    }
+
+    /// 返回键落在区间内的行号，按行号升序排列，与全表扫描的顺序一致
+    pub fn range(&self, lower: Bound<&IndexKey>, upper: Bound<&IndexKey>) -> Vec<usize> {
+        // 下界大于上界时 BTreeMap::range 会 panic，这种区间直接为空
+        if let (Bound::Included(l) | Bound::Excluded(l), Bound::Included(u) | Bound::Excluded(u)) = (lower, upper) {
+            if l > u || (l == u && !(matches!(lower, Bound::Included(_)) && matches!(upper, Bound::Included(_)))) {
+                return Vec::new();
+            }
+        }
+
+        let mut rows: Vec<usize> = self.entries.range((lower, upper))
+            .flat_map(|(_, rows)| rows.iter().copied())
+            .collect();
+        rows.sort_unstable();
+        rows
+    }
+}
@@ -1,5 +1,8 @@
+pub mod clock;
+pub mod csv;
 pub mod db;
 pub mod error;
+pub mod index;
 pub mod types;
 pub mod sql;
 pub mod storage;
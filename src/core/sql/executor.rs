@@ -1,11 +1,30 @@
 use crate::core::error::DbError;
-use crate::core::types::{DataType, Table, TypeError};
+use crate::core::clock::{format_timestamp, system_clock, Clock};
+use crate::core::index::{Index, IndexKey};
+use crate::core::types::{Column, ColumnDefault, ColumnType, DataType, Table, TypeError, VERSION_COLUMN};
 use crate::core::storage::Storage;
-use super::{SqlStatement, WhereClause, Operator, TableFormatter};
+use super::{sample_rows, OutputFormat, SampleSize, SqlStatement, WhereClause, Operator, TableFormatter, TableStyle};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::ops::Bound;
+
+/// 惰性产出查询结果行的迭代器
+pub type RowIter<'a> = Box<dyn Iterator<Item = Result<Vec<DataType>, DbError>> + 'a>;
+
+// 惰性产出表中满足条件的行的引用
+type RowRefIter<'a> = Box<dyn Iterator<Item = Result<&'a [DataType], DbError>> + 'a>;
 
 pub struct SqlExecutor<'a> {
     storage: &'a mut dyn Storage,
     has_output: bool,
+    rows_affected: usize, // 最近一条语句插入、修改、删除或导出的行数
+    clock: Clock, // 用于 DEFAULT CURRENT_TIMESTAMP 等需要当前时间的场景
+    max_column_width: Option<usize>, // 表格输出中单元格的最大显示宽度，None 表示不截断
+    table_style: TableStyle, // 表格输出的样式
+    output_format: OutputFormat, // 查询结果的输出格式
+    row_numbers: bool, // 表格输出是否在最前面加上行号列
+    cross_table_lookup: bool, // 列不在查询的表中时，是否到其他表中查找同名列
+    returned_rows: Option<Vec<Vec<DataType>>>, // 执行 RETURNING 时收集语句写入的行
 }
 
 impl<'a> SqlExecutor<'a> {
@@ -13,21 +32,131 @@ impl<'a> SqlExecutor<'a> {
         SqlExecutor { 
             storage,
             has_output: false,
+            rows_affected: 0,
+            clock: system_clock(),
+            max_column_width: None,
+            table_style: TableStyle::default(),
+            output_format: OutputFormat::default(),
+            row_numbers: false,
+            cross_table_lookup: false,
+            returned_rows: None,
         }
     }
 
+    pub fn set_clock(&mut self, clock: Clock) {
+        self.clock = clock;
+    }
+
+    pub fn set_max_column_width(&mut self, max_column_width: Option<usize>) {
+        self.max_column_width = max_column_width;
+    }
+
+    pub fn set_table_style(&mut self, table_style: TableStyle) {
+        self.table_style = table_style;
+    }
+
+    pub fn set_output_format(&mut self, output_format: OutputFormat) {
+        self.output_format = output_format;
+    }
+
+    pub fn set_row_numbers(&mut self, row_numbers: bool) {
+        self.row_numbers = row_numbers;
+    }
+
+    pub fn set_cross_table_lookup(&mut self, cross_table_lookup: bool) {
+        self.cross_table_lookup = cross_table_lookup;
+    }
+
     pub fn has_output(&self) -> bool {
         self.has_output
     }
 
+    pub fn rows_affected(&self) -> usize {
+        self.rows_affected
+    }
+
     pub fn execute(&mut self, statement: SqlStatement) -> Result<(), DbError> {
         self.has_output = false;
+        self.rows_affected = 0;
         
+        // 每条语句的修改在结束时统一写盘，直接修改表数据的语句也能被持久化
+        self.storage.begin_batch();
+        let result = self.resolve_and_execute(statement);
+        let flushed = self.storage.end_batch();
+        result.and(flushed)
+    }
+
+    // 执行带 RETURNING 的语句并返回结果集而不输出，写盘方式与 execute 相同
+    pub fn execute_returning(&mut self, mut statement: SqlStatement, columns: &[String]) -> Result<(Vec<String>, Vec<Vec<DataType>>), DbError> {
+        self.has_output = false;
+        self.rows_affected = 0;
+        
+        self.storage.begin_batch();
+        let result = resolve_subqueries(&*self.storage, &mut statement)
+            .and_then(|_| self.returning_result(statement, columns));
+        let flushed = self.storage.end_batch();
+        let result = result?;
+        flushed?;
+        Ok(result)
+    }
+
+    fn resolve_and_execute(&mut self, mut statement: SqlStatement) -> Result<(), DbError> {
+        resolve_subqueries(&*self.storage, &mut statement)?;
+        self.execute_statement(statement)
+    }
+
+    fn execute_statement(&mut self, statement: SqlStatement) -> Result<(), DbError> {
         match statement {
-            SqlStatement::CreateTable { name, columns } => {
-                let table = Table::new(name, columns);
+            SqlStatement::CreateTable { name, columns, options } => {
+                let mut table = Table::new(name, columns);
+                table.max_rows = options.max_rows;
+                table.comment = options.comment;
+                if options.row_version {
+                    table.enable_row_versions();
+                }
+                self.storage.create_table(table)
+            }
+            SqlStatement::CreateTableLike { name, source } => {
+                // 复制列定义和表选项，不复制数据；索引名在数据库内唯一，因此也不复制索引
+                let source_table = self.storage.get_table(&source)?
+                    .ok_or_else(|| DbError::TableError(format!("表 {} 不存在", source)))?;
+                let mut table = Table::new(name, source_table.columns.clone());
+                table.max_rows = source_table.max_rows;
+                table.comment = source_table.comment.clone();
+                if source_table.has_row_versions() {
+                    table.enable_row_versions();
+                }
                 self.storage.create_table(table)
             }
+            SqlStatement::CreateIndex { name, table, column } => {
+                // 索引名在整个数据库内唯一，DROP INDEX 时只需给出索引名
+                for table_data in self.storage.get_tables()? {
+                    if table_data.indexes.iter().any(|index| index.name == name) {
+                        return Err(DbError::TableError(format!("索引 {} 已存在", name)));
+                    }
+                }
+                
+                let table_data = self.storage.get_table_mut(&table)?
+                    .ok_or_else(|| DbError::TableError(format!("表 {} 不存在", table)))?;
+                if !table_data.columns.iter().any(|c| c.name == column) {
+                    return Err(DbError::SqlError(format!("列 {} 在表 {} 中不存在", column, table)));
+                }
+                if let Some(index) = table_data.index_on(&column) {
+                    return Err(DbError::TableError(format!("列 {} 上已有索引 {}", column, index.name)));
+                }
+                table_data.indexes.push(Index { name, column });
+                Ok(())
+            }
+            SqlStatement::DropIndex { name } => {
+                let table = self.storage.get_tables()?.into_iter()
+                    .find(|table| table.indexes.iter().any(|index| index.name == name))
+                    .map(|table| table.name.clone())
+                    .ok_or_else(|| DbError::TableError(format!("索引 {} 不存在", name)))?;
+                if let Some(table_data) = self.storage.get_table_mut(&table)? {
+                    table_data.drop_index(&name);
+                }
+                Ok(())
+            }
             SqlStatement::DropTable { name } => {
                 self.storage.drop_table(&name)
             }
@@ -41,7 +170,7 @@ impl<'a> SqlExecutor<'a> {
                 }
                 Ok(())
             }
-            SqlStatement::Insert { table, values } => {
+            SqlStatement::Insert { table, mut values, ignore_conflicts } => {
                 // 获取表结构以检查主键
                 let table_struct = self.storage.get_table(&table)?
                     .ok_or_else(|| DbError::TableError(format!("表 {} 不存在", table)))?;
@@ -49,65 +178,63 @@ impl<'a> SqlExecutor<'a> {
                 // 克隆表结构相关信息，避免借用冲突
                 let table_columns = table_struct.columns.clone();
                 
-                // 检查值的数量是否与表列数匹配
-                if values.len() != table_columns.len() {
+                // 检查值的数量是否超过表列数
+                if values.len() > table_columns.len() {
                     return Err(DbError::SqlError(format!(
                         "值的数量({})与表列数({})不匹配", 
                         values.len(), table_columns.len()
                     )));
                 }
                 
-                // 检查主键和非空约束
+                // 省略的末尾列使用默认值或 NULL 填充，必填列会在下面的非空检查中报错
+                let omitted = table_columns[values.len()..].iter().map(|col| self.default_value(col)).collect::<Vec<_>>();
+                values.extend(omitted);
+                
+                // 检查非空约束，主键列在建表时已标记为 NOT NULL
                 for (i, col) in table_columns.iter().enumerate() {
-                    // 检查主键
-                    if col.primary_key && matches!(values[i], DataType::Null) {
-                        return Err(DbError::TypeError(TypeError::NullValue(col.name.clone())));
-                    }
-                    
-                    // 检查非空约束
                     if !col.nullable && matches!(values[i], DataType::Null) {
                         return Err(DbError::TypeError(TypeError::NullValue(col.name.clone())));
                     }
                 }
                 
-                self.storage.insert_row(&table, values)
+                self.insert_row(&table, values, ignore_conflicts)
             }
-            SqlStatement::InsertMultiple { table, rows } => {
+            SqlStatement::InsertMultiple { table, rows, ignore_conflicts } => {
                 // 获取表结构以检查主键
                 let table_struct = self.storage.get_table(&table)?
                     .ok_or_else(|| DbError::TableError(format!("表 {} 不存在", table)))?;
                 
                 // 克隆表结构相关信息，避免借用冲突
                 let table_columns = table_struct.columns.clone();
+                let multi_row = rows.len() > 1;
                 
-                // 依次插入每一行数据
-                for values in rows {
-                    // 检查值的数量是否与表列数匹配
-                    if values.len() != table_columns.len() {
+                // 先补全并检查每一行，再一次性插入
+                let mut full_rows = Vec::with_capacity(rows.len());
+                for (row_index, mut values) in rows.into_iter().enumerate() {
+                    // 检查值的数量是否超过表列数
+                    if values.len() > table_columns.len() {
                         return Err(DbError::SqlError(format!(
                             "值的数量({})与表列数({})不匹配", 
                             values.len(), table_columns.len()
                         )));
                     }
                     
-                    // 检查主键和非空约束
+                    // 省略的末尾列使用默认值或 NULL 填充
+                    let omitted = table_columns[values.len()..].iter().map(|col| self.default_value(col)).collect::<Vec<_>>();
+                    values.extend(omitted);
+                    
+                    // 检查非空约束，主键列在建表时已标记为 NOT NULL
                     for (i, col) in table_columns.iter().enumerate() {
-                        // 检查主键
-                        if col.primary_key && matches!(values[i], DataType::Null) {
-                            return Err(DbError::TypeError(TypeError::NullValue(col.name.clone())));
-                        }
-                        
-                        // 检查非空约束
                         if !col.nullable && matches!(values[i], DataType::Null) {
-                            return Err(DbError::TypeError(TypeError::NullValue(col.name.clone())));
+                            return Err(null_value_error(&col.name, row_index + 1, multi_row));
                         }
                     }
                     
-                    self.storage.insert_row(&table, values)?;
+                    full_rows.push(values);
                 }
-                Ok(())
+                self.insert_rows(&table, full_rows, ignore_conflicts)
             }
-            SqlStatement::InsertWithColumns { table, columns, rows } => {
+            SqlStatement::InsertWithColumns { table, columns, rows, ignore_conflicts } => {
                 // 获取表结构
                 let table_struct = self.storage.get_table(&table)?
                     .ok_or_else(|| DbError::TableError(format!("表 {} 不存在", table)))?;
@@ -122,8 +249,10 @@ impl<'a> SqlExecutor<'a> {
                     }
                 }
                 
-                // 处理每一行数据
-                for row_values in rows {
+                // 处理每一行数据，全部检查通过后一次性插入
+                let multi_row = rows.len() > 1;
+                let mut full_rows = Vec::with_capacity(rows.len());
+                for (row_index, row_values) in rows.into_iter().enumerate() {
                     // 检查值的数量是否与列名数量匹配
                     if row_values.len() != columns.len() {
                         return Err(DbError::SqlError(format!(
@@ -132,131 +261,121 @@ impl<'a> SqlExecutor<'a> {
                         )));
                     }
                     
-                    // 创建完整的行数据（按表的列顺序）
-                    let mut full_row = vec![DataType::Null; table_columns.len()];
+                    // 创建完整的行数据（按表的列顺序），未指定的列使用默认值
+                    let mut full_row: Vec<DataType> = table_columns.iter().map(|col| self.default_value(col)).collect();
                     
-                    // 填充指定的列
+                    // 填充指定的列，并按目标列检查值的类型
                     for (i, col) in columns.iter().enumerate() {
                         if let Some(col_index) = table_columns.iter().position(|c| &c.name == col) {
+                            let expected = &table_columns[col_index].data_type;
+                            if !row_values[i].matches_column_type(expected) {
+                                return Err(DbError::TypeError(TypeError::ColumnTypeMismatch {
+                                    column: col.clone(),
+                                    expected: expected.clone(),
+                                    actual: row_values[i].clone(),
+                                }));
+                            }
                             full_row[col_index] = row_values[i].clone();
                         }
                     }
                     
-                    // 检查约束
+                    // 检查非空约束，主键列在建表时已标记为 NOT NULL
                     for (i, col) in table_columns.iter().enumerate() {
-                        // 检查非空约束
                         if !col.nullable && matches!(full_row[i], DataType::Null) {
-                            return Err(DbError::TypeError(TypeError::NullValue(col.name.clone())));
-                        }
-                        
-                        // 检查主键
-                        if col.primary_key && matches!(full_row[i], DataType::Null) {
-                            return Err(DbError::TypeError(TypeError::NullValue(col.name.clone())));
+                            return Err(null_value_error(&col.name, row_index + 1, multi_row));
                         }
                     }
                     
-                    // 插入行
-                    self.storage.insert_row(&table, full_row)?;
+                    full_rows.push(full_row);
                 }
                 
-                Ok(())
+                self.insert_rows(&table, full_rows, ignore_conflicts)
             }
-            SqlStatement::SelectExpression { expressions, original_sql } => {
+            SqlStatement::SelectExpression { mut expressions, aliases, original_sql } => {
                 // 计算每个表达式的值
                 let mut results = Vec::new();
-                let mut headers = Vec::new();
                 
-                // 从原始 SQL 中提取表达式部分
-                let select_expressions = original_sql.trim_start()
-                    .strip_prefix("select")
-                    .or_else(|| original_sql.trim_start().strip_prefix("SELECT"))
-                    .unwrap_or(&original_sql)
-                    .trim()
-                    .trim_end_matches(';')
-                    .trim();
+                reject_aggregates_without_from(&expressions)?;
+                // 使用原始 SQL 中的表达式作为表头
+                let mut headers = select_list_headers(&original_sql, &expressions);
+                resolve_column_aliases(&mut expressions, &aliases, &mut headers, &[])?;
                 
-                // 按逗号分割表达式
-                let expr_parts: Vec<&str> = select_expressions.split(',').collect();
-                
-                for (i, expr) in expressions.iter().enumerate() {
+                for expr in &expressions {
                     // 计算表达式
                     let result = self.evaluate_expression(expr, None, "")?;
-                    
-                    // 使用原始 SQL 中的表达式作为表头
-                    let header = if i < expr_parts.len() {
-                        expr_parts[i].trim().to_string()
-                    } else {
-                        // 如果无法找到对应的原始表达式，使用生成的字符串
-                        self.expression_to_string(expr)
-                    };
-                    
-                    results.push(result.to_string());
-                    headers.push(header);
+                    results.push(result);
                 }
                 
                 // 将结果格式化为表格
-                let formatted_table = TableFormatter::format_table(&headers, &[results]);
+                let formatted_table = self.format_table(&headers, &[results]);
                 print!("{}", formatted_table);
                 
                 self.has_output = true;
                 
                 Ok(())
             }
-            SqlStatement::SelectWithExpressions { expressions, table, where_clause, order_by, original_sql } => {
+            SqlStatement::SelectWithExpressions { mut expressions, aliases, table, where_clause, group_by, having, order_by, original_sql } => {
                 let table_data = self.storage.get_table(&table)?
                     .ok_or_else(|| DbError::TableError(format!("表 {} 不存在", table)))?;
                 
-                // 从原始 SQL 中提取 SELECT 部分
-                let select_part = original_sql.trim_start()
-                    .strip_prefix("select")
-                    .or_else(|| original_sql.trim_start().strip_prefix("SELECT"))
-                    .unwrap_or(&original_sql)
-                    .trim();
-                
-                // 提取 FROM 之前的部分
-                let expr_part = if let Some(from_pos) = select_part.to_lowercase().find("from") {
-                    select_part[..from_pos].trim()
-                } else {
-                    select_part.trim()
-                };
-                
-                // 按逗号分割表达式
-                let expr_parts: Vec<&str> = expr_part.split(',').collect();
-                
                 // 准备表头 - 从原始 SQL 表达式生成
-                let mut headers = Vec::new();
-                for (i, expr) in expressions.iter().enumerate() {
-                    if i < expr_parts.len() {
-                        headers.push(expr_parts[i].trim().to_string());
+                let mut headers = select_list_headers(&original_sql, &expressions);
+                resolve_column_aliases(&mut expressions, &aliases, &mut headers, &table_data.columns)?;
+                let (expressions, headers) = expand_wildcards(expressions, headers, table_data)?;
+                
+                // 聚合或分组查询：先分组，每组汇总为一行结果
+                if needs_grouping(&expressions, &group_by, having.as_ref()) {
+                    let grouped = if is_count_star_only(&expressions, &group_by, having.as_ref()) {
+                        vec![vec![count_matching_rows(&*self.storage, table_data, where_clause)?]]
                     } else {
-                        // 如果无法找到对应的原始表达式，使用生成的字符串
-                        headers.push(self.expression_to_string(expr));
+                        let matched_rows = rows_matching(&*self.storage, table_data, where_clause)?
+                            .collect::<Result<Vec<_>, DbError>>()?;
+                        group_rows(&expressions, &group_by, having.as_ref(), matched_rows, &table_data.columns)?
+                    };
+                    let mut selected_rows = grouped;
+                    
+                    if let Some(order_by) = order_by {
+                        sort_rows(&mut selected_rows, &headers, &order_by)?;
+                    }
+                    
+                    if !selected_rows.is_empty() {
+                        print!("{}", self.format_table(&headers, &selected_rows));
+                        self.has_output = true;
                     }
+                    return Ok(());
                 }
                 
                 // 收集满足条件的行数据
-                let mut selected_rows: Vec<Vec<String>> = Vec::new();
-                for row in &table_data.rows {
-                    if where_clause.is_none() || evaluate_where_clause(row, where_clause.as_ref().unwrap(), &table_data.columns)? {
-                        // 计算每个表达式的值
-                        let mut row_values = Vec::new();
-                        for expr in &expressions {
-                            // 计算表达式的值
-                            let result = self.evaluate_expression(expr, Some(row), &table)?;
-                            row_values.push(result.to_string());
-                        }
-                        selected_rows.push(row_values);
+                let hidden_sort_column = unprojected_sort_column(&headers, order_by.as_ref(), table_data);
+                let mut selected_rows: Vec<Vec<DataType>> = Vec::new();
+                for row in rows_matching(&*self.storage, table_data, where_clause)? {
+                    let row = row?;
+                    // 计算每个表达式的值
+                    let mut row_values = Vec::new();
+                    for expr in &expressions {
+                        // 计算表达式的值
+                        let result = self.evaluate_expression(expr, Some(row), &table)?;
+                        row_values.push(result);
+                    }
+                    // 排序列不在结果中时附加在行末
+                    if let Some(index) = hidden_sort_column {
+                        row_values.push(row[index].clone());
                     }
+                    selected_rows.push(row_values);
                 }
                 
                 // 如果有ORDER BY子句，对结果进行排序
                 if let Some(order_by) = order_by {
-                    self.apply_order_by(&mut selected_rows, &headers, &order_by)?;
+                    if hidden_sort_column.is_some() {
+                        self.apply_hidden_order_by(&mut selected_rows, &headers, &order_by)?;
+                    } else {
+                        sort_rows(&mut selected_rows, &headers, &order_by)?;
+                    }
                 }
                 
                 // 使用TableFormatter格式化并输出结果
                 if !selected_rows.is_empty() {
-                    let formatted_table = TableFormatter::format_table(&headers, &selected_rows);
+                    let formatted_table = self.format_table(&headers, &selected_rows);
                     print!("{}", formatted_table);
                     self.has_output = true;
                 } else {
@@ -268,23 +387,32 @@ impl<'a> SqlExecutor<'a> {
                 let table_data = self.storage.get_table_mut(&table)?
                     .ok_or_else(|| DbError::TableError(format!("表 {} 不存在", table)))?;
                 
-                let columns = table_data.columns.clone();
-                let mut rows_to_update = Vec::new();
+                if table_data.has_row_versions() && set.iter().any(|(column, _)| column == VERSION_COLUMN) {
+                    return Err(DbError::SqlError(format!("{} 是表 {} 的行版本号，不能直接修改", VERSION_COLUMN, table)));
+                }
                 
                 // 找出需要更新的行
-                for (i, row) in table_data.rows.iter().enumerate() {
-                    if where_clause.is_none() || evaluate_where_clause(row, where_clause.as_ref().unwrap(), &columns)? {
-                        rows_to_update.push(i);
-                    }
-                }
+                let rows_to_update = matching_row_indices(table_data, where_clause.as_ref())?;
 
-                // 更新行
+                // 先计算所有更新后的行，任何一行违反约束时不更新任何行
+                let mut updates = Vec::with_capacity(rows_to_update.len());
                 for row_index in rows_to_update {
+                    let mut row = table_data.rows[row_index].clone();
                     for (column_name, value) in &set {
                         if let Some(col_index) = table_data.columns.iter().position(|col| &col.name == column_name) {
-                            table_data.rows[row_index][col_index] = value.clone();
+                            row[col_index] = value.clone();
                         }
                     }
+                    updates.push((row_index, row));
+                }
+                table_data.validate_updates(&updates)?;
+
+                self.rows_affected = updates.len();
+                if let Some(returned_rows) = self.returned_rows.as_mut() {
+                    returned_rows.extend(updates.iter().map(|(_, row)| row.clone()));
+                }
+                for (row_index, row) in updates {
+                    table_data.replace_row(row_index, row);
                 }
                 Ok(())
             }
@@ -292,21 +420,13 @@ impl<'a> SqlExecutor<'a> {
                 let table_data = self.storage.get_table_mut(&table)?
                     .ok_or_else(|| DbError::TableError(format!("表 {} 不存在", table)))?;
 
-                if where_clause.is_none() {
-                    table_data.rows.clear();
-                    return Ok(());
-                }
-
-                let columns = table_data.columns.clone();
-                let where_clause = where_clause.unwrap();
-                let mut i = 0;
-                while i < table_data.rows.len() {
-                    if evaluate_where_clause(&table_data.rows[i], &where_clause, &columns)? {
-                        table_data.rows.remove(i);
-                    } else {
-                        i += 1;
-                    }
+                // 先找出所有要删除的行，求值出错时不删除任何行
+                let rows_to_delete = matching_row_indices(table_data, where_clause.as_ref())?;
+                self.rows_affected = rows_to_delete.len();
+                if let Some(returned_rows) = self.returned_rows.as_mut() {
+                    returned_rows.extend(rows_to_delete.iter().map(|&i| table_data.rows[i].clone()));
                 }
+                table_data.remove_rows(&rows_to_delete);
                 Ok(())
             }
             SqlStatement::Select { columns, table, where_clause, order_by } => {
@@ -323,34 +443,42 @@ impl<'a> SqlExecutor<'a> {
                 };
 
                 // 收集满足条件的行数据
-                let mut selected_rows: Vec<Vec<String>> = Vec::new();
-                for row in &table_data.rows {
-                    if where_clause.is_none() || evaluate_where_clause(row, where_clause.as_ref().unwrap(), &table_data.columns)? {
-                        let values: Vec<String> = if is_select_all {
-                            // 如果是 SELECT *，获取所有列的值
-                            row.iter().map(|val| val.to_string()).collect()
-                        } else {
-                            // 否则只获取指定列的值
-                            display_columns.iter().map(|col| {
-                                if let Some(index) = table_data.columns.iter().position(|c| &c.name == col) {
-                                    row[index].to_string()
-                                } else {
-                                    "NULL".to_string()
-                                }
-                            }).collect()
-                        };
-                        selected_rows.push(values);
+                let hidden_sort_column = unprojected_sort_column(&display_columns, order_by.as_ref(), table_data);
+                let mut selected_rows: Vec<Vec<DataType>> = Vec::new();
+                for row in rows_matching(&*self.storage, table_data, where_clause)? {
+                    let row = row?;
+                    let mut values: Vec<DataType> = if is_select_all {
+                        // 如果是 SELECT *，获取所有列的值
+                        row.to_vec()
+                    } else {
+                        // 否则只获取指定列的值
+                        display_columns.iter().map(|col| {
+                            if let Some(index) = table_data.columns.iter().position(|c| &c.name == col) {
+                                row[index].clone()
+                            } else {
+                                DataType::Null
+                            }
+                        }).collect()
+                    };
+                    // 排序列不在结果中时附加在行末
+                    if let Some(index) = hidden_sort_column {
+                        values.push(row[index].clone());
                     }
+                    selected_rows.push(values);
                 }
 
                 // 如果有ORDER BY子句，对结果进行排序
                 if let Some(order_by) = order_by {
-                    self.apply_order_by(&mut selected_rows, &display_columns, &order_by)?;
+                    if hidden_sort_column.is_some() {
+                        self.apply_hidden_order_by(&mut selected_rows, &display_columns, &order_by)?;
+                    } else {
+                        sort_rows(&mut selected_rows, &display_columns, &order_by)?;
+                    }
                 }
 
                 // 使用TableFormatter格式化并输出结果
                 if !selected_rows.is_empty() {
-                    let formatted_table = TableFormatter::format_table(&display_columns, &selected_rows);
+                    let formatted_table = self.format_table(&display_columns, &selected_rows);
                     print!("{}", formatted_table);
                     self.has_output = true;
                 } else {
@@ -358,6 +486,437 @@ impl<'a> SqlExecutor<'a> {
                 }
                 Ok(())
             }
+            SqlStatement::Describe { table } => {
+                let (headers, rows) = self.describe_result(&table)?;
+                let table_data = self.storage.get_table(&table)?
+                    .ok_or_else(|| DbError::TableError(format!("表 {} 不存在", table)))?;
+                
+                print!("{}", self.format_table(&headers, &rows));
+                if let Some(comment) = &table_data.comment {
+                    println!("Comment: {}", comment);
+                }
+                self.has_output = true;
+                Ok(())
+            }
+            SqlStatement::ShowIndexes { table } => {
+                let (headers, rows) = self.show_indexes_result(&table)?;
+                print!("{}", self.format_table(&headers, &rows));
+                self.has_output = true;
+                Ok(())
+            }
+            SqlStatement::AlterTable { table, action } => {
+                let table_data = self.storage.get_table_mut(&table)?
+                    .ok_or_else(|| DbError::TableError(format!("表 {} 不存在", table)))?;
+                
+                match action {
+                    super::AlterAction::RenameColumn { old_name, new_name } => {
+                        if table_data.columns.iter().any(|c| c.name == new_name)
+                            || (table_data.has_row_versions() && new_name == VERSION_COLUMN) {
+                            return Err(DbError::SqlError(format!("列 {} 在表 {} 中已存在", new_name, table)));
+                        }
+                        
+                        // 只修改列名，列的位置不变，行数据无需改动
+                        let column = table_data.columns.iter_mut()
+                            .find(|c| c.name == old_name)
+                            .ok_or_else(|| DbError::SqlError(format!("列 {} 在表 {} 中不存在", old_name, table)))?;
+                        for index in table_data.indexes.iter_mut().filter(|index| index.column == old_name) {
+                            index.column = new_name.clone();
+                        }
+                        column.name = new_name.clone();
+                        // 已建立的索引按列名缓存，改名后重新建立
+                        table_data.invalidate_index(&old_name);
+                        table_data.invalidate_index(&new_name);
+                    }
+                    super::AlterAction::ModifyColumn { column, data_type } => {
+                        let col_index = table_data.columns.iter()
+                            .position(|c| c.name == column)
+                            .ok_or_else(|| DbError::SqlError(format!("列 {} 在表 {} 中不存在", column, table)))?;
+                        
+                        // 先转换所有行的值，任何一个失败都不修改表，保证原子性
+                        let converted = table_data.rows.iter()
+                            .map(|row| row[col_index].convert_to(&data_type)
+                                .ok_or_else(|| DbError::TypeError(TypeError::TypeMismatch {
+                                    expected: data_type.clone(),
+                                    actual: row[col_index].clone(),
+                                })))
+                            .collect::<Result<Vec<DataType>, DbError>>()?;
+                        
+                        for (row, value) in table_data.rows.iter_mut().zip(converted) {
+                            row[col_index] = value;
+                        }
+                        table_data.columns[col_index].data_type = data_type;
+                        // 直接修改了该列的数据，只需丢弃该列上已建立的索引
+                        table_data.invalidate_index(&column);
+                    }
+                }
+                Ok(())
+            }
+            SqlStatement::CopyFrom { table, path, header } => {
+                let table_columns = self.storage.get_table(&table)?
+                    .ok_or_else(|| DbError::TableError(format!("表 {} 不存在", table)))?
+                    .columns.clone();
+                
+                let content = std::fs::read_to_string(&path).map_err(DbError::IoError)?;
+                let records = crate::core::csv::parse_csv(&content)?;
+                if header {
+                    return self.copy_from_with_header(table, &table_columns, records);
+                }
+                
+                // 先转换全部记录，任何一行格式错误都不插入数据
+                let mut rows = Vec::with_capacity(records.len());
+                for (line, record) in records.into_iter().enumerate() {
+                    if record.len() != table_columns.len() {
+                        return Err(DbError::SqlError(format!(
+                            "第 {} 行的字段数量({})与表列数({})不匹配",
+                            line + 1, record.len(), table_columns.len()
+                        )));
+                    }
+                    
+                    // 按位置将字段转换为对应列的类型
+                    let row = record.into_iter().zip(&table_columns)
+                        .map(|(field, col)| self.csv_field_value(field, col))
+                        .collect::<Result<Vec<DataType>, DbError>>()?;
+                    rows.push(row);
+                }
+                
+                // 任何一行违反约束时整批不插入
+                self.insert_rows(&table, rows, false)
+            }
+            SqlStatement::CopyTo { query, path } => {
+                let (headers, rows) = query_rows(&*self.storage, *query)?;
+                let rows = rows.collect::<Result<Vec<_>, DbError>>()?;
+                
+                std::fs::write(&path, TableFormatter::format_csv(&headers, &rows))
+                    .map_err(DbError::IoError)?;
+                self.rows_affected = rows.len();
+                Ok(())
+            }
+            statement @ (SqlStatement::Union { .. } | SqlStatement::Sample { .. }) => {
+                // UNION 的表头取自左侧查询
+                let (headers, rows) = query_rows(&*self.storage, statement)?;
+                let rows = rows.collect::<Result<Vec<_>, DbError>>()?;
+                
+                if !rows.is_empty() {
+                    print!("{}", self.format_table(&headers, &rows));
+                    self.has_output = true;
+                }
+                Ok(())
+            }
+            SqlStatement::Explain { statement } => {
+                let (headers, rows) = self.explain_result(&statement)?;
+                let formatted_table = self.format_table(&headers, &rows);
+                print!("{}", formatted_table);
+                self.has_output = true;
+                Ok(())
+            }
+            SqlStatement::Returning { statement, columns } => {
+                let (headers, rows) = self.returning_result(*statement, &columns)?;
+                if !rows.is_empty() {
+                    print!("{}", self.format_table(&headers, &rows));
+                    self.has_output = true;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    // 执行带 RETURNING 的语句，返回受影响的行中指定列的值
+    // 插入的行包含默认值等补全的值，更新返回修改后的行，删除返回删除前的行
+    fn returning_result(&mut self, statement: SqlStatement, columns: &[String]) -> Result<(Vec<String>, Vec<Vec<DataType>>), DbError> {
+        let table = match &statement {
+            SqlStatement::Insert { table, .. }
+            | SqlStatement::InsertMultiple { table, .. }
+            | SqlStatement::InsertWithColumns { table, .. }
+            | SqlStatement::Update { table, .. }
+            | SqlStatement::Delete { table, .. } => table.clone(),
+            _ => return Err(DbError::SqlError("RETURNING 只支持 INSERT、UPDATE 和 DELETE 语句".to_string())),
+        };
+        let table_data = self.storage.get_table(&table)?
+            .ok_or_else(|| DbError::TableError(format!("表 {} 不存在", table)))?;
+        
+        // 执行前检查列名，避免写入后才发现 RETURNING 子句有误
+        let (headers, indices): (Vec<String>, Vec<usize>) = if columns.len() == 1 && columns[0] == "*" {
+            table_data.columns.iter().enumerate().map(|(i, c)| (c.name.clone(), i)).unzip()
+        } else {
+            columns.iter().map(|column| {
+                table_data.columns.iter().position(|c| &c.name == column)
+                    .map(|index| (column.clone(), index))
+                    .ok_or_else(|| DbError::SqlError(format!("列 {} 在表 {} 中不存在", column, table)))
+            }).collect::<Result<Vec<_>, DbError>>()?.into_iter().unzip()
+        };
+        
+        self.returned_rows = Some(Vec::new());
+        let result = self.execute_statement(statement);
+        let rows = self.returned_rows.take().unwrap_or_default();
+        result?;
+        
+        let rows = rows.into_iter()
+            .map(|row| indices.iter().map(|&i| row[i].clone()).collect())
+            .collect();
+        Ok((headers, rows))
+    }
+
+    // DESCRIBE 的结果：每列一行，依次为列名、类型、可否为空、键、默认值和注释；开启 ROW_VERSION 时最后一行是行版本号
+    pub fn describe_result(&self, table: &str) -> Result<(Vec<String>, Vec<Vec<DataType>>), DbError> {
+        let table_data = self.storage.get_table(table)?
+            .ok_or_else(|| DbError::TableError(format!("表 {} 不存在", table)))?;
+        
+        let headers: Vec<String> = ["Field", "Type", "Null", "Key", "Default", "Comment"]
+            .iter().map(|h| h.to_string()).collect();
+        let mut rows = table_data.columns.iter().map(|col| vec![
+            DataType::Varchar(col.name.clone()),
+            DataType::Varchar(col.data_type.to_string()),
+            DataType::Varchar(if col.nullable { "YES" } else { "NO" }.to_string()),
+            DataType::Varchar(if col.primary_key { "PRI" } else { "" }.to_string()),
+            match &col.default {
+                Some(ColumnDefault::Value(value)) => value.clone(),
+                Some(ColumnDefault::CurrentTimestamp) => DataType::Varchar("CURRENT_TIMESTAMP".to_string()),
+                None => DataType::Null,
+            },
+            DataType::Varchar(col.comment.clone().unwrap_or_default()),
+        ]).collect::<Vec<_>>();
+        // 行版本号不是真正的列，放在最后并在注释中说明
+        if table_data.has_row_versions() {
+            rows.push(vec![
+                DataType::Varchar(VERSION_COLUMN.to_string()),
+                DataType::Varchar(ColumnType::Int(None).to_string()),
+                DataType::Varchar("NO".to_string()),
+                DataType::Varchar(String::new()),
+                DataType::Null,
+                DataType::Varchar("行版本号，每次更新加 1".to_string()),
+            ]);
+        }
+        Ok((headers, rows))
+    }
+
+    // SHOW INDEXES 的结果：每个索引一行，依次为索引名、列名和索引类型
+    pub fn show_indexes_result(&self, table: &str) -> Result<(Vec<String>, Vec<Vec<DataType>>), DbError> {
+        let table_data = self.storage.get_table(table)?
+            .ok_or_else(|| DbError::TableError(format!("表 {} 不存在", table)))?;
+        
+        let headers: Vec<String> = ["Name", "Column", "Type"]
+            .iter().map(|h| h.to_string()).collect();
+        let rows = table_data.indexes.iter().map(|index| vec![
+            DataType::Varchar(index.name.clone()),
+            DataType::Varchar(index.column.clone()),
+            // 索引都是按列值排序的 B 树
+            DataType::Varchar("BTREE".to_string()),
+        ]).collect();
+        Ok((headers, rows))
+    }
+
+    // EXPLAIN 的结果：为每个步骤编号，按执行顺序排列
+    pub fn explain_result(&self, statement: &SqlStatement) -> Result<(Vec<String>, Vec<Vec<DataType>>), DbError> {
+        let steps = self.explain_plan(statement)?;
+        
+        let headers = vec!["id".to_string(), "operation".to_string(), "detail".to_string()];
+        let rows = steps.into_iter()
+            .enumerate()
+            .map(|(i, (operation, detail))| vec![
+                DataType::Int(i as i32 + 1),
+                DataType::Varchar(operation),
+                DataType::Varchar(detail),
+            ])
+            .collect();
+        Ok((headers, rows))
+    }
+
+    // 插入时省略某列所使用的值，没有默认值时为 NULL
+    fn default_value(&self, column: &Column) -> DataType {
+        match &column.default {
+            Some(ColumnDefault::Value(value)) => value.clone(),
+            Some(ColumnDefault::CurrentTimestamp) => DataType::Varchar(format_timestamp((self.clock)())),
+            None => DataType::Null,
+        }
+    }
+
+    // 将 CSV 字段转换为列类型的值，空字段取列的默认值（没有时为 NULL），加引号的空字段是空字符串
+    fn csv_field_value(&self, field: Option<String>, column: &Column) -> Result<DataType, DbError> {
+        match (field, &column.data_type) {
+            (None, _) => Ok(self.default_value(column)),
+            (Some(text), ColumnType::Varchar(_)) if text.is_empty() => Ok(DataType::Varchar(text)),
+            (Some(text), column_type) => DataType::parse_as(&text, column_type),
+        }
+    }
+
+    // 插入一行；忽略冲突时跳过违反主键约束的行
+    fn insert_row(&mut self, table: &str, values: Vec<DataType>, ignore_conflicts: bool) -> Result<(), DbError> {
+        let returned = self.returned_rows.is_some().then(|| values.clone());
+        match self.storage.insert_row(table, values) {
+            Ok(()) => {
+                self.rows_affected += 1;
+                if let (Some(returned_rows), Some(row)) = (self.returned_rows.as_mut(), returned) {
+                    returned_rows.push(row);
+                }
+                Ok(())
+            }
+            Err(DbError::TypeError(TypeError::PrimaryKeyViolation(_))) if ignore_conflicts => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+
+    // 按表头中的列名导入 CSV 记录，未出现在表头中的列与 INSERT 指定列时一样使用默认值
+    fn copy_from_with_header(&mut self, table: String, table_columns: &[Column], records: Vec<Vec<Option<String>>>) -> Result<(), DbError> {
+        let mut records = records.into_iter();
+        let header = records.next()
+            .ok_or_else(|| DbError::SqlError("CSV文件缺少表头".to_string()))?;
+        
+        let mut columns: Vec<String> = Vec::with_capacity(header.len());
+        let mut header_columns = Vec::with_capacity(header.len());
+        for field in header {
+            let name = field.map(|name| name.trim().to_string()).unwrap_or_default();
+            if name.is_empty() {
+                return Err(DbError::SqlError("CSV表头中存在空的列名".to_string()));
+            }
+            let column = table_columns.iter().find(|c| c.name == name)
+                .ok_or_else(|| DbError::SqlError(format!("列 {} 在表 {} 中不存在", name, table)))?;
+            if columns.contains(&name) {
+                return Err(DbError::SqlError(format!("CSV表头中的列 {} 重复", name)));
+            }
+            header_columns.push(column);
+            columns.push(name);
+        }
+        
+        // 表头是第 1 行，数据从第 2 行开始
+        let mut rows = Vec::new();
+        for (line, record) in records.enumerate() {
+            if record.len() != columns.len() {
+                return Err(DbError::SqlError(format!(
+                    "第 {} 行的字段数量({})与表头列数({})不匹配",
+                    line + 2, record.len(), columns.len()
+                )));
+            }
+            let row = record.into_iter().zip(&header_columns)
+                .map(|(field, column)| self.csv_field_value(field, column))
+                .collect::<Result<Vec<DataType>, DbError>>()?;
+            rows.push(row);
+        }
+        
+        self.execute_statement(SqlStatement::InsertWithColumns { table, columns, rows, ignore_conflicts: false })
+    }
+
+    // 插入多行；忽略冲突时逐行插入以跳过冲突的行，否则整体插入，任何一行失败时不插入任何行
+    fn insert_rows(&mut self, table: &str, rows: Vec<Vec<DataType>>, ignore_conflicts: bool) -> Result<(), DbError> {
+        if ignore_conflicts {
+            for row in rows {
+                self.insert_row(table, row, true)?;
+            }
+            return Ok(());
+        }
+        let count = rows.len();
+        let returned = self.returned_rows.is_some().then(|| rows.clone());
+        self.storage.insert_rows(table, rows)?;
+        self.rows_affected += count;
+        if let (Some(returned_rows), Some(rows)) = (self.returned_rows.as_mut(), returned) {
+            returned_rows.extend(rows);
+        }
+        Ok(())
+    }
+
+    // 生成查询的执行计划，返回 (操作, 说明) 步骤列表
+    fn explain_plan(&self, statement: &SqlStatement) -> Result<Vec<(String, String)>, DbError> {
+        let mut steps = Vec::new();
+        
+        match statement {
+            SqlStatement::SelectExpression { expressions, .. } => {
+                let exprs: Vec<String> = expressions.iter().map(expression_to_string).collect();
+                steps.push(("constant".to_string(), "no table access".to_string()));
+                steps.push(("project".to_string(), exprs.join(", ")));
+            }
+            SqlStatement::Select { columns, table, where_clause, order_by } => {
+                let table_data = self.storage.get_table(table)?
+                    .ok_or_else(|| DbError::TableError(format!("表 {} 不存在", table)))?;
+                
+                steps.push(scan_step(table_data, where_clause.as_ref()));
+                if let Some(where_clause) = where_clause {
+                    steps.push(("filter".to_string(), self.where_clause_to_string(where_clause)));
+                }
+                if let Some(order_by) = order_by {
+                    steps.push(("sort".to_string(), order_by_to_string(order_by)));
+                }
+                steps.push(("project".to_string(), columns.join(", ")));
+            }
+            SqlStatement::SelectWithExpressions { expressions, table, where_clause, group_by, having, order_by, .. } => {
+                let table_data = self.storage.get_table(table)?
+                    .ok_or_else(|| DbError::TableError(format!("表 {} 不存在", table)))?;
+                
+                steps.push(scan_step(table_data, where_clause.as_ref()));
+                if let Some(where_clause) = where_clause {
+                    steps.push(("filter".to_string(), self.where_clause_to_string(where_clause)));
+                }
+                if !group_by.is_empty() {
+                    steps.push(("group".to_string(), group_by.join(", ")));
+                }
+                if let Some(having) = having {
+                    steps.push(("having".to_string(), self.where_clause_to_string(having)));
+                }
+                if let Some(order_by) = order_by {
+                    steps.push(("sort".to_string(), order_by_to_string(order_by)));
+                }
+                let exprs: Vec<String> = expressions.iter().map(expression_to_string).collect();
+                if needs_grouping(expressions, group_by, having.as_ref()) {
+                    steps.push(("aggregate".to_string(), exprs.join(", ")));
+                } else {
+                    steps.push(("project".to_string(), exprs.join(", ")));
+                }
+            }
+            SqlStatement::Union { left, right, all } => {
+                steps.extend(self.explain_plan(left)?);
+                steps.extend(self.explain_plan(right)?);
+                let operation = if *all { "union all" } else { "union" };
+                steps.push((operation.to_string(), "combine results".to_string()));
+            }
+            SqlStatement::Sample { statement, size, .. } => {
+                steps.extend(self.explain_plan(statement)?);
+                let detail = match size {
+                    SampleSize::Rows(count) => format!("{} rows", count),
+                    SampleSize::Percent(percent) => format!("{} percent", percent),
+                };
+                steps.push(("sample".to_string(), detail));
+            }
+            _ => return Err(DbError::SqlError("EXPLAIN 只支持 SELECT 语句".to_string())),
+        }
+        
+        Ok(steps)
+    }
+
+    // 将WHERE子句转换为字符串表示
+    fn where_clause_to_string(&self, where_clause: &WhereClause) -> String {
+        match where_clause {
+            WhereClause::Simple { column, operator, value } => match operator {
+                operator if operator.is_unary() => format!("{} {}", column, operator_to_string(operator)),
+                _ => format!("{} {} {}", column, operator_to_string(operator), value),
+            },
+            WhereClause::Expression { left, operator, .. } if operator.is_unary() => {
+                format!("{} {}", expression_to_string(left), operator_to_string(operator))
+            }
+            WhereClause::Expression { left, operator, right } => format!(
+                "{} {} {}",
+                expression_to_string(left),
+                operator_to_string(operator),
+                expression_to_string(right)
+            ),
+            WhereClause::And { left, right } => format!(
+                "({} AND {})",
+                self.where_clause_to_string(left),
+                self.where_clause_to_string(right)
+            ),
+            WhereClause::Or { left, right } => format!(
+                "({} OR {})",
+                self.where_clause_to_string(left),
+                self.where_clause_to_string(right)
+            ),
+            WhereClause::In { left, list, negated } => {
+                let list_str = match list {
+                    super::InList::Values(values) => values.iter()
+                        .map(expression_to_string)
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    super::InList::Subquery(_) => "subquery".to_string(),
+                };
+                let not_str = if *negated { "NOT " } else { "" };
+                format!("{} {}IN ({})", expression_to_string(left), not_str, list_str)
+            },
         }
     }
 
@@ -396,23 +955,21 @@ impl<'a> SqlExecutor<'a> {
                         }
                     }
                     
-                    // 如果没有找到，尝试在所有表中查找
-                    if table_name.is_empty() || table_name == current_table {
-                        // 从所有表中查找此列名
-                        for (i, _) in self.storage.get_tables()?.iter().enumerate() {
-                            if let Ok(Some(table)) = self.storage.get_table_by_index(i) {
-                                if &table.name != current_table { // 跳过当前表，因为已经查找过
-                                    if let Some(col_index) = table.columns.iter().position(|col| &col.name == name) {
-                                        if col_index < row_data.len() {
-                                            return Ok(row_data[col_index].clone());
-                                        }
+                    // 如果没有找到且开启了跨表查找，尝试在所有表中查找
+                    if self.cross_table_lookup && (table_name.is_empty() || table_name == current_table) {
+                        // 按表名顺序从所有表中查找此列名
+                        for table in self.storage.get_tables()? {
+                            if &table.name != current_table { // 跳过当前表，因为已经查找过
+                                if let Some(col_index) = table.columns.iter().position(|col| &col.name == name) {
+                                    if col_index < row_data.len() {
+                                        return Ok(row_data[col_index].clone());
                                     }
                                 }
                             }
                         }
                     }
                     
-                    Err(DbError::SqlError(format!("列 {} 未找到", name)))
+                    Err(column_not_found(&*self.storage, name, table_name))
                 } else {
                     // 没有行上下文，无法获取列值
                     Err(DbError::SqlError("无法获取列值，因为没有行上下文".to_string()))
@@ -421,223 +978,744 @@ impl<'a> SqlExecutor<'a> {
             super::Expression::Binary { left, operator, right } => {
                 let left_value = self.evaluate_expression(left, row, current_table)?;
                 let right_value = self.evaluate_expression(right, row, current_table)?;
-                
-                match (left_value, right_value) {
-                    (DataType::Int(a), DataType::Int(b)) => {
-                        let result = match operator {
-                            super::ArithmeticOperator::Add => a + b,
-                            super::ArithmeticOperator::Subtract => a - b,
-                            super::ArithmeticOperator::Multiply => a * b,
-                            super::ArithmeticOperator::Divide => {
-                                if b == 0 {
-                                    return Err(DbError::SqlError("除数不能为零".to_string()));
-                                }
-                                a / b
-                            },
-                        };
-                        Ok(DataType::Int(result))
-                    },
-                    (DataType::Float(a), DataType::Float(b)) => {
-                        let result = match operator {
-                            super::ArithmeticOperator::Add => a + b,
-                            super::ArithmeticOperator::Subtract => a - b,
-                            super::ArithmeticOperator::Multiply => a * b,
-                            super::ArithmeticOperator::Divide => {
-                                if b == 0.0 {
-                                    return Err(DbError::SqlError("除数不能为零".to_string()));
-                                }
-                                a / b
-                            },
-                        };
-                        Ok(DataType::Float(result))
-                    },
-                    (DataType::Int(a), DataType::Float(b)) => {
-                        let a_float = a as f64;
-                        let result = match operator {
-                            super::ArithmeticOperator::Add => a_float + b,
-                            super::ArithmeticOperator::Subtract => a_float - b,
-                            super::ArithmeticOperator::Multiply => a_float * b,
-                            super::ArithmeticOperator::Divide => {
-                                if b == 0.0 {
-                                    return Err(DbError::SqlError("除数不能为零".to_string()));
-                                }
-                                a_float / b
-                            },
-                        };
-                        Ok(DataType::Float(result))
-                    },
-                    (DataType::Float(a), DataType::Int(b)) => {
-                        let b_float = b as f64;
-                        let result = match operator {
-                            super::ArithmeticOperator::Add => a + b_float,
-                            super::ArithmeticOperator::Subtract => a - b_float,
-                            super::ArithmeticOperator::Multiply => a * b_float,
-                            super::ArithmeticOperator::Divide => {
-                                if b == 0 {
-                                    return Err(DbError::SqlError("除数不能为零".to_string()));
-                                }
-                                a / b_float
-                            },
-                        };
-                        Ok(DataType::Float(result))
-                    },
-                    // 可以添加更多类型组合的处理
-                    _ => Err(DbError::SqlError("不支持的操作数类型".to_string())),
-                }
+                evaluate_binary(&left_value, operator, &right_value, expr)
             },
-        }
-    }
-    
-    // 将表达式转换为字符串表示
-    fn expression_to_string(&self, expr: &super::Expression) -> String {
-        match expr {
-            super::Expression::Literal(value) => value.to_string(),
-            super::Expression::Column(name) => name.clone(),
-            super::Expression::Binary { left, operator, right } => {
-                let left_str = self.expression_to_string(left);
-                let right_str = self.expression_to_string(right);
-                let op_str = match operator {
-                    super::ArithmeticOperator::Add => "+",
-                    super::ArithmeticOperator::Subtract => "-",
-                    super::ArithmeticOperator::Multiply => "*",
-                    super::ArithmeticOperator::Divide => "/",
+            super::Expression::Aggregate { .. } => {
+                Err(DbError::SqlError("聚合函数不能用于此处".to_string()))
+            },
+            super::Expression::Subquery(subquery) => {
+                let outer_table = self.storage.get_table(current_table)?;
+                let outer = match (outer_table, row) {
+                    (Some(table), Some(row)) => Some((table, row)),
+                    _ => None,
                 };
-                format!("{}{}{}", left_str, op_str, right_str)
+                evaluate_scalar_subquery(&*self.storage, subquery, outer)
             },
         }
     }
-
-    // 应用ORDER BY排序
-    fn apply_order_by(&self, rows: &mut Vec<Vec<String>>, headers: &[String], order_by: &super::OrderBy) -> Result<(), DbError> {
-        // 查找排序列的索引
-        let sort_col_index = headers.iter().position(|col| col == &order_by.column)
-            .ok_or_else(|| DbError::SqlError(format!("ORDER BY列 {} 不存在于结果集中", order_by.column)))?;
-        
-        // 排序
-        rows.sort_by(|a, b| {
-            let a_val = &a[sort_col_index];
-            let b_val = &b[sort_col_index];
-            
-            // 首先尝试将值解析为数字并比较
-            match (a_val.parse::<i64>(), b_val.parse::<i64>()) {
-                (Ok(a_num), Ok(b_num)) => {
-                    // 数值比较
-                    match order_by.direction {
-                        super::SortDirection::Asc => a_num.cmp(&b_num),
-                        super::SortDirection::Desc => b_num.cmp(&a_num),
-                    }
-                },
-                _ => {
-                    // 字符串比较
-                    match order_by.direction {
-                        super::SortDirection::Asc => a_val.cmp(b_val),
-                        super::SortDirection::Desc => b_val.cmp(a_val),
-                    }
-                }
+    
+    // 按设置的输出格式格式化结果，表格输出使用设置的最大列宽和样式
+    fn format_table(&self, headers: &[String], rows: &[Vec<DataType>]) -> String {
+        match self.output_format {
+            OutputFormat::Table if self.row_numbers => {
+                let (headers, rows) = TableFormatter::with_row_numbers(headers, &to_string_rows(rows));
+                TableFormatter::format_table_styled(&headers, &rows, self.max_column_width, self.table_style)
             }
-        });
-        
+            OutputFormat::Table => TableFormatter::format_table_styled(headers, &to_string_rows(rows), self.max_column_width, self.table_style),
+            OutputFormat::Json { pretty } => TableFormatter::format_json(headers, rows, pretty),
+        }
+    }
+    
+    // 按不在结果中的列排序：每行末尾附加了该列的值，排序后去掉
+    fn apply_hidden_order_by(&self, rows: &mut Vec<Vec<DataType>>, headers: &[String], order_by: &super::OrderBy) -> Result<(), DbError> {
+        let mut sort_headers = headers.to_vec();
+        sort_headers.push(order_by.column.clone());
+        sort_rows(rows, &sort_headers, order_by)?;
+        for row in rows.iter_mut() {
+            row.pop();
+        }
         Ok(())
     }
 }
 
-fn evaluate_where_clause(row: &[DataType], where_clause: &WhereClause, columns: &[crate::core::types::Column]) -> Result<bool, DbError> {
-    match where_clause {
-        WhereClause::Simple { column, operator, value } => {
-            let column_index = columns.iter()
-                .position(|col| col.name == *column)
-                .ok_or_else(|| DbError::SqlError(format!("列 {} 不存在", column)))?;
-
-            let row_value = &row[column_index];
-            let compare_value = value;
-
-            let result = match operator {
-                Operator::Eq => row_value == compare_value,
-                Operator::Ne => row_value != compare_value,
-                Operator::Gt => match (row_value, compare_value) {
-                    (DataType::Int(a), DataType::Int(b)) => a > b,
-                    (DataType::Float(a), DataType::Float(b)) => a > b,
-                    (DataType::Float(a), DataType::Int(b)) => a > &(*b as f64),
-                    (DataType::Int(a), DataType::Float(b)) => &(*a as f64) > b,
-                    (DataType::Varchar(a), DataType::Varchar(b)) => a > b,
-                    _ => return Err(DbError::SqlError("类型不匹配".to_string())),
-                },
-                Operator::Lt => match (row_value, compare_value) {
-                    (DataType::Int(a), DataType::Int(b)) => a < b,
-                    (DataType::Float(a), DataType::Float(b)) => a < b,
-                    (DataType::Float(a), DataType::Int(b)) => a < &(*b as f64),
-                    (DataType::Int(a), DataType::Float(b)) => &(*a as f64) < b,
-                    (DataType::Varchar(a), DataType::Varchar(b)) => a < b,
-                    _ => return Err(DbError::SqlError("类型不匹配".to_string())),
-                },
-                Operator::Ge => match (row_value, compare_value) {
-                    (DataType::Int(a), DataType::Int(b)) => a >= b,
-                    (DataType::Float(a), DataType::Float(b)) => a >= b,
-                    (DataType::Float(a), DataType::Int(b)) => a >= &(*b as f64),
-                    (DataType::Int(a), DataType::Float(b)) => &(*a as f64) >= b,
-                    (DataType::Varchar(a), DataType::Varchar(b)) => a >= b,
-                    _ => return Err(DbError::SqlError("类型不匹配".to_string())),
-                },
-                Operator::Le => match (row_value, compare_value) {
-                    (DataType::Int(a), DataType::Int(b)) => a <= b,
-                    (DataType::Float(a), DataType::Float(b)) => a <= b,
-                    (DataType::Float(a), DataType::Int(b)) => a <= &(*b as f64),
-                    (DataType::Int(a), DataType::Float(b)) => &(*a as f64) <= b,
-                    (DataType::Varchar(a), DataType::Varchar(b)) => a <= b,
-                    _ => return Err(DbError::SqlError("类型不匹配".to_string())),
-                },
-                Operator::IsNull => matches!(row_value, DataType::Null),
-                Operator::IsNotNull => !matches!(row_value, DataType::Null),
+/// 以迭代器方式执行查询，逐行应用WHERE过滤并投影，不预先物化整个结果集
+/// 只有带 ORDER BY 的查询需要先收集全部结果再排序
+/// 返回结果列名和行迭代器
+pub fn query_rows<'a>(storage: &'a dyn Storage, mut statement: SqlStatement) -> Result<(Vec<String>, RowIter<'a>), DbError> {
+    resolve_subqueries(storage, &mut statement)?;
+    match statement {
+        SqlStatement::SelectExpression { mut expressions, aliases, original_sql } => {
+            reject_aggregates_without_from(&expressions)?;
+            // 无表查询只产出一行，表头与打印的表格相同
+            let mut headers = select_list_headers(&original_sql, &expressions);
+            resolve_column_aliases(&mut expressions, &aliases, &mut headers, &[])?;
+            let row: Result<Vec<DataType>, DbError> = expressions.iter()
+                .map(|expr| evaluate_expression_without_storage(expr, &[], &[]))
+                .collect();
+            Ok((headers, Box::new(std::iter::once(row))))
+        }
+        SqlStatement::Select { columns, table, where_clause, order_by } => {
+            let table_data = storage.get_table(&table)?
+                .ok_or_else(|| DbError::TableError(format!("表 {} 不存在", table)))?;
+            
+            // 预先解析投影列的索引
+            let is_select_all = columns.len() == 1 && columns[0] == "*";
+            let headers: Vec<String> = if is_select_all {
+                table_data.columns.iter().map(|c| c.name.clone()).collect()
+            } else {
+                columns
             };
-
-            Ok(result)
-        },
-        WhereClause::Expression { left, operator, right } => {
-            // 使用不需要存储引用的函数评估表达式
+            let indices = headers.iter()
+                .map(|col| table_data.columns.iter()
+                    .position(|c| &c.name == col)
+                    .ok_or_else(|| DbError::SqlError(format!("列 {} 不存在", col))))
+                .collect::<Result<Vec<usize>, DbError>>()?;
+            
+            // 排序列不在结果中时，附加在行末用于排序
+            let hidden_sort_column = unprojected_sort_column(&headers, order_by.as_ref(), table_data);
+            let rows = rows_matching(storage, table_data, where_clause)?
+                .map(move |row| row.map(|row| indices.iter().chain(&hidden_sort_column).map(|&i| row[i].clone()).collect()));
+            
+            let rows = match hidden_sort_column {
+                Some(_) => sort_by_hidden_column(Box::new(rows), &headers, order_by)?,
+                None => sort_if_ordered(Box::new(rows), &headers, order_by)?,
+            };
+            Ok((headers, rows))
+        }
+        SqlStatement::SelectWithExpressions { mut expressions, aliases, table, where_clause, group_by, having, order_by, original_sql } => {
+            let table_data = storage.get_table(&table)?
+                .ok_or_else(|| DbError::TableError(format!("表 {} 不存在", table)))?;
+            
+            // 表头与打印的表格相同，取自原始 SQL
+            let mut headers = select_list_headers(&original_sql, &expressions);
+            resolve_column_aliases(&mut expressions, &aliases, &mut headers, &table_data.columns)?;
+            let (expressions, headers) = expand_wildcards(expressions, headers, table_data)?;
+            
+            // 聚合或分组查询需要先收集所有满足条件的行
+            if needs_grouping(&expressions, &group_by, having.as_ref()) {
+                let grouped = if is_count_star_only(&expressions, &group_by, having.as_ref()) {
+                    vec![vec![count_matching_rows(storage, table_data, where_clause)?]]
+                } else {
+                    let matched_rows = rows_matching(storage, table_data, where_clause)?
+                        .collect::<Result<Vec<_>, DbError>>()?;
+                    group_rows(&expressions, &group_by, having.as_ref(), matched_rows, &table_data.columns)?
+                };
+                let rows = sort_if_ordered(Box::new(grouped.into_iter().map(Ok)), &headers, order_by)?;
+                return Ok((headers, rows));
+            }
+            
+            // 排序列不在结果中时，附加在行末用于排序
+            let hidden_sort_column = unprojected_sort_column(&headers, order_by.as_ref(), table_data);
+            let rows = rows_matching(storage, table_data, where_clause)?.map(move |row| {
+                row.and_then(|row| {
+                    let mut values = expressions.iter()
+                        .map(|expr| evaluate_projection(storage, expr, row, table_data))
+                        .collect::<Result<Vec<DataType>, DbError>>()?;
+                    values.extend(hidden_sort_column.map(|i| row[i].clone()));
+                    Ok(values)
+                })
+            });
+            
+            let rows = match hidden_sort_column {
+                Some(_) => sort_by_hidden_column(Box::new(rows), &headers, order_by)?,
+                None => sort_if_ordered(Box::new(rows), &headers, order_by)?,
+            };
+            Ok((headers, rows))
+        }
+        SqlStatement::Union { left, right, all } => {
+            let (headers, left_rows) = query_rows(storage, *left)?;
+            let (right_headers, right_rows) = query_rows(storage, *right)?;
+            if headers.len() != right_headers.len() {
+                return Err(DbError::SqlError(format!(
+                    "UNION 两侧的列数不一致: {} 与 {}", headers.len(), right_headers.len()
+                )));
+            }
+            
+            let rows = left_rows.chain(right_rows);
+            if all {
+                return Ok((headers, Box::new(rows)));
+            }
+            
+            // UNION 去重，保留每行首次出现的位置
+            let mut seen = HashSet::new();
+            let rows = rows.filter(move |row| match row {
+                Ok(values) => seen.insert(format!("{:?}", values)),
+                Err(_) => true,
+            });
+            Ok((headers, Box::new(rows)))
+        }
+        SqlStatement::Sample { statement, size, seed } => {
+            // 抽样需要知道全部行，先物化内层查询的结果
+            let (headers, rows) = query_rows(storage, *statement)?;
+            let rows = rows.collect::<Result<Vec<_>, DbError>>()?;
+            Ok((headers, Box::new(sample_rows(rows, size, seed).into_iter().map(Ok))))
+        }
+        _ => Err(DbError::SqlError("只有 SELECT 语句可以迭代结果".to_string())),
+    }
+}
+
+// 构造非空约束错误，多行插入时附带出错的行号（从1开始）
+fn null_value_error(column: &str, row_number: usize, multi_row: bool) -> DbError {
+    if multi_row {
+        DbError::TypeError(TypeError::NullValueAtRow {
+            column: column.to_string(),
+            row: row_number,
+        })
+    } else {
+        DbError::TypeError(TypeError::NullValue(column.to_string()))
+    }
+}
+
+// 满足可选 WHERE 条件的行，按表中顺序排列，SELECT 的各种形式共用
+// 条件允许时利用主键或索引缩小扫描范围
+fn rows_matching<'t>(
+    storage: &'t dyn Storage,
+    table: &'t Table,
+    where_clause: Option<WhereClause>,
+) -> Result<RowRefIter<'t>, DbError> {
+    let rows = scan_rows(storage, table, where_clause.as_ref())?;
+    Ok(Box::new(rows.filter_map(move |row| match matches_where(row, where_clause.as_ref(), &table.columns) {
+        Ok(true) => Some(Ok(row)),
+        Ok(false) => None,
+        Err(e) => Some(Err(e)),
+    })))
+}
+
+// 是否只查询 COUNT(*)，没有分组和 HAVING，此时只需统计行数
+fn is_count_star_only(expressions: &[super::Expression], group_by: &[String], having: Option<&WhereClause>) -> bool {
+    matches!(
+        expressions,
+        [super::Expression::Aggregate { function: super::AggregateFunction::Count, argument: None, .. }]
+    ) && group_by.is_empty() && having.is_none()
+}
+
+// 统计满足条件的行数，没有 WHERE 时直接取表的行数，不收集任何行
+fn count_matching_rows(storage: &dyn Storage, table: &Table, where_clause: Option<WhereClause>) -> Result<DataType, DbError> {
+    let count = match where_clause {
+        None => table.rows.len(),
+        Some(where_clause) => {
+            let mut count = 0;
+            for row in rows_matching(storage, table, Some(where_clause))? {
+                row?;
+                count += 1;
+            }
+            count
+        }
+    };
+    Ok(DataType::Int(count as i32))
+}
+
+// 满足可选 WHERE 条件的行号，从小到大排列，供 UPDATE 和 DELETE 修改表数据
+fn matching_row_indices(table: &Table, where_clause: Option<&WhereClause>) -> Result<Vec<usize>, DbError> {
+    // 开启 ROW_VERSION 的表可以在条件中使用行版本号，如 WHERE id = 1 AND version = 3
+    let versioned_columns = table.has_row_versions().then(|| {
+        let mut columns = table.columns.clone();
+        columns.push(Column {
+            name: VERSION_COLUMN.to_string(),
+            data_type: ColumnType::Int(None),
+            nullable: false,
+            primary_key: false,
+            comment: None,
+            default: None,
+        });
+        columns
+    });
+    
+    let mut indices = Vec::new();
+    for (i, row) in table.rows.iter().enumerate() {
+        let matched = match &versioned_columns {
+            Some(columns) => {
+                let mut row = row.clone();
+                row.push(DataType::Int(table.row_version(i).unwrap_or_default() as i32));
+                matches_where(&row, where_clause, columns)?
+            }
+            None => matches_where(row, where_clause, &table.columns)?,
+        };
+        if matched {
+            indices.push(i);
+        }
+    }
+    Ok(indices)
+}
+
+// 判断行是否满足可选的WHERE条件
+fn matches_where(row: &[DataType], where_clause: Option<&WhereClause>, columns: &[Column]) -> Result<bool, DbError> {
+    match where_clause {
+        Some(where_clause) => evaluate_where_clause(row, where_clause, columns),
+        None => Ok(true),
+    }
+}
+
+// 计算算术表达式，除数为零时在错误中指出出错的表达式
+fn evaluate_binary(left: &DataType, operator: &super::ArithmeticOperator, right: &DataType, expr: &super::Expression) -> Result<DataType, DbError> {
+    let divides_number_by_zero = matches!(operator, super::ArithmeticOperator::Divide)
+        && matches!(left, DataType::Int(_) | DataType::Float(_))
+        && (matches!(right, DataType::Int(0)) || matches!(right, DataType::Float(f) if *f == 0.0));
+    if divides_number_by_zero {
+        return Err(DbError::SqlError(format!("除数不能为零: {}", expression_to_sql(expr))));
+    }
+    left.arithmetic(operator, right)
+}
+
+// 将表达式转换为带空格的 SQL 文本，嵌套的算术表达式加括号，用于错误信息
+fn expression_to_sql(expr: &super::Expression) -> String {
+    match expr {
+        super::Expression::Literal(value) => value.to_sql_literal(),
+        super::Expression::Binary { left, operator, right } => {
+            let operand = |operand: &super::Expression| match operand {
+                super::Expression::Binary { .. } => format!("({})", expression_to_sql(operand)),
+                _ => expression_to_sql(operand),
+            };
+            let op_str = match operator {
+                super::ArithmeticOperator::Add => "+",
+                super::ArithmeticOperator::Subtract => "-",
+                super::ArithmeticOperator::Multiply => "*",
+                super::ArithmeticOperator::Divide => "/",
+            };
+            format!("{} {} {}", operand(left), op_str, operand(right))
+        },
+        _ => expression_to_string(expr),
+    }
+}
+
+// 将表达式转换为字符串表示
+fn expression_to_string(expr: &super::Expression) -> String {
+    match expr {
+        super::Expression::Literal(value) => value.to_string(),
+        super::Expression::Column(name) => name.clone(),
+        super::Expression::Binary { left, operator, right } => {
+            let left_str = expression_to_string(left);
+            let right_str = expression_to_string(right);
+            let op_str = match operator {
+                super::ArithmeticOperator::Add => "+",
+                super::ArithmeticOperator::Subtract => "-",
+                super::ArithmeticOperator::Multiply => "*",
+                super::ArithmeticOperator::Divide => "/",
+            };
+            format!("{}{}{}", left_str, op_str, right_str)
+        },
+        super::Expression::Aggregate { function, argument, distinct } => {
+            let argument_str = match argument {
+                Some(argument) => expression_to_string(argument),
+                None => "*".to_string(),
+            };
+            let distinct_str = if *distinct { "DISTINCT " } else { "" };
+            format!("{}({}{})", aggregate_function_name(function), distinct_str, argument_str)
+        },
+        super::Expression::Subquery(_) => "(subquery)".to_string(),
+    }
+}
+
+// 从原始 SQL 中提取 SELECT 与 FROM 之间的各个表达式文本作为表头
+// 只在括号和引号之外按逗号分割，原始文本无法对应时使用生成的字符串
+fn select_list_headers(original_sql: &str, expressions: &[super::Expression]) -> Vec<String> {
+    let sql = original_sql.trim().trim_end_matches(';');
+    let select_part = if sql.len() >= 6 && sql[..6].eq_ignore_ascii_case("select") {
+        &sql[6..]
+    } else {
+        sql
+    };
+    
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0;
+    let mut in_string = false;
+    let chars: Vec<char> = select_part.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if in_string {
+            in_string = c != '\'';
+        } else {
+            match c {
+                '\'' => in_string = true,
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                ',' if depth == 0 => {
+                    parts.push(std::mem::take(&mut current));
+                    i += 1;
+                    continue;
+                }
+                _ => {}
+            }
+            
+            // 顶层的 FROM 关键字之后不再属于表达式列表
+            if depth == 0 && (i == 0 || chars[i - 1].is_whitespace()) {
+                let word: String = chars[i..].iter()
+                    .take_while(|c| c.is_alphanumeric() || **c == '_')
+                    .collect();
+                if word.eq_ignore_ascii_case("from") {
+                    break;
+                }
+            }
+        }
+        current.push(c);
+        i += 1;
+    }
+    parts.push(current);
+    
+    expressions.iter().enumerate().map(|(i, expr)| match parts.get(i) {
+        Some(part) if !part.trim().is_empty() => part.trim().to_string(),
+        _ => expression_to_string(expr),
+    }).collect()
+}
+
+// 将表达式列表中的 * 展开为表的全部列，表头取自表结构，其余表达式保持原有顺序和表头
+// 表名限定的 t.* 展开为以表名限定的列，表头同样带表名
+// 用 AS 别名作为表头，并把表达式中对前面别名的引用替换为该别名的表达式（从左到右依次处理）
+// 表中的同名列优先于别名；引用自身或后面才定义的别名时报错
+fn resolve_column_aliases(
+    expressions: &mut [super::Expression],
+    aliases: &[Option<String>],
+    headers: &mut [String],
+    columns: &[Column],
+) -> Result<(), DbError> {
+    for i in 0..expressions.len() {
+        let (defined, rest) = expressions.split_at_mut(i);
+        substitute_aliases(&mut rest[0], defined, &aliases[..i], &aliases[i..], columns)?;
+        if let Some(alias) = &aliases[i] {
+            headers[i] = alias.clone();
+        }
+    }
+    Ok(())
+}
+
+// 替换表达式中引用的别名，defined 是前面已解析的表达式，与 earlier 中的别名一一对应
+fn substitute_aliases(
+    expr: &mut super::Expression,
+    defined: &[super::Expression],
+    earlier: &[Option<String>],
+    later: &[Option<String>],
+    columns: &[Column],
+) -> Result<(), DbError> {
+    match expr {
+        super::Expression::Column(name) if !columns.iter().any(|c| &c.name == name) => {
+            let is_alias = |alias: &Option<String>| alias.as_deref() == Some(name.as_str());
+            if let Some(j) = earlier.iter().rposition(is_alias) {
+                *expr = defined[j].clone();
+            } else if later.iter().any(is_alias) {
+                return Err(DbError::SqlError(format!("列别名 {} 在定义之前被引用", name)));
+            }
+            Ok(())
+        }
+        super::Expression::Binary { left, right, .. } => {
+            substitute_aliases(left, defined, earlier, later, columns)?;
+            substitute_aliases(right, defined, earlier, later, columns)
+        }
+        super::Expression::Aggregate { argument: Some(argument), .. } => {
+            substitute_aliases(argument, defined, earlier, later, columns)
+        }
+        _ => Ok(()),
+    }
+}
+
+fn expand_wildcards(expressions: Vec<super::Expression>, headers: Vec<String>, table: &Table) -> Result<(Vec<super::Expression>, Vec<String>), DbError> {
+    let mut expanded = Vec::new();
+    let mut expanded_headers = Vec::new();
+    for (expr, header) in expressions.into_iter().zip(headers) {
+        match expr {
+            super::Expression::Column(name) if name == "*" => {
+                for column in &table.columns {
+                    expanded.push(super::Expression::Column(column.name.clone()));
+                    expanded_headers.push(column.name.clone());
+                }
+            }
+            super::Expression::Column(name) if name.ends_with(".*") => {
+                let qualifier = &name[..name.len() - 2];
+                if qualifier != table.name {
+                    return Err(DbError::SqlError(format!("表 {} 不在查询中", qualifier)));
+                }
+                for column in &table.columns {
+                    let qualified = format!("{}.{}", qualifier, column.name);
+                    expanded.push(super::Expression::Column(qualified.clone()));
+                    expanded_headers.push(qualified);
+                }
+            }
+            expr => {
+                expanded.push(expr);
+                expanded_headers.push(header);
+            }
+        }
+    }
+    Ok((expanded, expanded_headers))
+}
+
+// 聚合函数的名称
+fn aggregate_function_name(function: &super::AggregateFunction) -> &'static str {
+    match function {
+        super::AggregateFunction::Count => "COUNT",
+        super::AggregateFunction::Sum => "SUM",
+        super::AggregateFunction::Avg => "AVG",
+        super::AggregateFunction::Min => "MIN",
+        super::AggregateFunction::Max => "MAX",
+    }
+}
+
+// 判断表达式中是否包含聚合函数
+fn contains_aggregate(expr: &super::Expression) -> bool {
+    match expr {
+        super::Expression::Aggregate { .. } => true,
+        super::Expression::Binary { left, right, .. } => contains_aggregate(left) || contains_aggregate(right),
+        _ => false,
+    }
+}
+
+// 表达式中的第一个聚合函数
+fn first_aggregate(expr: &super::Expression) -> Option<&super::AggregateFunction> {
+    match expr {
+        super::Expression::Aggregate { function, .. } => Some(function),
+        super::Expression::Binary { left, right, .. } => first_aggregate(left).or_else(|| first_aggregate(right)),
+        _ => None,
+    }
+}
+
+// 没有 FROM 的表达式查询没有可聚合的行，不能使用聚合函数
+fn reject_aggregates_without_from(expressions: &[super::Expression]) -> Result<(), DbError> {
+    match expressions.iter().find_map(first_aggregate) {
+        Some(function) => Err(DbError::SqlError(format!("聚合函数 {} 需要 FROM 子句", aggregate_function_name(function)))),
+        None => Ok(()),
+    }
+}
+
+// 是否需要按分组方式执行查询：含聚合函数、GROUP BY 或 HAVING
+fn needs_grouping(expressions: &[super::Expression], group_by: &[String], having: Option<&WhereClause>) -> bool {
+    expressions.iter().any(contains_aggregate) || !group_by.is_empty() || having.is_some()
+}
+
+// 按 GROUP BY 列对行分组，计算每组的投影结果并用 HAVING 过滤
+// 没有 GROUP BY 时所有行作为一组
+fn group_rows(
+    expressions: &[super::Expression],
+    group_by: &[String],
+    having: Option<&WhereClause>,
+    rows: Vec<&[DataType]>,
+    columns: &[Column],
+) -> Result<Vec<Vec<DataType>>, DbError> {
+    let mut groups: Vec<Vec<&[DataType]>> = Vec::new();
+    if group_by.is_empty() {
+        groups.push(rows);
+    } else {
+        let indices = group_by.iter()
+            .map(|name| columns.iter()
+                .position(|c| &c.name == name)
+                .ok_or_else(|| DbError::SqlError(format!("GROUP BY列 {} 不存在", name))))
+            .collect::<Result<Vec<usize>, DbError>>()?;
+        
+        // 以分组列的值作为分组键，各组保持首次出现的顺序
+        let mut group_index: HashMap<String, usize> = HashMap::new();
+        for row in rows {
+            let key = format!("{:?}", indices.iter().map(|&i| &row[i]).collect::<Vec<_>>());
+            let index = *group_index.entry(key).or_insert_with(|| {
+                groups.push(Vec::new());
+                groups.len() - 1
+            });
+            groups[index].push(row);
+        }
+    }
+    
+    let mut results = Vec::new();
+    for group in &groups {
+        if let Some(having) = having {
+            if !evaluate_group_condition(having, group, columns)? {
+                continue;
+            }
+        }
+        results.push(aggregate_row(expressions, group, columns)?);
+    }
+    Ok(results)
+}
+
+// 在一组行上评估 HAVING 条件
+fn evaluate_group_condition(condition: &WhereClause, rows: &[&[DataType]], columns: &[Column]) -> Result<bool, DbError> {
+    match condition {
+        WhereClause::Simple { column, operator, value } => {
+            let column_value = evaluate_group_expression(&super::Expression::Column(column.clone()), rows, columns)?;
+            compare_with_operator(&column_value, operator, value)
+        },
+        WhereClause::Expression { left, operator, right } => {
+            let left_value = evaluate_group_expression(left, rows, columns)?;
+            let right_value = evaluate_group_expression(right, rows, columns)?;
+            compare_with_operator(&left_value, operator, &right_value)
+        },
+        WhereClause::And { left, right } => {
+            Ok(evaluate_group_condition(left, rows, columns)? && evaluate_group_condition(right, rows, columns)?)
+        },
+        WhereClause::Or { left, right } => {
+            Ok(evaluate_group_condition(left, rows, columns)? || evaluate_group_condition(right, rows, columns)?)
+        },
+        WhereClause::In { left, list, negated } => {
+            let value = evaluate_group_expression(left, rows, columns)?;
+            let candidates = match list {
+                super::InList::Values(values) => values.iter()
+                    .map(|expr| evaluate_group_expression(expr, rows, columns))
+                    .collect::<Result<Vec<DataType>, DbError>>()?,
+                super::InList::Subquery(_) => return Err(DbError::SqlError("子查询尚未执行".to_string())),
+            };
+            Ok(value_in_list(&value, &candidates, *negated))
+        },
+    }
+}
+
+// 在一组行上计算所有投影表达式，得到一行聚合结果
+fn aggregate_row(expressions: &[super::Expression], rows: &[&[DataType]], columns: &[Column]) -> Result<Vec<DataType>, DbError> {
+    expressions.iter()
+        .map(|expr| evaluate_group_expression(expr, rows, columns))
+        .collect()
+}
+
+// 在一组行上求值：聚合函数对整组计算，普通列取组内第一行的值
+fn evaluate_group_expression(expr: &super::Expression, rows: &[&[DataType]], columns: &[Column]) -> Result<DataType, DbError> {
+    match expr {
+        super::Expression::Aggregate { function, argument, distinct } => {
+            let argument = match argument {
+                Some(argument) => argument,
+                None => return Ok(DataType::Int(rows.len() as i32)), // COUNT(*)
+            };
+            
+            // 聚合时忽略 NULL 值
+            let mut values = Vec::new();
+            for row in rows {
+                let value = evaluate_expression_without_storage(argument, row, columns)?;
+                if !matches!(value, DataType::Null) {
+                    values.push(value);
+                }
+            }
+            
+            if *distinct {
+                values.sort_by(compare_values);
+                values.dedup();
+            }
+            
+            compute_aggregate(function, values)
+        },
+        super::Expression::Binary { left, operator, right } => {
+            let left_value = evaluate_group_expression(left, rows, columns)?;
+            let right_value = evaluate_group_expression(right, rows, columns)?;
+            evaluate_binary(&left_value, operator, &right_value, expr)
+        },
+        _ => match rows.first() {
+            Some(row) => evaluate_expression_without_storage(expr, row, columns),
+            None => Ok(DataType::Null),
+        },
+    }
+}
+
+// 对一组非 NULL 值计算聚合结果，空集合时 COUNT 为 0，其余为 NULL
+fn compute_aggregate(function: &super::AggregateFunction, values: Vec<DataType>) -> Result<DataType, DbError> {
+    match function {
+        super::AggregateFunction::Count => Ok(DataType::Int(values.len() as i32)),
+        super::AggregateFunction::Sum | super::AggregateFunction::Avg => {
+            if values.is_empty() {
+                return Ok(DataType::Null);
+            }
+            
+            let count = values.len();
+            let mut sum = DataType::Int(0);
+            for value in values {
+                if !matches!(value, DataType::Int(_) | DataType::Float(_)) {
+                    return Err(DbError::SqlError(format!(
+                        "{} 只能用于数值类型", aggregate_function_name(function)
+                    )));
+                }
+                sum = sum.arithmetic(&super::ArithmeticOperator::Add, &value)?;
+            }
+            
+            if *function == super::AggregateFunction::Sum {
+                return Ok(sum);
+            }
+            match sum {
+                DataType::Int(n) => Ok(DataType::Float(n as f64 / count as f64)),
+                DataType::Float(f) => Ok(DataType::Float(f / count as f64)),
+                _ => Ok(DataType::Null),
+            }
+        },
+        super::AggregateFunction::Min => Ok(values.into_iter().min_by(compare_values).unwrap_or(DataType::Null)),
+        super::AggregateFunction::Max => Ok(values.into_iter().max_by(compare_values).unwrap_or(DataType::Null)),
+    }
+}
+
+// 有 ORDER BY 时物化全部行并排序，否则原样返回迭代器
+// 必须使用稳定排序：排序键相同的行保持扫描时的顺序，DESC 也只反转键的比较结果
+fn sort_if_ordered<'a>(rows: RowIter<'a>, headers: &[String], order_by: Option<super::OrderBy>) -> Result<RowIter<'a>, DbError> {
+    let order_by = match order_by {
+        Some(order_by) => order_by,
+        None => return Ok(rows),
+    };
+    
+    let mut materialized = rows.collect::<Result<Vec<Vec<DataType>>, DbError>>()?;
+    sort_rows(&mut materialized, headers, &order_by)?;
+    Ok(Box::new(materialized.into_iter().map(Ok)))
+}
+
+// 按 ORDER BY 列排序已收集的行，各种查询路径共用同一比较规则
+// sort_by 是稳定排序，排序键相同的行保持原有顺序（即插入顺序）
+fn sort_rows(rows: &mut [Vec<DataType>], headers: &[String], order_by: &super::OrderBy) -> Result<(), DbError> {
+    let sort_col_index = headers.iter().position(|col| col == &order_by.column)
+        .ok_or_else(|| DbError::SqlError(format!("ORDER BY列 {} 不存在于结果集中", order_by.column)))?;
+    
+    rows.sort_by(|a, b| {
+        let ordering = compare_values(&a[sort_col_index], &b[sort_col_index]);
+        match order_by.direction {
+            super::SortDirection::Asc => ordering,
+            super::SortDirection::Desc => ordering.reverse(),
+        }
+    });
+    Ok(())
+}
+
+// ORDER BY 的列不在结果中但在表中时，返回该列在表中的位置，排序键取自投影前的源行
+fn unprojected_sort_column(headers: &[String], order_by: Option<&super::OrderBy>, table: &Table) -> Option<usize> {
+    let order_by = order_by?;
+    if headers.iter().any(|header| header == &order_by.column) {
+        return None;
+    }
+    table.columns.iter().position(|c| c.name == order_by.column)
+}
+
+// 按不在结果中的列排序：每行末尾附加了该列的值，排序后去掉
+fn sort_by_hidden_column<'a>(rows: RowIter<'a>, headers: &[String], order_by: Option<super::OrderBy>) -> Result<RowIter<'a>, DbError> {
+    let mut sort_headers = headers.to_vec();
+    sort_headers.extend(order_by.as_ref().map(|order_by| order_by.column.clone()));
+    let rows = sort_if_ordered(rows, &sort_headers, order_by)?;
+    Ok(Box::new(rows.map(|row| row.map(|mut values| {
+        values.pop();
+        values
+    }))))
+}
+
+// 排序用的值比较：NULL 最小，数值按大小，字符串按字典序
+fn compare_values(a: &DataType, b: &DataType) -> Ordering {
+    match (a, b) {
+        (DataType::Null, DataType::Null) => Ordering::Equal,
+        (DataType::Null, _) => Ordering::Less,
+        (_, DataType::Null) => Ordering::Greater,
+        _ => a.partial_cmp_coerced(b).unwrap_or_else(|| a.to_string().cmp(&b.to_string())),
+    }
+}
+
+// 比较操作符的字符串表示
+fn operator_to_string(operator: &Operator) -> &'static str {
+    match operator {
+        Operator::Eq => "=",
+        Operator::Ne => "!=",
+        Operator::Gt => ">",
+        Operator::Lt => "<",
+        Operator::Ge => ">=",
+        Operator::Le => "<=",
+        Operator::NullSafeEq => "<=>",
+        Operator::IsNull => "IS NULL",
+        Operator::IsNotNull => "IS NOT NULL",
+        Operator::IsTrue => "IS TRUE",
+        Operator::IsNotTrue => "IS NOT TRUE",
+        Operator::IsFalse => "IS FALSE",
+        Operator::IsNotFalse => "IS NOT FALSE",
+    }
+}
+
+// 排序子句的字符串表示
+fn order_by_to_string(order_by: &super::OrderBy) -> String {
+    match order_by.direction {
+        super::SortDirection::Asc => format!("{} ASC", order_by.column),
+        super::SortDirection::Desc => format!("{} DESC", order_by.column),
+    }
+}
+
+fn evaluate_where_clause(row: &[DataType], where_clause: &WhereClause, columns: &[crate::core::types::Column]) -> Result<bool, DbError> {
+    match where_clause {
+        WhereClause::Simple { column, operator, value } => {
+            let column_index = columns.iter()
+                .position(|col| col.name == *column)
+                .ok_or_else(|| DbError::SqlError(format!("列 {} 不存在", column)))?;
+
+            compare_with_operator(&row[column_index], operator, value)
+        },
+        WhereClause::Expression { left, operator, right } => {
+            // 使用不需要存储引用的函数评估表达式
             let left_value = evaluate_expression_without_storage(left, row, columns)?;
             let right_value = evaluate_expression_without_storage(right, row, columns)?;
             
             // 比较两个表达式的结果
-            let result = match operator {
-                Operator::Eq => left_value == right_value,
-                Operator::Ne => left_value != right_value,
-                Operator::Gt => match (&left_value, &right_value) {
-                    (DataType::Int(a), DataType::Int(b)) => a > b,
-                    (DataType::Float(a), DataType::Float(b)) => a > b,
-                    (DataType::Float(a), DataType::Int(b)) => a > &(*b as f64),
-                    (DataType::Int(a), DataType::Float(b)) => &(*a as f64) > b,
-                    (DataType::Varchar(a), DataType::Varchar(b)) => a > b,
-                    _ => return Err(DbError::SqlError("类型不匹配".to_string())),
-                },
-                Operator::Lt => match (&left_value, &right_value) {
-                    (DataType::Int(a), DataType::Int(b)) => a < b,
-                    (DataType::Float(a), DataType::Float(b)) => a < b,
-                    (DataType::Float(a), DataType::Int(b)) => a < &(*b as f64),
-                    (DataType::Int(a), DataType::Float(b)) => &(*a as f64) < b,
-                    (DataType::Varchar(a), DataType::Varchar(b)) => a < b,
-                    _ => return Err(DbError::SqlError("类型不匹配".to_string())),
-                },
-                Operator::Ge => match (&left_value, &right_value) {
-                    (DataType::Int(a), DataType::Int(b)) => a >= b,
-                    (DataType::Float(a), DataType::Float(b)) => a >= b,
-                    (DataType::Float(a), DataType::Int(b)) => a >= &(*b as f64),
-                    (DataType::Int(a), DataType::Float(b)) => &(*a as f64) >= b,
-                    (DataType::Varchar(a), DataType::Varchar(b)) => a >= b,
-                    _ => return Err(DbError::SqlError("类型不匹配".to_string())),
-                },
-                Operator::Le => match (&left_value, &right_value) {
-                    (DataType::Int(a), DataType::Int(b)) => a <= b,
-                    (DataType::Float(a), DataType::Float(b)) => a <= b,
-                    (DataType::Float(a), DataType::Int(b)) => a <= &(*b as f64),
-                    (DataType::Int(a), DataType::Float(b)) => &(*a as f64) <= b,
-                    (DataType::Varchar(a), DataType::Varchar(b)) => a <= b,
-                    _ => return Err(DbError::SqlError("类型不匹配".to_string())),
-                },
-                Operator::IsNull => matches!(left_value, DataType::Null),
-                Operator::IsNotNull => !matches!(left_value, DataType::Null),
-            };
-            
-            Ok(result)
+            compare_with_operator(&left_value, operator, &right_value)
         },
         WhereClause::And { left, right } => {
             // 对于 AND，两边都需要为真
@@ -663,6 +1741,292 @@ fn evaluate_where_clause(row: &[DataType], where_clause: &WhereClause, columns:
             let right_result = evaluate_where_clause(row, right, columns)?;
             Ok(left_result || right_result)
         },
+        WhereClause::In { left, list, negated } => {
+            let value = evaluate_expression_without_storage(left, row, columns)?;
+            let candidates = match list {
+                super::InList::Values(values) => values.iter()
+                    .map(|expr| evaluate_expression_without_storage(expr, row, columns))
+                    .collect::<Result<Vec<DataType>, DbError>>()?,
+                super::InList::Subquery(_) => return Err(DbError::SqlError("子查询尚未执行".to_string())),
+            };
+            Ok(value_in_list(&value, &candidates, *negated))
+        },
+    }
+}
+
+// 判断值是否在候选列表中，按与 = 相同的规则比较（整数与浮点数可以相等）
+// 三值逻辑：值为 NULL，或没有匹配但列表中有 NULL 时结果未知，IN 和 NOT IN 都为假
+fn value_in_list(value: &DataType, candidates: &[DataType], negated: bool) -> bool {
+    if matches!(value, DataType::Null) {
+        return false;
+    }
+    if candidates.iter().any(|candidate| value.partial_cmp_coerced(candidate) == Some(Ordering::Equal)) {
+        return !negated;
+    }
+    if candidates.iter().any(|candidate| matches!(candidate, DataType::Null)) {
+        return false;
+    }
+    negated
+}
+
+// 执行语句中 IN 右侧的子查询，替换为值列表
+// 子查询不依赖外层行，每条语句只需执行一次
+fn resolve_subqueries(storage: &dyn Storage, statement: &mut SqlStatement) -> Result<(), DbError> {
+    match statement {
+        SqlStatement::Select { where_clause, .. }
+        | SqlStatement::Update { where_clause, .. }
+        | SqlStatement::Delete { where_clause, .. } => {
+            if let Some(where_clause) = where_clause {
+                resolve_where_subqueries(storage, where_clause)?;
+            }
+        }
+        SqlStatement::SelectWithExpressions { where_clause, having, .. } => {
+            for clause in [where_clause, having].into_iter().flatten() {
+                resolve_where_subqueries(storage, clause)?;
+            }
+        }
+        SqlStatement::Union { left, right, .. } => {
+            resolve_subqueries(storage, left)?;
+            resolve_subqueries(storage, right)?;
+        }
+        SqlStatement::Explain { statement }
+        | SqlStatement::Sample { statement, .. }
+        | SqlStatement::CopyTo { query: statement, .. }
+        | SqlStatement::Returning { statement, .. } => {
+            resolve_subqueries(storage, statement)?;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn resolve_where_subqueries(storage: &dyn Storage, where_clause: &mut WhereClause) -> Result<(), DbError> {
+    match where_clause {
+        WhereClause::And { left, right } | WhereClause::Or { left, right } => {
+            resolve_where_subqueries(storage, left)?;
+            resolve_where_subqueries(storage, right)
+        }
+        WhereClause::In { list, .. } => {
+            if let super::InList::Subquery(_) = list {
+                let subquery = match std::mem::replace(list, super::InList::Values(Vec::new())) {
+                    super::InList::Subquery(subquery) => *subquery,
+                    super::InList::Values(_) => unreachable!(),
+                };
+                let (headers, rows) = query_rows(storage, subquery)?;
+                if headers.len() != 1 {
+                    return Err(DbError::SqlError(format!("IN 子查询只能返回一列，实际返回 {} 列", headers.len())));
+                }
+                
+                let values = rows
+                    .map(|row| row.map(|mut values| super::Expression::Literal(values.remove(0))))
+                    .collect::<Result<Vec<_>, DbError>>()?;
+                *list = super::InList::Values(values);
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+// 按比较操作符比较两个值，数值类型之间自动转换
+fn compare_with_operator(left_value: &DataType, operator: &Operator, right_value: &DataType) -> Result<bool, DbError> {
+    let ordering = || left_value.partial_cmp_coerced(right_value)
+        .ok_or_else(|| DbError::SqlError("类型不匹配".to_string()));
+    
+    // 与 NULL 做大小比较时结果未知，视为不匹配，而不是报类型错误
+    let has_null = matches!(left_value, DataType::Null) || matches!(right_value, DataType::Null);
+    if has_null && matches!(operator, Operator::Gt | Operator::Lt | Operator::Ge | Operator::Le) {
+        return Ok(false);
+    }
+    
+    let result = match operator {
+        Operator::Eq => match left_value.partial_cmp_coerced(right_value) {
+            Some(ordering) => ordering == Ordering::Equal,
+            None => left_value == right_value,
+        },
+        Operator::Ne => match left_value.partial_cmp_coerced(right_value) {
+            Some(ordering) => ordering != Ordering::Equal,
+            None => left_value != right_value,
+        },
+        Operator::Gt => ordering()? == Ordering::Greater,
+        Operator::Lt => ordering()? == Ordering::Less,
+        Operator::Ge => ordering()? != Ordering::Less,
+        Operator::Le => ordering()? != Ordering::Greater,
+        Operator::NullSafeEq => match (left_value, right_value) {
+            (DataType::Null, DataType::Null) => true,
+            (DataType::Null, _) | (_, DataType::Null) => false,
+            _ => left_value.partial_cmp_coerced(right_value) == Some(Ordering::Equal),
+        },
+        Operator::IsNull => matches!(left_value, DataType::Null),
+        Operator::IsNotNull => !matches!(left_value, DataType::Null),
+        Operator::IsTrue => truth_value(left_value)? == Some(true),
+        Operator::IsNotTrue => truth_value(left_value)? != Some(true),
+        Operator::IsFalse => truth_value(left_value)? == Some(false),
+        Operator::IsNotFalse => truth_value(left_value)? != Some(false),
+    };
+    
+    Ok(result)
+}
+
+// IS TRUE / IS FALSE 判断的真假：数值非零为真、零为假，NULL 为未知
+fn truth_value(value: &DataType) -> Result<Option<bool>, DbError> {
+    match value {
+        DataType::Int(n) => Ok(Some(*n != 0)),
+        DataType::Float(f) => Ok(Some(*f != 0.0)),
+        DataType::Null => Ok(None),
+        DataType::Varchar(_) => Err(DbError::SqlError(format!("IS TRUE / IS FALSE 只能用于数值: {}", value.to_sql_literal()))),
+    }
+}
+
+// 将结果行转换为用于表格输出的字符串
+fn to_string_rows(rows: &[Vec<DataType>]) -> Vec<Vec<String>> {
+    rows.iter()
+        .map(|row| row.iter().map(|value| value.to_string()).collect())
+        .collect()
+}
+
+// WHERE 中可以通过索引查找的列及其取值区间
+struct IndexRange {
+    column: String,
+    lower: Bound<IndexKey>,
+    upper: Bound<IndexKey>,
+}
+
+// 返回可能满足 WHERE 条件的行，按表中顺序排列
+// 条件为主键等值比较时按主键查找，带有索引列的区间比较时只访问区间内的行，否则全表扫描；调用方仍需对每行完整求值 WHERE
+fn scan_rows<'t>(
+    storage: &'t dyn Storage,
+    table: &'t Table,
+    where_clause: Option<&WhereClause>,
+) -> Result<Box<dyn Iterator<Item = &'t [DataType]> + 't>, DbError> {
+    if let Some(key) = where_clause.and_then(|where_clause| primary_key_lookup(table, where_clause)) {
+        let row = storage.find_by_primary_key(&table.name, &key)?;
+        return Ok(Box::new(row.into_iter()));
+    }
+    if let Some(range) = where_clause.and_then(|where_clause| index_range_for(table, where_clause)) {
+        if let Some(row_indices) = table.index_range(&range.column, range.lower.as_ref(), range.upper.as_ref()) {
+            return Ok(Box::new(row_indices.into_iter().map(move |i| table.rows[i].as_slice())));
+        }
+    }
+    Ok(Box::new(table.rows.iter().map(|row| row.as_slice())))
+}
+
+// 从 WHERE 条件中找出主键列的等值比较，返回要查找的主键值
+fn primary_key_lookup(table: &Table, where_clause: &WhereClause) -> Option<DataType> {
+    let primary_key = table.columns.iter().find(|c| c.primary_key)?;
+    match where_clause {
+        WhereClause::Simple { column, operator: Operator::Eq, value } if column == &primary_key.name => Some(value.clone()),
+        WhereClause::Expression { left, operator: Operator::Eq, right } => match (left.as_ref(), right.as_ref()) {
+            (super::Expression::Column(column), super::Expression::Literal(value))
+            | (super::Expression::Literal(value), super::Expression::Column(column)) if column == &primary_key.name => {
+                Some(value.clone())
+            }
+            _ => None,
+        },
+        WhereClause::And { left, right } => {
+            primary_key_lookup(table, left).or_else(|| primary_key_lookup(table, right))
+        }
+        _ => None,
+    }
+}
+
+// 查询计划中的扫描方式
+fn scan_step(table: &Table, where_clause: Option<&WhereClause>) -> (String, String) {
+    if where_clause.and_then(|where_clause| primary_key_lookup(table, where_clause)).is_some() {
+        let primary_key = table.columns.iter().find(|c| c.primary_key).map_or("", |c| c.name.as_str());
+        return ("primary key lookup".to_string(), format!("{} using {}", table.name, primary_key));
+    }
+    let index = where_clause
+        .and_then(|where_clause| index_range_for(table, where_clause))
+        .and_then(|range| table.index_on(&range.column));
+    match index {
+        Some(index) => ("index range scan".to_string(), format!("{} using {}", table.name, index.name)),
+        None => ("full scan".to_string(), table.name.clone()),
+    }
+}
+
+// 从 WHERE 条件中找出索引列上的区间，OR 等无法确定区间的条件返回 None
+fn index_range_for(table: &Table, where_clause: &WhereClause) -> Option<IndexRange> {
+    match where_clause {
+        WhereClause::Simple { column, operator, value } => comparison_range(table, column, operator, value),
+        WhereClause::Expression { left, operator, right } => match (left.as_ref(), right.as_ref()) {
+            (super::Expression::Column(column), super::Expression::Literal(value)) => {
+                comparison_range(table, column, operator, value)
+            }
+            // 5 < age 等价于 age > 5
+            (super::Expression::Literal(value), super::Expression::Column(column)) => {
+                let flipped = match operator {
+                    Operator::Gt => Operator::Lt,
+                    Operator::Lt => Operator::Gt,
+                    Operator::Ge => Operator::Le,
+                    Operator::Le => Operator::Ge,
+                    Operator::Eq => Operator::Eq,
+                    Operator::NullSafeEq => Operator::NullSafeEq,
+                    _ => return None,
+                };
+                comparison_range(table, column, &flipped, value)
+            }
+            _ => None,
+        },
+        // AND 的结果是两侧结果的子集，任一侧的区间都可以使用，同一列上的区间取交集
+        WhereClause::And { left, right } => match (index_range_for(table, left), index_range_for(table, right)) {
+            (Some(left), Some(right)) if left.column == right.column => Some(IndexRange {
+                column: left.column,
+                lower: tighter_bound(left.lower, right.lower, Ordering::Greater),
+                upper: tighter_bound(left.upper, right.upper, Ordering::Less),
+            }),
+            (Some(range), _) | (None, Some(range)) => Some(range),
+            (None, None) => None,
+        },
+        _ => None,
+    }
+}
+
+// 单个比较条件对应的区间，列上没有索引或值与列类型不可比较时返回 None
+fn comparison_range(table: &Table, column: &str, operator: &Operator, value: &DataType) -> Option<IndexRange> {
+    table.index_on(column)?;
+    let column_type = &table.columns.iter().find(|c| c.name == column)?.data_type;
+    let key = IndexKey::from_value(value)?;
+    
+    // 类型不匹配时交给全表扫描报错
+    let comparable = matches!(
+        (&key, column_type),
+        (IndexKey::Number(_), ColumnType::Int(_) | ColumnType::Float(_)) | (IndexKey::Text(_), ColumnType::Varchar(_))
+    );
+    if !comparable {
+        return None;
+    }
+    
+    let (lower, upper) = match operator {
+        // 值不为 NULL 时 <=> 与 = 相同，NULL 不在索引中，from_value 已经返回 None
+        Operator::Eq | Operator::NullSafeEq => (Bound::Included(key.clone()), Bound::Included(key)),
+        Operator::Gt => (Bound::Excluded(key), Bound::Unbounded),
+        Operator::Ge => (Bound::Included(key), Bound::Unbounded),
+        Operator::Lt => (Bound::Unbounded, Bound::Excluded(key)),
+        Operator::Le => (Bound::Unbounded, Bound::Included(key)),
+        _ => return None,
+    };
+    Some(IndexRange { column: column.to_string(), lower, upper })
+}
+
+// 取两个边界中更严格的一个，下界取较大者，上界取较小者
+fn tighter_bound(a: Bound<IndexKey>, b: Bound<IndexKey>, prefer: Ordering) -> Bound<IndexKey> {
+    match (&a, &b) {
+        (Bound::Unbounded, _) => b,
+        (_, Bound::Unbounded) => a,
+        (Bound::Included(x) | Bound::Excluded(x), Bound::Included(y) | Bound::Excluded(y)) => {
+            let ordering = x.cmp(y);
+            if ordering == prefer {
+                a
+            } else if ordering == prefer.reverse() {
+                b
+            } else if matches!(a, Bound::Excluded(_)) {
+                // 键相同时开区间更严格
+                a
+            } else {
+                b
+            }
+        }
     }
 }
 
@@ -696,68 +2060,150 @@ pub fn evaluate_expression_without_storage(expr: &super::Expression, row: &[Data
         super::Expression::Binary { left, operator, right } => {
             let left_value = evaluate_expression_without_storage(left, row, columns)?;
             let right_value = evaluate_expression_without_storage(right, row, columns)?;
-            
-            match (left_value, right_value) {
-                (DataType::Int(a), DataType::Int(b)) => {
-                    let result = match operator {
-                        super::ArithmeticOperator::Add => a + b,
-                        super::ArithmeticOperator::Subtract => a - b,
-                        super::ArithmeticOperator::Multiply => a * b,
-                        super::ArithmeticOperator::Divide => {
-                            if b == 0 {
-                                return Err(DbError::SqlError("除数不能为零".to_string()));
-                            }
-                            a / b
-                        },
-                    };
-                    Ok(DataType::Int(result))
-                },
-                (DataType::Float(a), DataType::Float(b)) => {
-                    let result = match operator {
-                        super::ArithmeticOperator::Add => a + b,
-                        super::ArithmeticOperator::Subtract => a - b,
-                        super::ArithmeticOperator::Multiply => a * b,
-                        super::ArithmeticOperator::Divide => {
-                            if b == 0.0 {
-                                return Err(DbError::SqlError("除数不能为零".to_string()));
-                            }
-                            a / b
-                        },
-                    };
-                    Ok(DataType::Float(result))
-                },
-                (DataType::Int(a), DataType::Float(b)) => {
-                    let a_float = a as f64;
-                    let result = match operator {
-                        super::ArithmeticOperator::Add => a_float + b,
-                        super::ArithmeticOperator::Subtract => a_float - b,
-                        super::ArithmeticOperator::Multiply => a_float * b,
-                        super::ArithmeticOperator::Divide => {
-                            if b == 0.0 {
-                                return Err(DbError::SqlError("除数不能为零".to_string()));
-                            }
-                            a_float / b
-                        },
-                    };
-                    Ok(DataType::Float(result))
-                },
-                (DataType::Float(a), DataType::Int(b)) => {
-                    let b_float = b as f64;
-                    let result = match operator {
-                        super::ArithmeticOperator::Add => a + b_float,
-                        super::ArithmeticOperator::Subtract => a - b_float,
-                        super::ArithmeticOperator::Multiply => a * b_float,
-                        super::ArithmeticOperator::Divide => {
-                            if b == 0 {
-                                return Err(DbError::SqlError("除数不能为零".to_string()));
-                            }
-                            a / b_float
-                        },
-                    };
-                    Ok(DataType::Float(result))
-                },
-                _ => Err(DbError::SqlError("不支持的操作数类型".to_string())),
-            }
+            evaluate_binary(&left_value, operator, &right_value, expr)
+        },
+        super::Expression::Aggregate { .. } => {
+            Err(DbError::SqlError("聚合函数不能用于此处".to_string()))
+        },
+        super::Expression::Subquery(_) => {
+            Err(DbError::SqlError("子查询不能用于此处".to_string()))
+        },
+    }
+}
+
+// 计算投影表达式的值，支持引用外层行的标量子查询
+fn evaluate_projection(storage: &dyn Storage, expr: &super::Expression, row: &[DataType], table: &Table) -> Result<DataType, DbError> {
+    match expr {
+        super::Expression::Subquery(subquery) => evaluate_scalar_subquery(storage, subquery, Some((table, row))),
+        super::Expression::Binary { left, operator, right } => {
+            let left_value = evaluate_projection(storage, left, row, table)?;
+            let right_value = evaluate_projection(storage, right, row, table)?;
+            evaluate_binary(&left_value, operator, &right_value, expr)
+        },
+        super::Expression::Column(name) if name != "*" && !name.contains('.') && !table.columns.iter().any(|c| &c.name == name) => {
+            Err(column_not_found(storage, name, &table.name))
         },
+        _ => evaluate_expression_without_storage(expr, row, &table.columns),
+    }
+}
+
+// 列不在查询的表中时的错误，其他表中有同名列时在错误中提示这些表
+fn column_not_found(storage: &dyn Storage, name: &str, table: &str) -> DbError {
+    let others: Vec<String> = storage.get_tables()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|other| other.name != table && other.columns.iter().any(|c| c.name == name))
+        .map(|other| other.name.clone())
+        .collect();
+    if others.is_empty() {
+        DbError::SqlError(format!("列 {} 未找到", name))
+    } else {
+        DbError::SqlError(format!("列 {} 不在表 {} 中，表 {} 中有同名的列", name, table, others.join("、")))
+    }
+}
+
+// 执行标量子查询，必须恰好返回一行一列
+// 子查询中引用的外层列先替换为外层当前行的值
+fn evaluate_scalar_subquery(storage: &dyn Storage, subquery: &SqlStatement, outer: Option<(&Table, &[DataType])>) -> Result<DataType, DbError> {
+    let mut subquery = subquery.clone();
+    if let Some((table, row)) = outer {
+        bind_outer_columns(storage, &mut subquery, table, row)?;
+    }
+    
+    let (headers, mut rows) = query_rows(storage, subquery)?;
+    if headers.len() != 1 {
+        return Err(DbError::SqlError(format!("标量子查询只能返回一列，实际返回 {} 列", headers.len())));
+    }
+    
+    let value = match rows.next() {
+        Some(row) => row?.remove(0),
+        None => return Err(DbError::SqlError("标量子查询没有返回任何行".to_string())),
+    };
+    if rows.next().is_some() {
+        return Err(DbError::SqlError("标量子查询返回了多行".to_string()));
+    }
+    Ok(value)
+}
+
+// 将子查询中引用外层表的列替换为外层行的值
+// 以外层表名限定的列，或子查询表中不存在而外层表中存在的列，视为外层列
+fn bind_outer_columns(storage: &dyn Storage, subquery: &mut SqlStatement, outer_table: &Table, outer_row: &[DataType]) -> Result<(), DbError> {
+    let (inner_table, expressions, where_clause, having) = match subquery {
+        SqlStatement::SelectWithExpressions { table, expressions, where_clause, having, .. } => {
+            (table.clone(), Some(expressions), where_clause, having)
+        }
+        SqlStatement::Select { table, where_clause, .. } => (table.clone(), None, where_clause, &mut None),
+        _ => return Ok(()),
+    };
+    let inner_columns = storage.get_table(&inner_table)?
+        .map(|t| t.columns.clone())
+        .unwrap_or_default();
+    
+    let lookup = |name: &str| -> Option<DataType> {
+        let column_name = match name.split_once('.') {
+            Some((prefix, column)) if prefix == outer_table.name && prefix != inner_table => column,
+            Some(_) => return None,
+            None if inner_columns.iter().any(|c| c.name == name) => return None,
+            None => name,
+        };
+        outer_table.columns.iter()
+            .position(|c| c.name == column_name)
+            .map(|i| outer_row[i].clone())
+    };
+    
+    if let Some(expressions) = expressions {
+        for expr in expressions.iter_mut() {
+            bind_expression(expr, &lookup);
+        }
+    }
+    for clause in [where_clause, having].into_iter().flatten() {
+        bind_where_clause(clause, &lookup);
+    }
+    Ok(())
+}
+
+fn bind_expression(expr: &mut super::Expression, lookup: &dyn Fn(&str) -> Option<DataType>) {
+    match expr {
+        super::Expression::Column(name) => {
+            if let Some(value) = lookup(name) {
+                *expr = super::Expression::Literal(value);
+            }
+        }
+        super::Expression::Binary { left, right, .. } => {
+            bind_expression(left, lookup);
+            bind_expression(right, lookup);
+        }
+        super::Expression::Aggregate { argument: Some(argument), .. } => bind_expression(argument, lookup),
+        _ => {}
     }
-} 
\ No newline at end of file
+}
+
+fn bind_where_clause(where_clause: &mut WhereClause, lookup: &dyn Fn(&str) -> Option<DataType>) {
+    match where_clause {
+        WhereClause::Simple { column, operator, value } => {
+            if let Some(outer_value) = lookup(column) {
+                *where_clause = WhereClause::Expression {
+                    left: Box::new(super::Expression::Literal(outer_value)),
+                    operator: operator.clone(),
+                    right: Box::new(super::Expression::Literal(value.clone())),
+                };
+            }
+        }
+        WhereClause::Expression { left, right, .. } => {
+            bind_expression(left, lookup);
+            bind_expression(right, lookup);
+        }
+        WhereClause::And { left, right } | WhereClause::Or { left, right } => {
+            bind_where_clause(left, lookup);
+            bind_where_clause(right, lookup);
+        }
+        WhereClause::In { left, list, .. } => {
+            bind_expression(left, lookup);
+            if let super::InList::Values(values) = list {
+                for value in values.iter_mut() {
+                    bind_expression(value, lookup);
+                }
+            }
+        }
+    }
+}
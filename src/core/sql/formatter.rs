@@ -1,26 +1,68 @@
+use crate::core::types::DataType;
+
 pub struct TableFormatter;
 
+/// 表格输出的样式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TableStyle {
+    /// 只在表头下画分隔线，使用 | 和 -
+    #[default]
+    Minimal,
+    /// 使用制表符画出完整边框，row_separators 为 true 时在每行数据之间也画分隔线
+    Box { row_separators: bool },
+}
+
+/// 查询结果的输出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// 表格，样式由 TableStyle 决定
+    #[default]
+    Table,
+    /// JSON，pretty 为 true 时缩进输出便于阅读，否则输出为一行便于管道处理
+    Json { pretty: bool },
+}
+
 impl TableFormatter {
     /// 格式化表格输出
     /// 所有字段在表单元格中，列中最长字段距离左右边界各1个空格，其他字段与最长字段向左对齐
     /// 每个单元格宽度至少为5个字符(包括内容和空格)，若超过则以列中最长内容+左右各1个空格为标准
     pub fn format_table(headers: &[String], rows: &[Vec<String>]) -> String {
+        Self::format_table_with_max_width(headers, rows, None)
+    }
+    
+    /// 格式化表格输出，显示宽度超过 max_width 的单元格（包括表头）截断并以 '…' 结尾
+    /// 宽度按终端显示宽度计算，中文等全角字符占2列；CSV 等格式不截断
+    pub fn format_table_with_max_width(headers: &[String], rows: &[Vec<String>], max_width: Option<usize>) -> String {
+        Self::format_table_styled(headers, rows, max_width, TableStyle::Minimal)
+    }
+    
+    /// 按指定样式格式化表格输出，单元格的宽度和截断规则与 format_table_with_max_width 相同
+    pub fn format_table_styled(headers: &[String], rows: &[Vec<String>], max_width: Option<usize>, style: TableStyle) -> String {
         let mut result = String::new();
         
+        let truncate = |cells: &[String]| -> Vec<String> {
+            match max_width {
+                Some(max_width) => cells.iter().map(|cell| Self::truncate_to_width(cell, max_width)).collect(),
+                None => cells.to_vec(),
+            }
+        };
+        let headers = truncate(headers);
+        let rows: Vec<Vec<String>> = rows.iter().map(|row| truncate(row)).collect();
+        
         // 计算每列的最大宽度
         let mut max_widths: Vec<usize> = vec![0; headers.len()];
         
         // 先检查表头宽度
         for (i, header) in headers.iter().enumerate() {
-            max_widths[i] = max_widths[i].max(header.len());
+            max_widths[i] = max_widths[i].max(Self::display_width(header));
         }
         
         // 再检查所有行的宽度
-        for row in rows {
+        for row in &rows {
             for (i, cell) in row.iter().enumerate() {
                 if i < max_widths.len() {
                     // 如果单元格是"NULL"则当作空字符串处理
-                    let cell_width = if cell == "NULL" { 0 } else { cell.len() };
+                    let cell_width = if cell == "NULL" { 0 } else { Self::display_width(cell) };
                     max_widths[i] = max_widths[i].max(cell_width);
                 }
             }
@@ -31,43 +73,169 @@ impl TableFormatter {
             *width = (*width).max(3);
         }
         
+        if let TableStyle::Box { row_separators } = style {
+            return Self::format_box(&headers, &rows, &max_widths, row_separators);
+        }
+        
         // 构建表头
-        result.push_str(&Self::format_row(headers, &max_widths));
+        result.push_str(&Self::format_row(&headers, &max_widths, '|'));
         result.push('\n');
         
         // 构建分隔线
         let mut separator = String::new();
         separator.push('|');
         
-        for (i, width) in max_widths.iter().enumerate() {
-            // 两边各留1个空格
+        for width in &max_widths {
+            // 两边各留1个空格，每列以竖线结束
             separator.push(' ');
             separator.push_str(&"-".repeat(*width));
             separator.push(' ');
-            
-            if i < max_widths.len() - 1 {
-                separator.push('|');
-            } else {
-                separator.push('|');
-            }
+            separator.push('|');
         }
         
         result.push_str(&separator);
         result.push('\n');
         
         // 构建数据行
+        for row in &rows {
+            result.push_str(&Self::format_row(row, &max_widths, '|'));
+            result.push('\n');
+        }
+        
+        result
+    }
+    
+    /// 使用制表符画出带边框的表格
+    fn format_box(headers: &[String], rows: &[Vec<String>], widths: &[usize], row_separators: bool) -> String {
+        let mut result = String::new();
+        
+        result.push_str(&Self::border_line(widths, '┌', '┬', '┐'));
+        result.push_str(&Self::format_row(headers, widths, '│'));
+        result.push('\n');
+        if !rows.is_empty() {
+            result.push_str(&Self::border_line(widths, '├', '┼', '┤'));
+        }
+        
+        for (i, row) in rows.iter().enumerate() {
+            if row_separators && i > 0 {
+                result.push_str(&Self::border_line(widths, '├', '┼', '┤'));
+            }
+            result.push_str(&Self::format_row(row, widths, '│'));
+            result.push('\n');
+        }
+        
+        result.push_str(&Self::border_line(widths, '└', '┴', '┘'));
+        result
+    }
+    
+    /// 边框线，每段宽度为列宽加左右各1个空格
+    fn border_line(widths: &[usize], left: char, middle: char, right: char) -> String {
+        let segments: Vec<String> = widths.iter().map(|width| "─".repeat(width + 2)).collect();
+        format!("{}{}{}\n", left, segments.join(&middle.to_string()), right)
+    }
+    
+    /// 在最前面加上表头为 # 的行号列，行号从1开始
+    /// 表格输出开启行号时使用；CSV 和 JSON 不加行号，需要时可先调用此函数
+    pub fn with_row_numbers(headers: &[String], rows: &[Vec<String>]) -> (Vec<String>, Vec<Vec<String>>) {
+        let headers = std::iter::once("#".to_string()).chain(headers.iter().cloned()).collect();
+        let rows = rows.iter().enumerate()
+            .map(|(i, row)| std::iter::once((i + 1).to_string()).chain(row.iter().cloned()).collect())
+            .collect();
+        (headers, rows)
+    }
+    
+    /// 格式化为CSV文本，第一行为表头
+    /// NULL 输出为空字段，内容为 "NULL" 的字符串原样输出；包含逗号、引号、换行的字段以及空字符串加引号，与 COPY FROM 的解析规则对应
+    pub fn format_csv(headers: &[String], rows: &[Vec<DataType>]) -> String {
+        let mut result = String::new();
+        
+        result.push_str(&headers.iter().map(|h| Self::escape_csv_field(h)).collect::<Vec<_>>().join(","));
+        result.push('\n');
+        
         for row in rows {
-            result.push_str(&Self::format_row(row, &max_widths));
+            let fields: Vec<String> = row.iter()
+                .map(|value| match value {
+                    DataType::Null => String::new(),
+                    value => Self::escape_csv_field(&value.to_string()),
+                })
+                .collect();
+            result.push_str(&fields.join(","));
             result.push('\n');
         }
         
         result
     }
     
+    /// 格式化为JSON文本：{"columns": [...], "rows": [[...], ...]}
+    /// 列保持原来的顺序，整数和浮点数输出为数字，字符串输出为字符串，NULL 输出为 null
+    pub fn format_json(headers: &[String], rows: &[Vec<DataType>], pretty: bool) -> String {
+        let rows: Vec<Vec<serde_json::Value>> = rows.iter()
+            .map(|row| row.iter().map(Self::json_value).collect())
+            .collect();
+        let value = serde_json::json!({ "columns": headers, "rows": rows });
+        let mut result = if pretty { serde_json::to_string_pretty(&value) } else { serde_json::to_string(&value) }
+            .expect("JSON 值的序列化不会失败");
+        result.push('\n');
+        result
+    }
+    
+    /// 单元格对应的JSON值，NaN 和无穷大不能表示为JSON数字，输出为 null
+    fn json_value(value: &DataType) -> serde_json::Value {
+        match value {
+            DataType::Int(n) => serde_json::Value::from(*n),
+            DataType::Float(n) => serde_json::Number::from_f64(*n).map_or(serde_json::Value::Null, serde_json::Value::Number),
+            DataType::Varchar(s) => serde_json::Value::String(s.clone()),
+            DataType::Null => serde_json::Value::Null,
+        }
+    }
+    
+    /// 必要时为CSV字段加引号
+    fn escape_csv_field(field: &str) -> String {
+        if field.is_empty() || field.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", field.replace('"', "\"\""))
+        } else {
+            field.to_string()
+        }
+    }
+    
+    /// 字符串在终端中的显示宽度，东亚全角字符占2列，其余字符占1列
+    pub fn display_width(text: &str) -> usize {
+        text.chars().map(Self::char_width).sum()
+    }
+    
+    fn char_width(c: char) -> usize {
+        match c as u32 {
+            0x1100..=0x115F | 0x2E80..=0x303E | 0x3041..=0xA4CF | 0xAC00..=0xD7A3 | 0xF900..=0xFAFF
+            | 0xFE30..=0xFE4F | 0xFF00..=0xFF60 | 0xFFE0..=0xFFE6 | 0x20000..=0x3FFFD => 2,
+            _ => 1,
+        }
+    }
+    
+    /// 截断到不超过 max_width 的显示宽度，被截断时以 '…' 结尾
+    fn truncate_to_width(text: &str, max_width: usize) -> String {
+        // NULL 显示为空白，不需要截断
+        if text == "NULL" || Self::display_width(text) <= max_width {
+            return text.to_string();
+        }
+        
+        let mut truncated = String::new();
+        let mut width = 0;
+        for c in text.chars() {
+            // 为省略号保留1列
+            if width + Self::char_width(c) + 1 > max_width {
+                break;
+            }
+            width += Self::char_width(c);
+            truncated.push(c);
+        }
+        truncated.push('…');
+        truncated
+    }
+    
     /// 格式化单行数据
-    fn format_row(cells: &[String], widths: &[usize]) -> String {
+    fn format_row(cells: &[String], widths: &[usize], border: char) -> String {
         let mut row_line = String::new();
-        row_line.push('|');
+        row_line.push(border);
         
         for (i, cell) in cells.iter().enumerate() {
             if i < widths.len() {
@@ -75,18 +243,13 @@ impl TableFormatter {
                 let display_cell = if cell == "NULL" { "" } else { cell };
                 
                 // 计算需要的填充空格
-                let padding = widths[i] - display_cell.len();
+                let padding = widths[i] - Self::display_width(display_cell);
                 
                 // 确保左右各有一个空格，内容左对齐
                 row_line.push(' ');
                 row_line.push_str(display_cell);
                 row_line.push_str(&" ".repeat(padding + 1)); // +1 确保右侧至少有一个空格
-                
-                if i < cells.len() - 1 {
-                    row_line.push('|');
-                } else {
-                    row_line.push('|');
-                }
+                row_line.push(border);
             }
         }
         
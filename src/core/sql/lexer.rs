@@ -25,6 +25,10 @@ pub enum Token {
     By,     // ORDER BY 子句的 BY
     Asc,    // 升序排序
     Desc,   // 降序排序
+    Explain, // 显示执行计划
+    Describe, // 显示表结构
+    Alter,    // 修改表结构
+    Copy,     // 导入/导出数据
     // 操作符
     Eq,    // =
     Ne,    // !=
@@ -32,6 +36,7 @@ pub enum Token {
     Lt,    // <
     Ge,    // >=
     Le,    // <=
+    NullSafeEq, // <=>，NULL 与 NULL 视为相等
     // 算术运算符
     Plus,     // +
     Minus,    // -
@@ -126,6 +131,10 @@ impl Lexer {
                     "BY" => Token::By,       // ORDER BY 子句的 BY
                     "ASC" => Token::Asc,     // 升序排序
                     "DESC" => Token::Desc,   // 降序排序
+                    "EXPLAIN" => Token::Explain, // 显示执行计划
+                    "DESCRIBE" => Token::Describe, // 显示表结构
+                    "ALTER" => Token::Alter,       // 修改表结构
+                    "COPY" => Token::Copy,         // 导入/导出数据
                     _ => Token::Identifier(identifier),
                 };
                 tokens.push(token);
@@ -134,9 +143,9 @@ impl Lexer {
 
             // 处理数字
             if c.is_digit(10) || (c == '.' && self.peek().map_or(false, |next| next.is_digit(10))) {
-                let number_str = self.read_number_str();
-                if number_str.contains('.') {
-                    // 如果包含小数点，解析为浮点数
+                let number_str = self.read_number_str()?;
+                if number_str.contains(['.', 'e', 'E']) {
+                    // 如果包含小数点或指数，解析为浮点数
                     match number_str.parse::<f64>() {
                         Ok(f) => tokens.push(Token::Float(f)),
                         Err(_) => return Err(DbError::SqlError(format!("无效的浮点数: {}", number_str))),
@@ -151,11 +160,11 @@ impl Lexer {
                 continue;
             }
 
-            // 处理字符串 - 支持单引号和双引号
+            // 处理字符串 - 支持单引号和双引号，连续两个引号表示引号字符本身
             if c == '\'' || c == '"' {
                 let quote_char = c; // 记住是哪种引号
                 self.position += 1;
-                let string = self.read_until(quote_char);
+                let string = self.read_string(quote_char);
                 // 安全地移动位置，避免越界
                 if self.position < self.input.len() {
                 self.position += 1;
@@ -176,10 +185,19 @@ impl Lexer {
                     Token::Ge
                 }
                 '>' => Token::Gt,
+                '<' if self.peek() == Some('=') && self.input.chars().nth(self.position + 2) == Some('>') => {
+                    self.position += 2;
+                    Token::NullSafeEq
+                }
                 '<' if self.peek() == Some('=') => {
                     self.position += 1;
                     Token::Le
                 }
+                // <> 与 != 相同
+                '<' if self.peek() == Some('>') => {
+                    self.position += 1;
+                    Token::Ne
+                }
                 '<' => Token::Lt,
                 ',' => Token::Comma,
                 ';' => Token::Semicolon,
@@ -218,6 +236,15 @@ impl Lexer {
             if c.is_alphanumeric() || c == '_' {
                 identifier.push(c);
                 self.position += 1;
+            } else if c == '.' && self.peek().is_some_and(|next| next.is_alphabetic() || next == '_') {
+                // 表名限定的列名，如 users.id
+                identifier.push(c);
+                self.position += 1;
+            } else if c == '.' && !identifier.is_empty() && self.peek() == Some('*') {
+                // 表名限定的星号，如 users.*，由执行器展开为该表的全部列
+                identifier.push_str(".*");
+                self.position += 2;
+                break;
             } else {
                 break;
             }
@@ -225,7 +252,7 @@ impl Lexer {
         identifier
     }
 
-    fn read_number_str(&mut self) -> String {
+    fn read_number_str(&mut self) -> Result<String, DbError> {
         let mut number = String::new();
         let mut has_decimal = false;
         
@@ -244,11 +271,29 @@ impl Lexer {
                 has_decimal = true;
                 number.push(c);
                 self.position += 1;
+            } else if c == 'e' || c == 'E' {
+                // 科学计数法，指数部分可以带正负号
+                number.push(c);
+                self.position += 1;
+                if let Some(sign) = self.input.chars().nth(self.position).filter(|ch| *ch == '+' || *ch == '-') {
+                    number.push(sign);
+                    self.position += 1;
+                }
+                let mut has_exponent = false;
+                while let Some(digit) = self.input.chars().nth(self.position).filter(|ch| ch.is_ascii_digit()) {
+                    number.push(digit);
+                    self.position += 1;
+                    has_exponent = true;
+                }
+                if !has_exponent {
+                    return Err(DbError::SqlError(format!("无效的浮点数: {}，指数部分缺少数字", number)));
+                }
+                break;
             } else {
                 break;
             }
         }
-        number
+        Ok(number)
     }
 
     fn read_until(&mut self, end: char) -> String {
@@ -269,6 +314,17 @@ impl Lexer {
         result
     }
     
+    // 读取引号内的字符串，'' 表示一个单引号（双引号同理），结束时停在结尾的引号上
+    fn read_string(&mut self, quote: char) -> String {
+        let mut result = self.read_until(quote);
+        while self.peek() == Some(quote) {
+            result.push(quote);
+            self.position += 2; // 跳过两个引号
+            result.push_str(&self.read_until(quote));
+        }
+        result
+    }
+    
     // 读取多行注释，直到遇到 */
     fn read_until_multiline_comment_end(&mut self) -> String {
         let mut result = String::new();
@@ -291,6 +347,6 @@ impl Lexer {
 
     // 保留原来的read_number方法以兼容现有代码
     fn read_number(&mut self) -> i32 {
-        self.read_number_str().parse().unwrap_or(0)
+        self.read_number_str().ok().and_then(|s| s.parse().ok()).unwrap_or(0)
     }
 } 
\ No newline at end of file
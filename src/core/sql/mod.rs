@@ -2,22 +2,41 @@ mod lexer;
 mod parser;
 mod executor;
 mod formatter;
+mod script;
+mod sample;
 
 pub use lexer::{Token, Lexer};
-pub use parser::Parser;
-pub use executor::SqlExecutor;
-pub use formatter::TableFormatter;
+pub use parser::{Parser, DEFAULT_MAX_DEPTH};
+pub use executor::{SqlExecutor, RowIter, query_rows};
+pub use formatter::{OutputFormat, TableFormatter, TableStyle};
+pub use script::{parse_delimiter_command, split_statements, split_statements_with_delimiter, ScriptItem, ScriptSplitter};
+pub use sample::{sample_rows, SampleRng, SampleSize};
 
+use crate::core::clock::Clock;
 use crate::core::error::DbError;
-use crate::core::types::{DataType, Column};
+use crate::core::types::{DataType, Column, ColumnType};
+pub use crate::core::types::ArithmeticOperator;
 use crate::core::storage::Storage;
 
 // SQL语句类型
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum SqlStatement {
     CreateTable {
         name: String,
         columns: Vec<Column>,
+        options: TableOptions,
+    },
+    CreateTableLike {
+        name: String,
+        source: String, // 复制其表结构的表
+    },
+    CreateIndex {
+        name: String,
+        table: String,
+        column: String,
+    },
+    DropIndex {
+        name: String,
     },
     DropTable {
         name: String,
@@ -28,15 +47,18 @@ pub enum SqlStatement {
     Insert {
         table: String,
         values: Vec<DataType>,
+        ignore_conflicts: bool, // ON CONFLICT DO NOTHING
     },
     InsertMultiple {
         table: String,
         rows: Vec<Vec<DataType>>,
+        ignore_conflicts: bool,
     },
     InsertWithColumns {
         table: String,
         columns: Vec<String>,
         rows: Vec<Vec<DataType>>,
+        ignore_conflicts: bool,
     },
     Update {
         table: String,
@@ -55,19 +77,80 @@ pub enum SqlStatement {
     },
     SelectExpression {
         expressions: Vec<Expression>,
+        aliases: Vec<Option<String>>, // 与 expressions 一一对应的 AS 列别名
         original_sql: String,
     },
     SelectWithExpressions {
         expressions: Vec<Expression>,
+        aliases: Vec<Option<String>>, // 与 expressions 一一对应的 AS 列别名
         table: String,
         where_clause: Option<WhereClause>,
+        group_by: Vec<String>,
+        having: Option<WhereClause>,
         order_by: Option<OrderBy>,
         original_sql: String,
     },
+    Union {
+        left: Box<SqlStatement>,
+        right: Box<SqlStatement>,
+        all: bool, // UNION ALL 保留重复行
+    },
+    Explain {
+        statement: Box<SqlStatement>,
+    },
+    Sample {
+        statement: Box<SqlStatement>,
+        size: SampleSize,
+        seed: u64, // 随机种子，执行前由数据库设置
+    },
+    Describe {
+        table: String,
+    },
+    ShowIndexes {
+        table: String,
+    },
+    AlterTable {
+        table: String,
+        action: AlterAction,
+    },
+    CopyFrom {
+        table: String,
+        path: String,
+        header: bool, // 第一行是表头，按列名而不是位置对应
+    },
+    CopyTo {
+        query: Box<SqlStatement>, // COPY t TO 时等价于 SELECT * FROM t
+        path: String,
+    },
+    Returning {
+        statement: Box<SqlStatement>, // 带 RETURNING 子句的 INSERT、UPDATE 或 DELETE
+        columns: Vec<String>,         // 要返回的列，["*"] 表示所有列
+    },
+}
+
+// ALTER TABLE 的具体操作
+#[derive(Debug, Clone)]
+pub enum AlterAction {
+    RenameColumn {
+        old_name: String,
+        new_name: String,
+    },
+    ModifyColumn {
+        column: String,
+        data_type: ColumnType,
+    },
+}
+
+// 建表语句末尾的表选项
+#[derive(Debug, Clone, Default)]
+pub struct TableOptions {
+    pub max_rows: Option<usize>,  // MAX_ROWS = n
+    pub comment: Option<String>,  // COMMENT = 'text'
+    pub row_version: bool,        // ROW_VERSION = ON，为每行维护隐藏的版本号
 }
 
 // WHERE子句
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum WhereClause {
     Simple {
         column: String,
@@ -87,10 +170,22 @@ pub enum WhereClause {
         left: Box<WhereClause>,
         right: Box<WhereClause>,
     },
+    In {
+        left: Box<Expression>,
+        list: InList,
+        negated: bool, // NOT IN
+    },
+}
+
+// IN 谓词右侧的取值集合
+#[derive(Debug, Clone)]
+pub enum InList {
+    Values(Vec<Expression>),
+    Subquery(Box<SqlStatement>), // 执行前会被替换为 Values
 }
 
 // 操作符
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Operator {
     Eq,
     Ne,
@@ -98,12 +193,28 @@ pub enum Operator {
     Lt,
     Ge,
     Le,
+    NullSafeEq, // <=>：两侧都为 NULL 时为真，只有一侧为 NULL 时为假
     IsNull,
     IsNotNull,
+    // IS [NOT] TRUE / FALSE：数值非零为真、零为假，NULL 既不是真也不是假
+    IsTrue,
+    IsNotTrue,
+    IsFalse,
+    IsNotFalse,
+}
+
+impl Operator {
+    // 只有左侧操作数的操作符（IS NULL、IS TRUE 等），右侧为 NULL 占位
+    pub fn is_unary(&self) -> bool {
+        matches!(
+            self,
+            Operator::IsNull | Operator::IsNotNull | Operator::IsTrue | Operator::IsNotTrue | Operator::IsFalse | Operator::IsNotFalse
+        )
+    }
 }
 
 // 表达式
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Expression {
     Literal(DataType),
     Column(String),
@@ -112,31 +223,45 @@ pub enum Expression {
         operator: ArithmeticOperator,
         right: Box<Expression>,
     },
+    Aggregate {
+        function: AggregateFunction,
+        argument: Option<Box<Expression>>, // None 表示 COUNT(*)
+        distinct: bool,                    // 是否只对去重后的值计算
+    },
+    Subquery(Box<SqlStatement>), // 标量子查询，可引用外层查询的列
 }
 
-// 算术运算符
-#[derive(Debug, PartialEq)]
-pub enum ArithmeticOperator {
-    Add,     // +
-    Subtract, // -
-    Multiply, // *
-    Divide,   // /
+// 聚合函数
+#[derive(Debug, Clone, PartialEq)]
+pub enum AggregateFunction {
+    Count,
+    Sum,
+    Avg,
+    Min,
+    Max,
 }
 
 // 排序方向
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum SortDirection {
     Asc,
     Desc,
 }
 
 // 排序子句
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct OrderBy {
     pub column: String,
     pub direction: SortDirection,
 }
 
+/// 解析结果，包括语句和语句中按出现顺序排列的注释（Token::Comment 或 Token::MultiLineComment）
+#[derive(Debug, Clone)]
+pub struct ParseOutput {
+    pub statement: SqlStatement,
+    pub comments: Vec<Token>,
+}
+
 // SQL解析器
 pub struct SqlParser {
     lexer: lexer::Lexer,
@@ -151,8 +276,29 @@ impl SqlParser {
         }
     }
 
+    // 设置 CURRENT_DATE 和 CURRENT_TIMESTAMP 使用的时钟
+    pub fn set_clock(&mut self, clock: Clock) {
+        self.parser.set_clock(clock);
+    }
+
+    // 设置表达式和条件允许的最大嵌套深度，默认为 DEFAULT_MAX_DEPTH
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.parser.set_max_depth(max_depth);
+    }
+
     pub fn parse(&mut self, sql: &str) -> Result<SqlStatement, DbError> {
         let tokens = self.lexer.tokenize(sql)?;
         self.parser.parse(tokens, sql)
     }
+
+    // 解析语句并保留其中的注释，供需要保留文档的工具使用；parse 会丢弃注释
+    pub fn parse_with_comments(&mut self, sql: &str) -> Result<ParseOutput, DbError> {
+        let tokens = self.lexer.tokenize(sql)?;
+        let comments = tokens.iter()
+            .filter(|token| matches!(token, Token::Comment(_) | Token::MultiLineComment(_)))
+            .cloned()
+            .collect();
+        let statement = self.parser.parse(tokens, sql)?;
+        Ok(ParseOutput { statement, comments })
+    }
 } 
\ No newline at end of file
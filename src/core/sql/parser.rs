@@ -1,11 +1,22 @@
 use super::lexer::Token;
-use super::SqlStatement;
+use super::{SampleSize, SqlStatement};
+use crate::core::clock::{format_date, format_timestamp, system_clock, Clock};
 use crate::core::error::DbError;
-use crate::core::types::{Column, ColumnType, DataType};
+use crate::core::types::{Column, ColumnDefault, ColumnType, DataType, VERSION_COLUMN};
+
+// TEXT 类型映射为 VARCHAR 时使用的长度
+const TEXT_LENGTH: usize = 65535;
+
+// 表达式和条件默认允许的最大嵌套深度，防止过深的输入导致栈溢出
+pub const DEFAULT_MAX_DEPTH: usize = 256;
 
 pub struct Parser {
     tokens: Vec<Token>,
     position: usize,
+    depth: usize,     // 当前表达式和条件的嵌套深度
+    max_depth: usize, // 允许的最大嵌套深度
+    too_deep: bool,   // 本次解析是否超过了最大嵌套深度
+    clock: Clock,     // CURRENT_DATE 和 CURRENT_TIMESTAMP 使用的时钟
 }
 
 impl Parser {
@@ -13,8 +24,22 @@ impl Parser {
         Parser {
             tokens: Vec::new(),
             position: 0,
+            depth: 0,
+            max_depth: DEFAULT_MAX_DEPTH,
+            too_deep: false,
+            clock: system_clock(),
         }
     }
+    
+    // 设置 CURRENT_DATE 和 CURRENT_TIMESTAMP 使用的时钟
+    pub fn set_clock(&mut self, clock: Clock) {
+        self.clock = clock;
+    }
+    
+    // 设置表达式和条件允许的最大嵌套深度
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.max_depth = max_depth;
+    }
 
     pub fn parse(&mut self, tokens: Vec<Token>, original_sql: &str) -> Result<SqlStatement, DbError> {
         // 过滤掉所有注释Token
@@ -22,14 +47,29 @@ impl Parser {
             .filter(|token| !matches!(token, Token::Comment(_) | Token::MultiLineComment(_)))
             .collect();
         self.position = 0;
+        self.depth = 0;
+        self.too_deep = false;
         
         // 如果过滤后没有Token，返回空语句错误
         if self.tokens.is_empty() {
             return Err(DbError::SqlError("空语句或仅包含注释".to_string()));
         }
         
-        // 解析语句，并传递原始SQL
-        self.parse_statement(original_sql)
+        // 解析语句，并传递原始SQL；嵌套过深的错误不会被回溯尝试的其他错误掩盖
+        let statement = match self.parse_statement(original_sql) {
+            Err(_) if self.too_deep => return Err(nesting_too_deep()),
+            result => result?,
+        };
+        
+        // 语句之后只允许一个结尾的分号
+        if matches!(self.peek(), Some(Token::Semicolon)) {
+            self.next();
+        }
+        if let Some(token) = self.peek() {
+            return Err(DbError::SqlError(format!("语句结束后存在多余的内容: {:?}", token)));
+        }
+        
+        Ok(statement)
     }
 
     fn parse_statement(&mut self, original_sql: &str) -> Result<SqlStatement, DbError> {
@@ -41,21 +81,47 @@ impl Parser {
             Some(Token::Insert) => self.parse_insert(),
             Some(Token::Update) => self.parse_update(),
             Some(Token::Delete) => self.parse_delete(),
-            Some(Token::Select) => {
-                // 检查下一个非空位置的 token
-                self.next(); // 消费 SELECT
+            Some(Token::Alter) => self.parse_alter_table(),
+            Some(Token::Copy) => self.parse_copy(original_sql),
+            Some(Token::Identifier(word)) if word.eq_ignore_ascii_case("SHOW") => self.parse_show(),
+            Some(Token::Describe) | Some(Token::Desc) => {
+                self.next(); // 消费 DESCRIBE / DESC
+                match self.next() {
+                    Some(Token::Identifier(table)) => Ok(SqlStatement::Describe { table }),
+                    _ => Err(DbError::SqlError("期望表名".to_string())),
+                }
+            },
+            Some(Token::Explain) => {
+                self.next(); // 消费 EXPLAIN
                 
-                // 保存当前位置以便回溯
-                let current_position = self.position;
+                // EXPLAIN 只能用于 SELECT 语句
+                if !matches!(self.peek(), Some(Token::Select)) {
+                    return Err(DbError::SqlError("EXPLAIN 只支持 SELECT 语句".to_string()));
+                }
                 
-                // 尝试解析表达式查询
-                if let Ok(expr_stmt) = self.parse_expression_select(original_sql) {
-                    return Ok(expr_stmt);
+                let statement = self.parse_statement(original_sql)?;
+                Ok(SqlStatement::Explain { statement: Box::new(statement) })
+            },
+            Some(Token::Select) => {
+                let left = self.parse_select(original_sql)?;
+                let left = self.parse_sample(left)?;
+                
+                // SELECT ... UNION [ALL] SELECT ...
+                if self.skip_keyword("UNION") {
+                    let all = self.skip_keyword("ALL");
+                    if !matches!(self.peek(), Some(Token::Select)) {
+                        return Err(DbError::SqlError("UNION 后期望 SELECT 语句".to_string()));
+                    }
+                    // 结果的表头取自左侧，右侧的原始SQL不需要单独截取
+                    let right = self.parse_statement("")?;
+                    return Ok(SqlStatement::Union {
+                        left: Box::new(left),
+                        right: Box::new(right),
+                        all,
+                    });
                 }
                 
-                // 如果不是表达式查询，恢复位置并解析普通查询
-                self.position = current_position;
-                self.parse_normal_select(original_sql)
+                Ok(left)
             },
             Some(token) => Err(DbError::SqlError(format!("意外的语句开始: {:?}", token))),
             None => Err(DbError::SqlError("空语句".to_string())),
@@ -64,31 +130,51 @@ impl Parser {
 
     fn parse_create_table(&mut self) -> Result<SqlStatement, DbError> {
         self.expect(Token::Create)?;
+        if self.skip_keyword("INDEX") {
+            return self.parse_create_index();
+        }
         self.expect(Token::Table)?;
         
-        let name = match self.next() {
-            Some(Token::Identifier(name)) => name,
+        let name = match self.next_identifier() {
+            Some(name) => name,
             _ => return Err(DbError::SqlError("期望表名".to_string())),
         };
 
+        // CREATE TABLE copy LIKE original 只复制表结构
+        if self.skip_keyword("LIKE") {
+            let source = self.next_identifier()
+                .ok_or_else(|| DbError::SqlError("期望表名".to_string()))?;
+            return Ok(SqlStatement::CreateTableLike { name, source });
+        }
+
         self.expect(Token::LParen)?;
         let mut columns = Vec::new();
+
+        // 拒绝没有任何列的表定义，如 CREATE TABLE t ()
+        if let Some(Token::RParen) = self.peek() {
+            return Err(DbError::SqlError("表必须至少有一个列".to_string()));
+        }
         
         loop {
-            let column_name = match self.next() {
-                Some(Token::Identifier(name)) => name,
+            let column_name = match self.next_identifier() {
+                Some(name) => name,
                 _ => return Err(DbError::SqlError("期望列名".to_string())),
             };
 
             let data_type = self.parse_column_type()?;
             let nullable = self.parse_nullable()?;
+            let default = self.parse_default(&data_type)?;
             let primary_key = self.parse_primary_key()?;
+            let comment = self.parse_comment()?;
             
             columns.push(Column {
                 name: column_name,
                 data_type,
-                nullable,
+                // 主键列隐式为 NOT NULL
+                nullable: nullable && !primary_key,
                 primary_key,
+                comment,
+                default,
             });
 
             match self.peek() {
@@ -110,7 +196,92 @@ impl Parser {
             return Err(DbError::SqlError("表中只能有一个主键".to_string()));
         }
 
-        Ok(SqlStatement::CreateTable { name, columns })
+        // 解析表选项
+        let options = self.parse_table_options()?;
+        if options.row_version && columns.iter().any(|c| c.name == VERSION_COLUMN) {
+            return Err(DbError::SqlError(format!("开启 ROW_VERSION 的表不能包含名为 {} 的列", VERSION_COLUMN)));
+        }
+
+        Ok(SqlStatement::CreateTable { name, columns, options })
+    }
+
+    // 解析建表语句末尾的表选项，支持 MAX_ROWS = n、COMMENT = 'text' 和 ROW_VERSION = ON
+    fn parse_table_options(&mut self) -> Result<super::TableOptions, DbError> {
+        let mut options = super::TableOptions::default();
+        
+        while let Some(Token::Identifier(option)) = self.peek().cloned() {
+            self.next(); // 消费选项名
+            
+            // 等号是可选的
+            if let Some(Token::Eq) = self.peek() {
+                self.next();
+            }
+            
+            match option.to_uppercase().as_str() {
+                "MAX_ROWS" => {
+                    options.max_rows = match self.next() {
+                        Some(Token::Number(n)) if n >= 0 => Some(n as usize),
+                        _ => return Err(DbError::SqlError("期望MAX_ROWS的行数".to_string())),
+                    };
+                }
+                "COMMENT" => {
+                    options.comment = match self.next() {
+                        Some(Token::String(text)) => Some(text),
+                        _ => return Err(DbError::SqlError("期望注释字符串".to_string())),
+                    };
+                }
+                "ROW_VERSION" => {
+                    options.row_version = match self.next() {
+                        Some(Token::Identifier(value)) if value.eq_ignore_ascii_case("ON") => true,
+                        Some(Token::Identifier(value)) if value.eq_ignore_ascii_case("OFF") => false,
+                        Some(Token::Number(1)) => true,
+                        Some(Token::Number(0)) => false,
+                        _ => return Err(DbError::SqlError("ROW_VERSION 的取值应为 ON 或 OFF".to_string())),
+                    };
+                }
+                _ => return Err(DbError::SqlError(format!("未知的表选项: {}", option))),
+            }
+        }
+        
+        Ok(options)
+    }
+
+    // 解析列定义中可选的 DEFAULT 值，支持常量和 CURRENT_TIMESTAMP
+    fn parse_default(&mut self, data_type: &ColumnType) -> Result<Option<ColumnDefault>, DbError> {
+        if !self.skip_keyword("DEFAULT") {
+            return Ok(None);
+        }
+        
+        if self.skip_keyword("CURRENT_TIMESTAMP") {
+            // 时间以 "YYYY-MM-DD HH:MM:SS" 字符串保存
+            return match data_type {
+                ColumnType::Varchar(len) if *len >= 19 => Ok(Some(ColumnDefault::CurrentTimestamp)),
+                _ => Err(DbError::SqlError("CURRENT_TIMESTAMP 只能用于 VARCHAR(19) 及以上的列".to_string())),
+            };
+        }
+        if matches!(self.peek(), Some(Token::Identifier(ident)) if ident.eq_ignore_ascii_case("CURRENT_DATE")) {
+            return Err(DbError::SqlError("DEFAULT 不支持 CURRENT_DATE，请使用 CURRENT_TIMESTAMP".to_string()));
+        }
+        
+        let value = self.parse_value()?;
+        if !value.matches_column_type(data_type) {
+            return Err(DbError::SqlError(format!("默认值 {} 与列类型 {} 不匹配", value, data_type)));
+        }
+        Ok(Some(ColumnDefault::Value(value)))
+    }
+
+    // 解析列定义中可选的 COMMENT 'text'
+    fn parse_comment(&mut self) -> Result<Option<String>, DbError> {
+        if let Some(Token::Identifier(ident)) = self.peek() {
+            if ident.to_uppercase() == "COMMENT" {
+                self.next(); // 消费 COMMENT
+                return match self.next() {
+                    Some(Token::String(text)) => Ok(Some(text)),
+                    _ => Err(DbError::SqlError("期望注释字符串".to_string())),
+                };
+            }
+        }
+        Ok(None)
     }
 
     fn parse_column_type(&mut self) -> Result<ColumnType, DbError> {
@@ -154,6 +325,44 @@ impl Parser {
                         self.expect(Token::RParen)?;
                         Ok(ColumnType::Varchar(length))
                     }
+                    // 其他数据库常用的类型名，映射为已有的类型
+                    "REAL" => Ok(ColumnType::Float(None)),
+                    "DOUBLE" => {
+                        self.skip_keyword("PRECISION");
+                        Ok(ColumnType::Float(None))
+                    }
+                    "DECIMAL" | "NUMERIC" => {
+                        // 精度和小数位数只做语法检查，按浮点数存储
+                        if let Some(Token::LParen) = self.peek() {
+                            self.next(); // 消费左括号
+                            if !matches!(self.next(), Some(Token::Number(_))) {
+                                return Err(DbError::SqlError(format!("期望{}精度", type_name.to_uppercase())));
+                            }
+                            if let Some(Token::Comma) = self.peek() {
+                                self.next(); // 消费逗号
+                                if !matches!(self.next(), Some(Token::Number(_))) {
+                                    return Err(DbError::SqlError(format!("期望{}小数位数", type_name.to_uppercase())));
+                                }
+                            }
+                            self.expect(Token::RParen)?;
+                        }
+                        Ok(ColumnType::Float(None))
+                    }
+                    "TEXT" => Ok(ColumnType::Varchar(TEXT_LENGTH)),
+                    "CHAR" | "CHARACTER" => {
+                        // 不指定长度时为 1
+                        if let Some(Token::LParen) = self.peek() {
+                            self.next(); // 消费左括号
+                            let length = match self.next() {
+                                Some(Token::Number(n)) => n as usize,
+                                _ => return Err(DbError::SqlError("期望CHAR长度".to_string())),
+                            };
+                            self.expect(Token::RParen)?;
+                            Ok(ColumnType::Varchar(length))
+                        } else {
+                            Ok(ColumnType::Varchar(1))
+                        }
+                    }
                     _ => Err(DbError::SqlError(format!("未知数据类型: {}", type_name))),
                 }
             }
@@ -201,13 +410,230 @@ impl Parser {
         }
     }
 
+    fn parse_alter_table(&mut self) -> Result<SqlStatement, DbError> {
+        self.expect(Token::Alter)?;
+        self.expect(Token::Table)?;
+        
+        let table = match self.next_identifier() {
+            Some(name) => name,
+            _ => return Err(DbError::SqlError("期望表名".to_string())),
+        };
+        
+        match self.next() {
+            Some(Token::Identifier(action)) if action.to_uppercase() == "RENAME" => {
+                // COLUMN 关键字是可选的
+                self.skip_keyword("COLUMN");
+                
+                let old_name = match self.next_identifier() {
+                    Some(name) => name,
+                    _ => return Err(DbError::SqlError("期望列名".to_string())),
+                };
+                
+                if !self.skip_keyword("TO") {
+                    return Err(DbError::SqlError("期望TO关键字".to_string()));
+                }
+                
+                let new_name = match self.next_identifier() {
+                    Some(name) => name,
+                    _ => return Err(DbError::SqlError("期望新列名".to_string())),
+                };
+                
+                Ok(SqlStatement::AlterTable {
+                    table,
+                    action: super::AlterAction::RenameColumn { old_name, new_name },
+                })
+            }
+            Some(Token::Identifier(action)) if action.to_uppercase() == "MODIFY" => {
+                // COLUMN 关键字是可选的
+                self.skip_keyword("COLUMN");
+                
+                let column = match self.next_identifier() {
+                    Some(name) => name,
+                    _ => return Err(DbError::SqlError("期望列名".to_string())),
+                };
+                let data_type = self.parse_column_type()?;
+                
+                Ok(SqlStatement::AlterTable {
+                    table,
+                    action: super::AlterAction::ModifyColumn { column, data_type },
+                })
+            }
+            _ => Err(DbError::SqlError("不支持的ALTER TABLE操作".to_string())),
+        }
+    }
+
+    fn parse_copy(&mut self, original_sql: &str) -> Result<SqlStatement, DbError> {
+        self.expect(Token::Copy)?;
+        
+        // COPY (SELECT ...) TO 'path' 导出查询结果
+        if let Some(Token::LParen) = self.peek() {
+            self.next(); // 消费左括号
+            if !matches!(self.peek(), Some(Token::Select)) {
+                return Err(DbError::SqlError("COPY 的括号中期望 SELECT 语句".to_string()));
+            }
+            // 查询的原始SQL从 COPY 后的第一个左括号开始，导出的表头与直接查询时相同
+            let query_sql = original_sql.find('(').map_or("", |start| &original_sql[start + 1..]);
+            let query = self.parse_statement(query_sql)?;
+            self.expect(Token::RParen)?;
+            
+            if !self.skip_keyword("TO") {
+                return Err(DbError::SqlError("期望TO关键字".to_string()));
+            }
+            let path = self.parse_file_path()?;
+            return Ok(SqlStatement::CopyTo { query: Box::new(query), path });
+        }
+        
+        let table = match self.next_identifier() {
+            Some(name) => name,
+            _ => return Err(DbError::SqlError("期望表名".to_string())),
+        };
+        
+        if self.skip_keyword("TO") {
+            // COPY t TO 'path' 导出整张表
+            let path = self.parse_file_path()?;
+            let query = SqlStatement::Select {
+                columns: vec!["*".to_string()],
+                table,
+                where_clause: None,
+                order_by: None,
+            };
+            return Ok(SqlStatement::CopyTo { query: Box::new(query), path });
+        }
+        
+        self.expect(Token::From)?;
+        let path = self.parse_file_path()?;
+        
+        // 可选的 [WITH] HEADER 表示第一行是表头
+        let before_with = self.position;
+        let with = self.skip_keyword("WITH");
+        let header = self.skip_keyword("HEADER");
+        if with && !header {
+            self.position = before_with;
+        }
+        
+        Ok(SqlStatement::CopyFrom { table, path, header })
+    }
+
+    fn parse_file_path(&mut self) -> Result<String, DbError> {
+        match self.next() {
+            Some(Token::String(path)) => Ok(path),
+            _ => Err(DbError::SqlError("期望文件路径".to_string())),
+        }
+    }
+
+    // 在需要表名或列名的位置读取标识符
+    // 部分关键字（如 order、key）也可以作为表名或列名，按小写形式返回
+    fn next_identifier(&mut self) -> Option<String> {
+        let name = match self.peek()? {
+            Token::Identifier(name) => name.clone(),
+            token => keyword_as_identifier(token)?.to_string(),
+        };
+        self.next();
+        Some(name)
+    }
+
+    // 如果下一个标识符是指定的关键字（不区分大小写）则消费它
+    fn skip_keyword(&mut self, keyword: &str) -> bool {
+        if let Some(Token::Identifier(ident)) = self.peek() {
+            if ident.to_uppercase() == keyword {
+                self.next();
+                return true;
+            }
+        }
+        false
+    }
+
+    // 可选的 SAMPLE n [PERCENT] 子句，对查询结果随机抽样
+    fn parse_sample(&mut self, statement: SqlStatement) -> Result<SqlStatement, DbError> {
+        if !self.skip_keyword("SAMPLE") {
+            return Ok(statement);
+        }
+        if matches!(statement, SqlStatement::SelectExpression { .. }) {
+            return Err(DbError::SqlError("表达式查询不支持 SAMPLE 子句".to_string()));
+        }
+        
+        let amount = match self.next() {
+            Some(Token::Number(n)) if n >= 0 => n as f64,
+            Some(Token::Float(f)) if f >= 0.0 => f,
+            _ => return Err(DbError::SqlError("SAMPLE 后期望非负的数字".to_string())),
+        };
+        let size = if self.skip_keyword("PERCENT") {
+            if amount > 100.0 {
+                return Err(DbError::SqlError(format!("抽样百分比不能超过 100: {}", amount)));
+            }
+            SampleSize::Percent(amount)
+        } else if amount.fract() == 0.0 {
+            SampleSize::Rows(amount as usize)
+        } else {
+            return Err(DbError::SqlError(format!("抽样行数必须是整数: {}", amount)));
+        };
+        
+        Ok(SqlStatement::Sample { statement: Box::new(statement), size, seed: 0 })
+    }
+
+    // CREATE INDEX name ON table (column)，CREATE INDEX 已被消费
+    fn parse_create_index(&mut self) -> Result<SqlStatement, DbError> {
+        let name = match self.next() {
+            Some(Token::Identifier(name)) => name,
+            _ => return Err(DbError::SqlError("期望索引名".to_string())),
+        };
+        
+        if !self.skip_keyword("ON") {
+            return Err(DbError::SqlError("期望 ON".to_string()));
+        }
+        
+        let table = match self.next_identifier() {
+            Some(name) => name,
+            _ => return Err(DbError::SqlError("期望表名".to_string())),
+        };
+        
+        self.expect(Token::LParen)?;
+        let column = match self.next_identifier() {
+            Some(name) => name,
+            _ => return Err(DbError::SqlError("期望列名".to_string())),
+        };
+        if let Some(Token::Comma) = self.peek() {
+            return Err(DbError::SqlError("索引只支持单列".to_string()));
+        }
+        self.expect(Token::RParen)?;
+        
+        Ok(SqlStatement::CreateIndex { name, table, column })
+    }
+
+    // SHOW INDEXES FROM table，也可以写作 SHOW INDEX / SHOW KEYS，FROM 可以换成 IN
+    fn parse_show(&mut self) -> Result<SqlStatement, DbError> {
+        self.next(); // 消费 SHOW
+        if !(self.skip_keyword("INDEXES") || self.skip_keyword("INDEX") || self.skip_keyword("KEYS")) {
+            return Err(DbError::SqlError("SHOW 只支持 SHOW INDEXES FROM 表名".to_string()));
+        }
+        if matches!(self.peek(), Some(Token::From)) {
+            self.next();
+        } else if !self.skip_keyword("IN") {
+            return Err(DbError::SqlError("期望 FROM".to_string()));
+        }
+        
+        match self.next_identifier() {
+            Some(table) => Ok(SqlStatement::ShowIndexes { table }),
+            _ => Err(DbError::SqlError("期望表名".to_string())),
+        }
+    }
+
     fn parse_drop_table(&mut self) -> Result<SqlStatement, DbError> {
         self.expect(Token::Drop)?;
+        
+        // DROP INDEX name
+        if self.skip_keyword("INDEX") {
+            return match self.next_identifier() {
+                Some(name) => Ok(SqlStatement::DropIndex { name }),
+                _ => Err(DbError::SqlError("期望索引名".to_string())),
+            };
+        }
+        
         self.expect(Token::Table)?;
         
         // 解析第一个表名
-        let name = match self.next() {
-            Some(Token::Identifier(name)) => name,
+        let name = match self.next_identifier() {
+            Some(name) => name,
             _ => return Err(DbError::SqlError("期望表名".to_string())),
         };
 
@@ -220,36 +646,50 @@ impl Parser {
             while let Some(&Token::Comma) = self.peek() {
                 self.next(); // 消费逗号
                 
-                match self.next() {
-                    Some(Token::Identifier(name)) => names.push(name),
+                match self.next_identifier() {
+                    Some(name) => names.push(name),
                     _ => return Err(DbError::SqlError("期望表名".to_string())),
                 }
             }
             
+            self.parse_drop_behavior()?;
             Ok(SqlStatement::DropTables { names })
         } else {
             // 只有一个表名
+            self.parse_drop_behavior()?;
             Ok(SqlStatement::DropTable { name })
         }
     }
 
+    // 解析可选的 CASCADE / RESTRICT，便于执行来自其他数据库的脚本
+    // 目前没有依赖于表的对象，两者效果相同
+    fn parse_drop_behavior(&mut self) -> Result<(), DbError> {
+        if !self.skip_keyword("CASCADE") {
+            self.skip_keyword("RESTRICT");
+        }
+        Ok(())
+    }
+
     fn parse_insert(&mut self) -> Result<SqlStatement, DbError> {
         self.expect(Token::Insert)?;
         self.expect(Token::Into)?;
         
-        let table = match self.next() {
-            Some(Token::Identifier(name)) => name,
+        let table = match self.next_identifier() {
+            Some(name) => name,
             _ => return Err(DbError::SqlError("期望表名".to_string())),
         };
 
         // 检查是否有列名列表
         let columns = if let Some(&Token::LParen) = self.peek() {
             self.next(); // 消费左括号
+            if let Some(&Token::RParen) = self.peek() {
+                return Err(DbError::SqlError("列名列表不能为空".to_string()));
+            }
             
             let mut columns = Vec::new();
             loop {
-                match self.next() {
-                    Some(Token::Identifier(col_name)) => columns.push(col_name),
+                match self.next_identifier() {
+                    Some(col_name) => columns.push(col_name),
                     _ => return Err(DbError::SqlError("期望列名".to_string())),
                 }
                 
@@ -273,107 +713,129 @@ impl Parser {
 
         self.expect(Token::Values)?;
         
-        // 检查是否有多行值
-        let mut rows = Vec::new();
-        let mut first_row = Vec::new();
+        // VALUES 后至少有一行，多行之间以逗号分隔
+        if !matches!(self.peek(), Some(&Token::LParen)) {
+            return Err(DbError::SqlError("VALUES 后缺少要插入的行".to_string()));
+        }
+        let mut rows = vec![self.parse_value_row(1)?];
+        while let Some(&Token::Comma) = self.peek() {
+            self.next(); // 消费逗号
+            if !matches!(self.peek(), Some(&Token::LParen)) {
+                return Err(DbError::SqlError("多行插入时期望左括号".to_string()));
+            }
+            rows.push(self.parse_value_row(rows.len() + 1)?);
+        }
         
-        // 处理第一行
-        self.expect(Token::LParen)?;
-        loop {
-            let value = self.parse_value()?;
-            first_row.push(value);
+        let ignore_conflicts = self.parse_on_conflict()?;
 
-            let next_token = self.peek().cloned();
-            match next_token {
-                Some(Token::Comma) => {
-                    self.next();
-                    continue;
-                }
-                Some(Token::RParen) => {
-                    self.next();
-                    break;
-                }
-                _ => return Err(DbError::SqlError("期望逗号或右括号".to_string())),
-            }
+        // 带列名的插入、单行插入或多行插入
+        let statement = match columns {
+            Some(columns) => SqlStatement::InsertWithColumns { table, columns, rows, ignore_conflicts },
+            None if rows.len() == 1 => SqlStatement::Insert { table, values: rows.remove(0), ignore_conflicts },
+            None => SqlStatement::InsertMultiple { table, rows, ignore_conflicts },
+        };
+        self.parse_returning(statement)
+    }
+
+    // 可选的 RETURNING * 或 RETURNING col, ... 子句，返回语句写入的行
+    fn parse_returning(&mut self, statement: SqlStatement) -> Result<SqlStatement, DbError> {
+        if !self.skip_keyword("RETURNING") {
+            return Ok(statement);
+        }
+        if matches!(self.peek(), Some(&Token::Asterisk)) {
+            self.next(); // 消费 *
+            return Ok(SqlStatement::Returning { statement: Box::new(statement), columns: vec!["*".to_string()] });
         }
-        rows.push(first_row);
         
-        // 检查是否是多行插入
-        let next_token = self.peek().cloned();
-        if let Some(Token::Comma) = next_token {
-            // 多行插入
-            while let Some(Token::Comma) = self.peek().cloned() {
-                self.next(); // 消费逗号
-                
-                // 可能有些空白，但我们期望接下来是左括号
-                let next_token = self.peek().cloned();
-                if let Some(Token::LParen) = next_token {
-                    self.next(); // 消费左括号
-                    
-                    let mut row_values = Vec::new();
-                    loop {
-                        let value = self.parse_value()?;
-                        row_values.push(value);
-
-                        let next_token = self.peek().cloned();
-                        match next_token {
-                            Some(Token::Comma) => {
-                                self.next();
-                                continue;
-                            }
-                            Some(Token::RParen) => {
-                                self.next();
-                                break;
-                            }
-                            _ => return Err(DbError::SqlError("期望逗号或右括号".to_string())),
-                        }
-                    }
-                    rows.push(row_values);
-                } else {
-                    return Err(DbError::SqlError("多行插入时期望左括号".to_string()));
-                }
+        let mut columns = Vec::new();
+        loop {
+            match self.next_identifier() {
+                Some(column) => columns.push(column),
+                None => return Err(DbError::SqlError("RETURNING 后期望 * 或列名".to_string())),
             }
-            
-            // 返回带列名的多行插入或普通多行插入
-            if let Some(cols) = columns {
-                return Ok(SqlStatement::InsertWithColumns { 
-                    table, 
-                    columns: cols, 
-                    rows 
-                });
-            } else {
-                return Ok(SqlStatement::InsertMultiple { table, rows });
+            if !matches!(self.peek(), Some(&Token::Comma)) {
+                break;
             }
-        } else {
-            // 单行插入
-            if let Some(cols) = columns {
-                return Ok(SqlStatement::InsertWithColumns { 
-                    table, 
-                    columns: cols, 
-                    rows: vec![rows[0].clone()] 
-                });
-            } else {
-                return Ok(SqlStatement::Insert { table, values: rows[0].clone() });
+            self.next(); // 消费逗号
+        }
+        Ok(SqlStatement::Returning { statement: Box::new(statement), columns })
+    }
+
+    // 解析 VALUES 中括号内的一行值，row_number 从1开始，用于错误信息
+    fn parse_value_row(&mut self, row_number: usize) -> Result<Vec<DataType>, DbError> {
+        self.expect(Token::LParen)?;
+        if matches!(self.peek(), Some(&Token::RParen)) {
+            return Err(DbError::SqlError(format!("VALUES 的第 {} 行没有值", row_number)));
+        }
+        
+        let mut values = Vec::new();
+        loop {
+            values.push(self.parse_value()?);
+            match self.next() {
+                Some(Token::Comma) => continue,
+                Some(Token::RParen) => break,
+                _ => return Err(DbError::SqlError("期望逗号或右括号".to_string())),
             }
         }
+        Ok(values)
+    }
+
+    // 解析可选的 ON CONFLICT DO NOTHING 子句，返回是否忽略冲突的行
+    fn parse_on_conflict(&mut self) -> Result<bool, DbError> {
+        if !self.skip_keyword("ON") {
+            return Ok(false);
+        }
+        if !self.skip_keyword("CONFLICT") {
+            return Err(DbError::SqlError("ON 后期望 CONFLICT".to_string()));
+        }
+        if !(self.skip_keyword("DO") && self.skip_keyword("NOTHING")) {
+            return Err(DbError::SqlError("ON CONFLICT 后期望 DO NOTHING".to_string()));
+        }
+        Ok(true)
     }
 
     fn parse_value(&mut self) -> Result<DataType, DbError> {
+        if let Some(value) = self.parse_current_time()? {
+            return Ok(value);
+        }
         match self.next() {
             Some(Token::Number(n)) => Ok(DataType::Int(n)),
             Some(Token::Float(f)) => Ok(DataType::Float(f)),
             Some(Token::String(s)) => Ok(DataType::Varchar(s)),
             Some(Token::Null) => Ok(DataType::Null),
             Some(Token::Identifier(ident)) if ident.to_uppercase() == "NULL" => Ok(DataType::Null),
+            // 负数
+            Some(Token::Minus) => match self.next() {
+                Some(Token::Number(n)) => Ok(DataType::Int(-n)),
+                Some(Token::Float(f)) => Ok(DataType::Float(-f)),
+                _ => Err(DbError::SqlError("期望数字".to_string())),
+            },
             _ => Err(DbError::SqlError("期望值".to_string())),
         }
     }
 
+    // CURRENT_DATE 和 CURRENT_TIMESTAMP（可以带空括号）在解析时取当前时间，同一条语句中的值相同
+    // 日期为 "YYYY-MM-DD"，时间为 "YYYY-MM-DD HH:MM:SS"，都是 UTC 时间的字符串
+    fn parse_current_time(&mut self) -> Result<Option<DataType>, DbError> {
+        let value = if self.skip_keyword("CURRENT_DATE") {
+            format_date((self.clock)())
+        } else if self.skip_keyword("CURRENT_TIMESTAMP") {
+            format_timestamp((self.clock)())
+        } else {
+            return Ok(None);
+        };
+        if let Some(&Token::LParen) = self.peek() {
+            self.next();
+            self.expect(Token::RParen)?;
+        }
+        Ok(Some(DataType::Varchar(value)))
+    }
+
     fn parse_update(&mut self) -> Result<SqlStatement, DbError> {
         self.expect(Token::Update)?;
         
-        let table = match self.next() {
-            Some(Token::Identifier(name)) => name,
+        let table = match self.next_identifier() {
+            Some(name) => name,
             _ => return Err(DbError::SqlError("期望表名".to_string())),
         };
 
@@ -381,8 +843,8 @@ impl Parser {
         
         let mut set = Vec::new();
         loop {
-            let column = match self.next() {
-                Some(Token::Identifier(name)) => name,
+            let column = match self.next_identifier() {
+                Some(name) => name,
                 _ => return Err(DbError::SqlError("期望列名".to_string())),
             };
 
@@ -396,6 +858,7 @@ impl Parser {
                     continue;
                 }
                 Some(&Token::Where) => break,
+                Some(Token::Identifier(word)) if word.eq_ignore_ascii_case("RETURNING") => break,
                 _ => return Err(DbError::SqlError("期望逗号或WHERE子句".to_string())),
             }
         }
@@ -406,15 +869,15 @@ impl Parser {
             None
         };
 
-        Ok(SqlStatement::Update { table, set, where_clause })
+        self.parse_returning(SqlStatement::Update { table, set, where_clause })
     }
 
     fn parse_delete(&mut self) -> Result<SqlStatement, DbError> {
         self.expect(Token::Delete)?;
         self.expect(Token::From)?;
         
-        let table = match self.next() {
-            Some(Token::Identifier(name)) => name,
+        let table = match self.next_identifier() {
+            Some(name) => name,
             _ => return Err(DbError::SqlError("期望表名".to_string())),
         };
 
@@ -424,21 +887,54 @@ impl Parser {
             None
         };
 
-        Ok(SqlStatement::Delete { table, where_clause })
+        self.parse_returning(SqlStatement::Delete { table, where_clause })
+    }
+
+    fn parse_select(&mut self, original_sql: &str) -> Result<SqlStatement, DbError> {
+        // 检查下一个非空位置的 token
+        self.next(); // 消费 SELECT
+        
+        // 保存当前位置以便回溯
+        let current_position = self.position;
+        
+        // 尝试解析表达式查询
+        if let Ok(expr_stmt) = self.parse_expression_select(original_sql) {
+            // 没有 FROM 的表达式查询不能带过滤、分组或排序子句
+            match self.peek() {
+                Some(Token::Where) => {
+                    return Err(DbError::SqlError("表达式查询不支持 WHERE 子句".to_string()));
+                }
+                Some(Token::Order) => {
+                    return Err(DbError::SqlError("表达式查询不支持 ORDER BY 子句".to_string()));
+                }
+                Some(Token::Identifier(ident)) if ident.eq_ignore_ascii_case("GROUP") => {
+                    return Err(DbError::SqlError("表达式查询不支持 GROUP BY 子句".to_string()));
+                }
+                _ => {}
+            }
+            return Ok(expr_stmt);
+        }
+        
+        // 如果不是表达式查询，恢复位置并解析普通查询
+        self.position = current_position;
+        self.parse_normal_select(original_sql)
     }
 
     fn parse_expression_select(&mut self, original_sql: &str) -> Result<SqlStatement, DbError> {
         let mut expressions = Vec::new();
+        let mut aliases = Vec::new();
         
         // 解析第一个表达式
         let expr = self.parse_expression()?;
         expressions.push(expr);
+        aliases.push(self.parse_column_alias()?);
         
         // 检查是否有更多的表达式 (以逗号分隔)
         while let Some(Token::Comma) = self.peek().cloned() {
             self.next(); // 消费逗号
             let expr = self.parse_expression()?;
             expressions.push(expr);
+            aliases.push(self.parse_column_alias()?);
         }
         
         // 表达式查询不能有 FROM 子句
@@ -448,65 +944,80 @@ impl Parser {
         
         Ok(SqlStatement::SelectExpression { 
             expressions,
+            aliases,
             original_sql: original_sql.to_string()
         })
     }
     
+    // 选择列表中表达式后可选的 AS 列别名
+    fn parse_column_alias(&mut self) -> Result<Option<String>, DbError> {
+        if !self.skip_keyword("AS") {
+            return Ok(None);
+        }
+        match self.next_identifier() {
+            Some(alias) => Ok(Some(alias)),
+            None => Err(DbError::SqlError("AS 之后期望列别名".to_string())),
+        }
+    }
+    
     fn parse_expression(&mut self) -> Result<super::Expression, DbError> {
-        self.parse_binary_expression()
+        self.nested(|parser| parser.parse_binary_expression())
+    }
+    
+    // 进入一层嵌套解析，超过最大深度后本次解析中的所有嵌套解析都立即失败
+    fn nested<T>(&mut self, parse: impl FnOnce(&mut Self) -> Result<T, DbError>) -> Result<T, DbError> {
+        if self.too_deep || self.depth >= self.max_depth {
+            self.too_deep = true;
+            return Err(nesting_too_deep());
+        }
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
     }
     
     fn parse_binary_expression(&mut self) -> Result<super::Expression, DbError> {
-        let left = self.parse_primary_expression()?;
-        
-        // 检查是否有运算符，先获取token的拷贝避免借用冲突
-        let next_token = self.peek().cloned();
-        
-        match next_token {
-            Some(Token::Plus) => {
-                self.next(); // 消费 +
-                let right = self.parse_expression()?;
-                Ok(super::Expression::Binary {
-                    left: Box::new(left),
-                    operator: super::ArithmeticOperator::Add,
-                    right: Box::new(right),
-                })
-            },
-            Some(Token::Minus) => {
-                self.next(); // 消费 -
-                let right = self.parse_expression()?;
-                Ok(super::Expression::Binary {
-                    left: Box::new(left),
-                    operator: super::ArithmeticOperator::Subtract,
-                    right: Box::new(right),
-                })
-            },
-            Some(Token::Asterisk) => {
-                self.next(); // 消费 *
-                let right = self.parse_expression()?;
-                Ok(super::Expression::Binary {
-                    left: Box::new(left),
-                    operator: super::ArithmeticOperator::Multiply,
-                    right: Box::new(right),
-                })
-            },
-            Some(Token::Slash) => {
-                self.next(); // 消费 /
-                let right = self.parse_expression()?;
-                Ok(super::Expression::Binary {
-                    left: Box::new(left),
-                    operator: super::ArithmeticOperator::Divide,
-                    right: Box::new(right),
-                })
-            },
-            _ => Ok(left),
+        // 循环读取运算符连接的各项，平铺的运算链不增加嵌套深度
+        let mut operands = vec![self.parse_primary_expression()?];
+        let mut operators = Vec::new();
+        loop {
+            let operator = match self.peek() {
+                Some(Token::Plus) => super::ArithmeticOperator::Add,
+                Some(Token::Minus) => super::ArithmeticOperator::Subtract,
+                Some(Token::Asterisk) => super::ArithmeticOperator::Multiply,
+                Some(Token::Slash) => super::ArithmeticOperator::Divide,
+                _ => break,
+            };
+            self.next(); // 消费运算符
+            operators.push(operator);
+            operands.push(self.parse_primary_expression()?);
         }
+        
+        // 从右向左组合为右结合的表达式树：a + (b + c)
+        let mut expr = operands.pop().expect("至少有一个操作数");
+        while let (Some(left), Some(operator)) = (operands.pop(), operators.pop()) {
+            expr = super::Expression::Binary {
+                left: Box::new(left),
+                operator,
+                right: Box::new(expr),
+            };
+        }
+        Ok(expr)
     }
     
     fn parse_primary_expression(&mut self) -> Result<super::Expression, DbError> {
         // 先获取当前token的拷贝而不是引用，避免借用冲突
+        if let Some(value) = self.parse_current_time()? {
+            return Ok(super::Expression::Literal(value));
+        }
         let current_token = self.peek().cloned();
         
+        // 可以作为列名的关键字
+        if let Some(name) = current_token.as_ref().and_then(keyword_as_identifier) {
+            self.next();
+            return Ok(super::Expression::Column(name.to_string()));
+        }
+        
         match current_token {
             Some(Token::Number(n)) => {
                 self.next(); // 消费数字
@@ -520,12 +1031,29 @@ impl Parser {
                 self.next(); // 消费字符串
                 Ok(super::Expression::Literal(crate::core::types::DataType::Varchar(s)))
             },
+            Some(Token::Null) => {
+                self.next(); // 消费 NULL
+                Ok(super::Expression::Literal(crate::core::types::DataType::Null))
+            },
             Some(Token::Identifier(name)) => {
                 self.next(); // 消费标识符
+                
+                // 标识符后紧跟左括号时按聚合函数解析
+                if let Some(&Token::LParen) = self.peek() {
+                    return self.parse_aggregate(&name);
+                }
                 Ok(super::Expression::Column(name))
             },
             Some(Token::LParen) => {
                 self.next(); // 消费左括号
+                
+                // 括号中是 SELECT 时按标量子查询解析
+                if let Some(&Token::Select) = self.peek() {
+                    let subquery = self.parse_statement("")?;
+                    self.expect(Token::RParen)?;
+                    return Ok(super::Expression::Subquery(Box::new(subquery)));
+                }
+                
                 let expr = self.parse_expression()?;
                 self.expect(Token::RParen)?;
                 Ok(expr)
@@ -534,14 +1062,43 @@ impl Parser {
         }
     }
     
+    // 解析聚合函数调用，如 COUNT(*)、SUM(DISTINCT price)
+    fn parse_aggregate(&mut self, name: &str) -> Result<super::Expression, DbError> {
+        let function = match name.to_uppercase().as_str() {
+            "COUNT" => super::AggregateFunction::Count,
+            "SUM" => super::AggregateFunction::Sum,
+            "AVG" => super::AggregateFunction::Avg,
+            "MIN" => super::AggregateFunction::Min,
+            "MAX" => super::AggregateFunction::Max,
+            _ => return Err(DbError::SqlError(format!("不支持的函数: {}", name))),
+        };
+        self.expect(Token::LParen)?;
+        
+        let distinct = self.skip_keyword("DISTINCT");
+        
+        // 只有 COUNT 可以使用 *
+        let argument = if let Some(&Token::Asterisk) = self.peek() {
+            if function != super::AggregateFunction::Count || distinct {
+                return Err(DbError::SqlError(format!("{}(*) 不受支持", name.to_uppercase())));
+            }
+            self.next(); // 消费 *
+            None
+        } else {
+            Some(Box::new(self.parse_expression()?))
+        };
+        
+        self.expect(Token::RParen)?;
+        Ok(super::Expression::Aggregate { function, argument, distinct })
+    }
+    
     fn parse_normal_select(&mut self, original_sql: &str) -> Result<SqlStatement, DbError> {
-        // 检查是否为星号(*)
-        if let Some(&Token::Asterisk) = self.peek() {
+        // 检查是否为单独的星号(*)，星号后跟其他列时按表达式列表解析
+        if let (Some(&Token::Asterisk), Some(&Token::From)) = (self.peek(), self.tokens.get(self.position + 1)) {
             self.next(); // 消耗星号
             
             self.expect(Token::From)?;
-            let table = match self.next() {
-                Some(Token::Identifier(name)) => name,
+            let table = match self.next_identifier() {
+                Some(name) => name,
                 _ => return Err(DbError::SqlError("期望表名".to_string())),
             };
 
@@ -565,29 +1122,39 @@ impl Parser {
         // 解析列表达式或列名
         let mut columns = Vec::new();
         let mut expressions = Vec::new();
+        let mut aliases = Vec::new();
         let mut has_expression = false;
         
         loop {
             // 保存当前位置以便回溯
             let current_position = self.position;
             
-            // 尝试解析为表达式
-            match self.parse_expression() {
-                Ok(expr) => {
-                    has_expression = true;
-                    expressions.push(expr);
-                },
-                Err(_) => {
-                    // 解析失败，回溯位置
-                    self.position = current_position;
-                    
-                    // 尝试解析为普通列名
-                    let column = match self.next() {
-                        Some(Token::Identifier(name)) => name,
-                        Some(Token::String(s)) => s,
-                        _ => return Err(DbError::SqlError("期望列名或表达式".to_string())),
-                    };
-                    columns.push(column);
+            // 星号与其他列混用时，由执行器展开为表的全部列
+            if let Some(&Token::Asterisk) = self.peek() {
+                self.next();
+                has_expression = true;
+                expressions.push(super::Expression::Column("*".to_string()));
+                aliases.push(None);
+            } else {
+                // 尝试解析为表达式
+                match self.parse_expression() {
+                    Ok(expr) => {
+                        has_expression = true;
+                        expressions.push(expr);
+                        aliases.push(self.parse_column_alias()?);
+                    },
+                    Err(_) => {
+                        // 解析失败，回溯位置
+                        self.position = current_position;
+                        
+                        // 尝试解析为普通列名
+                        let column = match self.next() {
+                            Some(Token::Identifier(name)) => name,
+                            Some(Token::String(s)) => s,
+                            _ => return Err(DbError::SqlError("期望列名或表达式".to_string())),
+                        };
+                        columns.push(column);
+                    }
                 }
             }
 
@@ -603,8 +1170,8 @@ impl Parser {
 
         self.expect(Token::From)?;
         
-        let table = match self.next() {
-            Some(Token::Identifier(name)) => name,
+        let table = match self.next_identifier() {
+            Some(name) => name,
             _ => return Err(DbError::SqlError("期望表名".to_string())),
         };
 
@@ -614,20 +1181,32 @@ impl Parser {
             None
         };
 
+        // 解析 GROUP BY 和 HAVING 子句
+        let group_by = self.parse_group_by()?;
+        let having = if self.skip_keyword("HAVING") {
+            Some(self.parse_or_condition()?)
+        } else {
+            None
+        };
+
         // 解析 ORDER BY 子句
         let order_by = self.parse_order_by()?;
 
-        // 如果有表达式，将所有列名转换为Column表达式
-        if has_expression {
+        // 如果有表达式或分组，将所有列名转换为Column表达式
+        if has_expression || !group_by.is_empty() || having.is_some() {
             // 将普通列名转换为Column表达式
             for col in columns {
                 expressions.push(super::Expression::Column(col));
+                aliases.push(None);
             }
             
             Ok(SqlStatement::SelectWithExpressions { 
                 expressions, 
+                aliases,
                 table, 
                 where_clause,
+                group_by,
+                having,
                 order_by,
                 original_sql: original_sql.to_string()
             })
@@ -648,48 +1227,49 @@ impl Parser {
     }
 
     fn parse_or_condition(&mut self) -> Result<super::WhereClause, DbError> {
-        let left = self.parse_and_condition()?;
+        self.nested(|parser| parser.parse_or_terms())
+    }
 
-        // 检查是否有 OR 关键字
-        if let Some(&Token::Or) = self.peek() {
+    // 循环读取 OR 连接的各项，平铺的条件链不增加嵌套深度
+    fn parse_or_terms(&mut self) -> Result<super::WhereClause, DbError> {
+        let mut terms = vec![self.parse_and_condition()?];
+        while let Some(&Token::Or) = self.peek() {
             self.next(); // 消费 OR
-            let right = self.parse_or_condition()?;
-            return Ok(super::WhereClause::Or {
-                left: Box::new(left),
-                right: Box::new(right),
-            });
+            terms.push(self.parse_and_condition()?);
         }
-
-        Ok(left)
+        Ok(fold_right(terms, |left, right| super::WhereClause::Or {
+            left: Box::new(left),
+            right: Box::new(right),
+        }))
     }
 
     fn parse_and_condition(&mut self) -> Result<super::WhereClause, DbError> {
-        let left = self.parse_condition()?;
-
-        // 检查是否有 AND 关键字
-        if let Some(&Token::And) = self.peek() {
+        let mut terms = vec![self.parse_condition()?];
+        while let Some(&Token::And) = self.peek() {
             self.next(); // 消费 AND
-            let right = self.parse_and_condition()?;
-            return Ok(super::WhereClause::And {
-                left: Box::new(left),
-                right: Box::new(right),
-            });
+            terms.push(self.parse_condition()?);
         }
-
-        Ok(left)
+        Ok(fold_right(terms, |left, right| super::WhereClause::And {
+            left: Box::new(left),
+            right: Box::new(right),
+        }))
     }
 
     fn parse_condition(&mut self) -> Result<super::WhereClause, DbError> {
-        // 处理括号中的条件
+        // 保存当前位置，以便尝试解析表达式
+        let current_position = self.position;
+
+        // 处理括号中的条件；若括号内不是完整条件（如 (a + 1) > 7），回溯后按表达式解析
         if let Some(&Token::LParen) = self.peek() {
             self.next(); // 消费左括号
-            let condition = self.parse_or_condition()?;
-            self.expect(Token::RParen)?;
-            return Ok(condition);
+            if let Ok(condition) = self.parse_or_condition() {
+                if let Some(&Token::RParen) = self.peek() {
+                    self.next(); // 消费右括号
+                    return Ok(condition);
+                }
+            }
+            self.position = current_position;
         }
-
-        // 保存当前位置，以便尝试解析表达式
-        let current_position = self.position;
         
         // 尝试解析左侧表达式
         let left_expr = match self.parse_expression() {
@@ -722,7 +1302,7 @@ impl Parser {
                 false
             };
             
-            // 期望NULL
+            // 期望NULL、TRUE或FALSE
             match self.next() {
                 Some(Token::Null) => {
                     // 根据是否有NOT返回不同的操作符
@@ -741,13 +1321,64 @@ impl Parser {
                             value: crate::core::types::DataType::Null 
                         });
                     } else {
-                        return Err(DbError::SqlError("IS NULL/NOT NULL 只能用于列名".to_string()));
+                        // 其他表达式按表达式条件处理，右侧同样使用Null占位
+                        return Ok(super::WhereClause::Expression {
+                            left: Box::new(left_expr),
+                            operator,
+                            right: Box::new(super::Expression::Literal(crate::core::types::DataType::Null)),
+                        });
                     }
                 }
-                _ => return Err(DbError::SqlError("期望NULL关键字".to_string())),
+                // IS [NOT] TRUE / FALSE，同样按表达式条件处理
+                Some(Token::Identifier(ident)) if ident.eq_ignore_ascii_case("TRUE") || ident.eq_ignore_ascii_case("FALSE") => {
+                    let operator = match (ident.eq_ignore_ascii_case("TRUE"), is_not) {
+                        (true, false) => super::Operator::IsTrue,
+                        (true, true) => super::Operator::IsNotTrue,
+                        (false, false) => super::Operator::IsFalse,
+                        (false, true) => super::Operator::IsNotFalse,
+                    };
+                    return Ok(super::WhereClause::Expression {
+                        left: Box::new(left_expr),
+                        operator,
+                        right: Box::new(super::Expression::Literal(crate::core::types::DataType::Null)),
+                    });
+                }
+                _ => return Err(DbError::SqlError("期望NULL、TRUE或FALSE关键字".to_string())),
             }
         }
 
+        // 处理 [NOT] IN (...) 的情况
+        let before_not = self.position;
+        let negated = self.skip_keyword("NOT");
+        if self.skip_keyword("IN") {
+            let list = self.parse_in_list()?;
+            return Ok(super::WhereClause::In {
+                left: Box::new(left_expr),
+                list,
+                negated,
+            });
+        }
+        self.position = before_not;
+
+        // 处理 BETWEEN low AND high，等价于 left >= low AND left <= high
+        if self.skip_keyword("BETWEEN") {
+            let low = self.parse_expression()?;
+            self.expect(Token::And)?;
+            let high = self.parse_expression()?;
+            return Ok(super::WhereClause::And {
+                left: Box::new(super::WhereClause::Expression {
+                    left: Box::new(left_expr.clone()),
+                    operator: super::Operator::Ge,
+                    right: Box::new(low),
+                }),
+                right: Box::new(super::WhereClause::Expression {
+                    left: Box::new(left_expr),
+                    operator: super::Operator::Le,
+                    right: Box::new(high),
+                }),
+            });
+        }
+
         let operator = match self.next() {
             Some(Token::Eq) => super::Operator::Eq,
             Some(Token::Ne) => super::Operator::Ne,
@@ -755,11 +1386,17 @@ impl Parser {
             Some(Token::Lt) => super::Operator::Lt,
             Some(Token::Ge) => super::Operator::Ge,
             Some(Token::Le) => super::Operator::Le,
+            Some(Token::NullSafeEq) => super::Operator::NullSafeEq,
             _ => return Err(DbError::SqlError("期望操作符".to_string())),
         };
 
-        // 解析右侧值
-        let right_expr = self.parse_expression()?;
+        // 解析右侧值，<=> 的右侧可以直接写 NULL
+        let right_expr = if operator == super::Operator::NullSafeEq && matches!(self.peek(), Some(Token::Null)) {
+            self.next();
+            super::Expression::Literal(DataType::Null)
+        } else {
+            self.parse_expression()?
+        };
         
         // 创建表达式条件
         Ok(super::WhereClause::Expression { 
@@ -769,6 +1406,52 @@ impl Parser {
         })
     }
 
+    // 解析 IN 右侧括号中的值列表或子查询
+    fn parse_in_list(&mut self) -> Result<super::InList, DbError> {
+        self.expect(Token::LParen)?;
+        
+        if let Some(&Token::Select) = self.peek() {
+            // 子查询的原始SQL无法单独截取，表头由表达式生成
+            let subquery = self.parse_statement("")?;
+            self.expect(Token::RParen)?;
+            return Ok(super::InList::Subquery(Box::new(subquery)));
+        }
+        
+        let mut values = Vec::new();
+        loop {
+            values.push(self.parse_expression()?);
+            match self.next() {
+                Some(Token::Comma) => continue,
+                Some(Token::RParen) => break,
+                _ => return Err(DbError::SqlError("期望逗号或右括号".to_string())),
+            }
+        }
+        Ok(super::InList::Values(values))
+    }
+
+    // 解析可选的 GROUP BY 子句，返回分组列名列表
+    fn parse_group_by(&mut self) -> Result<Vec<String>, DbError> {
+        let mut columns = Vec::new();
+        if !self.skip_keyword("GROUP") {
+            return Ok(columns);
+        }
+        self.expect(Token::By)?;
+        
+        loop {
+            match self.next_identifier() {
+                Some(name) => columns.push(name),
+                _ => return Err(DbError::SqlError("GROUP BY 后期望列名".to_string())),
+            }
+            if let Some(&Token::Comma) = self.peek() {
+                self.next(); // 消费逗号
+            } else {
+                break;
+            }
+        }
+        
+        Ok(columns)
+    }
+
     fn parse_order_by(&mut self) -> Result<Option<super::OrderBy>, DbError> {
         // 检查是否有 ORDER BY 关键字
         if let Some(&Token::Order) = self.peek() {
@@ -776,8 +1459,8 @@ impl Parser {
             self.expect(Token::By)?; // 消费 BY
 
             // 获取排序列名
-            let column = match self.next() {
-                Some(Token::Identifier(name)) => name,
+            let column = match self.next_identifier() {
+                Some(name) => name,
                 _ => return Err(DbError::SqlError("期望列名".to_string())),
             };
 
@@ -818,4 +1501,32 @@ impl Parser {
         self.position += 1;
         token
     }
-} 
\ No newline at end of file
+}
+
+// 嵌套超过最大深度时的错误
+fn nesting_too_deep() -> DbError {
+    DbError::SqlError("表达式嵌套过深".to_string())
+}
+
+// 将非空的各项从右向左组合为右结合的树：a AND (b AND c)
+fn fold_right<T>(mut terms: Vec<T>, combine: impl Fn(T, T) -> T) -> T {
+    let mut result = terms.pop().expect("至少有一项");
+    while let Some(left) = terms.pop() {
+        result = combine(left, result);
+    }
+    result
+}
+
+// 可以在表名、列名位置使用的关键字及其名称
+// 只包含在这些位置不会引起歧义的关键字，如 SELECT、FROM、WHERE 等仍然不能作为名称
+fn keyword_as_identifier(token: &Token) -> Option<&'static str> {
+    match token {
+        Token::Key => Some("key"),
+        Token::Order => Some("order"),
+        Token::By => Some("by"),
+        Token::Asc => Some("asc"),
+        Token::Desc => Some("desc"),
+        Token::Is => Some("is"),
+        _ => None,
+    }
+}
@@ -0,0 +1,75 @@
+use crate::core::types::DataType;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// SAMPLE 子句的抽样大小
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SampleSize {
+    Rows(usize),   // SAMPLE n，随机抽取 n 行
+    Percent(f64),  // SAMPLE n PERCENT，每行以 n% 的概率保留
+}
+
+/// 可设置种子的伪随机数生成器（SplitMix64），相同种子产生相同的抽样结果
+#[derive(Debug, Clone, Copy)]
+pub struct SampleRng {
+    state: u64,
+}
+
+impl SampleRng {
+    pub fn new(seed: u64) -> Self {
+        SampleRng { state: seed }
+    }
+
+    /// 以当前时间为种子
+    pub fn from_time() -> Self {
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_nanos() as u64).unwrap_or(0);
+        SampleRng::new(nanos)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    // [0, 1) 之间的浮点数
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    // [0, bound) 之间的整数
+    fn next_below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+}
+
+/// 从结果行中抽样，保留的行维持原来的相对顺序
+pub fn sample_rows(rows: Vec<Vec<DataType>>, size: SampleSize, seed: u64) -> Vec<Vec<DataType>> {
+    let mut rng = SampleRng::new(seed);
+    match size {
+        SampleSize::Rows(count) => {
+            if rows.len() <= count {
+                return rows;
+            }
+            // 蓄水池抽样选出行号，再按行号排序
+            let mut chosen: Vec<usize> = (0..count).collect();
+            for i in count..rows.len() {
+                let j = rng.next_below(i + 1);
+                if j < count {
+                    chosen[j] = i;
+                }
+            }
+            chosen.sort_unstable();
+            let mut chosen = chosen.into_iter().peekable();
+            rows.into_iter()
+                .enumerate()
+                .filter(|(i, _)| chosen.next_if_eq(i).is_some())
+                .map(|(_, row)| row)
+                .collect()
+        }
+        SampleSize::Percent(percent) => {
+            rows.into_iter().filter(|_| rng.next_f64() * 100.0 < percent).collect()
+        }
+    }
+}
@@ -0,0 +1,198 @@
+/// 移除SQL语句中的注释
+fn remove_comments(sql: &str) -> String {
+    let mut result = String::new();
+    let mut in_multi_comment = false;
+    let mut in_single_comment = false;
+    let mut in_string = false;
+    let mut string_quote = '\0'; // 存储字符串的引号类型（单引号或双引号）
+    let mut i = 0;
+    
+    let chars: Vec<char> = sql.chars().collect();
+    
+    while i < chars.len() {
+        let c = chars[i];
+        
+        // 检查是否在字符串中
+        if !in_single_comment && !in_multi_comment && (c == '\'' || c == '"') && (i == 0 || chars[i-1] != '\\') {
+            if !in_string {
+                in_string = true;
+                string_quote = c;
+            } else if c == string_quote {
+                in_string = false;
+            }
+        }
+        
+        // 处理单行注释开始 --
+        if !in_string && !in_multi_comment && !in_single_comment && c == '-' && i + 1 < chars.len() && chars[i + 1] == '-' {
+            in_single_comment = true;
+            i += 2;
+            continue;
+        }
+        
+        // 处理多行注释开始 /*
+        if !in_string && !in_single_comment && !in_multi_comment && c == '/' && i + 1 < chars.len() && chars[i + 1] == '*' {
+            in_multi_comment = true;
+            i += 2;
+            continue;
+        }
+        
+        // 处理多行注释结束 */
+        if !in_string && !in_single_comment && in_multi_comment && c == '*' && i + 1 < chars.len() && chars[i + 1] == '/' {
+            in_multi_comment = false;
+            i += 2;
+            continue;
+        }
+        
+        // 处理单行注释结束（遇到换行）
+        if in_single_comment && (c == '\n' || c == '\r') {
+            in_single_comment = false;
+        }
+        
+        // 只有不在注释中的内容才添加到结果中
+        if !in_single_comment && !in_multi_comment {
+            result.push(c);
+        }
+        
+        i += 1;
+    }
+    
+    result
+}
+
+/// 移除注释后按分号分割多条SQL语句，引号中的分号不作为分隔符，空语句被忽略
+pub fn split_statements(sql: &str) -> Vec<String> {
+    split_statements_with_delimiter(sql, ";")
+}
+
+/// 与 split_statements 相同，但使用指定的语句分隔符（可以是多个字符，如 `//`）
+pub fn split_statements_with_delimiter(sql: &str, delimiter: &str) -> Vec<String> {
+    let chars: Vec<char> = remove_comments(sql).chars().collect();
+    let delimiter: Vec<char> = delimiter.chars().collect();
+    let mut statements = Vec::new();
+    let mut pos = 0;
+    while pos < chars.len() {
+        let (end, next) = match find_delimiter(&chars, pos, &delimiter) {
+            Some(end) => (end, end + delimiter.len()),
+            None => (chars.len(), chars.len()),
+        };
+        statements.push(chars[pos..end].iter().collect::<String>());
+        pos = next;
+    }
+    
+    statements.into_iter()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+// 从 start 开始查找引号之外的第一个分隔符，返回其位置
+fn find_delimiter(chars: &[char], start: usize, delimiter: &[char]) -> Option<usize> {
+    if delimiter.is_empty() {
+        return None;
+    }
+    let mut string_quote: Option<char> = None; // 当前所在字符串的引号类型
+    let mut i = start;
+    while i < chars.len() {
+        let c = chars[i];
+        match string_quote {
+            Some(quote) if c == quote => string_quote = None,
+            Some(_) => {}
+            None if c == '\'' || c == '"' => string_quote = Some(c),
+            None if chars[i..].starts_with(delimiter) => return Some(i),
+            None => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// 解析修改语句分隔符的命令 `DELIMITER //` 或 `SET DELIMITER //`，返回新的分隔符
+///
+/// 命令以行尾结束，不需要分隔符，行中其余的字符都是新的分隔符。
+/// 带等号的 `SET delimiter = //` 是普通的 SET 命令，不在此处理。不是该命令时返回 None
+pub fn parse_delimiter_command(line: &str) -> Option<&str> {
+    let line = line.trim();
+    let line = match line.get(..4) {
+        Some(set) if set.eq_ignore_ascii_case("set ") => line[4..].trim_start(),
+        _ => line,
+    };
+    if !line.get(..9).is_some_and(|k| k.eq_ignore_ascii_case("delimiter")) {
+        return None;
+    }
+    let rest = &line[9..];
+    if !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let rest = rest.trim();
+    if rest.is_empty() || rest.starts_with('=') || rest.contains(char::is_whitespace) {
+        return None;
+    }
+    Some(rest)
+}
+
+/// 脚本中的一项：SQL语句或修改分隔符的命令
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScriptItem {
+    Statement(String),
+    Delimiter(String),
+}
+
+/// 逐条拆分SQL脚本，语句开头的 DELIMITER 命令单独返回
+///
+/// 拆分器不会自行切换分隔符：调用方确认新的分隔符有效后调用 set_delimiter，
+/// 之后的语句按新的分隔符拆分
+pub struct ScriptSplitter {
+    chars: Vec<char>,
+    pos: usize,
+    delimiter: Vec<char>,
+}
+
+impl ScriptSplitter {
+    pub fn new(sql: &str, delimiter: &str) -> Self {
+        ScriptSplitter {
+            chars: remove_comments(sql).chars().collect(),
+            pos: 0,
+            delimiter: delimiter.chars().collect(),
+        }
+    }
+    
+    pub fn set_delimiter(&mut self, delimiter: &str) {
+        self.delimiter = delimiter.chars().collect();
+    }
+}
+
+impl Iterator for ScriptSplitter {
+    type Item = ScriptItem;
+    
+    fn next(&mut self) -> Option<ScriptItem> {
+        loop {
+            while self.pos < self.chars.len() && self.chars[self.pos].is_whitespace() {
+                self.pos += 1;
+            }
+            if self.pos >= self.chars.len() {
+                return None;
+            }
+            
+            // 语句开头的一整行是 DELIMITER 命令
+            let line_end = self.chars[self.pos..].iter()
+                .position(|&c| c == '\n')
+                .map_or(self.chars.len(), |n| self.pos + n);
+            let line: String = self.chars[self.pos..line_end].iter().collect();
+            if let Some(delimiter) = parse_delimiter_command(&line) {
+                self.pos = line_end;
+                return Some(ScriptItem::Delimiter(delimiter.to_string()));
+            }
+            
+            let (end, next) = match find_delimiter(&self.chars, self.pos, &self.delimiter) {
+                Some(end) => (end, end + self.delimiter.len()),
+                None => (self.chars.len(), self.chars.len()),
+            };
+            let statement: String = self.chars[self.pos..end].iter().collect();
+            self.pos = next;
+            let statement = statement.trim();
+            if !statement.is_empty() {
+                return Some(ScriptItem::Statement(statement.to_string()));
+            }
+        }
+    }
+}
@@ -1,14 +1,21 @@
+use std::cell::Cell;
 use std::fs;
-use std::path::PathBuf;
-use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::collections::{HashMap, HashSet};
 use serde_json;
 use crate::core::error::DbError;
 use crate::core::types::{Table, DataType};
-use super::Storage;
+use super::{IntegrityReport, Storage};
+use super::wal::{Wal, WalRecord};
 
 pub struct FileStorage {
     base_dir: PathBuf,
     tables: HashMap<String, Table>,
+    wal: Wal, // 预写日志，保证多表修改的崩溃一致性
+    batch_depth: usize,         // 批量模式嵌套层数，大于0时延迟写盘
+    dirty_tables: HashSet<String>, // 批量模式下被修改（或删除）的表
+    files_written: Cell<usize>, // 写入或删除表文件的累计次数
 }
 
 impl FileStorage {
@@ -20,9 +27,14 @@ impl FileStorage {
             let _ = fs::create_dir_all(&tables_dir);
         }
         
+        let wal = Wal::new(base_dir.join("wal.log"));
         let mut storage = FileStorage {
             base_dir,
             tables: HashMap::new(),
+            wal,
+            batch_depth: 0,
+            dirty_tables: HashSet::new(),
+            files_written: Cell::new(0),
         };
         
         // 加载所有表
@@ -30,38 +42,91 @@ impl FileStorage {
         storage
     }
     
+    // 自创建以来写入或删除表文件的次数
+    pub fn files_written(&self) -> usize {
+        self.files_written.get()
+    }
+    
     // 获取表文件路径
     fn get_table_path(&self, table_name: &str) -> PathBuf {
         self.base_dir.join("tables").join(format!("{}.json", table_name))
     }
     
     // 加载单个表
-    fn load_table(&mut self, table_name: &str) -> Result<(), DbError> {
+    // 从磁盘读取单个表，文件不存在时返回 None
+    fn read_table(&self, table_name: &str) -> Result<Option<Table>, DbError> {
         let table_path = self.get_table_path(table_name);
         
         if table_path.exists() {
             let content = fs::read_to_string(&table_path)
                 .map_err(|e| DbError::IoError(e))?;
-            let table: Table = serde_json::from_str(&content)
+            let mut table: Table = serde_json::from_str(&content)
                 .map_err(|e| DbError::Serialization(e.to_string()))?;
-            self.tables.insert(table_name.to_string(), table);
+            // 兼容主键列仍标记为可空的旧文件
+            table.mark_primary_key_not_null();
+            return Ok(Some(table));
         }
         
-        Ok(())
+        Ok(None)
     }
     
-    // 保存单个表
-    fn save_table(&self, table_name: &str) -> Result<(), DbError> {
-        if let Some(table) = self.tables.get(table_name) {
-            let table_path = self.get_table_path(table_name);
-            let json = serde_json::to_string_pretty(table)
-                .map_err(|e| DbError::Serialization(e.to_string()))?;
-            fs::write(&table_path, json)
-                .map_err(|e| DbError::IoError(e))?;
+    // 表发生修改后调用：批量模式下只做标记，否则立即写盘
+    fn table_changed(&mut self, table_name: &str) -> Result<(), DbError> {
+        if self.batch_depth > 0 {
+            self.dirty_tables.insert(table_name.to_string());
+            return Ok(());
         }
         
+        if self.tables.contains_key(table_name) {
+            self.save_table(table_name)
+        } else {
+            self.commit(vec![WalRecord::Drop { name: table_name.to_string() }])
+        }
+    }
+    
+    // 先将一组修改写入预写日志，再应用到表文件，全部完成后执行检查点清空日志
+    fn commit(&self, records: Vec<WalRecord>) -> Result<(), DbError> {
+        self.wal.append_group(&records)?;
+        for record in &records {
+            self.apply_record(record)?;
+        }
+        self.wal.truncate()
+    }
+    
+    // 将一条日志记录应用到磁盘上的表文件
+    fn apply_record(&self, record: &WalRecord) -> Result<(), DbError> {
+        if !matches!(record, WalRecord::Commit) {
+            self.files_written.set(self.files_written.get() + 1);
+        }
+        match record {
+            WalRecord::Put { table } => {
+                let table_path = self.get_table_path(&table.name);
+                let json = serde_json::to_string_pretty(table)
+                    .map_err(|e| DbError::Serialization(e.to_string()))?;
+                fs::write(&table_path, json)
+                    .map_err(|e| DbError::IoError(e))?;
+            }
+            WalRecord::Drop { name } => {
+                let table_path = self.get_table_path(name);
+                if table_path.exists() {
+                    fs::remove_file(table_path)
+                        .map_err(|e| DbError::IoError(e))?;
+                }
+            }
+            WalRecord::Commit => {}
+        }
         Ok(())
     }
+    
+    // 重放上次会话中已提交但可能未完全写入表文件的修改
+    fn recover(&self) -> Result<(), DbError> {
+        for group in self.wal.read_committed()? {
+            for record in &group {
+                self.apply_record(record)?;
+            }
+        }
+        self.wal.truncate()
+    }
 }
 
 impl Storage for FileStorage {
@@ -73,18 +138,13 @@ impl Storage for FileStorage {
         }
         
         self.tables.insert(table_name.clone(), table);
-        self.save_table(&table_name)
+        self.table_changed(&table_name)
     }
 
     fn drop_table(&mut self, table_name: &str) -> Result<(), DbError> {
         if self.tables.remove(table_name).is_some() {
             // 删除表文件
-            let table_path = self.get_table_path(table_name);
-            if table_path.exists() {
-                fs::remove_file(table_path)
-                    .map_err(|e| DbError::IoError(e))?;
-            }
-            Ok(())
+            self.table_changed(table_name)
         } else {
             Err(DbError::TableError(format!("表 {} 不存在", table_name)))
         }
@@ -95,6 +155,11 @@ impl Storage for FileStorage {
     }
 
     fn get_table_mut(&mut self, table_name: &str) -> Result<Option<&mut Table>, DbError> {
+        // 批量模式下，通过可变引用直接修改的表同样需要在结束时写盘
+        if self.batch_depth > 0 && self.tables.contains_key(table_name) {
+            self.dirty_tables.insert(table_name.to_string());
+        }
+        // 通过表的方法修改数据时索引同步更新，无需在这里失效
         Ok(self.tables.get_mut(table_name))
     }
 
@@ -103,11 +168,20 @@ impl Storage for FileStorage {
     }
 
     fn get_tables(&self) -> Result<Vec<&Table>, DbError> {
-        Ok(self.tables.values().collect())
+        let mut tables: Vec<&Table> = self.tables.values().collect();
+        tables.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(tables)
     }
 
     fn get_table_by_index(&self, index: usize) -> Result<Option<&Table>, DbError> {
-        Ok(self.tables.values().nth(index))
+        Ok(self.get_tables()?.get(index).copied())
+    }
+
+    fn find_by_primary_key(&self, table_name: &str, key: &DataType) -> Result<Option<&[DataType]>, DbError> {
+        // 使用表上缓存的主键索引，避免逐行扫描
+        let table = self.tables.get(table_name)
+            .ok_or_else(|| DbError::TableError(format!("表 {} 不存在", table_name)))?;
+        Ok(table.find_by_primary_key(key))
     }
 
     fn insert_row(&mut self, table_name: &str, row: Vec<DataType>) -> Result<(), DbError> {
@@ -116,16 +190,24 @@ impl Storage for FileStorage {
         
         // 直接调用insert_row，保留原始错误类型
         table.insert_row(row)?;
-        self.save_table(table_name)
+        self.table_changed(table_name)
+    }
+
+    fn insert_rows(&mut self, table_name: &str, rows: Vec<Vec<DataType>>) -> Result<(), DbError> {
+        let table = self.get_table_mut(table_name)?
+            .ok_or_else(|| DbError::TableError(format!("表 {} 不存在", table_name)))?;
+        
+        // 先校验并插入全部行，任何一行失败时不插入任何行，全部成功后只写一次盘
+        table.insert_rows(rows)?;
+        self.table_changed(table_name)
     }
 
     fn delete_row(&mut self, table_name: &str, row_index: usize) -> Result<(), DbError> {
         let table = self.get_table_mut(table_name)?
             .ok_or_else(|| DbError::TableError(format!("表 {} 不存在", table_name)))?;
         if row_index < table.rows.len() {
-            table.rows.remove(row_index);
-            self.save_table(table_name)?;
-            Ok(())
+            table.remove_row(row_index);
+            self.table_changed(table_name)
         } else {
             Err(DbError::TableError(format!("行索引 {} 超出范围", row_index)))
         }
@@ -134,27 +216,39 @@ impl Storage for FileStorage {
     fn update_row(&mut self, table_name: &str, row_index: usize, row: Vec<DataType>) -> Result<(), DbError> {
         let table = self.get_table_mut(table_name)?
             .ok_or_else(|| DbError::TableError(format!("表 {} 不存在", table_name)))?;
-        table.validate_row(&row)?;
+        let update = (row_index, row);
+        table.validate_updates(std::slice::from_ref(&update))?;
         if row_index < table.rows.len() {
-            table.rows[row_index] = row;
-            self.save_table(table_name)?;
-            Ok(())
+            table.replace_row(row_index, update.1);
+            self.table_changed(table_name)
         } else {
             Err(DbError::TableError(format!("行索引 {} 超出范围", row_index)))
         }
     }
 
     fn save(&self) -> Result<(), DbError> {
-        // 保存所有表
-        for table_name in self.tables.keys() {
-            self.save_table(table_name)?;
+        // 所有表作为一组修改提交，要么全部写入，要么在恢复时全部重放
+        let records = self.tables.values()
+            .map(|table| WalRecord::Put { table: table.clone() })
+            .collect();
+        self.commit(records)
+    }
+
+    fn save_table(&self, table_name: &str) -> Result<(), DbError> {
+        // 只写入该表的文件，其他表（包括批量模式中未写盘的修改）不受影响
+        if let Some(table) = self.tables.get(table_name) {
+            self.commit(vec![WalRecord::Put { table: table.clone() }])?;
         }
+        
         Ok(())
     }
 
     fn load(&mut self) -> Result<(), DbError> {
-        // 清空现有表
-        self.tables.clear();
+        // 先完成未结束的日志重放，保证表文件处于一致状态
+        self.recover()?;
+        
+        // 先读入临时表集合，全部成功后再替换，读取中途出错时保留现有的表
+        let mut tables = HashMap::new();
         
         // 获取tables目录下的所有json文件
         let tables_dir = self.base_dir.join("tables");
@@ -163,22 +257,151 @@ impl Storage for FileStorage {
                 .map_err(|e| DbError::IoError(e))?;
             
             for entry in entries {
-                if let Ok(entry) = entry {
-                    let path = entry.path();
-                    if path.is_file() && path.extension().map_or(false, |ext| ext == "json") {
-                        if let Some(file_stem) = path.file_stem() {
-                            if let Some(table_name) = file_stem.to_str() {
-                                self.load_table(table_name)?;
-                            }
+                let path = entry.map_err(|e| DbError::IoError(e))?.path();
+                if path.is_file() && path.extension().map_or(false, |ext| ext == "json") {
+                    if let Some(table_name) = path.file_stem().and_then(|stem| stem.to_str()) {
+                        if let Some(table) = self.read_table(table_name)? {
+                            tables.insert(table_name.to_string(), table);
                         }
                     }
                 }
             }
         }
         
+        // 替换现有表（批量模式下尚未写盘的修改一并丢弃）
+        self.tables = tables;
+        self.dirty_tables.clear();
+        
+        Ok(())
+    }
+    
+    fn check_integrity(&self) -> Result<IntegrityReport, DbError> {
+        let mut results = Vec::new();
+        let tables_dir = self.base_dir.join("tables");
+        if !tables_dir.exists() {
+            return Ok(results);
+        }
+        
+        // 与 load 读取相同的文件，隔离目录中的文件不再检查
+        for entry in fs::read_dir(&tables_dir).map_err(DbError::IoError)? {
+            let path = entry.map_err(DbError::IoError)?.path();
+            if path.is_file() && path.extension().is_some_and(|ext| ext == "json") {
+                if let Some(table_name) = path.file_stem().and_then(|stem| stem.to_str()) {
+                    let result = self.read_table(table_name).map(|_| ()).map_err(|e| e.detailed_message());
+                    results.push((table_name.to_string(), result));
+                }
+            }
+        }
+        results.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(results)
+    }
+    
+    fn repair(&mut self) -> Result<Vec<String>, DbError> {
+        let broken: Vec<String> = self.check_integrity()?
+            .into_iter()
+            .filter(|(_, result)| result.is_err())
+            .map(|(name, _)| name)
+            .collect();
+        
+        // 损坏的文件保留在 tables/.broken 中，便于手动恢复
+        if !broken.is_empty() {
+            let broken_dir = self.base_dir.join("tables").join(".broken");
+            fs::create_dir_all(&broken_dir).map_err(DbError::IoError)?;
+            for name in &broken {
+                fs::rename(self.get_table_path(name), broken_dir.join(format!("{}.json", name)))
+                    .map_err(DbError::IoError)?;
+            }
+        }
+        
+        self.load()?;
+        Ok(broken)
+    }
+    
+    fn relocate(&mut self, new_path: PathBuf) -> Result<(), DbError> {
+        if new_path == self.base_dir {
+            return Ok(());
+        }
+        
+        // 不能移动到自身的子目录中；先完成全部检查再创建目录，被拒绝时不留下多余的目录
+        if let (Ok(target), Ok(base_dir)) = (resolve_path(&new_path), self.base_dir.canonicalize()) {
+            if target.starts_with(base_dir) {
+                return Err(DbError::IoError(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("不能将数据库移动到其子目录 {}", new_path.display()),
+                )));
+            }
+        }
+        
+        // 已存在的空目录可以作为目标，非空时不覆盖其中的文件
+        let target_exists = new_path.exists();
+        if target_exists {
+            let is_empty_dir = new_path.is_dir()
+                && fs::read_dir(&new_path).map_err(DbError::IoError)?.next().is_none();
+            if !is_empty_dir {
+                return Err(DbError::IoError(io::Error::new(
+                    io::ErrorKind::AlreadyExists,
+                    format!("目标目录 {} 已存在且不为空", new_path.display()),
+                )));
+            }
+        }
+        
+        if let Some(parent) = new_path.parent() {
+            fs::create_dir_all(parent).map_err(DbError::IoError)?;
+        }
+        if target_exists {
+            fs::remove_dir(&new_path).map_err(DbError::IoError)?;
+        }
+        
+        // 先尝试直接重命名，跨文件系统时改为复制后删除原目录
+        if fs::rename(&self.base_dir, &new_path).is_err() {
+            if let Err(e) = copy_dir_all(&self.base_dir, &new_path) {
+                let _ = fs::remove_dir_all(&new_path);
+                return Err(DbError::IoError(e));
+            }
+            fs::remove_dir_all(&self.base_dir).map_err(DbError::IoError)?;
+        }
+        
+        // 预写日志随目录一起移动，之后的写入都在新目录中进行
+        self.wal = Wal::new(new_path.join("wal.log"));
+        self.base_dir = new_path;
+        fs::create_dir_all(self.base_dir.join("tables")).map_err(DbError::IoError)?;
+        Ok(())
+    }
+    
+    fn begin_batch(&mut self) {
+        self.batch_depth += 1;
+    }
+    
+    fn end_batch(&mut self) -> Result<(), DbError> {
+        if self.batch_depth == 0 {
+            return Ok(());
+        }
+        self.batch_depth -= 1;
+        if self.batch_depth > 0 {
+            return Ok(());
+        }
+        
+        // 批量期间修改过的表作为一组提交，只写一次盘
+        let records = self.dirty_tables.iter()
+            .map(|name| match self.tables.get(name) {
+                Some(table) => WalRecord::Put { table: table.clone() },
+                None => WalRecord::Drop { name: name.clone() },
+            })
+            .collect::<Vec<_>>();
+        
+        if records.is_empty() {
+            return Ok(());
+        }
+        // 写入成功后才清空，失败时这些表仍视为未保存，下次批量结束时重试
+        self.commit(records)?;
+        self.dirty_tables.clear();
         Ok(())
     }
     
+    fn has_unsaved_changes(&self) -> bool {
+        !self.dirty_tables.is_empty()
+    }
+    
     fn is_file_storage(&self) -> bool {
         true
     }
@@ -186,4 +409,42 @@ impl Storage for FileStorage {
     fn get_path(&self) -> PathBuf {
         self.base_dir.clone()
     }
-} 
\ No newline at end of file
+} 
+
+// 将可能还不存在的路径转换为绝对路径：规范化最近的已存在祖先目录，再接上其余部分
+fn resolve_path(path: &Path) -> io::Result<PathBuf> {
+    let mut existing = path;
+    let mut rest = Vec::new();
+    while !existing.exists() {
+        match (existing.parent(), existing.file_name()) {
+            (Some(parent), Some(name)) => {
+                rest.push(name);
+                existing = parent;
+            }
+            _ => break,
+        }
+    }
+    // 相对路径的祖先都不存在时从当前目录开始
+    let mut resolved = if existing.as_os_str().is_empty() {
+        std::env::current_dir()?
+    } else {
+        existing.canonicalize()?
+    };
+    resolved.extend(rest.iter().rev());
+    Ok(resolved)
+}
+
+// 递归复制目录
+fn copy_dir_all(from: &Path, to: &Path) -> io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir_all(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
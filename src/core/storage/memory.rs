@@ -37,6 +37,7 @@ impl Storage for MemoryStorage {
     }
 
     fn get_table_mut(&mut self, table_name: &str) -> Result<Option<&mut Table>, DbError> {
+        // 通过表的方法修改数据时索引同步更新，无需在这里失效
         Ok(self.tables.get_mut(table_name))
     }
 
@@ -45,15 +46,24 @@ impl Storage for MemoryStorage {
     }
 
     fn get_tables(&self) -> Result<Vec<&Table>, DbError> {
-        Ok(self.tables.values().collect())
+        let mut tables: Vec<&Table> = self.tables.values().collect();
+        tables.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(tables)
     }
 
     fn get_table_by_index(&self, index: usize) -> Result<Option<&Table>, DbError> {
-        Ok(self.tables.values().nth(index))
+        Ok(self.get_tables()?.get(index).copied())
+    }
+
+    fn find_by_primary_key(&self, table_name: &str, key: &DataType) -> Result<Option<&[DataType]>, DbError> {
+        // 使用表上缓存的主键索引，避免逐行扫描
+        let table = self.tables.get(table_name)
+            .ok_or_else(|| DbError::TableError(format!("表 {} 不存在", table_name)))?;
+        Ok(table.find_by_primary_key(key))
     }
 
     fn insert_row(&mut self, table_name: &str, row: Vec<DataType>) -> Result<(), DbError> {
-        let table = self.tables.get_mut(table_name)
+        let table = self.get_table_mut(table_name)?
             .ok_or_else(|| DbError::TableError(format!("表 {} 不存在", table_name)))?;
         
         // 直接调用insert_row，保留原始错误类型
@@ -61,24 +71,34 @@ impl Storage for MemoryStorage {
         Ok(())
     }
 
+    fn insert_rows(&mut self, table_name: &str, rows: Vec<Vec<DataType>>) -> Result<(), DbError> {
+        let table = self.get_table_mut(table_name)?
+            .ok_or_else(|| DbError::TableError(format!("表 {} 不存在", table_name)))?;
+        
+        // 任何一行失败时不插入任何行
+        table.insert_rows(rows)?;
+        Ok(())
+    }
+
     fn delete_row(&mut self, table_name: &str, row_index: usize) -> Result<(), DbError> {
-        let table = self.tables.get_mut(table_name)
+        let table = self.get_table_mut(table_name)?
             .ok_or_else(|| DbError::TableError(format!("表 {} 不存在", table_name)))?;
         if row_index >= table.rows.len() {
             return Err(DbError::TableError(format!("行索引 {} 超出范围", row_index)));
         }
-        table.rows.remove(row_index);
+        table.remove_row(row_index);
         Ok(())
     }
 
     fn update_row(&mut self, table_name: &str, row_index: usize, row: Vec<DataType>) -> Result<(), DbError> {
-        let table = self.tables.get_mut(table_name)
+        let table = self.get_table_mut(table_name)?
             .ok_or_else(|| DbError::TableError(format!("表 {} 不存在", table_name)))?;
         if row_index >= table.rows.len() {
             return Err(DbError::TableError(format!("行索引 {} 超出范围", row_index)));
         }
-        table.validate_row(&row)?;
-        table.rows[row_index] = row;
+        let update = (row_index, row);
+        table.validate_updates(std::slice::from_ref(&update))?;
+        table.replace_row(row_index, update.1);
         Ok(())
     }
 
@@ -86,6 +106,10 @@ impl Storage for MemoryStorage {
         Ok(()) // 内存存储无需持久化
     }
 
+    fn save_table(&self, _table_name: &str) -> Result<(), DbError> {
+        Ok(()) // 内存存储无需持久化
+    }
+
     fn load(&mut self) -> Result<(), DbError> {
         Ok(()) // 内存存储无需加载
     }
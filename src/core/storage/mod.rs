@@ -1,10 +1,14 @@
 pub mod file;
 pub mod memory;
+pub mod wal;
 
 use crate::core::error::DbError;
 use crate::core::types::Table;
 use std::path::PathBuf;
 
+/// 完整性检查的结果：每个表文件的表名，以及读取失败时的错误信息
+pub type IntegrityReport = Vec<(String, Result<(), String>)>;
+
 pub trait Storage {
     // 表操作
     fn create_table(&mut self, table: Table) -> Result<(), DbError>;
@@ -12,18 +16,58 @@ pub trait Storage {
     fn get_table(&self, table_name: &str) -> Result<Option<&Table>, DbError>;
     fn get_table_mut(&mut self, table_name: &str) -> Result<Option<&mut Table>, DbError>;
     fn list_tables(&self) -> Result<Vec<String>, DbError>;
+    // 按表名排序，保证每次遍历的顺序相同
     fn get_tables(&self) -> Result<Vec<&Table>, DbError>;
+    // 按 get_tables 的顺序取第 index 个表
     fn get_table_by_index(&self, index: usize) -> Result<Option<&Table>, DbError>;
 
+    // 按主键查找行，默认逐行扫描；表没有主键或键为 NULL 时返回 None
+    fn find_by_primary_key(&self, table_name: &str, key: &crate::core::types::DataType) -> Result<Option<&[crate::core::types::DataType]>, DbError> {
+        let table = self.get_table(table_name)?
+            .ok_or_else(|| DbError::TableError(format!("表 {} 不存在", table_name)))?;
+        let pk_index = match table.columns.iter().position(|c| c.primary_key) {
+            Some(pk_index) => pk_index,
+            None => return Ok(None),
+        };
+        Ok(table.rows.iter()
+            .find(|row| row[pk_index].partial_cmp_coerced(key) == Some(std::cmp::Ordering::Equal))
+            .map(|row| row.as_slice()))
+    }
+
     // 数据操作
     fn insert_row(&mut self, table_name: &str, row: Vec<crate::core::types::DataType>) -> Result<(), DbError>;
+    // 插入多行，默认逐行插入
+    fn insert_rows(&mut self, table_name: &str, rows: Vec<Vec<crate::core::types::DataType>>) -> Result<(), DbError> {
+        for row in rows {
+            self.insert_row(table_name, row)?;
+        }
+        Ok(())
+    }
     fn delete_row(&mut self, table_name: &str, row_index: usize) -> Result<(), DbError>;
     fn update_row(&mut self, table_name: &str, row_index: usize, row: Vec<crate::core::types::DataType>) -> Result<(), DbError>;
 
     // 持久化
     fn save(&self) -> Result<(), DbError>;
+    // 只保存一个表，表不存在时不做任何操作
+    fn save_table(&self, table_name: &str) -> Result<(), DbError>;
     fn load(&mut self) -> Result<(), DbError>;
     
+    // 完整性检查：逐个读取磁盘上的表文件，返回每个表的检查结果，按表名排序
+    fn check_integrity(&self) -> Result<IntegrityReport, DbError> { Ok(Vec::new()) } // 默认实现，没有表文件
+    // 将无法读取的表文件移到隔离目录后重新加载，返回被隔离的表名
+    fn repair(&mut self) -> Result<Vec<String>, DbError> { Ok(Vec::new()) } // 默认实现，无需修复
+    
+    // 批量模式：期间的修改只作用于内存，结束时统一写入
+    fn begin_batch(&mut self) {} // 默认实现，无需批量处理
+    fn end_batch(&mut self) -> Result<(), DbError> { Ok(()) } // 默认实现，无需写入
+    // 是否有只作用于内存、尚未写盘的修改
+    fn has_unsaved_changes(&self) -> bool { false } // 默认实现，没有磁盘状态
+    
+    // 将存储目录整体移动到新路径，之后的读写都使用新路径
+    fn relocate(&mut self, _new_path: PathBuf) -> Result<(), DbError> {
+        Err(DbError::TableError("内存存储没有存储目录，无法移动".to_string())) // 默认实现，没有存储目录
+    }
+    
     // 存储类型和路径
     fn is_file_storage(&self) -> bool { false } // 默认实现，返回false
     fn get_path(&self) -> PathBuf { PathBuf::from("") } // 默认实现，返回空路径
@@ -0,0 +1,87 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use serde::{Serialize, Deserialize};
+use crate::core::error::DbError;
+use crate::core::types::Table;
+
+/// 预写日志中的一条记录
+/// 每组修改以若干 Put/Drop 记录开始，以 Commit 记录结束，只有完整提交的组才会在恢复时重放
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "op")]
+pub enum WalRecord {
+    Put { table: Table },  // 写入表的完整内容
+    Drop { name: String }, // 删除表
+    Commit,                // 一组修改的提交标记
+}
+
+/// 追加写入的预写日志文件
+pub struct Wal {
+    path: PathBuf,
+}
+
+impl Wal {
+    pub fn new(path: PathBuf) -> Self {
+        Wal { path }
+    }
+
+    // 追加一组修改并写入提交标记，返回前确保数据已落盘
+    pub fn append_group(&self, records: &[WalRecord]) -> Result<(), DbError> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(DbError::IoError)?;
+
+        let mut buffer = String::new();
+        for record in records.iter().chain(std::iter::once(&WalRecord::Commit)) {
+            let line = serde_json::to_string(record)
+                .map_err(|e| DbError::Serialization(e.to_string()))?;
+            buffer.push_str(&line);
+            buffer.push('\n');
+        }
+
+        file.write_all(buffer.as_bytes()).map_err(DbError::IoError)?;
+        file.sync_all().map_err(DbError::IoError)?;
+        Ok(())
+    }
+
+    // 读取所有已完整提交的修改组，末尾未提交或损坏的部分会被丢弃
+    pub fn read_committed(&self) -> Result<Vec<Vec<WalRecord>>, DbError> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let file = File::open(&self.path).map_err(DbError::IoError)?;
+        let mut groups = Vec::new();
+        let mut pending = Vec::new();
+
+        for line in BufReader::new(file).lines() {
+            let line = line.map_err(DbError::IoError)?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            // 写入中途崩溃会留下不完整的行，之后的内容都不可信
+            let record: WalRecord = match serde_json::from_str(&line) {
+                Ok(record) => record,
+                Err(_) => break,
+            };
+
+            match record {
+                WalRecord::Commit => groups.push(std::mem::take(&mut pending)),
+                record => pending.push(record),
+            }
+        }
+
+        Ok(groups)
+    }
+
+    // 检查点：所有修改都已写入表文件后清空日志
+    pub fn truncate(&self) -> Result<(), DbError> {
+        if self.path.exists() {
+            fs::write(&self.path, "").map_err(DbError::IoError)?;
+        }
+        Ok(())
+    }
+}
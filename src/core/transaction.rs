@@ -1,5 +1,8 @@
 use std::collections::HashMap;
 use crate::core::error::DbError;
+use crate::core::db::QueryResult;
+use crate::core::sql::{query_rows, SqlParser};
+use crate::core::storage::memory::MemoryStorage;
 use crate::core::storage::Storage;
 use crate::core::types::{Table, DataType};
 
@@ -16,7 +19,7 @@ pub struct Transaction<'a> {
     table_changes: HashMap<String, Vec<TableChange>>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 enum TableChange {
     Insert(Vec<DataType>),
     Update { row_index: usize, row: Vec<DataType> },
@@ -32,10 +35,16 @@ impl<'a> Transaction<'a> {
         }
     }
 
-    pub fn create_table(&mut self, name: String, columns: Vec<crate::core::types::Column>) -> Result<(), DbError> {
+    // 事务结束后不能再修改
+    fn ensure_active(&self) -> Result<(), DbError> {
         if self.state != TransactionState::Active {
             return Err(DbError::TransactionError("Transaction is not active".to_string()));
         }
+        Ok(())
+    }
+
+    pub fn create_table(&mut self, name: String, columns: Vec<crate::core::types::Column>) -> Result<(), DbError> {
+        self.ensure_active()?;
         let table = Table::new(name.clone(), columns);
         self.storage.create_table(table)?;
         self.table_changes.insert(name, Vec::new());
@@ -43,67 +52,144 @@ impl<'a> Transaction<'a> {
     }
 
     pub fn drop_table(&mut self, table_name: &str) -> Result<(), DbError> {
-        if self.state != TransactionState::Active {
-            return Err(DbError::TransactionError("Transaction is not active".to_string()));
-        }
+        self.ensure_active()?;
         self.storage.drop_table(table_name)?;
         self.table_changes.remove(table_name);
         Ok(())
     }
 
     pub fn insert_row(&mut self, table_name: &str, row: Vec<DataType>) -> Result<(), DbError> {
+        self.ensure_active()?;
         let changes = self.table_changes
             .entry(table_name.to_string())
-            .or_insert_with(Vec::new);
+            .or_default();
         changes.push(TableChange::Insert(row));
         Ok(())
     }
 
     pub fn update_row(&mut self, table_name: &str, row_index: usize, row: Vec<DataType>) -> Result<(), DbError> {
+        self.ensure_active()?;
         let changes = self.table_changes
             .entry(table_name.to_string())
-            .or_insert_with(Vec::new);
+            .or_default();
         changes.push(TableChange::Update { row_index, row });
         Ok(())
     }
 
     pub fn delete_row(&mut self, table_name: &str, row_index: usize) -> Result<(), DbError> {
+        self.ensure_active()?;
         let changes = self.table_changes
             .entry(table_name.to_string())
-            .or_insert_with(Vec::new);
+            .or_default();
         changes.push(TableChange::Delete(row_index));
         Ok(())
     }
 
-    pub fn commit(self) -> Result<(), DbError> {
-        // 将所有更改应用到存储
-        for (table_name, changes) in self.table_changes {
-            let table = self.storage.get_table_mut(&table_name)?
-                .ok_or_else(|| DbError::TableError(format!("表 {} 不存在", table_name)))?;
-
-            for change in changes {
-                match change {
-                    TableChange::Insert(row) => {
-                        table.insert_row(row)?;
-                    }
-                    TableChange::Update { row_index, row } => {
-                        if row_index < table.rows.len() {
-                            table.rows[row_index] = row;
-                        }
-                    }
-                    TableChange::Delete(row_index) => {
-                        if row_index < table.rows.len() {
-                            table.rows.remove(row_index);
-                        }
-                    }
-                }
+    // 读取事务中看到的表：存储中的数据加上本事务尚未提交的修改
+    pub fn get_table(&self, table_name: &str) -> Result<Option<Table>, DbError> {
+        let mut table = match self.storage.get_table(table_name)? {
+            Some(table) => table.clone(),
+            None => return Ok(None),
+        };
+        if let Some(changes) = self.table_changes.get(table_name) {
+            apply_changes(&mut table, changes.iter().cloned())?;
+        }
+        table.invalidate_indexes();
+        Ok(Some(table))
+    }
+
+    // 在事务中执行查询，结果包含本事务尚未提交的修改
+    pub fn query(&self, sql: &str) -> Result<QueryResult, DbError> {
+        let statement = SqlParser::new().parse(sql)?;
+
+        // 在所有表的快照上执行，子查询引用的其他表同样能看到未提交的修改
+        let mut snapshot = MemoryStorage::new();
+        for table_name in self.storage.list_tables()? {
+            if let Some(table) = self.get_table(&table_name)? {
+                snapshot.create_table(table)?;
             }
         }
-        Ok(())
+
+        let (columns, rows) = query_rows(&snapshot, statement)?;
+        let rows = rows.collect::<Result<Vec<_>, DbError>>()?;
+        Ok(QueryResult { columns, rows })
     }
 
-    pub fn rollback(self) -> Result<(), DbError> {
-        // 不需要做任何事情，因为更改还没有应用到存储
+    // 提交后事务结束；提交失败时没有任何修改生效，事务同样结束并视为已回滚
+    pub fn commit(&mut self) -> Result<(), DbError> {
+        self.ensure_active()?;
+        let table_changes = std::mem::take(&mut self.table_changes);
+        match self.apply_all(table_changes) {
+            Ok(()) => {
+                self.state = TransactionState::Committed;
+                Ok(())
+            }
+            Err(e) => {
+                self.state = TransactionState::RolledBack;
+                Err(e)
+            }
+        }
+    }
+
+    // 丢弃尚未提交的修改，之后事务不能再使用
+    pub fn rollback(&mut self) -> Result<(), DbError> {
+        self.ensure_active()?;
+        // 修改还没有应用到存储，丢弃缓存即可
+        self.table_changes.clear();
+        self.state = TransactionState::RolledBack;
         Ok(())
     }
-} 
\ No newline at end of file
+
+    // 先在表的副本上应用全部修改，任何一条修改失败时存储中的数据都不变
+    fn apply_all(&mut self, table_changes: HashMap<String, Vec<TableChange>>) -> Result<(), DbError> {
+        let mut updated = Vec::with_capacity(table_changes.len());
+        for (table_name, changes) in table_changes {
+            let mut table = self.storage.get_table(&table_name)?
+                .ok_or_else(|| DbError::TableError(format!("表 {} 不存在", table_name)))?
+                .clone();
+            apply_changes(&mut table, changes)?;
+            table.invalidate_indexes();
+            updated.push(table);
+        }
+        
+        // 全部修改成功后再替换存储中的表，文件存储在批量结束时将这些表作为一组写盘
+        self.storage.begin_batch();
+        for table in updated {
+            if let Some(stored) = self.storage.get_table_mut(&table.name)? {
+                *stored = table;
+            }
+        }
+        self.storage.end_batch()
+    }
+}
+
+// 按顺序将修改应用到表上，提交和事务内读取共用
+fn apply_changes(table: &mut Table, changes: impl IntoIterator<Item = TableChange>) -> Result<(), DbError> {
+    for change in changes {
+        match change {
+            TableChange::Insert(row) => {
+                table.insert_row(row)?;
+            }
+            TableChange::Update { row_index, row } => {
+                check_row_index(table, row_index)?;
+                // 与存储的 update_row 相同，替换前检查类型、非空和主键约束
+                let update = (row_index, row);
+                table.validate_updates(std::slice::from_ref(&update))?;
+                table.replace_row(row_index, update.1);
+            }
+            TableChange::Delete(row_index) => {
+                check_row_index(table, row_index)?;
+                table.remove_row(row_index);
+            }
+        }
+    }
+    Ok(())
+}
+
+// 修改和删除的行号必须在表中存在，与存储的 update_row / delete_row 一致
+fn check_row_index(table: &Table, row_index: usize) -> Result<(), DbError> {
+    if row_index >= table.rows.len() {
+        return Err(DbError::TableError(format!("行索引 {} 超出范围", row_index)));
+    }
+    Ok(())
+}
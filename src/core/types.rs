@@ -1,6 +1,12 @@
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::fmt;
+use std::ops::Bound;
 use thiserror::Error;
 use serde::{Serialize, Deserialize};
+use crate::core::error::DbError;
+use crate::core::index::{BTreeIndex, Index, IndexKey};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum DataType {
@@ -10,19 +16,42 @@ pub enum DataType {
     Null,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ColumnType {
     Int(Option<usize>), // 整数类型可选位数
     Float(Option<usize>), // 浮点数类型可选位数
     Varchar(usize),     // 存储varchar的最大长度
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Column {
     pub name: String,
     pub data_type: ColumnType,
     pub nullable: bool,
     pub primary_key: bool, // 新增主键标识
+    #[serde(default)]
+    pub comment: Option<String>, // 列注释
+    #[serde(default)]
+    pub default: Option<ColumnDefault>, // 插入时省略该列所使用的默认值
+}
+
+// 列的默认值
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ColumnDefault {
+    Value(DataType),  // DEFAULT 常量
+    CurrentTimestamp, // DEFAULT CURRENT_TIMESTAMP，插入时填入当前时间
+}
+
+// 开启 ROW_VERSION 的表中行版本号的名称，可以在 UPDATE 和 DELETE 的条件中使用
+pub const VERSION_COLUMN: &str = "version";
+
+// 算术运算符
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArithmeticOperator {
+    Add,     // +
+    Subtract, // -
+    Multiply, // *
+    Divide,   // /
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -30,6 +59,31 @@ pub struct Table {
     pub name: String,
     pub columns: Vec<Column>,
     pub rows: Vec<Vec<DataType>>,
+    #[serde(default)]
+    pub max_rows: Option<usize>, // 表的最大行数限制，None 表示不限制
+    #[serde(default)]
+    pub comment: Option<String>, // 表注释
+    #[serde(default)]
+    pub indexes: Vec<Index>, // 表上的单列索引
+    #[serde(default)]
+    row_versions: Option<Vec<u64>>, // 开启 ROW_VERSION 时每行的版本号，与 rows 一一对应
+    #[serde(skip)]
+    index_cache: RefCell<HashMap<String, BTreeIndex>>, // 按列名缓存已建立的索引，通过表的方法修改数据时同步更新
+}
+
+// 两个表的列和行都相同时相等，不比较表名、注释和索引等其他属性
+impl PartialEq for Table {
+    fn eq(&self, other: &Self) -> bool {
+        self.columns == other.columns && self.rows == other.rows
+    }
+}
+
+// Table::diff 报告的一行差异
+#[derive(Debug, Clone, PartialEq)]
+pub enum RowDiff {
+    Added(Vec<DataType>),   // 只在另一个表中的行
+    Removed(Vec<DataType>), // 只在当前表中的行
+    Changed { old: Vec<DataType>, new: Vec<DataType> }, // 主键（或位置）相同但内容不同的行
 }
 
 #[derive(Error, Debug)]
@@ -40,6 +94,13 @@ pub enum TypeError {
         actual: DataType,
     },
     
+    #[error("列 {column} 的类型不匹配: 期望 {expected}, 实际值 {}", .actual.to_sql_literal())]
+    ColumnTypeMismatch {
+        column: String,
+        expected: ColumnType,
+        actual: DataType,
+    },
+    
     #[error("字符串长度超出限制: 最大长度 {max_length}, 实际长度 {actual_length}")]
     StringLengthExceeded {
         max_length: usize,
@@ -49,8 +110,17 @@ pub enum TypeError {
     #[error("Error: Field '{0}' doesn't have a default value")]
     NullValue(String),
 
+    #[error("Error: Field '{column}' doesn't have a default value at row {row}")]
+    NullValueAtRow {
+        column: String,
+        row: usize, // 多行插入中出错的行号，从1开始
+    },
+
     #[error("Error: Duplicate entry '{0}' for key 'PRIMARY'")]
     PrimaryKeyViolation(String),
+
+    #[error("Error: The table '{0}' is full")]
+    TableFull(String),
 }
 
 impl DataType {
@@ -65,6 +135,130 @@ impl DataType {
     }
 }
 
+impl DataType {
+    // 将值转换为目标列类型，无法安全转换时返回 None（如非数字字符串转整数、超长字符串）
+    pub fn convert_to(&self, column_type: &ColumnType) -> Option<DataType> {
+        match (self, column_type) {
+            (DataType::Null, _) => Some(DataType::Null),
+            (DataType::Int(n), ColumnType::Int(_)) => Some(DataType::Int(*n)),
+            (DataType::Int(n), ColumnType::Float(_)) => Some(DataType::Float(*n as f64)),
+            (DataType::Float(f), ColumnType::Int(_)) => {
+                // 只有没有小数部分且在范围内的浮点数才能转为整数
+                if f.fract() == 0.0 && *f >= i32::MIN as f64 && *f <= i32::MAX as f64 {
+                    Some(DataType::Int(*f as i32))
+                } else {
+                    None
+                }
+            }
+            (DataType::Float(f), ColumnType::Float(_)) => Some(DataType::Float(*f)),
+            (DataType::Varchar(s), ColumnType::Int(_)) => s.trim().parse::<i32>().ok().map(DataType::Int),
+            (DataType::Varchar(s), ColumnType::Float(_)) => s.trim().parse::<f64>().ok().map(DataType::Float),
+            (value, ColumnType::Varchar(max_len)) => {
+                let s = value.to_string();
+                if s.len() <= *max_len {
+                    Some(DataType::Varchar(s))
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    // 将导入的原始字符串解析为列类型的值：空字符串为 NULL，数字类型忽略首尾空白，
+    // 字符串类型检查长度；无法解析时返回类型不匹配错误
+    pub fn parse_as(s: &str, column_type: &ColumnType) -> Result<DataType, DbError> {
+        if s.is_empty() {
+            return Ok(DataType::Null);
+        }
+        
+        let value = DataType::Varchar(s.to_string());
+        value.convert_to(column_type)
+            .ok_or_else(|| DbError::TypeError(TypeError::TypeMismatch {
+                expected: column_type.clone(),
+                actual: value,
+            }))
+    }
+
+    // 比较两个值，整数与浮点数之间自动转换；NULL 或类型不兼容时返回 None
+    pub fn partial_cmp_coerced(&self, other: &DataType) -> Option<Ordering> {
+        match (self, other) {
+            (DataType::Int(a), DataType::Int(b)) => Some(a.cmp(b)),
+            (DataType::Int(a), DataType::Float(b)) => (*a as f64).partial_cmp(b),
+            (DataType::Float(a), DataType::Int(b)) => a.partial_cmp(&(*b as f64)),
+            (DataType::Float(a), DataType::Float(b)) => a.partial_cmp(b),
+            (DataType::Varchar(a), DataType::Varchar(b)) => Some(a.cmp(b)),
+            _ => None,
+        }
+    }
+
+    // 算术运算，整数与浮点数混合时结果为浮点数
+    pub fn arithmetic(&self, operator: &ArithmeticOperator, other: &DataType) -> Result<DataType, DbError> {
+        match (self, other) {
+            (DataType::Int(a), DataType::Int(b)) => {
+                let result = match operator {
+                    ArithmeticOperator::Add => a.checked_add(*b),
+                    ArithmeticOperator::Subtract => a.checked_sub(*b),
+                    ArithmeticOperator::Multiply => a.checked_mul(*b),
+                    ArithmeticOperator::Divide => {
+                        if *b == 0 {
+                            return Err(DbError::SqlError("除数不能为零".to_string()));
+                        }
+                        // i32::MIN / -1 同样会溢出
+                        a.checked_div(*b)
+                    },
+                };
+                result.map(DataType::Int)
+                    .ok_or_else(|| DbError::SqlError("整数溢出".to_string()))
+            },
+            (DataType::Int(_) | DataType::Float(_), DataType::Int(_) | DataType::Float(_)) => {
+                let a = self.as_f64();
+                let b = other.as_f64();
+                let result = match operator {
+                    ArithmeticOperator::Add => a + b,
+                    ArithmeticOperator::Subtract => a - b,
+                    ArithmeticOperator::Multiply => a * b,
+                    ArithmeticOperator::Divide => {
+                        if b == 0.0 {
+                            return Err(DbError::SqlError("除数不能为零".to_string()));
+                        }
+                        a / b
+                    },
+                };
+                Ok(DataType::Float(result))
+            },
+            // 任一操作数为 NULL 时结果为 NULL
+            (DataType::Null, _) | (_, DataType::Null) => Ok(DataType::Null),
+            (DataType::Varchar(_), _) | (_, DataType::Varchar(_)) => {
+                Err(DbError::SqlError("字符串不能参与算术运算".to_string()))
+            },
+        }
+    }
+
+    // 转换为可以重新解析的 SQL 字面量
+    pub fn to_sql_literal(&self) -> String {
+        match self {
+            DataType::Int(n) => n.to_string(),
+            DataType::Float(f) => {
+                // 保证带有小数点，避免被解析为整数
+                let s = f.to_string();
+                if s.contains('.') || !f.is_finite() { s } else { format!("{}.0", s) }
+            }
+            // 字符串中的单引号写成两个单引号
+            DataType::Varchar(s) => format!("'{}'", s.replace('\'', "''")),
+            DataType::Null => "NULL".to_string(),
+        }
+    }
+
+    // 数值转为浮点数，非数值返回 0
+    fn as_f64(&self) -> f64 {
+        match self {
+            DataType::Int(n) => *n as f64,
+            DataType::Float(f) => *f,
+            _ => 0.0,
+        }
+    }
+}
+
 impl fmt::Display for DataType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -76,15 +270,199 @@ impl fmt::Display for DataType {
     }
 }
 
+impl fmt::Display for ColumnType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ColumnType::Int(Some(bits)) => write!(f, "INT({})", bits),
+            ColumnType::Int(None) => write!(f, "INT"),
+            ColumnType::Float(Some(bits)) => write!(f, "FLOAT({})", bits),
+            ColumnType::Float(None) => write!(f, "FLOAT"),
+            ColumnType::Varchar(length) => write!(f, "VARCHAR({})", length),
+        }
+    }
+}
+
 impl Table {
     pub fn new(name: String, columns: Vec<Column>) -> Self {
-        Table {
+        let mut table = Table {
             name,
             columns,
             rows: Vec::new(),
+            max_rows: None,
+            comment: None,
+            indexes: Vec::new(),
+            row_versions: None,
+            index_cache: RefCell::new(HashMap::new()),
+        };
+        table.mark_primary_key_not_null();
+        table
+    }
+
+    // 主键列隐式为 NOT NULL，插入和更新时只需检查 nullable
+    pub fn mark_primary_key_not_null(&mut self) {
+        for column in self.columns.iter_mut().filter(|c| c.primary_key) {
+            column.nullable = false;
+        }
+    }
+
+    // 为每行维护隐藏的版本号：新插入的行版本为 1，每次更新加 1
+    // 已有的行从版本 1 开始
+    pub fn enable_row_versions(&mut self) {
+        if self.row_versions.is_none() {
+            self.row_versions = Some(vec![1; self.rows.len()]);
+        }
+    }
+
+    pub fn has_row_versions(&self) -> bool {
+        self.row_versions.is_some()
+    }
+
+    // 读取一行的版本号，未开启 ROW_VERSION 或超出范围时返回 None
+    pub fn row_version(&self, index: usize) -> Option<u64> {
+        self.row_versions.as_ref()?.get(index).copied()
+    }
+
+    // 替换一行，行号必须在范围内；开启 ROW_VERSION 时该行的版本号加 1
+    pub fn replace_row(&mut self, index: usize, row: Vec<DataType>) {
+        let old = std::mem::replace(&mut self.rows[index], row);
+        for (column, cached) in self.index_cache.get_mut().iter_mut() {
+            if let Some(column_index) = self.columns.iter().position(|c| &c.name == column) {
+                if old[column_index] != self.rows[index][column_index] {
+                    cached.remove(&old[column_index], index);
+                    cached.insert(&self.rows[index][column_index], index);
+                }
+            }
+        }
+        if let Some(version) = self.row_versions.as_mut().and_then(|versions| versions.get_mut(index)) {
+            *version += 1;
+        }
+    }
+
+    // 删除一行，行号必须在范围内
+    pub fn remove_row(&mut self, index: usize) -> Vec<DataType> {
+        if let Some(versions) = self.row_versions.as_mut() {
+            versions.remove(index);
+        }
+        for cached in self.index_cache.get_mut().values_mut() {
+            cached.remove_rows(&[index]);
+        }
+        self.rows.remove(index)
+    }
+
+    // 删除给定的多行，行号需按升序排列
+    pub fn remove_rows(&mut self, indices: &[usize]) {
+        let mut to_delete = indices.iter().peekable();
+        let keep: Vec<bool> = (0..self.rows.len()).map(|i| to_delete.next_if_eq(&&i).is_none()).collect();
+        let mut keep_row = keep.iter().copied();
+        self.rows.retain(|_| keep_row.next().unwrap_or(true));
+        if let Some(versions) = self.row_versions.as_mut() {
+            let mut keep_version = keep.iter().copied();
+            versions.retain(|_| keep_version.next().unwrap_or(true));
+        }
+        for cached in self.index_cache.get_mut().values_mut() {
+            cached.remove_rows(indices);
         }
     }
 
+    // 丢弃全部已建立的索引，下次查询时重新建立
+    pub fn invalidate_indexes(&mut self) {
+        self.index_cache.get_mut().clear();
+    }
+
+    // 直接修改某列的数据或列名后调用，只丢弃该列上已建立的索引
+    pub fn invalidate_index(&mut self, column: &str) {
+        self.index_cache.get_mut().remove(column);
+    }
+
+    // 按行号（从0开始，即插入顺序）读取一行的副本，超出范围时返回 None
+    pub fn get_row(&self, index: usize) -> Option<Vec<DataType>> {
+        self.rows.get(index).cloned()
+    }
+
+    // 估算表中数据占用的内存字节数：整数 4 字节，浮点数 8 字节，字符串按字节长度，NULL 不占空间
+    // 不包括 Vec 和 String 的额外开销以及索引，只用于比较表的大小
+    pub fn approximate_size_bytes(&self) -> usize {
+        self.rows.iter()
+            .flatten()
+            .map(|value| match value {
+                DataType::Int(_) => std::mem::size_of::<i32>(),
+                DataType::Float(_) => std::mem::size_of::<f64>(),
+                DataType::Varchar(s) => s.len(),
+                DataType::Null => 0,
+            })
+            .sum()
+    }
+
+    // 按名称删除索引并丢弃已建立的索引结构，索引不存在时返回 None
+    pub fn drop_index(&mut self, name: &str) -> Option<Index> {
+        let position = self.indexes.iter().position(|index| index.name == name)?;
+        let index = self.indexes.remove(position);
+        self.index_cache.get_mut().remove(&index.column);
+        Some(index)
+    }
+
+    pub fn index_on(&self, column: &str) -> Option<&Index> {
+        self.indexes.iter().find(|index| index.column == column)
+    }
+
+    // 通过列上的索引查找值落在区间内的行号，列上没有索引时返回 None
+    pub fn index_range(&self, column: &str, lower: Bound<&IndexKey>, upper: Bound<&IndexKey>) -> Option<Vec<usize>> {
+        self.index_on(column)?;
+        self.with_cached_index(column, |index| index.range(lower, upper))
+    }
+
+    // 按主键值查找行，主键列的索引在第一次查找时建立；没有主键或键为 NULL 时返回 None
+    pub fn find_by_primary_key(&self, key: &DataType) -> Option<&[DataType]> {
+        let primary_key = self.columns.iter().find(|c| c.primary_key)?;
+        let key = IndexKey::from_value(key)?;
+        let row_index = self.with_cached_index(&primary_key.name, |index| index.get(&key))??;
+        Some(&self.rows[row_index])
+    }
+
+    // 使用列上缓存的索引，缓存中没有时扫描该列建立
+    fn with_cached_index<R>(&self, column: &str, f: impl FnOnce(&BTreeIndex) -> R) -> Option<R> {
+        let column_index = self.columns.iter().position(|c| c.name == column)?;
+        
+        let mut cache = self.index_cache.borrow_mut();
+        let index = cache.entry(column.to_string())
+            .or_insert_with(|| BTreeIndex::build(&self.rows, column_index));
+        Some(f(index))
+    }
+
+    // 根据表结构重建 CREATE TABLE 语句（不含末尾分号）
+    pub fn create_table_sql(&self) -> String {
+        let columns: Vec<String> = self.columns.iter().map(|col| {
+            let mut def = format!("{} {}", col.name, col.data_type);
+            if !col.nullable && !col.primary_key {
+                def.push_str(" NOT NULL");
+            }
+            match &col.default {
+                Some(ColumnDefault::Value(value)) => def.push_str(&format!(" DEFAULT {}", value.to_sql_literal())),
+                Some(ColumnDefault::CurrentTimestamp) => def.push_str(" DEFAULT CURRENT_TIMESTAMP"),
+                None => {}
+            }
+            if col.primary_key {
+                def.push_str(" PRIMARY KEY");
+            }
+            if let Some(comment) = &col.comment {
+                def.push_str(&format!(" COMMENT {}", DataType::Varchar(comment.clone()).to_sql_literal()));
+            }
+            def
+        }).collect();
+        
+        let mut sql = format!("CREATE TABLE {} ({})", self.name, columns.join(", "));
+        if let Some(max_rows) = self.max_rows {
+            sql.push_str(&format!(" MAX_ROWS = {}", max_rows));
+        }
+        if let Some(comment) = &self.comment {
+            sql.push_str(&format!(" COMMENT = {}", DataType::Varchar(comment.clone()).to_sql_literal()));
+        }
+        if self.has_row_versions() {
+            sql.push_str(" ROW_VERSION = ON");
+        }
+        sql
+    }
+
     // 检查主键是否重复
     fn check_primary_key_constraint(&self, row: &[DataType]) -> Result<(), TypeError> {
         // 查找主键列的索引
@@ -111,6 +489,43 @@ impl Table {
     }
 
     pub fn validate_row(&self, row: &[DataType]) -> Result<(), TypeError> {
+        self.check_row_values(row)?;
+        
+        // 检查主键约束
+        self.check_primary_key_constraint(row)?;
+
+        Ok(())
+    }
+
+    /// 检查用新的行替换指定行号的行后是否满足约束
+    ///
+    /// 主键只与替换后的其他行比较，行保持原来的主键值不算重复
+    pub fn validate_updates(&self, updates: &[(usize, Vec<DataType>)]) -> Result<(), TypeError> {
+        for (_, row) in updates {
+            self.check_row_values(row)?;
+        }
+
+        let Some(pk_index) = self.columns.iter().position(|col| col.primary_key) else {
+            return Ok(());
+        };
+        let updated: HashMap<usize, &Vec<DataType>> = updates.iter().map(|(i, row)| (*i, row)).collect();
+        for (row_index, row) in updates {
+            let pk_value = &row[pk_index];
+            if let DataType::Null = pk_value {
+                continue;
+            }
+            for (i, existing_row) in self.rows.iter().enumerate() {
+                let existing_value = &updated.get(&i).copied().unwrap_or(existing_row)[pk_index];
+                if i != *row_index && existing_value == pk_value {
+                    return Err(TypeError::PrimaryKeyViolation(pk_value.to_string()));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // 检查行的列数、类型和非空约束
+    fn check_row_values(&self, row: &[DataType]) -> Result<(), TypeError> {
         if row.len() != self.columns.len() {
             return Err(TypeError::TypeMismatch {
                 expected: ColumnType::Int(None),
@@ -118,7 +533,7 @@ impl Table {
             });
         }
 
-        for (_i, (value, column)) in row.iter().zip(&self.columns).enumerate() {
+        for (value, column) in row.iter().zip(&self.columns) {
             if !value.matches_column_type(&column.data_type) {
                 return Err(TypeError::TypeMismatch {
                     expected: column.data_type.clone(),
@@ -126,26 +541,84 @@ impl Table {
                 });
             }
 
-            // 检查非空约束
+            // 检查非空约束，主键列的 nullable 总是 false
             if !column.nullable && matches!(value, DataType::Null) {
                 return Err(TypeError::NullValue(column.name.clone()));
             }
-            
-            // 检查主键不能为NULL
-            if column.primary_key && matches!(value, DataType::Null) {
-                return Err(TypeError::NullValue(column.name.clone()));
-            }
         }
-        
-        // 检查主键约束
-        self.check_primary_key_constraint(row)?;
 
         Ok(())
     }
 
+    // 依次插入多行，任何一行失败时撤销本次已插入的行
+    pub fn insert_rows(&mut self, rows: Vec<Vec<DataType>>) -> Result<(), TypeError> {
+        let original_len = self.rows.len();
+        for row in rows {
+            if let Err(e) = self.insert_row(row) {
+                self.rows.truncate(original_len);
+                if let Some(versions) = self.row_versions.as_mut() {
+                    versions.truncate(original_len);
+                }
+                for cached in self.index_cache.get_mut().values_mut() {
+                    cached.truncate(original_len);
+                }
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
     pub fn insert_row(&mut self, row: Vec<DataType>) -> Result<(), TypeError> {
+        // 检查行数限制
+        if let Some(max_rows) = self.max_rows {
+            if self.rows.len() >= max_rows {
+                return Err(TypeError::TableFull(self.name.clone()));
+            }
+        }
+        
         self.validate_row(&row)?;
+        let row_index = self.rows.len();
+        for (column, cached) in self.index_cache.get_mut().iter_mut() {
+            if let Some(column_index) = self.columns.iter().position(|c| &c.name == column) {
+                cached.insert(&row[column_index], row_index);
+            }
+        }
         self.rows.push(row);
+        if let Some(versions) = self.row_versions.as_mut() {
+            versions.push(1);
+        }
         Ok(())
     }
+
+    // 比较两个表的数据，返回另一个表相对当前表新增、删除和修改的行
+    // 两个表的主键列同名时按主键对应行，否则按行的位置对应
+    pub fn diff(&self, other: &Table) -> Vec<RowDiff> {
+        let primary_key = |table: &Table| table.columns.iter().position(|c| c.primary_key);
+        let keys = match (primary_key(self), primary_key(other)) {
+            (Some(i), Some(j)) if self.columns[i].name == other.columns[j].name => Some((i, j)),
+            _ => None,
+        };
+
+        let mut diffs = Vec::new();
+        let mut matched = vec![false; other.rows.len()];
+        for (row_index, row) in self.rows.iter().enumerate() {
+            let counterpart = match keys {
+                Some((i, j)) => other.rows.iter().position(|other_row| other_row[j] == row[i]),
+                None => (row_index < other.rows.len()).then_some(row_index),
+            };
+            match counterpart {
+                Some(other_index) => {
+                    matched[other_index] = true;
+                    if other.rows[other_index] != *row {
+                        diffs.push(RowDiff::Changed { old: row.clone(), new: other.rows[other_index].clone() });
+                    }
+                }
+                None => diffs.push(RowDiff::Removed(row.clone())),
+            }
+        }
+        for (other_row, _) in other.rows.iter().zip(&matched).filter(|(_, matched)| !**matched) {
+            diffs.push(RowDiff::Added(other_row.clone()));
+        }
+        diffs
+    }
 } 
\ No newline at end of file
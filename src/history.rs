@@ -0,0 +1,90 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// 交互式Shell默认的历史记录文件
+pub const HISTORY_FILE: &str = ".history";
+
+/// 历史记录默认保留的条数
+pub const DEFAULT_MAX_ENTRIES: usize = 1000;
+
+/// 交互式Shell的命令历史
+///
+/// 每条记录是一条完整的命令或SQL语句，多行输入的SQL合并为一行保存，
+/// 历史文件中每行一条记录
+#[derive(Debug, Clone)]
+pub struct History {
+    entries: Vec<String>,
+    max_entries: usize,
+    path: Option<PathBuf>,
+}
+
+impl History {
+    /// 创建不写入文件的历史记录
+    pub fn new(max_entries: usize) -> Self {
+        History { entries: Vec::new(), max_entries, path: None }
+    }
+
+    /// 从历史文件加载记录，文件不存在时从空记录开始
+    pub fn load(path: impl AsRef<Path>, max_entries: usize) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut history = History::new(max_entries);
+        match fs::read_to_string(&path) {
+            Ok(content) => {
+                for line in content.lines() {
+                    history.push(line);
+                }
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+        history.path = Some(path);
+        Ok(history)
+    }
+
+    /// 添加一条记录并写入历史文件，返回是否实际添加
+    ///
+    /// 空输入和与上一条相同的输入不会被记录
+    pub fn add(&mut self, input: &str) -> io::Result<bool> {
+        if !self.push(input) {
+            return Ok(false);
+        }
+        self.save()?;
+        Ok(true)
+    }
+
+    /// 全部记录，最早的在前
+    pub fn entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    /// 将记录写入历史文件
+    pub fn save(&self) -> io::Result<()> {
+        match &self.path {
+            Some(path) => {
+                let mut content = self.entries.join("\n");
+                if !content.is_empty() {
+                    content.push('\n');
+                }
+                fs::write(path, content)
+            }
+            None => Ok(()),
+        }
+    }
+
+    // 添加一条记录，超过上限时丢弃最早的记录
+    fn push(&mut self, input: &str) -> bool {
+        // 多行输入合并为一行，保证历史文件中每行一条记录
+        let entry = input.lines().map(str::trim).filter(|line| !line.is_empty()).collect::<Vec<_>>().join(" ");
+        if entry.is_empty() || self.entries.last() == Some(&entry) {
+            return false;
+        }
+
+        self.entries.push(entry);
+        if self.entries.len() > self.max_entries {
+            let excess = self.entries.len() - self.max_entries;
+            self.entries.drain(..excess);
+        }
+        true
+    }
+}
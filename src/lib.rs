@@ -1,8 +1,13 @@
 pub mod core;
+pub mod completion;
+pub mod history;
 
-pub use core::db::{Database, ErrorDisplayMode, StorageType};
+pub use core::db::{Database, ErrorDisplayMode, QueryResult, Snapshot, StatementResult, StorageKind, StorageType};
+use core::sql::{parse_delimiter_command, split_statements_with_delimiter, OutputFormat, ScriptItem, ScriptSplitter, TableFormatter};
+use history::History;
 use std::io::{self, Write};
 use std::path::PathBuf;
+use std::process::ExitCode;
 
 /// SQL执行结果结构体
 #[derive(Debug, Clone)]
@@ -11,6 +16,29 @@ pub struct SqlResult {
     pub error_message: String, // 错误信息（如果有）
 }
 
+/// 脚本执行的统计结果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScriptSummary {
+    pub succeeded: usize,            // 执行成功的语句数（包括 SET 命令）
+    pub failed: usize,               // 执行失败的语句数，结束时写盘失败也计入
+    pub first_error: Option<String>, // 第一个错误的详细信息
+}
+
+impl ScriptSummary {
+    /// 所有语句都执行成功
+    pub fn is_success(&self) -> bool {
+        self.failed == 0
+    }
+
+    // 记录一次失败，只保留第一个错误
+    fn record_error(&mut self, error: &core::error::DbError) {
+        self.failed += 1;
+        if self.first_error.is_none() {
+            self.first_error = Some(error.detailed_message());
+        }
+    }
+}
+
 /// 执行SQL语句的带路径接口
 /// 
 /// # 参数
@@ -22,138 +50,125 @@ pub struct SqlResult {
 /// * `bool` - 执行成功返回true，失败返回false
 pub fn execute_sql_with_path(sql_statement: &str, db_path: Option<PathBuf>, stop_on_error: bool) -> bool {
     // 创建数据库实例
-    let storage_type = match db_path {
-        Some(path) => StorageType::File(path),
-        None => StorageType::Memory,
-    };
-    
-    let mut db = Database::new(storage_type);
-    let mut success = true;
-    
-    // 处理输入，移除注释
-    let cleaned_sql = remove_comments(sql_statement);
+    let mut db = Database::new(StorageType::from(db_path));
+    execute_script(&mut db, sql_statement, stop_on_error)
+}
+
+/// 在已有的数据库实例上执行SQL脚本
+/// 
+/// # 参数
+/// * `db` - 要执行脚本的数据库实例，其设置（如错误显示模式、verbose）对脚本生效
+/// * `sql_statement` - 要执行的SQL语句，可以包含多条语句和注释
+/// * `stop_on_error` - 是否在遇到第一个错误时立即停止执行
+/// 
+/// # 返回值
+/// * `bool` - 执行成功返回true，失败返回false
+pub fn execute_script(db: &mut Database, sql_statement: &str, stop_on_error: bool) -> bool {
+    run_script(db, sql_statement, stop_on_error).is_success()
+}
+
+/// 读取并执行SQL脚本文件，返回成功和失败的语句数
+///
+/// 语句的输出和错误信息与 execute_script 相同；遇错停止时，之后的语句不计入统计。
+/// 无法读取文件时返回错误
+pub fn run_script_file(db: &mut Database, path: impl AsRef<std::path::Path>, stop_on_error: bool) -> Result<ScriptSummary, core::error::DbError> {
+    let sql_content = std::fs::read_to_string(path)?;
+    Ok(run_script(db, &sql_content, stop_on_error))
+}
+
+/// 在已有的数据库实例上执行SQL脚本，返回执行的统计结果
+pub fn run_script(db: &mut Database, sql_statement: &str, stop_on_error: bool) -> ScriptSummary {
+    let mut summary = ScriptSummary::default();
     
-    // 分割多条SQL语句
-    let statements: Vec<String> = cleaned_sql.split(';')
-        .map(|s| s.trim().to_string())
-        .filter(|s| !s.is_empty())
-        .collect();
+    // 移除注释后逐条拆分SQL语句，字符串中的分隔符不作为分隔符；DELIMITER 命令修改之后语句的分隔符
+    let mut statements = ScriptSplitter::new(sql_statement, db.delimiter());
     
     // 记录上一条是否有输出（用于判断是否需要添加空行）
     let mut last_had_output = false;
-    // 记录是否执行了任何SELECT语句
-    let mut has_executed_select = false;
-    // 记录是否有任何表格输出
-    let mut has_table_output = false;
+    // 脚本中的修改在内存中累积，结束时一次性写盘
+    db.begin_batch();
     
     // 依次执行每条语句
-    for stmt in statements {
+    while let Some(item) = statements.next() {
+        let stmt = match item {
+            ScriptItem::Statement(stmt) => stmt,
+            ScriptItem::Delimiter(delimiter) => {
+                match db.set_delimiter(&delimiter) {
+                    Ok(()) => {
+                        statements.set_delimiter(&delimiter);
+                        summary.succeeded += 1;
+                    }
+                    Err(e) => {
+                        println!("{}", db.format_error(&e));
+                        summary.record_error(&e);
+                        if stop_on_error {
+                            break;
+                        }
+                    }
+                }
+                continue;
+            }
+        };
         if !stmt.is_empty() {
+            // SET 命令修改运行时选项，对脚本中后续的语句生效
+            if let Some((name, value)) = parse_set_command(&stmt) {
+                match db.set_option(&name, &value) {
+                    Ok(()) => {
+                        statements.set_delimiter(db.delimiter());
+                        summary.succeeded += 1;
+                    }
+                    Err(e) => {
+                        println!("{}", db.format_error(&e));
+                        summary.record_error(&e);
+                        if stop_on_error {
+                            break;
+                        }
+                    }
+                }
+                continue;
+            }
+            
             // 检查当前语句是否为SELECT语句
-            let is_select = stmt.trim_start().to_uppercase().starts_with("SELECT");
+            let upper_stmt = stmt.trim_start().to_uppercase();
+            let is_select = ["SELECT", "EXPLAIN", "DESC", "SHOW"].iter().any(|kw| upper_stmt.starts_with(kw));
             
-            if is_select {
-                has_executed_select = true;
-                
-                // 如果上一条也有输出，添加一个空行
-                if last_had_output {
-                    println!();
-                }
+            // 如果上一条也有输出，添加一个空行
+            if is_select && last_had_output {
+                println!();
             }
             
             match db.execute_sql_with_output(&format!("{};", stmt)) {
                 Ok(has_output) => {
-                    // 更新状态
-                    last_had_output = has_output;
-                    if has_output {
-                        has_table_output = true;
+                    summary.succeeded += 1;
+                    // 查询结果为空时紧跟在该语句后提示一次；没有 FROM 的常量查询总有一行结果
+                    if is_select && !has_output {
+                        println!("There are no results to be displayed.");
                     }
+                    last_had_output = has_output || is_select;
                 },
                 Err(e) => {
                     // 使用当前错误显示模式格式化错误信息并打印
                     println!("{}", db.format_error(&e));
-                    success = false;
+                    summary.record_error(&e);
                     last_had_output = false; // 执行失败，重置状态
                     
                     // 如果设置了遇到错误立即停止，则中断执行
                     if stop_on_error {
                         // println!("遇到错误，终止执行");
-                        return false;
+                        break;
                     }
                 }
             }
         }
     }
     
-    // 如果执行了SELECT语句但没有输出
-    if has_executed_select && !has_table_output {
-        println!("There are no results to be displayed.");
+    // 写入批量期间的全部修改，出错前已成功执行的语句同样需要保存
+    if let Err(e) = db.end_batch() {
+        println!("{}", db.format_error(&e));
+        summary.record_error(&e);
     }
     
-    success
-}
-
-/// 移除SQL语句中的注释
-fn remove_comments(sql: &str) -> String {
-    let mut result = String::new();
-    let mut in_multi_comment = false;
-    let mut in_single_comment = false;
-    let mut in_string = false;
-    let mut string_quote = '\0'; // 存储字符串的引号类型（单引号或双引号）
-    let mut i = 0;
-    
-    let chars: Vec<char> = sql.chars().collect();
-    
-    while i < chars.len() {
-        let c = chars[i];
-        
-        // 检查是否在字符串中
-        if !in_single_comment && !in_multi_comment {
-            if (c == '\'' || c == '"') && (i == 0 || chars[i-1] != '\\') {
-                if !in_string {
-                    in_string = true;
-                    string_quote = c;
-                } else if c == string_quote {
-                    in_string = false;
-                }
-            }
-        }
-        
-        // 处理单行注释开始 --
-        if !in_string && !in_multi_comment && !in_single_comment && c == '-' && i + 1 < chars.len() && chars[i + 1] == '-' {
-            in_single_comment = true;
-            i += 2;
-            continue;
-        }
-        
-        // 处理多行注释开始 /*
-        if !in_string && !in_single_comment && !in_multi_comment && c == '/' && i + 1 < chars.len() && chars[i + 1] == '*' {
-            in_multi_comment = true;
-            i += 2;
-            continue;
-        }
-        
-        // 处理多行注释结束 */
-        if !in_string && !in_single_comment && in_multi_comment && c == '*' && i + 1 < chars.len() && chars[i + 1] == '/' {
-            in_multi_comment = false;
-            i += 2;
-            continue;
-        }
-        
-        // 处理单行注释结束（遇到换行）
-        if in_single_comment && (c == '\n' || c == '\r') {
-            in_single_comment = false;
-        }
-        
-        // 只有不在注释中的内容才添加到结果中
-        if !in_single_comment && !in_multi_comment {
-            result.push(c);
-        }
-        
-        i += 1;
-    }
-    
-    result
+    summary
 }
 
 /// 执行SQL语句的统一接口（使用内存存储）
@@ -167,6 +182,41 @@ pub fn execute_sql(sql_statement: &str) -> bool {
     execute_sql_with_path(sql_statement, None, false)
 }
 
+/// 解析 `SET option = value` 命令，返回选项名和取值
+///
+/// 取值可以用单引号或双引号括起来，末尾的分号可以省略；不是 SET 命令时返回 None
+pub fn parse_set_command(input: &str) -> Option<(String, String)> {
+    let input = input.trim().trim_end_matches(';').trim();
+    if input.len() < 4 || !input[..4].eq_ignore_ascii_case("set ") {
+        return None;
+    }
+    
+    // 同一行中还有其他语句时按语句逐条处理
+    if input.contains(';') {
+        return None;
+    }
+    
+    let (name, value) = input[4..].split_once('=')?;
+    let name = name.trim();
+    if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        return None;
+    }
+    
+    let value = value.trim();
+    let value = ['\'', '"'].iter()
+        .find_map(|q| value.strip_prefix(*q).and_then(|v| v.strip_suffix(*q)))
+        .unwrap_or(value);
+    Some((name.to_string(), value.to_string()))
+}
+
+/// 执行 SET 命令，返回要显示的提示信息
+fn run_set_command(db: &mut Database, name: &str, value: &str) -> String {
+    match db.set_option(name, value) {
+        Ok(()) => format!("选项 {} 已设置为 {}", name, value),
+        Err(e) => db.format_error(&e),
+    }
+}
+
 /// 获取默认数据库路径
 pub fn get_default_db_path() -> PathBuf {
     // 使用当前目录
@@ -181,46 +231,104 @@ pub fn run_interactive_shell(db: &mut Database) -> Result<(), Box<dyn std::error
     let mut sql_buffer = String::new();
     // 记录提示符状态
     let mut is_continuation = false;
+    // 命令历史保存在当前目录下，读取失败时只在本次会话中记录
+    let mut history = History::load(history::HISTORY_FILE, history::DEFAULT_MAX_ENTRIES)
+        .unwrap_or_else(|e| {
+            println!("无法读取历史记录: {}", e);
+            History::new(history::DEFAULT_MAX_ENTRIES)
+        });
+    let mut reader = LineReader::new(&history);
 
     loop {
         // 根据是否在继续输入SQL语句显示不同的提示符
-        if is_continuation {
-            print!("-> ");
-        } else {
-            print!("> ");
-        }
-        io::stdout().flush()?;
-
-        let mut input = String::new();
-        io::stdin().read_line(&mut input)?;
+        let prompt = if is_continuation { "-> " } else { "> " };
+        let input = match reader.read_line(prompt)? {
+            Some(input) => input,
+            // 输入结束（如 Ctrl-D）时与 exit 一样退出，先换行使终端提示符另起一行
+            None => {
+                println!();
+                break;
+            }
+        };
         let input = input.trim();
 
         if input.is_empty() {
             continue;
         }
 
+        // DELIMITER 命令以行尾结束，不需要分隔符
+        if !is_continuation {
+            if let Some(delimiter) = parse_delimiter_command(input) {
+                record_history(&mut history, &mut reader, input);
+                match db.set_delimiter(delimiter) {
+                    Ok(()) => println!("语句分隔符已设置为 {}", delimiter),
+                    Err(e) => println!("{}", db.format_error(&e)),
+                }
+                continue;
+            }
+        }
+
+        // 特殊命令单独记入历史，SQL语句在输入完整后再记录
+        if !is_continuation && is_shell_command(input) {
+            record_history(&mut history, &mut reader, input);
+        }
+
+        // SET 命令修改运行时选项，不需要分号
+        if !is_continuation {
+            if let Some((name, value)) = parse_set_command(input) {
+                record_history(&mut history, &mut reader, input);
+                println!("{}", run_set_command(db, &name, &value));
+                continue;
+            }
+        }
+
         // 处理特殊命令，这些命令不需要分号
         match input {
             "exit" => break,
             "help" => {
                 println!("可用命令:");
                 println!("  help - 显示帮助信息");
-                println!("  exit - 退出程序");
+                println!("  exit - 退出程序（autosave 开启时先保存数据库）");
                 println!("  list - 列出所有表");
+                println!("  stats - 显示每个表的行数、列数和估算的数据大小");
+                println!("  schema [table] - 输出建表语句，不指定表名时输出所有表");
                 println!("  save - 保存数据库");
                 println!("  load - 加载数据库");
+                println!("  reload - 丢弃未保存的修改，从磁盘重新加载数据库");
+                println!("  check - 检查磁盘上的表文件能否读取");
+                println!("  repair - 将损坏的表文件移到 tables/.broken 目录，并重新加载其余的表");
                 println!("  clear - 清除当前SQL缓冲区");
+                println!("  history - 显示命令历史");
                 println!("  toggle_error_mode - 切换错误显示模式（简略/详细）");
                 println!("  error_mode - 显示当前错误显示模式");
+                println!("  SET option = value - 修改运行时选项:");
+                println!("    error_mode = brief | detailed");
+                println!("    locale = en | zh | default");
+                println!("    verbose = on | off");
+                println!("    max_width = <列宽> | off");
+                println!("    table_style = minimal | box | box_rows");
+                println!("    output = table | json | json_pretty");
+                println!("    rownum = on | off");
+                println!("    autosave = on | off");
+                println!("    cross_table_lookup = on | off");
+                println!("    delimiter = <分隔符>");
+                println!("  DELIMITER <分隔符> - 修改语句分隔符，如 DELIMITER // ，用 DELIMITER ; 恢复");
                 println!("SQL命令: (以分号结束)");
                 println!("  -- 这是SQL注释");
                 println!("  CREATE TABLE table_name (column1 type1, column2 type2, ...);");
+                println!("  CREATE TABLE new_table LIKE table_name;");
                 println!("  DROP TABLE table_name;");
+                println!("  CREATE INDEX index_name ON table_name (column);");
+                println!("  DROP INDEX index_name;");
+                println!("  SHOW INDEXES FROM table_name;");
                 println!("  INSERT INTO table_name VALUES (1, 'value1');  -- 可以使用单引号");
                 println!("  INSERT INTO table_name VALUES (2, \"value2\");  -- 或双引号");
                 println!("  UPDATE table_name SET column = value WHERE condition;");
                 println!("  DELETE FROM table_name WHERE condition;");
                 println!("  SELECT * FROM table_name WHERE condition;");
+                println!("  SELECT * FROM table_name SAMPLE 10;  -- 随机抽取10行，或 SAMPLE 5 PERCENT");
+                println!("  SELECT price AS p, p * 2 FROM table_name;  -- 后面的表达式可以引用前面的列别名");
+                println!("  SELECT CURRENT_DATE, CURRENT_TIMESTAMP;  -- 当前的 UTC 日期和时间，也可用于 INSERT 和 UPDATE");
                 is_continuation = false;
                 sql_buffer.clear();
                 continue;
@@ -259,6 +367,32 @@ pub fn run_interactive_shell(db: &mut Database) -> Result<(), Box<dyn std::error
                 sql_buffer.clear();
                 continue;
             },
+            "stats" => {
+                let stats = db.table_stats()?;
+                if stats.is_empty() {
+                    println!("没有表");
+                } else {
+                    let headers = vec!["table".to_string(), "rows".to_string(), "columns".to_string(), "bytes".to_string()];
+                    let rows: Vec<Vec<String>> = stats.into_iter()
+                        .map(|s| vec![s.name, s.row_count.to_string(), s.column_count.to_string(), s.size_bytes.to_string()])
+                        .collect();
+                    print!("{}", TableFormatter::format_table(&headers, &rows));
+                }
+                is_continuation = false;
+                sql_buffer.clear();
+                continue;
+            },
+            cmd if cmd == "schema" || cmd.starts_with("schema ") => {
+                let table_name = cmd["schema".len()..].trim().trim_end_matches(';').trim();
+                let table_name = if table_name.is_empty() { None } else { Some(table_name) };
+                match db.schema_sql(table_name) {
+                    Ok(sql) => print!("{}", sql),
+                    Err(e) => println!("{}", db.format_error(&e)),
+                }
+                is_continuation = false;
+                sql_buffer.clear();
+                continue;
+            },
             "save" => {
                 db.save()?;
                 println!("数据库已保存");
@@ -273,6 +407,50 @@ pub fn run_interactive_shell(db: &mut Database) -> Result<(), Box<dyn std::error
                 sql_buffer.clear();
                 continue;
             },
+            "history" => {
+                for (i, entry) in history.entries().iter().enumerate() {
+                    println!("{:>5}  {}", i + 1, entry);
+                }
+                is_continuation = false;
+                sql_buffer.clear();
+                continue;
+            },
+            "reload" => {
+                match db.reload() {
+                    Ok(count) => println!("已丢弃未保存的修改，重新加载了 {} 个表", count),
+                    Err(e) => println!("{}", db.format_error(&e)),
+                }
+                is_continuation = false;
+                sql_buffer.clear();
+                continue;
+            },
+            "check" => {
+                match db.check_integrity() {
+                    Ok(results) if results.is_empty() => println!("没有表文件"),
+                    Ok(results) => {
+                        for (name, result) in results {
+                            match result {
+                                Ok(()) => println!("  {}: 正常", name),
+                                Err(e) => println!("  {}: 损坏 ({})", name, e),
+                            }
+                        }
+                    }
+                    Err(e) => println!("{}", db.format_error(&e)),
+                }
+                is_continuation = false;
+                sql_buffer.clear();
+                continue;
+            },
+            "repair" => {
+                match db.repair() {
+                    Ok(broken) if broken.is_empty() => println!("没有损坏的表文件"),
+                    Ok(broken) => println!("已将损坏的表文件移到 tables/.broken: {}", broken.join(", ")),
+                    Err(e) => println!("{}", db.format_error(&e)),
+                }
+                is_continuation = false;
+                sql_buffer.clear();
+                continue;
+            },
             "clear" => {
                 // 添加清除当前输入缓冲区的命令
                 println!("已清除当前SQL缓冲区");
@@ -283,8 +461,9 @@ pub fn run_interactive_shell(db: &mut Database) -> Result<(), Box<dyn std::error
             _ => handle_sql_input(input, &mut sql_buffer)?
         }
 
-        // 检查SQL缓冲区是否包含分号，表示SQL语句结束
-        if sql_buffer.contains(';') {
+        // 检查SQL缓冲区是否包含分隔符，表示SQL语句结束
+        if sql_buffer.contains(db.delimiter()) {
+            record_history(&mut history, &mut reader, &sql_buffer);
             process_sql_statements(db, &mut sql_buffer, &mut is_continuation)?;
         } else {
             // 没有分号，继续接受输入
@@ -292,9 +471,114 @@ pub fn run_interactive_shell(db: &mut Database) -> Result<(), Box<dyn std::error
         }
     }
 
+    save_before_exit(db);
     Ok(())
 }
 
+/// 退出交互式Shell前按 autosave 选项保存数据库，有未写盘的修改时给出提示
+fn save_before_exit(db: &mut Database) {
+    let unsaved = db.has_unsaved_changes();
+    if !db.autosave() {
+        if unsaved {
+            println!("自动保存已关闭，未保存的修改已丢弃");
+        }
+        return;
+    }
+    match db.save() {
+        Ok(()) if unsaved => println!("已保存未写盘的修改"),
+        Ok(()) => {}
+        Err(e) => println!("退出前保存失败: {}", db.format_error(&e)),
+    }
+}
+
+/// 交互式Shell读取输入的方式
+///
+/// 启用 line-editing 特性且标准输入是终端时使用 rustyline，支持方向键编辑和上翻历史；
+/// 否则（包括从管道读取脚本时）逐行读取标准输入
+struct LineReader {
+    #[cfg(feature = "line-editing")]
+    editor: Option<rustyline::DefaultEditor>,
+}
+
+impl LineReader {
+    /// 创建读取器，已有的历史记录可以通过上翻找回
+    #[cfg(feature = "line-editing")]
+    fn new(history: &History) -> Self {
+        use std::io::IsTerminal;
+        let mut editor = if io::stdin().is_terminal() {
+            rustyline::DefaultEditor::new().ok()
+        } else {
+            None
+        };
+        if let Some(editor) = editor.as_mut() {
+            for entry in history.entries() {
+                let _ = editor.add_history_entry(entry.as_str());
+            }
+        }
+        LineReader { editor }
+    }
+
+    #[cfg(not(feature = "line-editing"))]
+    fn new(_history: &History) -> Self {
+        LineReader {}
+    }
+
+    /// 显示提示符并读取一行输入，输入结束时返回 None
+    fn read_line(&mut self, prompt: &str) -> io::Result<Option<String>> {
+        #[cfg(feature = "line-editing")]
+        if let Some(editor) = self.editor.as_mut() {
+            use rustyline::error::ReadlineError;
+            return match editor.readline(prompt) {
+                Ok(line) => Ok(Some(line)),
+                Err(ReadlineError::Eof) => Ok(None),
+                // Ctrl-C 放弃正在输入的这一行
+                Err(ReadlineError::Interrupted) => Ok(Some(String::new())),
+                Err(ReadlineError::Io(e)) => Err(e),
+                Err(e) => Err(io::Error::other(e)),
+            };
+        }
+        read_input(prompt)
+    }
+
+    /// 将一条历史记录加入上翻历史
+    fn add_history(&mut self, _entry: &str) {
+        #[cfg(feature = "line-editing")]
+        if let Some(editor) = self.editor.as_mut() {
+            let _ = editor.add_history_entry(_entry);
+        }
+    }
+}
+
+/// 显示提示符并读取一行输入，输入结束时返回 None
+fn read_input(prompt: &str) -> io::Result<Option<String>> {
+    print!("{}", prompt);
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    if io::stdin().read_line(&mut input)? == 0 {
+        return Ok(None);
+    }
+    Ok(Some(input))
+}
+
+/// 判断输入是否为不需要分号的特殊命令
+fn is_shell_command(input: &str) -> bool {
+    matches!(input, "exit" | "help" | "toggle_error_mode" | "error_mode" | "list" | "stats" | "save" | "load" | "reload" | "check" | "repair" | "clear" | "history")
+        || input == "schema" || input.starts_with("schema ")
+}
+
+/// 记录一条历史，写入历史文件失败不影响继续执行
+fn record_history(history: &mut History, reader: &mut LineReader, input: &str) {
+    let added = history.add(input).unwrap_or_else(|e| {
+        println!("无法写入历史记录: {}", e);
+        true
+    });
+    // 上翻历史与历史文件一致，多行输入同样合并为一条
+    if let (true, Some(entry)) = (added, history.entries().last()) {
+        reader.add_history(entry);
+    }
+}
+
 /// 处理SQL输入
 fn handle_sql_input(input: &str, sql_buffer: &mut String) -> Result<(), Box<dyn std::error::Error>> {
     // 将输入添加到SQL缓冲区
@@ -333,14 +617,12 @@ fn handle_sql_input(input: &str, sql_buffer: &mut String) -> Result<(), Box<dyn
 
 /// 处理SQL语句
 fn process_sql_statements(db: &mut Database, sql_buffer: &mut String, is_continuation: &mut bool) -> Result<(), Box<dyn std::error::Error>> {
-    // 拆分SQL语句（可能有多个语句用分号分隔）
+    // 拆分SQL语句（可能有多个语句用当前分隔符分隔）
     // 将分割后的语句复制到一个新的向量，避免对sql_buffer的借用
-    let statements: Vec<String> = sql_buffer.split(';')
-        .map(|s| s.trim().to_string())
-        .collect();
+    let statements = split_statements_with_delimiter(sql_buffer, db.delimiter());
     
-    // 判断是否以分号结尾
-    let ends_with_semicolon = sql_buffer.trim().ends_with(';');
+    // 判断是否以分隔符结尾
+    let ends_with_semicolon = sql_buffer.trim().ends_with(db.delimiter());
     
     // 清空SQL缓冲区，这样就不会有借用问题
     sql_buffer.clear();
@@ -350,7 +632,9 @@ fn process_sql_statements(db: &mut Database, sql_buffer: &mut String, is_continu
         if !stmt.is_empty() {
             // 显示执行的SQL语句
             println!("执行SQL: {}", stmt);
-            if let Err(e) = db.execute_sql(&format!("{};", stmt)) {
+            if let Some((name, value)) = parse_set_command(stmt) {
+                println!("{}", run_set_command(db, &name, &value));
+            } else if let Err(e) = db.execute_sql(&format!("{};", stmt)) {
                 // 使用当前错误显示模式格式化错误信息
                 println!("{}", db.format_error(&e));
             }
@@ -377,8 +661,8 @@ fn process_sql_statements(db: &mut Database, sql_buffer: &mut String, is_continu
 /// * `args` - 命令行参数，通过 std::env::args().collect::<Vec<String>>() 获取
 ///
 /// # 返回值
-/// * `Result<(), Box<dyn std::error::Error>>` - 执行结果
-pub fn run_simple_db(args: Vec<String>) -> Result<(), Box<dyn std::error::Error>> {
+/// * `Result<ExitCode, Box<dyn std::error::Error>>` - 执行结果，文件模式下有语句执行失败时为 ExitCode::FAILURE
+pub fn run_simple_db(args: Vec<String>) -> Result<ExitCode, Box<dyn std::error::Error>> {
 
     // 使用当前目录作为数据库目录
     let db_path = get_default_db_path();
@@ -386,21 +670,40 @@ pub fn run_simple_db(args: Vec<String>) -> Result<(), Box<dyn std::error::Error>
     let mut db = Database::new(StorageType::File(db_path.clone()));
     db.load()?;
 
+    // 解析命令行选项（--verbose / -v 输出每条语句的执行耗时，--max-width=N 限制表格单元格的显示宽度，
+    // --format=table|json 设置输出格式，--pretty 缩进输出JSON），其余为位置参数
+    let is_verbose_flag = |arg: &String| arg == "--verbose" || arg == "-v";
+    let is_option = |arg: &String| {
+        is_verbose_flag(arg) || arg == "--pretty" || arg.starts_with("--max-width=") || arg.starts_with("--format=")
+    };
+    db.set_verbose(args.iter().skip(1).any(is_verbose_flag));
+    if let Some(arg) = args.iter().skip(1).rev().find(|arg| arg.starts_with("--max-width=")) {
+        let width = arg["--max-width=".len()..].parse::<usize>()
+            .map_err(|_| format!("无效的最大列宽: {}", arg))?;
+        db.set_max_column_width(Some(width));
+    }
+    if let Some(arg) = args.iter().skip(1).rev().find(|arg| arg.starts_with("--format=")) {
+        let pretty = args.iter().skip(1).any(|arg| arg == "--pretty");
+        let format = match &arg["--format=".len()..] {
+            "table" => OutputFormat::Table,
+            "json" => OutputFormat::Json { pretty },
+            _ => return Err(format!("无效的输出格式: {}", arg).into()),
+        };
+        db.set_output_format(format);
+    }
+    let positional: Vec<&String> = args.iter().skip(1).filter(|arg| !is_option(arg)).collect();
+
     // 检查是否提供了SQL文件参数
-    if args.len() == 2 {
+    if positional.len() == 1 {
         // 文件模式 - 读取并执行SQL文件
-        let sql_file_path = &args[1];
+        let sql_file_path = positional[0];
         // println!("执行SQL文件: {}", sql_file_path);
 
-        // 读取文件内容
-        let sql_content = std::fs::read_to_string(sql_file_path)
-            .map_err(|e| format!("无法读取SQL文件: {}", e))?;
-
-        // 执行SQL语句，脚本模式下遇到错误立即停止
-        if execute_sql_with_path(&sql_content, Some(db.get_storage_path()), true) {
-            // println!("SQL文件执行成功");
-        } else {
-            // println!("SQL文件执行过程中出现错误");
+        // 执行SQL语句，脚本模式下遇到错误立即停止，有语句失败时以非零状态退出
+        let summary = run_script_file(&mut db, sql_file_path, true)
+            .map_err(|e| format!("无法读取SQL文件: {}", e.detailed_message()))?;
+        if !summary.is_success() {
+            return Ok(ExitCode::FAILURE);
         }
     } else {
         // 交互式模式
@@ -413,5 +716,5 @@ pub fn run_simple_db(args: Vec<String>) -> Result<(), Box<dyn std::error::Error>
         run_interactive_shell(&mut db)?;
     }
 
-    Ok(())
+    Ok(ExitCode::SUCCESS)
 }
@@ -1,7 +1,8 @@
 use std::env;
+use std::process::ExitCode;
 use simple_db::run_simple_db;
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
+fn main() -> Result<ExitCode, Box<dyn std::error::Error>> {
     // 收集命令行参数
     let args: Vec<String> = env::args().collect();
     